@@ -0,0 +1,37 @@
+use adventofcode2021::problems;
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::fs;
+use std::time::Duration;
+
+// Each day's parts on its real input, timing only the solve. Days without an input.txt in their
+// folder are left out.
+fn solve(c: &mut Criterion) {
+    problems::commands().iter().for_each(|command| {
+        let file = format!(
+            "{}/{}/input.txt",
+            env!("CARGO_MANIFEST_DIR"),
+            command.input_folder()
+        );
+        let input = match fs::read_to_string(file) {
+            Ok(input) => input,
+            Err(_) => return,
+        };
+
+        let mut group = c.benchmark_group(command.name());
+        [1u8, 2u8].iter().for_each(|part| {
+            if let Ok(run) = command.prepare(*part, &input) {
+                group.bench_function(format!("part{}", part), |b| {
+                    b.iter_custom(|iterations| (0..iterations).map(|_| run().1).sum::<Duration>())
+                });
+            }
+        });
+        group.finish();
+    });
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = solve
+}
+criterion_main!(benches);
@@ -0,0 +1,103 @@
+use adventofcode2021::PreparedRun;
+use std::time::Duration;
+
+pub const DEFAULT_SAMPLES: &str = "10";
+
+pub const DEFAULT_BUDGET_SECONDS: &str = "5";
+
+// How long a part's solve took over a number of samples.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Measurement {
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub max: Duration,
+}
+
+impl Measurement {
+    fn from_samples(mut samples: Vec<Duration>) -> Measurement {
+        samples.sort();
+        let total: Duration = samples.iter().sum();
+        Measurement {
+            samples: samples.len(),
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            mean: total / samples.len() as u32,
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+// Runs once to warm up, then samples until there are enough or the budget is spent, so one slow
+// day doesn't hold up the rest. There is always at least one sample.
+pub fn measure(run: &PreparedRun, samples: usize, budget: Duration) -> Measurement {
+    let (_, warm_up) = run();
+    let mut spent = warm_up;
+    let mut times = Vec::with_capacity(samples);
+
+    while times.is_empty() || (times.len() < samples && spent < budget) {
+        let (_, elapsed) = run();
+        spent += elapsed;
+        times.push(elapsed);
+    }
+
+    Measurement::from_samples(times)
+}
+
+// The parts whose median went over the limit, slowest first, or nothing when every part kept to it.
+pub fn slow_report(medians: &[(String, u8, Duration)], limit: Duration) -> Option<String> {
+    let mut slow: Vec<&(String, u8, Duration)> = medians
+        .iter()
+        .filter(|(_, _, median)| *median > limit)
+        .collect();
+    if slow.is_empty() {
+        return None;
+    }
+    slow.sort_by_key(|(_, _, median)| std::cmp::Reverse(*median));
+
+    let mut report = format!("Slow days, over {:?}:", limit);
+    slow.iter().for_each(|(name, part, median)| {
+        report.push_str(&format!(
+            "\n    {} part {}: median {:?}",
+            name, part, median
+        ))
+    });
+    Some(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measurement_orders_samples() {
+        let measurement = Measurement::from_samples(
+            [4u64, 1, 3, 2]
+                .iter()
+                .map(|millis| Duration::from_millis(*millis))
+                .collect(),
+        );
+
+        assert_eq!(measurement.samples, 4);
+        assert_eq!(measurement.min, Duration::from_millis(1));
+        assert_eq!(measurement.median, Duration::from_millis(3));
+        assert_eq!(measurement.mean, Duration::from_micros(2500));
+        assert_eq!(measurement.max, Duration::from_millis(4));
+    }
+
+    #[test]
+    fn slow_report_lists_parts_over_the_limit() {
+        let medians = vec![
+            ("sonar_sweep".to_string(), 1u8, Duration::from_millis(2)),
+            ("amphipod".to_string(), 1u8, Duration::from_millis(40)),
+            ("amphipod".to_string(), 2u8, Duration::from_millis(90)),
+        ];
+
+        assert_eq!(
+            slow_report(&medians, Duration::from_millis(10)).unwrap(),
+            "Slow days, over 10ms:\n    amphipod part 2: median 90ms\n    amphipod part 1: median 40ms"
+        );
+        assert_eq!(slow_report(&medians, Duration::from_millis(100)), None);
+    }
+}
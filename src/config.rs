@@ -0,0 +1,185 @@
+use anyhow::Error;
+use simple_error::SimpleError;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+use strum_macros::{EnumString, EnumVariantNames};
+use toml::Value;
+
+pub const CONFIG_FILE_NAME: &str = "aoc.toml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "kebab_case")]
+pub enum OutputFormat {
+    Pretty,
+    Quiet,
+}
+
+// Everything the command line can be told once in aoc.toml instead of on every run, for example:
+//
+//     input_dir = "inputs"
+//     output_format = "quiet"
+//     results_log = "results.jsonl"
+//     session = "53616c7465645f5f..."
+//     threads = 4
+//
+//     [flags]
+//     smoke-basin = ["--basin-report"]
+//
+// Anything also passed on the command line takes its value from the command line.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Config {
+    pub input_dir: Option<String>,
+    pub output_format: Option<OutputFormat>,
+    pub results_log: Option<String>,
+    pub session: Option<String>,
+    pub threads: Option<usize>,
+    pub flags: HashMap<String, Vec<String>>,
+}
+
+impl Config {
+    // Uses the first aoc.toml found in the current directory or any of its parents, and the
+    // defaults when there isn't one.
+    pub fn discover() -> Result<Config, Error> {
+        match find_config_file(&std::env::current_dir()?) {
+            Some(path) => Config::load(&path),
+            None => Ok(Config::default()),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Config, Error> {
+        let contents = fs::read_to_string(path)?;
+        Config::parse(&contents).map_err(|e| {
+            SimpleError::new(format!("Invalid config file {}: {}", path.display(), e)).into()
+        })
+    }
+
+    pub fn parse(contents: &str) -> Result<Config, Error> {
+        let table = match contents.parse::<Value>()? {
+            Value::Table(table) => table,
+            _ => return Err(SimpleError::new("Expected a table").into()),
+        };
+
+        table
+            .into_iter()
+            .try_fold(Config::default(), |mut config, (key, value)| {
+                match key.as_str() {
+                    "input_dir" => config.input_dir = Some(string_value(&key, &value)?),
+                    "output_format" => {
+                        config.output_format =
+                            Some(string_value(&key, &value)?.parse().map_err(|_| {
+                                SimpleError::new(format!("Unknown output_format {}", value))
+                            })?)
+                    }
+                    "results_log" => config.results_log = Some(string_value(&key, &value)?),
+                    "session" => config.session = Some(string_value(&key, &value)?),
+                    "threads" => config.threads = Some(threads_value(&value)?),
+                    "flags" => config.flags = day_flags(&value)?,
+                    _ => return Err(SimpleError::new(format!("Unknown key {}", key)).into()),
+                }
+                Ok(config)
+            })
+    }
+
+    pub fn day_flags(&self, day: &str) -> &[String] {
+        self.flags
+            .get(day)
+            .map(|flags| flags.as_slice())
+            .unwrap_or(&[])
+    }
+}
+
+pub fn find_config_file(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|directory| directory.join(CONFIG_FILE_NAME))
+        .find(|path| path.is_file())
+}
+
+fn string_value(key: &str, value: &Value) -> Result<String, Error> {
+    value
+        .as_str()
+        .map(String::from)
+        .ok_or_else(|| SimpleError::new(format!("Expected {} to be a string", key)).into())
+}
+
+fn threads_value(value: &Value) -> Result<usize, Error> {
+    value
+        .as_integer()
+        .and_then(|threads| usize::try_from(threads).ok())
+        .filter(|threads| *threads > 0)
+        .ok_or_else(|| SimpleError::new("Expected threads to be a positive integer").into())
+}
+
+fn day_flags(value: &Value) -> Result<HashMap<String, Vec<String>>, Error> {
+    value
+        .as_table()
+        .ok_or_else::<Error, _>(|| SimpleError::new("Expected flags to be a table").into())?
+        .iter()
+        .map(|(day, flags)| {
+            flags
+                .as_array()
+                .and_then(|flags| {
+                    flags
+                        .iter()
+                        .map(|flag| flag.as_str().map(String::from))
+                        .collect::<Option<Vec<String>>>()
+                })
+                .map(|flags| (day.clone(), flags))
+                .ok_or_else(|| {
+                    SimpleError::new(format!("Expected the flags for {} to be strings", day)).into()
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_config() {
+        let config = Config::parse(
+            "input_dir = \"inputs\"\n\
+            output_format = \"quiet\"\n\
+            results_log = \"runs.jsonl\"\n\
+            session = \"abc123\"\n\
+            threads = 4\n\
+            \n\
+            [flags]\n\
+            smoke-basin = [\"--basin-report\", \"-l\", \"labels.txt\"]\n",
+        )
+        .unwrap();
+
+        assert_eq!(config.input_dir, Some("inputs".to_string()));
+        assert_eq!(config.output_format, Some(OutputFormat::Quiet));
+        assert_eq!(config.results_log, Some("runs.jsonl".to_string()));
+        assert_eq!(config.session, Some("abc123".to_string()));
+        assert_eq!(config.threads, Some(4));
+        assert_eq!(
+            config.day_flags("smoke-basin"),
+            &["--basin-report", "-l", "labels.txt"]
+        );
+        assert!(config.day_flags("sonar-sweep").is_empty());
+    }
+
+    #[test]
+    fn parse_rejects_unknown_keys() {
+        assert!(Config::parse("workers = 4\n").is_err());
+        assert!(Config::parse("threads = 0\n").is_err());
+        assert!(Config::parse("output_format = \"xml\"\n").is_err());
+        assert!(Config::parse("[flags]\nsmoke-basin = \"-b\"\n").is_err());
+    }
+
+    #[test]
+    fn find_config_file_searches_parents() {
+        let root = std::env::temp_dir().join(format!("aoc_config_search_{}", std::process::id()));
+        let nested = root.join("a").join("b");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(root.join(CONFIG_FILE_NAME), "").unwrap();
+
+        assert_eq!(find_config_file(&nested), Some(root.join(CONFIG_FILE_NAME)));
+    }
+}
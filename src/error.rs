@@ -0,0 +1,114 @@
+use std::fmt;
+use std::io;
+
+// Every error the library returns. The binary wraps them in anyhow along with its own.
+#[derive(Debug)]
+pub enum AocError {
+    Io(io::Error),
+    // Lines and columns count from 1. The context is the rest of the line the parser stopped on.
+    Parse {
+        line: usize,
+        column: usize,
+        context: String,
+    },
+    Validation(String),
+    Timeout,
+    Unsupported(String),
+}
+
+// Enough of the line to find it in the input without printing all of a very long one.
+const CONTEXT_LENGTH: usize = 40;
+
+impl AocError {
+    pub fn validation(message: impl Into<String>) -> Self {
+        AocError::Validation(message.into())
+    }
+
+    pub fn unsupported(message: impl Into<String>) -> Self {
+        AocError::Unsupported(message.into())
+    }
+
+    // rest has to be the end of input, as nom leaves it in its errors.
+    pub fn parse(input: &str, rest: &str) -> Self {
+        let consumed = &input[..input.len() - rest.len()];
+        let line_start = consumed.rfind('\n').map_or(0, |index| index + 1);
+
+        AocError::Parse {
+            line: consumed.matches('\n').count() + 1,
+            column: consumed[line_start..].chars().count() + 1,
+            context: rest
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .chars()
+                .take(CONTEXT_LENGTH)
+                .collect(),
+        }
+    }
+
+    // For a parser run on a single line, moves the error to that line of the whole input.
+    pub fn on_line(self, line: usize) -> Self {
+        match self {
+            AocError::Parse {
+                column, context, ..
+            } => AocError::Parse {
+                line,
+                column,
+                context,
+            },
+            other => other,
+        }
+    }
+
+    pub fn from_nom(input: &str, error: nom::Err<nom::error::Error<&str>>) -> Self {
+        match error {
+            nom::Err::Error(error) | nom::Err::Failure(error) => {
+                AocError::parse(input, error.input)
+            }
+            nom::Err::Incomplete(_) => AocError::parse(input, ""),
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::Io(error) => error.fmt(f),
+            AocError::Parse {
+                line,
+                column,
+                context,
+            } if context.is_empty() => write!(
+                f,
+                "Parse error at line {}, column {}: unexpected end of line",
+                line, column
+            ),
+            AocError::Parse {
+                line,
+                column,
+                context,
+            } => write!(
+                f,
+                "Parse error at line {}, column {}: {}",
+                line, column, context
+            ),
+            AocError::Validation(message) | AocError::Unsupported(message) => f.write_str(message),
+            AocError::Timeout => f.write_str("Timed out before the solver finished"),
+        }
+    }
+}
+
+impl std::error::Error for AocError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AocError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for AocError {
+    fn from(error: io::Error) -> Self {
+        AocError::Io(error)
+    }
+}
@@ -0,0 +1,213 @@
+use anyhow::Error;
+use serde_json::{json, Value};
+use simple_error::SimpleError;
+use std::{
+    collections::HashMap,
+    fs::{self, File, OpenOptions},
+    io::{BufReader, ErrorKind, Read, Write},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+pub const DEFAULT_RESULTS_LOG: &str = "results.jsonl";
+
+// One solved part as it's kept in the results log, one JSON object per line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunRecord {
+    pub timestamp: u64,
+    pub day: String,
+    pub part: String,
+    pub arguments: String,
+    pub input_hash: String,
+    pub answer: String,
+    pub elapsed: Duration,
+}
+
+impl RunRecord {
+    pub fn new(
+        day: &str,
+        part: &str,
+        arguments: &str,
+        input_hash: &str,
+        answer: &str,
+        elapsed: Duration,
+    ) -> RunRecord {
+        RunRecord {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|now| now.as_secs())
+                .unwrap_or(0u64),
+            day: day.to_string(),
+            part: part.to_string(),
+            arguments: arguments.to_string(),
+            input_hash: input_hash.to_string(),
+            answer: answer.to_string(),
+            elapsed,
+        }
+    }
+
+    // Runs with custom arguments only compare against runs with the same arguments.
+    fn comparison_key(&self) -> (&str, &str, &str, &str) {
+        let arguments = if self.part == "custom" {
+            self.arguments.as_str()
+        } else {
+            ""
+        };
+        (&self.day, &self.part, arguments, &self.input_hash)
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "timestamp": self.timestamp,
+            "day": self.day,
+            "part": self.part,
+            "arguments": self.arguments,
+            "input_hash": self.input_hash,
+            "answer": self.answer,
+            "elapsed_ns": self.elapsed.as_nanos() as u64,
+        })
+    }
+
+    fn from_json(value: &Value) -> Option<RunRecord> {
+        Some(RunRecord {
+            timestamp: value["timestamp"].as_u64()?,
+            day: value["day"].as_str()?.to_string(),
+            part: value["part"].as_str()?.to_string(),
+            arguments: value["arguments"].as_str()?.to_string(),
+            input_hash: value["input_hash"].as_str()?.to_string(),
+            answer: value["answer"].as_str()?.to_string(),
+            elapsed: Duration::from_nanos(value["elapsed_ns"].as_u64()?),
+        })
+    }
+}
+
+pub fn append_record(path: &str, record: &RunRecord) -> Result<(), Error> {
+    let mut log = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(log, "{}", record.to_json())?;
+    Ok(())
+}
+
+// A log that doesn't exist yet has no runs in it.
+pub fn read_records(path: &str) -> Result<Vec<RunRecord>, Error> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line)
+                .ok()
+                .and_then(|value| RunRecord::from_json(&value))
+                .ok_or_else(|| {
+                    SimpleError::new(format!(
+                        "Line {} of {} is not a run record",
+                        index + 1,
+                        path
+                    ))
+                    .into()
+                })
+        })
+        .collect()
+}
+
+// The answer each record replaced, for records whose answer differs from the previous run of the
+// same day and part on the same input.
+pub fn changed_answers(records: &[RunRecord]) -> Vec<Option<String>> {
+    let mut last_answers: HashMap<(&str, &str, &str, &str), &str> = HashMap::new();
+
+    records
+        .iter()
+        .map(|record| {
+            last_answers
+                .insert(record.comparison_key(), &record.answer)
+                .filter(|previous| *previous != record.answer)
+                .map(String::from)
+        })
+        .collect()
+}
+
+// FNV-1a, read a chunk at a time so inputs too big to hold in memory can still be hashed. Unlike
+// the standard library's hasher it's guaranteed to give the same value on every build.
+pub fn hash_file(path: &str) -> Result<String, Error> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut buffer = [0u8; 64 * 1024];
+    let mut hash = 0xcbf29ce484222325u64;
+
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        buffer[..read].iter().for_each(|byte| {
+            hash ^= *byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        });
+    }
+
+    Ok(format!("{:016x}", hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_round_trip() {
+        let path = std::env::temp_dir().join(format!(
+            "history_records_round_trip_{}.jsonl",
+            std::process::id()
+        ));
+        let path = path.to_str().unwrap();
+        let _ = fs::remove_file(path);
+        let record = RunRecord::new(
+            "sonar-sweep",
+            "1",
+            "part1",
+            "0123456789abcdef",
+            "7",
+            Duration::from_micros(15),
+        );
+
+        append_record(path, &record).unwrap();
+        append_record(path, &record).unwrap();
+
+        assert_eq!(read_records(path).unwrap(), vec![record.clone(), record]);
+    }
+
+    #[test]
+    fn changed_answers_compare_matching_runs() {
+        let record = |part: &str, input_hash: &str, answer: &str| {
+            RunRecord::new("lanternfish", part, "", input_hash, answer, Duration::ZERO)
+        };
+        let records = vec![
+            record("1", "aa", "5934"),
+            record("2", "aa", "26984457539"),
+            record("1", "bb", "1"),
+            record("1", "aa", "5934"),
+            record("1", "aa", "5935"),
+        ];
+
+        assert_eq!(
+            changed_answers(&records),
+            vec![None, None, None, None, Some("5934".to_string())]
+        );
+    }
+
+    #[test]
+    fn hash_file_is_stable() {
+        let path = std::env::temp_dir().join(format!(
+            "history_hash_file_is_stable_{}.txt",
+            std::process::id()
+        ));
+        fs::write(&path, "a").unwrap();
+
+        assert_eq!(
+            hash_file(path.to_str().unwrap()).unwrap(),
+            "af63dc4c8601ec8c"
+        );
+    }
+}
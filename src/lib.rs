@@ -1,112 +1,1374 @@
 #![feature(const_fn_fn_ptr_basics)]
+#![feature(map_first_last)]
 
-use anyhow::Error;
-use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+pub mod error;
+pub mod math;
+pub mod problems;
+
+pub use error::AocError;
+pub use math::random;
+pub use math::rotation;
+pub use math::seven_segment;
+pub use math::snailfish;
+
+use clap::{value_t_or_exit, App, AppSettings, Arg, ArgMatches, SubCommand};
 use nom::branch::alt;
-use nom::bytes::complete::tag;
+use nom::bytes::complete::{tag, take_while1};
+use nom::character::complete::{line_ending, none_of};
+use nom::combinator::map_opt;
 use nom::combinator::recognize;
-use nom::sequence::pair;
+use nom::multi::{many0, many1, separated_list0};
+use nom::sequence::{pair, terminated};
 use nom::{character::complete::digit1, combinator::map_res, IResult};
-use simple_error::SimpleError;
+use num_bigint::{BigInt, BigUint};
+use num_integer::{Integer, Roots};
+use num_traits::{CheckedAdd, CheckedMul, CheckedSub};
+use random::SplitMix64;
+use std::cell::{Cell, RefCell};
+use std::collections::{hash_map, BTreeMap, HashMap, VecDeque};
 use std::fmt;
-use std::fs::File;
-use std::io::Read;
-use std::ops::Sub;
+#[cfg(feature = "cli")]
+use std::fs::{self, File};
+use std::hash::Hash;
+use std::io::{self, BufRead};
+#[cfg(feature = "cli")]
+use std::io::{BufReader, Read};
+use std::iter::{self, FromIterator};
+use std::num::ParseIntError;
+use std::ops::{Add, Sub};
+#[cfg(feature = "cli")]
+use std::path::{self, Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+use strum_macros::{EnumString, EnumVariantNames};
+
+pub const FIRST_YEAR: usize = 2021;
+
+pub const STREAMING_THRESHOLD_BYTES: u64 = 256 * 1024 * 1024;
+
+#[derive(PartialEq, Eq)]
+pub enum CommandResult {
+    Isize(isize),
+    Usize(usize),
+    I128(i128),
+    U128(u128),
+    Big(BigInt),
+    // Answers that aren't integers, such as fractions, already formatted.
+    Text(String),
+    Labeled(Vec<(String, CommandResult)>),
+}
+
+impl fmt::Debug for CommandResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandResult::Isize(val) => val.fmt(f),
+            CommandResult::Usize(val) => val.fmt(f),
+            CommandResult::I128(val) => val.fmt(f),
+            CommandResult::U128(val) => val.fmt(f),
+            CommandResult::Big(val) => val.fmt(f),
+            CommandResult::Text(val) => f.write_str(val),
+            CommandResult::Labeled(values) => {
+                values
+                    .iter()
+                    .enumerate()
+                    .try_for_each(|(index, (label, val))| {
+                        if index > 0 {
+                            writeln!(f)?;
+                        }
+                        write!(f, "{}: ", label)?;
+                        val.fmt(f)
+                    })
+            }
+        }
+    }
+}
+
+impl From<isize> for CommandResult {
+    fn from(item: isize) -> Self {
+        CommandResult::Isize(item)
+    }
+}
+
+impl From<usize> for CommandResult {
+    fn from(item: usize) -> Self {
+        CommandResult::Usize(item)
+    }
+}
+
+impl From<i128> for CommandResult {
+    fn from(item: i128) -> Self {
+        CommandResult::I128(item)
+    }
+}
+
+impl From<u128> for CommandResult {
+    fn from(item: u128) -> Self {
+        CommandResult::U128(item)
+    }
+}
+
+impl From<BigInt> for CommandResult {
+    fn from(item: BigInt) -> Self {
+        CommandResult::Big(item)
+    }
+}
+
+impl From<BigUint> for CommandResult {
+    fn from(item: BigUint) -> Self {
+        CommandResult::Big(item.into())
+    }
+}
+
+// Counts small enough for a usize keep the usual result, so answers compare the same whichever
+// width a day counted them in. Only counts that don't fit use a wider variant.
+pub fn count_result<T>(count: T) -> CommandResult
+where
+    T: Clone + TryInto<usize> + Into<CommandResult>,
+{
+    match count.clone().try_into() {
+        Ok(count) => CommandResult::Usize(count),
+        Err(_) => count.into(),
+    }
+}
+
+impl From<String> for CommandResult {
+    fn from(item: String) -> Self {
+        CommandResult::Text(item)
+    }
+}
+
+impl From<Vec<(String, CommandResult)>> for CommandResult {
+    fn from(item: Vec<(String, CommandResult)>) -> Self {
+        CommandResult::Labeled(item)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counter<K: Hash + Eq> {
+    counts: HashMap<K, usize>,
+}
+
+impl<K: Hash + Eq> Counter<K> {
+    pub fn new() -> Self {
+        Counter {
+            counts: HashMap::new(),
+        }
+    }
+
+    pub fn from_counts(counts: impl IntoIterator<Item = (K, usize)>) -> Self {
+        counts
+            .into_iter()
+            .fold(Counter::new(), |mut counter, (key, count)| {
+                counter.add(key, count);
+                counter
+            })
+    }
+
+    pub fn add(&mut self, key: K, count: usize) {
+        *self.counts.entry(key).or_insert(0usize) += count;
+    }
+
+    pub fn get(&self, key: &K) -> usize {
+        *self.counts.get(key).unwrap_or(&0usize)
+    }
+
+    pub fn merge(&mut self, other: Counter<K>) {
+        other
+            .into_iter()
+            .for_each(|(key, count)| self.add(key, count));
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    pub fn keys(&self) -> hash_map::Keys<'_, K, usize> {
+        self.counts.keys()
+    }
+
+    pub fn iter(&self) -> hash_map::Iter<'_, K, usize> {
+        self.counts.iter()
+    }
+
+    pub fn most_common(&self) -> Vec<(&K, usize)> {
+        let mut most_common: Vec<(&K, usize)> = self
+            .counts
+            .iter()
+            .map(|(key, count)| (key, *count))
+            .collect();
+        most_common.sort_by(|(_, a), (_, b)| b.cmp(a));
+        most_common
+    }
+}
+
+impl<K: Hash + Eq + Ord> Counter<K> {
+    // most_common, with ties in key order in deterministic mode rather than in hash order.
+    pub fn most_common_ordered(&self) -> Vec<(&K, usize)> {
+        if deterministic() {
+            self.most_common_in_key_order()
+        } else {
+            self.most_common()
+        }
+    }
+
+    fn most_common_in_key_order(&self) -> Vec<(&K, usize)> {
+        let mut most_common = self.most_common();
+        most_common.sort_by(|(a_key, a), (b_key, b)| b.cmp(a).then(a_key.cmp(b_key)));
+        most_common
+    }
+}
+
+impl<K: Hash + Eq> Default for Counter<K> {
+    fn default() -> Self {
+        Counter::new()
+    }
+}
+
+impl<K: Hash + Eq> FromIterator<K> for Counter<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        Counter::from_counts(iter.into_iter().map(|key| (key, 1usize)))
+    }
+}
+
+impl<K: Hash + Eq> IntoIterator for Counter<K> {
+    type Item = (K, usize);
+    type IntoIter = hash_map::IntoIter<K, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.into_iter()
+    }
+}
+
+impl<'a, K: Hash + Eq> IntoIterator for &'a Counter<K> {
+    type Item = (&'a K, &'a usize);
+    type IntoIter = hash_map::Iter<'a, K, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.iter()
+    }
+}
+
+// Iterator adapters for the patterns several days share, available on every iterator.
+pub trait IteratorExt: Iterator + Sized {
+    // Each item alongside the one after it: a, b, c gives (a, b) then (b, c).
+    fn pairwise(self) -> Pairwise<Self>
+    where
+        Self::Item: Clone,
+    {
+        Pairwise {
+            iter: self,
+            previous: None,
+        }
+    }
+
+    // Every pair of items at different positions, each pair once and in order: a, b, c gives
+    // (a, b), (a, c) then (b, c).
+    fn combinations2(self) -> std::vec::IntoIter<(Self::Item, Self::Item)>
+    where
+        Self::Item: Clone,
+    {
+        let items: Vec<Self::Item> = self.collect();
+        items
+            .iter()
+            .enumerate()
+            .flat_map(|(index, first)| {
+                items[(index + 1)..]
+                    .iter()
+                    .map(move |second| (first.clone(), second.clone()))
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    // The sum of every run of size consecutive items, kept as a running total rather than
+    // re-adding each window. A size of 0 gives nothing.
+    fn window_sums(self, size: usize) -> WindowSums<Self>
+    where
+        Self::Item: Copy + Add<Output = Self::Item> + Sub<Output = Self::Item>,
+    {
+        WindowSums {
+            iter: self,
+            size,
+            window: VecDeque::with_capacity(size),
+            sum: None,
+        }
+    }
+}
+
+impl<I: Iterator> IteratorExt for I {}
+
+pub struct Pairwise<I: Iterator> {
+    iter: I,
+    previous: Option<I::Item>,
+}
+
+impl<I: Iterator> Iterator for Pairwise<I>
+where
+    I::Item: Clone,
+{
+    type Item = (I::Item, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let previous = match self.previous.take() {
+            Some(previous) => previous,
+            None => self.iter.next()?,
+        };
+        let next = self.iter.next()?;
+        self.previous = Some(next.clone());
+        Some((previous, next))
+    }
+}
+
+pub struct WindowSums<I: Iterator> {
+    iter: I,
+    size: usize,
+    window: VecDeque<I::Item>,
+    sum: Option<I::Item>,
+}
+
+impl<I: Iterator> Iterator for WindowSums<I>
+where
+    I::Item: Copy + Add<Output = I::Item> + Sub<Output = I::Item>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.size == 0 {
+            return None;
+        }
+
+        while self.window.len() < self.size {
+            let item = self.iter.next()?;
+            self.window.push_back(item);
+            self.sum = Some(match self.sum {
+                Some(sum) => sum + item,
+                None => item,
+            });
+        }
+
+        let sum = self.sum?;
+        let oldest = self.window.pop_front()?;
+        self.sum = if self.window.is_empty() {
+            None
+        } else {
+            Some(sum - oldest)
+        };
+        Some(sum)
+    }
+}
+
+// A set of cells in a fixed size grid, one bit per cell, for tracking which cells a search has
+// visited without hashing or allocating per cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitGrid {
+    rows: usize,
+    columns: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    pub fn new(rows: usize, columns: usize) -> Self {
+        BitGrid {
+            rows,
+            columns,
+            words: vec![0u64; (rows * columns).div_ceil(64)],
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    fn position(&self, row: usize, column: usize) -> (usize, u64) {
+        assert!(
+            row < self.rows && column < self.columns,
+            "{},{} is outside a {} by {} grid",
+            row,
+            column,
+            self.rows,
+            self.columns
+        );
+        let index = row * self.columns + column;
+        (index / 64, 1u64 << (index % 64))
+    }
+
+    pub fn contains(&self, row: usize, column: usize) -> bool {
+        let (word, bit) = self.position(row, column);
+        self.words[word] & bit != 0
+    }
+
+    // Returns whether the cell was newly added, like HashSet::insert.
+    pub fn insert(&mut self, row: usize, column: usize) -> bool {
+        let (word, bit) = self.position(row, column);
+        let added = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        added
+    }
+
+    pub fn remove(&mut self, row: usize, column: usize) -> bool {
+        let (word, bit) = self.position(row, column);
+        let removed = self.words[word] & bit != 0;
+        self.words[word] &= !bit;
+        removed
+    }
+
+    pub fn clear(&mut self) {
+        self.words.iter_mut().for_each(|word| *word = 0u64);
+    }
+
+    pub fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|word| *word == 0u64)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.rows)
+            .flat_map(move |row| (0..self.columns).map(move |column| (row, column)))
+            .filter(move |(row, column)| self.contains(*row, *column))
+    }
+}
+
+// A compass heading on a grid. x grows to the east and y to the south, the way the grids in the
+// inputs are printed, so north is up the page.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    // Clockwise from north.
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    pub const CARDINALS: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    // The (x, y) step one space in this direction takes.
+    pub fn to_vector(self) -> (isize, isize) {
+        match self {
+            Direction::North => (0, -1),
+            Direction::NorthEast => (1, -1),
+            Direction::East => (1, 0),
+            Direction::SouthEast => (1, 1),
+            Direction::South => (0, 1),
+            Direction::SouthWest => (-1, 1),
+            Direction::West => (-1, 0),
+            Direction::NorthWest => (-1, -1),
+        }
+    }
+
+    fn rotate(self, eighths: usize) -> Direction {
+        Direction::ALL[(self as usize + eighths) % Direction::ALL.len()]
+    }
+
+    pub fn turn_left(self) -> Direction {
+        self.rotate(6)
+    }
+
+    pub fn turn_right(self) -> Direction {
+        self.rotate(2)
+    }
+
+    pub fn opposite(self) -> Direction {
+        self.rotate(4)
+    }
+
+    // None if the move would leave the first quadrant or overflow.
+    pub fn offset(self, (x, y): (usize, usize), distance: usize) -> Option<(usize, usize)> {
+        let (dx, dy) = self.to_vector();
+        let distance = isize::try_from(distance).ok()?;
+        Some((
+            x.checked_add_signed(dx.checked_mul(distance)?)?,
+            y.checked_add_signed(dy.checked_mul(distance)?)?,
+        ))
+    }
+
+    // One space in this direction on a grid whose edges wrap around to the other side.
+    pub fn wrapping_offset(
+        self,
+        (x, y): (usize, usize),
+        (width, height): (usize, usize),
+    ) -> (usize, usize) {
+        let (dx, dy) = self.to_vector();
+        (
+            (x + width).wrapping_add_signed(dx) % width,
+            (y + height).wrapping_add_signed(dy) % height,
+        )
+    }
+}
+
+// Accepts the compass abbreviations (N, NE, ...) or the names (north, north-east, ...), in any
+// case.
+impl FromStr for Direction {
+    type Err = AocError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let name = input.to_ascii_lowercase().replace('-', "");
+        Direction::ALL
+            .iter()
+            .find(|direction| {
+                let abbreviation = direction.to_string().to_ascii_lowercase();
+                let full_name = format!("{:?}", direction).to_ascii_lowercase();
+                name == abbreviation || name == full_name
+            })
+            .copied()
+            .ok_or_else(|| AocError::validation(format!("{} is not a direction", input)))
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Direction::North => "N",
+            Direction::NorthEast => "NE",
+            Direction::East => "E",
+            Direction::SouthEast => "SE",
+            Direction::South => "S",
+            Direction::SouthWest => "SW",
+            Direction::West => "W",
+            Direction::NorthWest => "NW",
+        })
+    }
+}
+
+pub fn parse_direction(input: &str) -> IResult<&str, Direction> {
+    map_res(
+        take_while1(|c: char| c.is_ascii_alphabetic() || c == '-'),
+        Direction::from_str,
+    )(input)
+}
+
+thread_local! {
+    static DEADLINE: Cell<Deadline> = Cell::new(Deadline::none());
+}
+
+// Solvers read the current deadline once and check it in their long loops, giving up early once it
+// has passed. The result is then reported as a timeout rather than an answer. The deadline belongs
+// to the thread running the day, so a solver that spawns threads sets it on each of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deadline {
+    expires_at: Option<Instant>,
+}
+
+impl Deadline {
+    pub fn none() -> Self {
+        Deadline {
+            expires_at: Option::None,
+        }
+    }
+
+    pub fn after(budget: Duration) -> Self {
+        Deadline {
+            expires_at: Option::Some(Instant::now() + budget),
+        }
+    }
+
+    pub fn current() -> Self {
+        DEADLINE.with(|deadline| deadline.get())
+    }
+
+    pub fn set_current(self) {
+        DEADLINE.with(|deadline| deadline.set(self))
+    }
+
+    pub fn expired(&self) -> bool {
+        self.expires_at
+            .map(|expires_at| Instant::now() >= expires_at)
+            .unwrap_or(false)
+    }
+}
+
+thread_local! {
+    static REPORTED_ERROR: RefCell<Option<AocError>> = const { RefCell::new(None) };
+}
+
+// Run can't return an error, so a day that fails partway, such as writing an export or reading
+// live input, hands the error to this instead. The run reports the first one in place of its
+// answer.
+pub fn report_error(error: AocError) {
+    REPORTED_ERROR.with(|reported_error| {
+        reported_error.borrow_mut().get_or_insert(error);
+    });
+}
+
+// Days that write extra files, such as an export or a CSV, report a failed write with the path.
+pub fn report_output(path: &str, result: io::Result<()>) {
+    if let Err(error) = result {
+        report_error(AocError::Io(io::Error::new(
+            error.kind(),
+            format!("{} can't be written: {}", path, error),
+        )));
+    }
+}
+
+static LOG_LEVEL: AtomicU8 = AtomicU8::new(LogLevel::Off as u8);
+
+// How much diagnostic detail solvers write to stderr, raised by each -v. The answer on stdout is
+// the same at every level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Off,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    pub fn from_verbosity(occurrences: u64) -> Self {
+        match occurrences {
+            0 => LogLevel::Off,
+            1 => LogLevel::Debug,
+            _ => LogLevel::Trace,
+        }
+    }
+
+    pub fn current() -> Self {
+        match LOG_LEVEL.load(Ordering::Relaxed) {
+            0 => LogLevel::Off,
+            1 => LogLevel::Debug,
+            _ => LogLevel::Trace,
+        }
+    }
+
+    pub fn set_current(self) {
+        LOG_LEVEL.store(self as u8, Ordering::Relaxed)
+    }
+
+    pub fn enabled(self) -> bool {
+        self != LogLevel::Off && self <= LogLevel::current()
+    }
+}
+
+// Writes the message to stderr when the level is enabled, without formatting it otherwise.
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)*) => {
+        if $level.enabled() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+thread_local! {
+    static PROFILE: RefCell<Option<Profile>> = const { RefCell::new(None) };
+}
+
+// The time spent in each stack of scopes while a profile is being recorded. Only scopes opened on
+// the thread that started the profile are recorded.
+struct Profile {
+    // The open scopes, outermost first, with when each started and how long the scopes inside it
+    // have taken so far.
+    open: Vec<(String, Instant, Duration)>,
+    // The time spent in each stack itself, not counting the scopes inside it.
+    stacks: BTreeMap<String, Duration>,
+}
+
+impl Profile {
+    fn close_scope(&mut self) {
+        if let Some((name, started, inner)) = self.open.pop() {
+            let elapsed = started.elapsed();
+            let stack = self
+                .open
+                .iter()
+                .map(|(name, _, _)| name.as_str())
+                .chain(iter::once(name.as_str()))
+                .collect::<Vec<&str>>()
+                .join(";");
+            *self.stacks.entry(stack).or_default() += elapsed.saturating_sub(inner);
+
+            if let Some((_, _, parent_inner)) = self.open.last_mut() {
+                *parent_inner += elapsed;
+            }
+        }
+    }
+}
+
+// Starts recording a profile on this thread, with every scope inside the root.
+pub fn start_profile(root: &str) {
+    PROFILE.with(|profile| {
+        *profile.borrow_mut() = Some(Profile {
+            open: vec![(root.to_string(), Instant::now(), Duration::ZERO)],
+            stacks: BTreeMap::new(),
+        })
+    });
+}
+
+// Stops recording and gives the profile in the collapsed stack format flame graph tools read, one
+// line per stack of scopes joined by ; and the microseconds spent in it.
+pub fn finish_profile() -> String {
+    PROFILE.with(|profile| {
+        profile
+            .borrow_mut()
+            .take()
+            .map(|mut profile| {
+                while !profile.open.is_empty() {
+                    profile.close_scope();
+                }
+                profile
+                    .stacks
+                    .iter()
+                    .map(|(stack, elapsed)| format!("{} {}\n", stack, elapsed.as_micros()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    })
+}
+
+// Times everything until it's dropped as a scope of the profile being recorded. Without one it
+// does nothing.
+#[must_use]
+pub struct ProfileScope {
+    recording: bool,
+}
+
+pub fn profile_scope(name: &str) -> ProfileScope {
+    let recording = PROFILE.with(|profile| {
+        profile
+            .borrow_mut()
+            .as_mut()
+            .map(|profile| {
+                profile
+                    .open
+                    .push((name.to_string(), Instant::now(), Duration::ZERO))
+            })
+            .is_some()
+    });
+
+    ProfileScope { recording }
+}
+
+impl Drop for ProfileScope {
+    fn drop(&mut self) {
+        if self.recording {
+            PROFILE.with(|profile| {
+                if let Some(profile) = profile.borrow_mut().as_mut() {
+                    profile.close_scope();
+                }
+            });
+        }
+    }
+}
+
+static DETERMINISTIC: AtomicBool = AtomicBool::new(false);
+
+// In deterministic mode anything that would come out in hash order is sorted first, so the same
+// run always gives byte identical output.
+pub fn deterministic() -> bool {
+    DETERMINISTIC.load(Ordering::Relaxed)
+}
+
+pub fn set_deterministic(deterministic: bool) {
+    DETERMINISTIC.store(deterministic, Ordering::Relaxed)
+}
+
+static THREADS: AtomicUsize = AtomicUsize::new(0);
+
+// How many threads a solver that splits up its work uses, set with --threads or threads in
+// aoc.toml. One per core when neither is given.
+pub fn threads() -> usize {
+    match THREADS.load(Ordering::Relaxed) {
+        0 => thread::available_parallelism().map_or(1usize, |threads| threads.get()),
+        threads => threads,
+    }
+}
+
+pub fn set_threads(threads: usize) {
+    THREADS.store(threads, Ordering::Relaxed)
+}
+
+static SEED: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    static USED_SEED: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+// The seed random modes use when they aren't given one of their own, set with --seed and 0
+// otherwise.
+pub fn seed() -> u64 {
+    SEED.load(Ordering::Relaxed)
+}
+
+pub fn set_seed(seed: u64) {
+    SEED.store(seed, Ordering::Relaxed)
+}
+
+// A day's own seed argument when it was passed, otherwise the global seed.
+pub fn seed_argument(arguments: &ArgMatches, name: &str) -> u64 {
+    if arguments.occurrences_of(name) > 0 {
+        value_t_or_exit!(arguments.value_of(name), u64)
+    } else {
+        seed()
+    }
+}
+
+// Every random mode builds its generator here, so the seed can be printed with the results and an
+// interesting run repeated.
+pub fn seeded_random(seed: u64) -> SplitMix64 {
+    USED_SEED.with(|used| used.set(Some(seed)));
+    SplitMix64::new(seed)
+}
+
+// The seed of the last generator built on this thread since the last call, if there was one.
+pub fn take_used_seed() -> Option<u64> {
+    USED_SEED.with(|used| used.take())
+}
+
+pub trait Command: Sync {
+    fn sub_command(&self) -> App<'static, 'static>;
+
+    fn name(&self) -> &str;
+
+    fn folder_name(&self) -> &str;
+
+    fn year(&self) -> usize;
+
+    fn input_folder(&self) -> String;
+
+    fn long_about(&self) -> &str;
+
+    fn examples(&self) -> &[Example];
+
+    fn example_input(&self) -> Option<&str>;
+
+    #[cfg(feature = "cli")]
+    fn run(&self, arguments: &ArgMatches, file: &String) -> Result<CommandResult, AocError>;
+
+    fn run_with_input(
+        &self,
+        arguments: &ArgMatches,
+        input: &String,
+    ) -> Result<CommandResult, AocError>;
+
+    fn solve(&self, part: u8, input: &str) -> Result<CommandResult, AocError>;
+
+    #[cfg(feature = "cli")]
+    fn run_both(&self, file: &String) -> Result<Vec<(CommandResult, Duration)>, AocError>;
+
+    fn run_both_with_input(
+        &self,
+        input: &String,
+    ) -> Result<Vec<(CommandResult, Duration)>, AocError>;
+
+    #[cfg(feature = "cli")]
+    fn validate(&self, file: &String) -> Result<String, AocError>;
+
+    fn validate_input(&self, input: &String) -> Result<String, AocError>;
+
+    #[cfg(feature = "cli")]
+    fn parse_only(&self, file: &String) -> Result<(String, Duration), AocError>;
+
+    // Parses the input without checking or solving it, giving the Debug of the parsed model and
+    // how long the parse took.
+    fn parse_only_input(&self, input: &String) -> Result<(String, Duration), AocError>;
+
+    fn generate(&self, seed: u64, size: usize) -> Result<String, AocError>;
+
+    // Parses and checks the input once for a part, returning a run that can be repeated to time
+    // the solve alone.
+    fn prepare(&self, part: u8, input: &str) -> Result<PreparedRun<'_>, AocError>;
+}
+
+// Solves a prepared part on a fresh copy of its parsed input, giving the answer and how long the
+// solve took. Copying the input isn't timed.
+pub type PreparedRun<'a> = Box<dyn Fn() -> (CommandResult, Duration) + 'a>;
+
+pub type StreamFn<A> = fn(A, &mut dyn BufRead) -> Result<CommandResult, AocError>;
+
+pub type GenerateFn = fn(&mut SplitMix64, usize) -> String;
+
+pub type InputCheckFn<A, T> = fn(&A, &T) -> Result<(), AocError>;
+
+// A puzzle input kept with its day along with the answer a part gives for it, so the solvers can
+// check themselves without any input files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Example {
+    pub input: &'static str,
+    pub part: u8,
+    pub expected: &'static str,
+}
+
+impl Example {
+    pub const fn new(input: &'static str, part: u8, expected: &'static str) -> Self {
+        Example {
+            input,
+            part,
+            expected,
+        }
+    }
+
+    // The answer the part gives for the input, formatted the same way as expected.
+    pub fn solve(&self, command: &dyn Command) -> Result<String, AocError> {
+        command
+            .solve(self.part, self.input)
+            .map(|result| format!("{:?}", result))
+    }
+}
+
+pub struct Problem<'a, A, T> {
+    sub_command: fn() -> App<'static, 'static>,
+    name: &'a str,
+    folder_name: &'a str,
+    year: usize,
+    parse_arguments: fn(&ArgMatches) -> A,
+    parse_file: fn(&String) -> IResult<&str, T>,
+    run: fn(A, T) -> CommandResult,
+    long_about: &'a str,
+    examples: &'a [Example],
+    // The sample input --example runs, so a day can be tried without any input files.
+    example_input: Option<&'a str>,
+    describe: Option<fn(&T) -> String>,
+    // Streaming only happens when reading input files, which the cli feature brings in.
+    #[cfg(feature = "cli")]
+    stream: Option<StreamFn<A>>,
+    #[cfg(feature = "cli")]
+    stream_if: Option<fn(&ArgMatches) -> bool>,
+    generate: Option<GenerateFn>,
+    cross_check: Option<fn(&ArgMatches) -> A>,
+    input_check: Option<InputCheckFn<A, T>>,
+}
+
+impl<'a, A, T> Problem<'a, A, T> {
+    pub const fn new(
+        sub_command: fn() -> App<'static, 'static>,
+        name: &'a str,
+        folder_name: &'a str,
+        parse_arguments: fn(&ArgMatches) -> A,
+        parse_file: fn(&String) -> IResult<&str, T>,
+        run: fn(A, T) -> CommandResult,
+    ) -> Problem<'a, A, T> {
+        Problem {
+            sub_command: sub_command,
+            name: name,
+            folder_name: folder_name,
+            year: FIRST_YEAR,
+            parse_arguments: parse_arguments,
+            parse_file: parse_file,
+            run: run,
+            long_about: "",
+            examples: &[],
+            example_input: Option::None,
+            describe: Option::None,
+            #[cfg(feature = "cli")]
+            stream: Option::None,
+            #[cfg(feature = "cli")]
+            stream_if: Option::None,
+            generate: Option::None,
+            cross_check: Option::None,
+            input_check: Option::None,
+        }
+    }
+
+    pub const fn with_year(self, year: usize) -> Self {
+        Problem { year, ..self }
+    }
+
+    pub const fn with_long_about(self, long_about: &'a str) -> Self {
+        Problem { long_about, ..self }
+    }
+
+    // The first example's input doubles as the input for --example.
+    pub const fn with_examples(self, examples: &'a [Example]) -> Self {
+        Problem {
+            examples,
+            example_input: match examples {
+                [first, ..] => Option::Some(first.input),
+                [] => Option::None,
+            },
+            ..self
+        }
+    }
+
+    pub const fn with_describe(self, describe: fn(&T) -> String) -> Self {
+        Problem {
+            describe: Option::Some(describe),
+            ..self
+        }
+    }
+
+    #[cfg(feature = "cli")]
+    pub const fn with_stream(self, stream: StreamFn<A>) -> Self {
+        Problem {
+            stream: Option::Some(stream),
+            ..self
+        }
+    }
+
+    // Without the cli feature input is never read from a file, so there's nothing to stream.
+    #[cfg(not(feature = "cli"))]
+    pub const fn with_stream(self, _stream: StreamFn<A>) -> Self {
+        self
+    }
+
+    // Streams the input whatever its size when stream_if holds, for inputs that can't be read as
+    // text such as raw binary files.
+    #[cfg(feature = "cli")]
+    pub const fn with_stream_if(self, stream_if: fn(&ArgMatches) -> bool) -> Self {
+        Problem {
+            stream_if: Option::Some(stream_if),
+            ..self
+        }
+    }
+
+    #[cfg(not(feature = "cli"))]
+    pub const fn with_stream_if(self, _stream_if: fn(&ArgMatches) -> bool) -> Self {
+        self
+    }
+
+    pub const fn with_generate(self, generate: GenerateFn) -> Self {
+        Problem {
+            generate: Option::Some(generate),
+            ..self
+        }
+    }
+
+    // alternate_arguments builds the arguments for the other strategy from the same matches, so
+    // --cross-check can run both against a single parse of the input.
+    pub const fn with_cross_check(self, alternate_arguments: fn(&ArgMatches) -> A) -> Self {
+        Problem {
+            cross_check: Option::Some(alternate_arguments),
+            ..self
+        }
+    }
+
+    // input_check can reject an input the given arguments can't handle, with an error describing
+    // why, before anything is run.
+    pub const fn with_input_check(self, input_check: InputCheckFn<A, T>) -> Self {
+        Problem {
+            input_check: Option::Some(input_check),
+            ..self
+        }
+    }
+
+    // The input parsed into the day's model, the step every run takes before solving.
+    pub fn parse(&self, input: &String) -> Result<T, AocError> {
+        complete_parsing(self.parse_file)(input)
+    }
+
+    // Solves an input that's already parsed, without the input check or deadline of a full run.
+    pub fn run_parsed(&self, arguments: A, parsed: T) -> CommandResult {
+        (self.run)(arguments, parsed)
+    }
+
+    fn part_matches(&self, part: u8) -> Result<ArgMatches<'static>, AocError> {
+        if part != 1 && part != 2 {
+            return Err(AocError::unsupported(format!(
+                "{} has no part {}",
+                self.name, part
+            )));
+        }
+
+        (self.sub_command)()
+            .get_matches_from_safe(vec![self.name.to_string(), format!("part{}", part)])
+            .map_err(|err| AocError::unsupported(err.message))
+    }
+}
+
+impl<A, T: Clone + fmt::Debug> Command for Problem<'_, A, T> {
+    fn sub_command(&self) -> App<'static, 'static> {
+        (self.sub_command)()
+    }
+
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn folder_name(&self) -> &str {
+        self.folder_name
+    }
+
+    fn year(&self) -> usize {
+        self.year
+    }
+
+    // The first year's inputs predate multi year support and live at the repository root, later
+    // years keep theirs under a folder named for the year.
+    fn input_folder(&self) -> String {
+        if self.year == FIRST_YEAR {
+            self.folder_name.to_string()
+        } else {
+            format!("{}/{}", self.year, self.folder_name)
+        }
+    }
+
+    fn long_about(&self) -> &str {
+        self.long_about
+    }
 
-pub enum CommandResult {
-    Isize(isize),
-    Usize(usize),
-}
+    fn examples(&self) -> &[Example] {
+        self.examples
+    }
 
-impl fmt::Debug for CommandResult {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            CommandResult::Isize(val) => val.fmt(f),
-            CommandResult::Usize(val) => val.fmt(f),
+    fn example_input(&self) -> Option<&str> {
+        self.example_input
+    }
+
+    #[cfg(feature = "cli")]
+    fn run(&self, arguments: &ArgMatches, file: &String) -> Result<CommandResult, AocError> {
+        if example_requested(arguments) {
+            return example_input(self)
+                .and_then(|input| self.run_with_input(arguments, &input.to_string()));
+        }
+
+        check_input_exists(self, file)?;
+        match self.stream {
+            Some(stream)
+                if fs::metadata(file)?.len() > STREAMING_THRESHOLD_BYTES
+                    || self.stream_if.is_some_and(|stream_if| stream_if(arguments)) =>
+            {
+                let _profile = profile_scope("stream");
+                File::open(file)
+                    .map_err(|e| e.into())
+                    .and_then(|file| {
+                        stream((self.parse_arguments)(arguments), &mut BufReader::new(file))
+                    })
+                    .and_then(within_deadline)
+            }
+            _ => file_to_string(file)
+                .and_then(|file_content| self.run_with_input(arguments, &file_content)),
         }
     }
-}
 
-impl From<isize> for CommandResult {
-    fn from(item: isize) -> Self {
-        CommandResult::Isize(item)
+    fn run_with_input(
+        &self,
+        arguments: &ArgMatches,
+        input: &String,
+    ) -> Result<CommandResult, AocError> {
+        let t = {
+            let _profile = profile_scope("parse");
+            self.parse(input)?
+        };
+        let parsed_arguments = (self.parse_arguments)(arguments);
+        if let Some(input_check) = self.input_check {
+            let _profile = profile_scope("input check");
+            input_check(&parsed_arguments, &t)?;
+        }
+
+        let _profile = profile_scope("solve");
+        match self.cross_check {
+            Some(alternate_arguments) if arguments.is_present("cross-check") => cross_check(
+                (self.run)(parsed_arguments, t.clone()),
+                (self.run)(alternate_arguments(arguments), t),
+            ),
+            _ => Ok((self.run)(parsed_arguments, t)),
+        }
+        .and_then(within_deadline)
     }
-}
 
-impl From<usize> for CommandResult {
-    fn from(item: usize) -> Self {
-        CommandResult::Usize(item)
+    fn solve(&self, part: u8, input: &str) -> Result<CommandResult, AocError> {
+        let arguments = self.part_matches(part)?;
+        self.run_with_input(&arguments, &input.to_string())
     }
-}
 
-pub trait Command: Sync {
-    fn sub_command(&self) -> App<'static, 'static>;
+    #[cfg(feature = "cli")]
+    fn run_both(&self, file: &String) -> Result<Vec<(CommandResult, Duration)>, AocError> {
+        check_input_exists(self, file)?;
+        file_to_string(file).and_then(|file_content| self.run_both_with_input(&file_content))
+    }
 
-    fn name(&self) -> &str;
+    fn run_both_with_input(
+        &self,
+        input: &String,
+    ) -> Result<Vec<(CommandResult, Duration)>, AocError> {
+        let t = {
+            let _profile = profile_scope("parse");
+            self.parse(input)?
+        };
 
-    fn folder_name(&self) -> &str;
+        ["part1", "part2"]
+            .iter()
+            .map(|part| {
+                let _profile = profile_scope(part);
+                let arguments = self
+                    .sub_command()
+                    .get_matches_from_safe(iter::once(self.name()).chain(iter::once(*part)))
+                    .map_err(|err| AocError::unsupported(err.message))?;
+                let parsed_arguments = (self.parse_arguments)(&arguments);
+                if let Some(input_check) = self.input_check {
+                    let _profile = profile_scope("input check");
+                    input_check(&parsed_arguments, &t)?;
+                }
+                let t = t.clone();
 
-    fn run(&self, arguments: &ArgMatches, file: &String) -> Result<CommandResult, Error>;
-}
+                let _profile = profile_scope("solve");
+                let now = Instant::now();
+                let result = within_deadline((self.run)(parsed_arguments, t))?;
+                Ok((result, now.elapsed()))
+            })
+            .collect()
+    }
 
-pub struct Problem<'a, A, T> {
-    sub_command: fn() -> App<'static, 'static>,
-    name: &'a str,
-    folder_name: &'a str,
-    parse_arguments: fn(&ArgMatches) -> A,
-    parse_file: fn(&String) -> IResult<&str, T>,
-    run: fn(A, T) -> CommandResult,
-}
+    #[cfg(feature = "cli")]
+    fn validate(&self, file: &String) -> Result<String, AocError> {
+        check_input_exists(self, file)?;
+        file_to_string(file).and_then(|file_content| self.validate_input(&file_content))
+    }
 
-impl<A, T> Problem<'_, A, T> {
-    pub const fn new<'a>(
-        sub_command: fn() -> App<'static, 'static>,
-        name: &'a str,
-        folder_name: &'a str,
-        parse_arguments: fn(&ArgMatches) -> A,
-        parse_file: fn(&String) -> IResult<&str, T>,
-        run: fn(A, T) -> CommandResult,
-    ) -> Problem<'a, A, T> {
-        Problem {
-            sub_command: sub_command,
-            name: name,
-            folder_name: folder_name,
-            parse_arguments: parse_arguments,
-            parse_file: parse_file,
-            run: run,
+    fn validate_input(&self, input: &String) -> Result<String, AocError> {
+        let mut report = vec![format!("{} lines", input.lines().count())];
+
+        if input.trim().is_empty() {
+            report.push("Anomaly: input is empty".to_string());
+        }
+
+        let (rest, t) =
+            (self.parse_file)(input).map_err(|error| AocError::from_nom(input, error))?;
+
+        if !rest.trim().is_empty() {
+            let consumed = input.len() - rest.len();
+            report.push(format!(
+                "Anomaly: {} characters were not parsed starting at line {}",
+                rest.trim_end().len(),
+                input[..consumed].matches('\n').count() + 1
+            ));
         }
+
+        if let Some(describe) = self.describe {
+            report.push(describe(&t));
+        }
+
+        Ok(report.join("\n"))
     }
-}
 
-impl<A, T> Command for Problem<'_, A, T> {
-    fn sub_command(&self) -> App<'static, 'static> {
-        (self.sub_command)()
+    #[cfg(feature = "cli")]
+    fn parse_only(&self, file: &String) -> Result<(String, Duration), AocError> {
+        check_input_exists(self, file)?;
+        file_to_string(file).and_then(|file_content| self.parse_only_input(&file_content))
     }
 
-    fn name(&self) -> &str {
-        self.name
+    fn parse_only_input(&self, input: &String) -> Result<(String, Duration), AocError> {
+        let now = Instant::now();
+        let t = {
+            let _profile = profile_scope("parse");
+            self.parse(input)?
+        };
+        let elapsed = now.elapsed();
+
+        Ok((format!("{:#?}", t), elapsed))
     }
 
-    fn folder_name(&self) -> &str {
-        self.folder_name
+    fn generate(&self, seed: u64, size: usize) -> Result<String, AocError> {
+        self.generate
+            .map(|generate| generate(&mut seeded_random(seed), size))
+            .ok_or_else(|| AocError::unsupported(format!("No input generator for {}", self.name)))
     }
 
-    fn run(&self, arguments: &ArgMatches, file: &String) -> Result<CommandResult, Error> {
-        file_to_string(file)
-            .and_then(|file_content| complete_parsing(self.parse_file)(&file_content))
-            .map(|t| (self.run)((self.parse_arguments)(arguments), t))
+    fn prepare(&self, part: u8, input: &str) -> Result<PreparedRun<'_>, AocError> {
+        let arguments = self.part_matches(part)?;
+        let t = self.parse(&input.to_string())?;
+        if let Some(input_check) = self.input_check {
+            input_check(&(self.parse_arguments)(&arguments), &t)?;
+        }
+
+        Ok(Box::new(move || {
+            let parsed_arguments = (self.parse_arguments)(&arguments);
+            let t = t.clone();
+
+            let now = Instant::now();
+            let result = self.run_parsed(parsed_arguments, t);
+            (result, now.elapsed())
+        }))
     }
 }
 
-pub fn default_sub_command<A, T>(
-    command: &Problem<A, T>,
+// Solves one part of a day from an input already in memory. Nothing here reads files, so it can be
+// used where there is no filesystem, such as a WebAssembly build without the cli feature.
+pub fn solve(day: u8, part: u8, input: &str) -> Result<String, AocError> {
+    problems::commands()
+        .get(usize::from(day).wrapping_sub(1))
+        .ok_or_else(|| AocError::unsupported(format!("No problem for day {}", day)))
+        .and_then(|command| command.solve(part, input))
+        .map(|result| format!("{:?}", result))
+}
+
+// A path for a test's scratch file, in a folder of the temp dir that only this test run uses, so
+// two runs of the suite at once don't write over each other's files.
+#[cfg(test)]
+pub(crate) fn scratch_path(name: &str) -> std::path::PathBuf {
+    let folder = std::env::temp_dir().join(format!("adventofcode2021_{}", std::process::id()));
+    std::fs::create_dir_all(&folder).unwrap();
+    folder.join(name)
+}
+
+pub fn run_problem_with_input<A, T: Clone + fmt::Debug>(
+    problem: &Problem<A, T>,
+    input: &str,
+    arguments: &[&str],
+) -> Result<CommandResult, AocError> {
+    problem
+        .sub_command()
+        .get_matches_from_safe(iter::once(problem.name()).chain(arguments.iter().copied()))
+        .map_err(|err| AocError::validation(err.message))
+        .and_then(|matches| problem.run_with_input(&matches, &input.to_string()))
+}
+
+fn example_argument() -> Arg<'static, 'static> {
+    Arg::with_name("example")
+        .long("example")
+        .help("If passed, runs on the sample input built into the binary instead of an input file.")
+}
+
+fn file_glob_argument() -> Arg<'static, 'static> {
+    Arg::with_name("file-glob")
+        .long("file-glob")
+        .value_name("PATTERN")
+        .help("If passed, runs on every file in the day's folder matching the pattern, where * matches any characters and ? any one, and prints a table of the answers.")
+        .takes_value(true)
+        .conflicts_with("example")
+}
+
+pub fn default_sub_command<A, T: Clone + fmt::Debug>(
+    command: &Problem<'static, A, T>,
     about: &'static str,
     file_help: &'static str,
     part1_docs: &'static str,
     part2_docs: &'static str,
 ) -> App<'static, 'static> {
-    SubCommand::with_name(command.name())
-        .about(about)
+    let sub_command = SubCommand::with_name(command.name()).about(about);
+    let sub_command = if command.long_about.is_empty() {
+        sub_command
+    } else {
+        sub_command.long_about(command.long_about)
+    };
+
+    let sub_command = sub_command
         .version("1.0.0")
         .setting(AppSettings::SubcommandsNegateReqs)
         .setting(AppSettings::ArgsNegateSubcommands)
@@ -115,21 +1377,341 @@ pub fn default_sub_command<A, T>(
                 .short("f")
                 .help(file_help)
                 .takes_value(true)
-                .required(true),
+                .required_unless_one(&["part", "explain", "example", "file-glob"]),
+        )
+        .arg(example_argument().conflicts_with("file"))
+        .arg(file_glob_argument().conflicts_with("file"))
+        .arg(
+            Arg::with_name("explain")
+                .long("explain")
+                .help("Prints a recap of the puzzle rules and examples, then exits."),
+        )
+        .arg(
+            Arg::with_name("part")
+                .long("part")
+                .help("Runs with the arguments for the given part. Can be combined with other arguments such as the input file.")
+                .takes_value(true)
+                .possible_values(&["1", "2"]),
         )
         .subcommand(
             SubCommand::with_name("part1")
                 .about(part1_docs)
-                .version("1.0.0"),
+                .version("1.0.0")
+                .arg(example_argument())
+                .arg(file_glob_argument()),
         )
         .subcommand(
             SubCommand::with_name("part2")
                 .about(part2_docs)
-                .version("1.0.0"),
+                .version("1.0.0")
+                .arg(example_argument())
+                .arg(file_glob_argument()),
+        )
+        .subcommand(
+            SubCommand::with_name("both")
+                .about("Runs part1 and part2 against a single parse of the input.")
+                .version("1.0.0")
+                .arg(
+                    Arg::with_name("file")
+                        .short("f")
+                        .help("Path to the input file. Defaults to the default input.")
+                        .takes_value(true),
+                )
+                .arg(example_argument().conflicts_with("file")),
+        )
+        .subcommand(
+            SubCommand::with_name("fetch")
+                .about("Downloads the puzzle input to the default input file. Needs the session cookie of a logged in adventofcode.com account.")
+                .version("1.0.0")
+                .arg(
+                    Arg::with_name("session")
+                        .short("s")
+                        .long("session")
+                        .help("The session cookie. Defaults to the AOC_SESSION environment variable, then session in aoc.toml.")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("create-dirs")
+                        .short("c")
+                        .long("create-dirs")
+                        .help("If passed, creates the day's folder when it doesn't exist yet."),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("validate")
+                .about("Parses the input and reports statistics and anomalies without solving.")
+                .version("1.0.0")
+                .arg(
+                    Arg::with_name("file")
+                        .short("f")
+                        .help("Path to the input file to validate. Defaults to the default input.")
+                        .takes_value(true),
+                ),
+        );
+
+    let sub_command = if command.cross_check.is_some() {
+        sub_command.arg(Arg::with_name("cross-check").long("cross-check").help(
+            "If passed, also runs the alternative strategy and errors if the answers differ.",
+        ))
+    } else {
+        sub_command
+    };
+
+    if command.generate.is_none() {
+        return sub_command;
+    }
+
+    sub_command.subcommand(
+        SubCommand::with_name("generate")
+            .about("Generates a random but valid input, for performance and differential testing.")
+            .version("1.0.0")
+            .arg(
+                Arg::with_name("seed")
+                    .short("s")
+                    .long("seed")
+                    .help("The seed for the generator. The same seed and size always give the same input. Defaults to the global --seed.")
+                    .takes_value(true),
+            )
+            .arg(
+                Arg::with_name("size")
+                    .short("n")
+                    .long("size")
+                    .help("How large an input to generate. What is counted depends on the problem.")
+                    .takes_value(true)
+                    .default_value("100"),
+            )
+            .arg(
+                Arg::with_name("output")
+                    .short("o")
+                    .long("output")
+                    .help("If passed, writes the input to the given path instead of stdout.")
+                    .takes_value(true),
+            ),
+    )
+}
+
+pub fn within_deadline(result: CommandResult) -> Result<CommandResult, AocError> {
+    if let Some(error) = REPORTED_ERROR.with(|reported_error| reported_error.borrow_mut().take()) {
+        Err(error)
+    } else if Deadline::current().expired() {
+        Err(AocError::Timeout)
+    } else {
+        Ok(result)
+    }
+}
+
+pub fn cross_check(result: CommandResult, other: CommandResult) -> Result<CommandResult, AocError> {
+    if result == other {
+        Ok(result)
+    } else {
+        Err(AocError::validation(format!(
+            "Cross check failed, the strategies disagree: {:?} and {:?}",
+            result, other
+        )))
+    }
+}
+
+// Rows of cells read from a block of lines. Rows can differ in length until check_grid has passed.
+pub type Grid<T> = Vec<Vec<T>>;
+
+pub fn describe_grid<T>(grid: &[Vec<T>]) -> String {
+    let row_length = grid.first().map(|row| row.len()).unwrap_or(0usize);
+    let ragged_rows: Vec<String> = grid
+        .iter()
+        .enumerate()
+        .filter(|(_, row)| row.len() != row_length)
+        .map(|(index, _)| (index + 1).to_string())
+        .collect();
+
+    let mut description = format!("Grid of {} rows by {} columns", grid.len(), row_length);
+    if !ragged_rows.is_empty() {
+        description.push_str(&format!(
+            "\nAnomaly: rows {} differ in length from the first row",
+            ragged_rows.join(", ")
+        ));
+    }
+
+    description
+}
+
+// For days that need a rectangle, fails on the first row that differs in length from the first.
+pub fn check_grid<T>(grid: &[Vec<T>]) -> Result<(), AocError> {
+    let row_length = grid.first().map(|row| row.len()).unwrap_or(0usize);
+
+    grid.iter()
+        .enumerate()
+        .find(|(_, row)| row.len() != row_length)
+        .map_or(Ok(()), |(index, row)| {
+            Err(AocError::validation(format!(
+                "Row {} has {} columns but the first row has {}",
+                index + 1,
+                row.len(),
+                row_length
+            )))
+        })
+}
+
+// How a grid of on and off cells is drawn as text. Block packs 1 by 2 cells into each character and
+// braille 2 by 4, so large grids still fit in a terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "kebab_case")]
+pub enum RenderStyle {
+    Ascii,
+    Block,
+    Braille,
+}
+
+// Draws the grid with # for on and . for off, or packed into block or braille characters. Cells
+// missing from short rows are off.
+pub fn render_grid(grid: &[Vec<bool>], style: RenderStyle) -> String {
+    let width = grid.iter().map(|row| row.len()).max().unwrap_or(0usize);
+    let cell = |row: usize, column: usize| {
+        grid.get(row)
+            .and_then(|cells| cells.get(column))
+            .copied()
+            .unwrap_or(false)
+    };
+    let (cell_width, cell_height) = match style {
+        RenderStyle::Ascii => (1usize, 1usize),
+        RenderStyle::Block => (1usize, 2usize),
+        RenderStyle::Braille => (2usize, 4usize),
+    };
+
+    (0..grid.len().div_ceil(cell_height))
+        .map(|row| {
+            (0..width.div_ceil(cell_width))
+                .map(|column| {
+                    let (row, column) = (row * cell_height, column * cell_width);
+                    match style {
+                        RenderStyle::Ascii => {
+                            if cell(row, column) {
+                                '#'
+                            } else {
+                                '.'
+                            }
+                        }
+                        RenderStyle::Block => match (cell(row, column), cell(row + 1, column)) {
+                            (false, false) => ' ',
+                            (true, false) => '\u{2580}',
+                            (false, true) => '\u{2584}',
+                            (true, true) => '\u{2588}',
+                        },
+                        RenderStyle::Braille => braille(|x, y| cell(row + y, column + x)),
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// Draws the points, given as x,y, on a grid from 0,0 to the furthest point.
+pub fn render_points(
+    points: impl IntoIterator<Item = (usize, usize)>,
+    style: RenderStyle,
+) -> String {
+    let points: Vec<(usize, usize)> = points.into_iter().collect();
+    let width = points.iter().map(|(x, _)| x + 1).max().unwrap_or(1usize);
+    let height = points.iter().map(|(_, y)| y + 1).max().unwrap_or(1usize);
+
+    let mut grid = vec![vec![false; width]; height];
+    points.iter().for_each(|(x, y)| grid[*y][*x] = true);
+    render_grid(&grid, style)
+}
+
+// A braille character's dots are numbered down the left column then the right, with the bottom
+// row added later as the last two bits.
+fn braille(dot: impl Fn(usize, usize) -> bool) -> char {
+    const DOTS: [(usize, usize); 8] = [
+        (0, 0),
+        (0, 1),
+        (0, 2),
+        (1, 0),
+        (1, 1),
+        (1, 2),
+        (0, 3),
+        (1, 3),
+    ];
+
+    let bits = DOTS
+        .iter()
+        .enumerate()
+        .filter(|(_, (x, y))| dot(*x, *y))
+        .fold(0u32, |bits, (bit, _)| bits | 1 << bit);
+    char::from_u32(0x2800 + bits).unwrap_or(' ')
+}
+
+pub fn selected_part<'a>(arguments: &'a ArgMatches) -> Option<&'a str> {
+    match arguments.value_of("part") {
+        Some("1") => Some("part1"),
+        Some("2") => Some("part2"),
+        _ => arguments.subcommand_name(),
+    }
+}
+
+// Whether --example was passed to the day or to the part1, part2 or both subcommand.
+pub fn example_requested(arguments: &ArgMatches) -> bool {
+    arguments.is_present("example")
+        || arguments
+            .subcommand()
+            .1
+            .is_some_and(|sub_command| sub_command.is_present("example"))
+}
+
+pub fn example_input<C: Command + ?Sized>(command: &C) -> Result<&str, AocError> {
+    command
+        .example_input()
+        .ok_or_else(|| AocError::unsupported(format!("{} has no example input", command.name())))
+}
+
+// The error for an input file that doesn't exist, saying where it was looked for and how to get it.
+#[cfg(feature = "cli")]
+#[derive(Debug)]
+pub struct MissingInput {
+    pub path: PathBuf,
+    pub folder: String,
+    pub command: String,
+}
+
+#[cfg(feature = "cli")]
+impl fmt::Display for MissingInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "No input file at {}", self.path.display())?;
+        writeln!(
+            f,
+            "Each day reads its input from the folder named for it, {}/input.txt, under the current directory or --input-dir.",
+            self.folder
+        )?;
+        write!(
+            f,
+            "Run `adventofcode2021 {} fetch` to download it, adding --create-dirs if the {} folder doesn't exist yet.",
+            self.command, self.folder
         )
+    }
+}
+
+#[cfg(feature = "cli")]
+impl std::error::Error for MissingInput {}
+
+#[cfg(feature = "cli")]
+fn check_input_exists<C: Command + ?Sized>(command: &C, file: &str) -> Result<(), AocError> {
+    if Path::new(file).exists() {
+        return Ok(());
+    }
+
+    Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        MissingInput {
+            path: path::absolute(file).unwrap_or_else(|_| PathBuf::from(file)),
+            folder: command.input_folder(),
+            command: command.name().to_string(),
+        },
+    )
+    .into())
 }
 
-pub fn file_to_string(file_name: &String) -> Result<String, Error> {
+#[cfg(feature = "cli")]
+pub fn file_to_string(file_name: &String) -> Result<String, AocError> {
     File::open(file_name)
         .and_then(|mut file| {
             let mut result = String::new();
@@ -138,13 +1720,13 @@ pub fn file_to_string(file_name: &String) -> Result<String, Error> {
         .map_err(|e| e.into())
 }
 
-pub fn complete_parsing<T, U, F>(mut parse_function: F) -> impl FnMut(&T) -> Result<U, Error>
+pub fn complete_parsing<U, F>(mut parse_function: F) -> impl FnMut(&String) -> Result<U, AocError>
 where
-    F: FnMut(&T) -> IResult<&str, U>,
+    F: FnMut(&String) -> IResult<&str, U>,
 {
-    move |t| -> Result<U, Error> {
-        parse_function(t)
-            .map_err(|_: nom::Err<nom::error::Error<&str>>| SimpleError::new("Parse Error").into())
+    move |input| -> Result<U, AocError> {
+        parse_function(input)
+            .map_err(|error| AocError::from_nom(input, error))
             .map(|(_, result)| result)
     }
 }
@@ -160,8 +1742,26 @@ pub fn parse_isize(input: &str) -> IResult<&str, isize> {
     )(input)
 }
 
-fn usisze_from_string(input: &str) -> Result<usize, Error> {
-    usize::from_str_radix(input, 10).map_err(|err| err.into())
+// A grid with one cell per character, which map_fn reads or rejects. Rows end with \n or \r\n and
+// any blank lines at the end are consumed. Ragged rows are kept so they can be described.
+pub fn parse_char_grid<T, F>(map_fn: F) -> impl FnMut(&str) -> IResult<&str, Grid<T>>
+where
+    F: Fn(char) -> Option<T>,
+{
+    move |input| {
+        terminated(
+            separated_list0(line_ending, many1(map_opt(none_of("\r\n"), &map_fn))),
+            many0(line_ending),
+        )(input)
+    }
+}
+
+pub fn parse_digit_grid(input: &str) -> IResult<&str, Grid<usize>> {
+    parse_char_grid(|cell| cell.to_digit(10).map(|digit| digit as usize))(input)
+}
+
+fn usisze_from_string(input: &str) -> Result<usize, ParseIntError> {
+    input.parse::<usize>()
 }
 
 pub fn absolute_difference<T>(x: T, y: T) -> T
@@ -174,3 +1774,449 @@ where
         y - x
     }
 }
+
+// The nth triangular number, 1 + 2 + ... + n.
+pub fn triangular<T: Integer + Copy>(n: T) -> T {
+    sum_range(T::one(), n)
+}
+
+pub fn checked_triangular<T>(n: T) -> Option<T>
+where
+    T: Integer + Copy + CheckedAdd + CheckedSub + CheckedMul,
+{
+    checked_sum_range(T::one(), n)
+}
+
+// The sum of the arithmetic series a + (a + 1) + ... + b, 0 when b is less than a.
+pub fn sum_range<T: Integer + Copy>(a: T, b: T) -> T {
+    if b < a {
+        return T::zero();
+    }
+
+    // One of the two factors is always even, halving it first keeps the product small.
+    let (ends, count) = (a + b, b - a + T::one());
+    let two = T::one() + T::one();
+    if ends.is_even() {
+        ends / two * count
+    } else {
+        count / two * ends
+    }
+}
+
+pub fn checked_sum_range<T>(a: T, b: T) -> Option<T>
+where
+    T: Integer + Copy + CheckedAdd + CheckedSub + CheckedMul,
+{
+    if b < a {
+        return Some(T::zero());
+    }
+
+    let ends = a.checked_add(&b)?;
+    let count = b.checked_sub(&a)?.checked_add(&T::one())?;
+    let two = T::one() + T::one();
+    if ends.is_even() {
+        (ends / two).checked_mul(&count)
+    } else {
+        (count / two).checked_mul(&ends)
+    }
+}
+
+// The smallest n whose triangular number is at least t, the positive root of n(n + 1) / 2 = t
+// rounded up.
+pub fn inverse_triangular<T: Integer + Roots + Copy>(t: T) -> T {
+    let four = T::one() + T::one() + T::one() + T::one();
+    inverse_from_discriminant(t, t * (four + four) + T::one())
+}
+
+// None when 8t + 1 doesn't fit, so the root can't be taken.
+pub fn checked_inverse_triangular<T>(t: T) -> Option<T>
+where
+    T: Integer + Roots + Copy + CheckedAdd + CheckedMul,
+{
+    let four = T::one() + T::one() + T::one() + T::one();
+    let discriminant = t.checked_mul(&(four + four))?.checked_add(&T::one())?;
+    Some(inverse_from_discriminant(t, discriminant))
+}
+
+fn inverse_from_discriminant<T: Integer + Roots + Copy>(t: T, discriminant: T) -> T {
+    if t <= T::zero() {
+        return T::zero();
+    }
+
+    let n = (discriminant.sqrt() - T::one()) / (T::one() + T::one());
+    if triangular(n) < t {
+        n + T::one()
+    } else {
+        n
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SONAR_SWEEP_SAMPLE: &str = include_str!("../day1_sonar_sweep/sample.txt");
+
+    #[test]
+    fn render_styles() {
+        let grid = vec![
+            vec![true, false, true],
+            vec![true, true],
+            vec![false, false, false],
+            vec![false, true, true],
+            vec![true, false, false],
+        ];
+
+        assert_eq!(
+            render_grid(&grid, RenderStyle::Ascii),
+            "#.#\n##.\n...\n.##\n#.."
+        );
+        assert_eq!(render_grid(&grid, RenderStyle::Block), "█▄▀\n ▄▄\n▀  ");
+        assert_eq!(render_grid(&grid, RenderStyle::Braille), "⢓⡁\n⠁⠀");
+        assert_eq!(
+            render_points(vec![(2usize, 0usize), (0usize, 1usize)], RenderStyle::Ascii),
+            "..#\n#.."
+        );
+    }
+
+    #[test]
+    fn digit_grid_parsing() {
+        assert_eq!(
+            parse_digit_grid("12\r\n34\n\n\n"),
+            Ok(("", vec![vec![1, 2], vec![3, 4]]))
+        );
+        assert_eq!(
+            parse_digit_grid("123\n45\nx"),
+            Ok(("x", vec![vec![1, 2, 3], vec![4, 5]]))
+        );
+        assert_eq!(
+            check_grid(&parse_digit_grid("123\n45").unwrap().1)
+                .unwrap_err()
+                .to_string(),
+            "Row 2 has 2 columns but the first row has 3"
+        );
+        assert_eq!(
+            parse_char_grid(|cell| Some(cell == '#'))(".#\n#."),
+            Ok(("", vec![vec![false, true], vec![true, false]]))
+        );
+    }
+
+    #[test]
+    fn profile_counts_time_in_each_stack() {
+        assert_eq!(finish_profile(), "");
+
+        start_profile("day");
+        {
+            let _outer = profile_scope("outer");
+            let _inner = profile_scope("inner");
+        }
+        drop(profile_scope("outer"));
+        let profile = finish_profile();
+
+        let stacks: Vec<&str> = profile
+            .lines()
+            .map(|line| line.rsplit_once(' ').unwrap().0)
+            .collect();
+        assert_eq!(stacks, vec!["day", "day;outer", "day;outer;inner"]);
+        assert!(profile.lines().all(|line| line
+            .rsplit_once(' ')
+            .unwrap()
+            .1
+            .parse::<u128>()
+            .is_ok()));
+        assert_eq!(finish_profile(), "");
+    }
+
+    #[test]
+    fn pairwise_pairs_neighbours() {
+        assert_eq!(
+            [1, 2, 3].iter().pairwise().collect::<Vec<_>>(),
+            vec![(&1, &2), (&2, &3)]
+        );
+        assert_eq!(iter::once(1).pairwise().count(), 0);
+    }
+
+    #[test]
+    fn combinations2_pairs_each_item_once() {
+        assert_eq!(
+            ['a', 'b', 'c'].iter().combinations2().collect::<Vec<_>>(),
+            vec![(&'a', &'b'), (&'a', &'c'), (&'b', &'c')]
+        );
+        assert_eq!(iter::once('a').combinations2().count(), 0);
+    }
+
+    #[test]
+    fn window_sums_slide() {
+        assert_eq!(
+            [1, 2, 3, 4, 5]
+                .into_iter()
+                .window_sums(3)
+                .collect::<Vec<_>>(),
+            vec![6, 9, 12]
+        );
+        assert_eq!([1, 2].into_iter().window_sums(3).count(), 0);
+        assert_eq!([1, 2].into_iter().window_sums(0).count(), 0);
+    }
+
+    #[test]
+    fn bit_grid_tracks_cells() {
+        let mut grid = BitGrid::new(3, 50);
+
+        assert!(grid.is_empty());
+        assert!(grid.insert(2, 49));
+        assert!(!grid.insert(2, 49));
+        assert!(grid.insert(0, 0));
+        assert!(grid.contains(2, 49));
+        assert!(!grid.contains(1, 49));
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid.iter().collect::<Vec<_>>(), vec![(0, 0), (2, 49)]);
+        assert!(grid.remove(0, 0));
+        assert!(!grid.remove(0, 0));
+        grid.clear();
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    fn log_level_counts_verbosity() {
+        assert_eq!(LogLevel::from_verbosity(0), LogLevel::Off);
+        assert_eq!(LogLevel::from_verbosity(1), LogLevel::Debug);
+        assert_eq!(LogLevel::from_verbosity(3), LogLevel::Trace);
+        assert!(!LogLevel::Off.enabled());
+    }
+
+    #[test]
+    fn reported_errors_replace_the_answer() {
+        report_error(AocError::validation("first"));
+        report_error(AocError::validation("second"));
+
+        assert_eq!(
+            within_deadline(1usize.into()).unwrap_err().to_string(),
+            "first"
+        );
+        assert_eq!(within_deadline(1usize.into()).unwrap(), 1usize.into());
+    }
+
+    #[test]
+    fn counter_counts_keys() {
+        let counter: Counter<char> = "abracadabra".chars().collect();
+
+        assert_eq!(counter.get(&'a'), 5);
+        assert_eq!(counter.get(&'z'), 0);
+        assert_eq!(counter.len(), 5);
+        assert_eq!(counter.total(), 11);
+        assert!(Counter::<char>::new().is_empty());
+    }
+
+    #[test]
+    fn counter_from_counts_adds_repeated_keys() {
+        let mut counter = Counter::from_counts([("a", 2usize), ("b", 1), ("a", 3)]);
+        assert_eq!(counter.get(&"a"), 5);
+        assert_eq!(counter.total(), 6);
+
+        counter.merge(Counter::from_counts([("b", 4usize), ("c", 1)]));
+        assert_eq!(
+            counter,
+            Counter::from_counts([("a", 5usize), ("b", 5), ("c", 1)])
+        );
+    }
+
+    #[test]
+    fn most_common_is_by_count() {
+        let counter: Counter<char> = "abracadabra".chars().collect();
+        let counts: Vec<usize> = counter
+            .most_common()
+            .iter()
+            .map(|(_, count)| *count)
+            .collect();
+
+        assert_eq!(counts, [5usize, 2, 2, 1, 1]);
+        assert_eq!(counter.most_common()[0], (&'a', 5usize));
+    }
+
+    #[test]
+    fn deterministic_ties_are_in_key_order() {
+        let counter = Counter::from_counts((0usize..50).map(|key| (key, 1usize + key % 2)));
+
+        let most_common: Vec<usize> = counter
+            .most_common_in_key_order()
+            .iter()
+            .map(|(key, _)| **key)
+            .collect();
+
+        assert_eq!(most_common[..3], [1usize, 3, 5]);
+        assert_eq!(most_common[25..28], [0usize, 2, 4]);
+    }
+
+    #[test]
+    fn solve_sample() {
+        assert_eq!(solve(1u8, 1u8, SONAR_SWEEP_SAMPLE).unwrap(), "7");
+        assert_eq!(solve(1u8, 2u8, SONAR_SWEEP_SAMPLE).unwrap(), "5");
+    }
+
+    // Each day's own tests cover its examples' answers, which can take a while to solve.
+    #[test]
+    fn examples_are_built_in() {
+        let commands = problems::commands();
+        commands
+            .iter()
+            .filter(|command| command.name() != "alu")
+            .for_each(|command| assert!(!command.examples().is_empty(), "{}", command.name()));
+
+        problems::day01::SONAR_SWEEP
+            .examples()
+            .iter()
+            .for_each(|example| {
+                assert_eq!(
+                    example.solve(&problems::day01::SONAR_SWEEP).unwrap(),
+                    example.expected
+                )
+            });
+    }
+
+    #[test]
+    fn parse_only_shows_the_parsed_input() {
+        let (parsed, _) = problems::day01::SONAR_SWEEP
+            .parse_only_input(&SONAR_SWEEP_SAMPLE.to_string())
+            .unwrap();
+
+        assert_eq!(
+            parsed,
+            format!(
+                "{:#?}",
+                vec![199usize, 200, 208, 210, 200, 207, 240, 269, 260, 263]
+            )
+        );
+    }
+
+    #[test]
+    fn solve_rejects_unknown_days_and_parts() {
+        assert!(solve(0u8, 1u8, SONAR_SWEEP_SAMPLE).is_err());
+        assert!(matches!(
+            solve(26u8, 1u8, SONAR_SWEEP_SAMPLE),
+            Err(AocError::Unsupported(_))
+        ));
+        assert!(solve(1u8, 3u8, SONAR_SWEEP_SAMPLE).is_err());
+    }
+
+    #[test]
+    fn parse_errors_give_the_position() {
+        let input = "199\n200\n2x0".to_string();
+        let error = complete_parsing(|input: &String| {
+            nom::combinator::all_consuming(separated_list0(line_ending, parse_usize))(input)
+        })(&input)
+        .unwrap_err();
+
+        assert!(matches!(
+            error,
+            AocError::Parse {
+                line: 3,
+                column: 2,
+                ..
+            }
+        ));
+        assert_eq!(error.to_string(), "Parse error at line 3, column 2: x0");
+        assert_eq!(
+            AocError::parse(&input, "").to_string(),
+            "Parse error at line 3, column 4: unexpected end of line"
+        );
+    }
+
+    #[cfg(feature = "cli")]
+    #[test]
+    fn example_runs_without_input_files() {
+        let run_example = |command: &dyn Command, arguments: &[&str]| {
+            let matches = command
+                .sub_command()
+                .get_matches_from_safe(iter::once(command.name()).chain(arguments.iter().copied()))
+                .unwrap();
+            command.run(&matches, &"no_such_folder/input.txt".to_string())
+        };
+
+        assert_eq!(
+            run_example(&problems::day01::SONAR_SWEEP, &["part1", "--example"]).unwrap(),
+            7usize.into()
+        );
+        assert_eq!(
+            run_example(&problems::day01::SONAR_SWEEP, &["--part", "2", "--example"]).unwrap(),
+            5usize.into()
+        );
+        assert_eq!(
+            run_example(&problems::day24::ALU, &["part1", "--example"])
+                .unwrap_err()
+                .to_string(),
+            "alu has no example input"
+        );
+    }
+
+    #[cfg(feature = "cli")]
+    #[test]
+    fn missing_input_suggests_fetch() {
+        let error = problems::day01::SONAR_SWEEP
+            .run_both(&"no_such_folder/input.txt".to_string())
+            .unwrap_err();
+        let AocError::Io(io_error) = &error else {
+            panic!("{:?} is not an io error", error);
+        };
+        let missing = io_error
+            .get_ref()
+            .and_then(|inner| inner.downcast_ref::<MissingInput>())
+            .unwrap();
+
+        assert_eq!(io_error.kind(), io::ErrorKind::NotFound);
+
+        assert!(missing.path.is_absolute());
+        assert_eq!(missing.folder, "day1_sonar_sweep");
+        assert!(error
+            .to_string()
+            .contains("adventofcode2021 sonar-sweep fetch"));
+    }
+
+    #[test]
+    fn triangular_numbers() {
+        assert_eq!(triangular(0usize), 0);
+        assert_eq!(triangular(4usize), 10);
+        assert_eq!(sum_range(3usize, 6usize), 18);
+        assert_eq!(sum_range(6usize, 3usize), 0);
+        assert_eq!(sum_range(-2isize, 4isize), 7);
+        assert_eq!(checked_triangular(usize::MAX), None);
+        assert_eq!(
+            checked_sum_range(u32::MAX - 1, u32::MAX),
+            None,
+            "the sum overflows even though each end fits"
+        );
+        assert_eq!(checked_triangular(92681u32), Some(4294930221));
+
+        assert_eq!(
+            (0usize..=11)
+                .map(inverse_triangular)
+                .collect::<Vec<usize>>(),
+            vec![0, 1, 2, 2, 3, 3, 3, 4, 4, 4, 4, 5]
+        );
+        assert_eq!(inverse_triangular(-5isize), 0);
+        assert_eq!(checked_inverse_triangular(usize::MAX), None);
+        assert_eq!(checked_inverse_triangular(4294930221u64), Some(92681));
+    }
+
+    #[test]
+    fn directions_turn_and_parse() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::SouthEast.turn_right(), Direction::SouthWest);
+        assert_eq!(Direction::NorthEast.opposite(), Direction::SouthWest);
+        assert_eq!(Direction::SouthWest.to_vector(), (-1, 1));
+
+        assert_eq!(Direction::West.offset((3, 2), 3), Some((0, 2)));
+        assert_eq!(Direction::North.offset((3, 2), 3), None);
+        assert_eq!(Direction::NorthWest.wrapping_offset((0, 0), (5, 4)), (4, 3));
+        assert_eq!(Direction::East.wrapping_offset((4, 0), (5, 4)), (0, 0));
+
+        assert_eq!("NE".parse::<Direction>().unwrap(), Direction::NorthEast);
+        assert_eq!(
+            "south-west".parse::<Direction>().unwrap(),
+            Direction::SouthWest
+        );
+        assert_eq!("West".parse::<Direction>().unwrap(), Direction::West);
+        assert!("up".parse::<Direction>().is_err());
+        assert_eq!(parse_direction("nw 3"), Ok((" 3", Direction::NorthWest)));
+    }
+}
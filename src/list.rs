@@ -0,0 +1,185 @@
+use adventofcode2021::Command;
+use clap::App;
+use serde_json::{json, Value};
+
+// Every day takes these, so the listing leaves them out.
+const COMMON_FLAGS: [&str; 3] = ["file", "explain", "part"];
+
+// A flag a day's sub command accepts, as read from its clap App.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Flag {
+    pub name: String,
+    pub short: Option<char>,
+    pub long: Option<String>,
+    pub takes_value: bool,
+    pub possible_values: Vec<String>,
+    pub default_value: Option<String>,
+}
+
+impl Flag {
+    fn to_json(&self) -> Value {
+        json!({
+            "name": self.name,
+            "short": self.short.map(|short| short.to_string()),
+            "long": self.long,
+            "takes_value": self.takes_value,
+            "possible_values": self.possible_values,
+            "default_value": self.default_value,
+        })
+    }
+
+    fn usage(&self) -> String {
+        let mut usage = [
+            self.short.map(|short| format!("-{}", short)),
+            self.long.as_ref().map(|long| format!("--{}", long)),
+        ]
+        .iter()
+        .flatten()
+        .cloned()
+        .collect::<Vec<String>>()
+        .join(", ");
+
+        if self.takes_value {
+            usage.push_str(&format!(" <{}>", self.name));
+        }
+        if !self.possible_values.is_empty() {
+            usage.push_str(&format!(" [{}]", self.possible_values.join(", ")));
+        }
+        if let Some(default_value) = &self.default_value {
+            usage.push_str(&format!(" (default {})", default_value));
+        }
+        usage
+    }
+}
+
+pub struct DayListing {
+    pub name: String,
+    pub year: usize,
+    pub day: Option<usize>,
+    pub folder: String,
+    pub flags: Vec<Flag>,
+    pub sub_commands: Vec<String>,
+}
+
+impl DayListing {
+    pub fn new(command: &dyn Command) -> DayListing {
+        let app = command.sub_command();
+        DayListing {
+            name: command.name().to_string(),
+            year: command.year(),
+            day: day_number(command),
+            folder: command.input_folder(),
+            flags: flags(&app),
+            sub_commands: app
+                .p
+                .subcommands
+                .iter()
+                .map(|sub_command| sub_command.p.meta.name.clone())
+                .collect(),
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "name": self.name,
+            "year": self.year,
+            "day": self.day,
+            "folder": self.folder,
+            "flags": self.flags.iter().map(Flag::to_json).collect::<Vec<Value>>(),
+            "sub_commands": self.sub_commands,
+        })
+    }
+
+    pub fn describe(&self) -> String {
+        let day = self
+            .day
+            .map(|day| format!("day {} of {}", day, self.year))
+            .unwrap_or_else(|| self.year.to_string());
+
+        let mut description = format!("{}: {}, input in {}", self.name, day, self.folder);
+        self.flags
+            .iter()
+            .for_each(|flag| description.push_str(&format!("\n    {}", flag.usage())));
+        description.push_str(&format!(
+            "\n    sub commands: {}",
+            self.sub_commands.join(", ")
+        ));
+        description
+    }
+}
+
+// Day folders are named dayN_title, possibly under a folder for the year.
+pub fn day_number(command: &dyn Command) -> Option<usize> {
+    command
+        .input_folder()
+        .rsplit('/')
+        .next()
+        .and_then(|name| name.strip_prefix("day"))
+        .and_then(|name| name.split('_').next())
+        .and_then(|day| day.parse().ok())
+}
+
+// The flags and options the app was built with, in name order, without the ones every day has.
+pub fn flags(app: &App) -> Vec<Flag> {
+    let switches = app.p.flags.iter().map(|flag| Flag {
+        name: flag.b.name.to_string(),
+        short: flag.s.short,
+        long: flag.s.long.map(String::from),
+        takes_value: false,
+        possible_values: Vec::new(),
+        default_value: None,
+    });
+    let options = app.p.opts.iter().map(|option| Flag {
+        name: option.b.name.to_string(),
+        short: option.s.short,
+        long: option.s.long.map(String::from),
+        takes_value: true,
+        possible_values: option
+            .v
+            .possible_vals
+            .iter()
+            .flatten()
+            .map(|value| value.to_string())
+            .collect(),
+        default_value: option
+            .v
+            .default_val
+            .map(|value| value.to_string_lossy().into_owned()),
+    });
+
+    let mut flags: Vec<Flag> = switches
+        .chain(options)
+        .filter(|flag| !COMMON_FLAGS.contains(&flag.name.as_str()))
+        .collect();
+    flags.sort_by(|a, b| a.name.cmp(&b.name));
+    flags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Arg;
+
+    #[test]
+    fn flags_are_read_from_the_app() {
+        let app = App::new("day")
+            .arg(Arg::with_name("file").short("f").takes_value(true))
+            .arg(Arg::with_name("quick").long("quick"))
+            .arg(
+                Arg::with_name("mode")
+                    .short("m")
+                    .long("mode")
+                    .possible_values(&["fast", "slow"])
+                    .default_value("fast"),
+            );
+
+        let flags = flags(&app);
+
+        assert_eq!(flags.len(), 2);
+        assert_eq!(
+            flags[0].usage(),
+            "-m, --mode <mode> [fast, slow] (default fast)"
+        );
+        assert_eq!(flags[1].usage(), "--quick");
+    }
+}
@@ -1,116 +1,955 @@
 #![feature(const_fn_fn_ptr_basics)]
-#![feature(map_first_last)]
-#![feature(fn_traits)]
-
-mod alu;
-mod amphipod;
-mod beacon_scanner;
-mod binary_diagnostic;
-mod chiton;
-mod dirac_dice;
-mod dive;
-mod dumbo_octopus;
-mod extended_polymerization;
-mod giant_squid;
-mod hydrothermal_venture;
-mod lanternfish;
-mod packet_decoder;
-mod passage_pathing;
-mod reactor_reboot;
-mod sea_cucumber;
-mod seven_segment;
-mod smoke_basin;
-mod snailfish;
-mod sonar_sweep;
-mod syntax_scoring;
-mod transparent_origami;
-mod trench_map;
-mod trick_shot;
-mod whale_treachery;
+
+mod bench;
+mod config;
+mod history;
+mod list;
+mod matrix;
+mod years;
 
 use anyhow::Error;
-use clap::{value_t_or_exit, App, AppSettings};
+use clap::{value_t, App, AppSettings, Arg, ArgMatches, ErrorKind, SubCommand};
 #[macro_use]
 extern crate lazy_static;
-use adventofcode2021::Command;
+use adventofcode2021::{
+    deterministic, example_input, example_requested, finish_profile, seed_argument, selected_part,
+    set_deterministic, set_seed, set_threads, start_profile, take_used_seed, Command,
+    CommandResult, Deadline, LogLevel,
+};
+use bench::{DEFAULT_BUDGET_SECONDS, DEFAULT_SAMPLES};
+use config::{Config, OutputFormat};
+use history::{RunRecord, DEFAULT_RESULTS_LOG};
+use list::DayListing;
 use simple_error::SimpleError;
-use std::{collections::HashMap, time::Instant};
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::Path,
+    time::{Duration, Instant},
+};
+use strum::VariantNames;
+use years::{Year, YEARS};
 
 const VERSION: &'static str = env!("CARGO_PKG_VERSION");
 
-lazy_static! {
-    static ref COMMANDS: Vec<Box<dyn Command>> = vec![
-        Box::new(sonar_sweep::SONAR_SWEEP),
-        Box::new(dive::DIVE),
-        Box::new(binary_diagnostic::BINARY_DIAGNOSTIC),
-        Box::new(giant_squid::GIANT_SQUID),
-        Box::new(hydrothermal_venture::HYDROTHERMAL_VENTURE),
-        Box::new(lanternfish::LANTERNFISH),
-        Box::new(whale_treachery::WHALE_TREACHERY),
-        Box::new(seven_segment::SEVEN_SEGMENT),
-        Box::new(smoke_basin::SMOKE_BASIN),
-        Box::new(syntax_scoring::SYNTAX_SCORING),
-        Box::new(dumbo_octopus::DUMBO_OCTOPUS),
-        Box::new(passage_pathing::PASSAGE_PATHING),
-        Box::new(transparent_origami::TRANSPARENT_ORIGAMI),
-        Box::new(extended_polymerization::EXTENDED_POLYMERIZATION),
-        Box::new(chiton::CHITON),
-        Box::new(packet_decoder::PACKET_DECODER),
-        Box::new(trick_shot::TRICK_SHOT),
-        Box::new(snailfish::SNAILFISH),
-        Box::new(beacon_scanner::BEACON_SCANNER),
-        Box::new(trench_map::TRENCH_MAP),
-        Box::new(dirac_dice::DIRAC_DICE),
-        Box::new(reactor_reboot::REACTOR_REBOOT),
-        Box::new(amphipod::AMPHIPOD),
-        Box::new(alu::ALU),
-        Box::new(sea_cucumber::SEA_CUCUMBER),
-    ];
+// What the run was configured with, from the command line or else aoc.toml.
+struct Settings {
+    input_dir: Option<String>,
+    output_format: OutputFormat,
+    // Runs are only recorded once a results log is chosen.
+    results_log: Option<String>,
+    session: Option<String>,
+    // Everything after the day's name, recorded with each run.
+    day_arguments: String,
+    parse_only: bool,
 }
 
 fn main() -> Result<(), Error> {
-    let app = App::new("Advent of code 2021")
-        .version(VERSION)
-        .about("Run the advent of code problems from this main program")
-        .setting(AppSettings::SubcommandRequiredElseHelp);
+    let config = Config::discover()?;
+    let arguments: Vec<String> = env::args().collect();
+    let year = selected_year(&arguments)?;
+    let app = with_global_args(
+        App::new("Advent of code")
+            .version(VERSION)
+            .about("Run the advent of code problems from this main program")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .global_setting(AppSettings::AllArgsOverrideSelf),
+    )
+    .subcommand(history_sub_command())
+    .subcommand(examples_sub_command())
+    .subcommand(list_sub_command())
+    .subcommand(bench_all_sub_command());
 
-    let matches = COMMANDS
+    let arguments = with_day_flags(arguments, year, &config);
+    let day_arguments = day_position(&arguments, year)
+        .map(|index| arguments[(index + 1)..].join(" "))
+        .unwrap_or_default();
+    let matches = year
+        .commands
         .iter()
         .fold(app, |app, command| app.subcommand(command.sub_command()))
-        .get_matches();
+        .get_matches_from(arguments);
 
-    let sub_commands: HashMap<&str, &Box<dyn Command>> = COMMANDS
+    let sub_commands: HashMap<&str, &Box<dyn Command>> = year
+        .commands
         .iter()
         .map(|command| (command.name(), command))
         .collect();
 
+    LogLevel::from_verbosity(matches.occurrences_of("verbose")).set_current();
+    set_deterministic(matches.is_present("deterministic"));
+    if matches.is_present("seed") {
+        set_seed(value_t!(matches, "seed", u64)?);
+    }
+    if matches.is_present("threads") {
+        set_threads(value_t!(matches, "threads", usize)?.max(1));
+    } else if let Some(threads) = config.threads {
+        set_threads(threads);
+    }
+
+    if matches.is_present("timeout") {
+        Deadline::after(Duration::from_secs(value_t!(matches, "timeout", u64)?)).set_current();
+    }
+
+    let settings = Settings {
+        input_dir: matches
+            .value_of("input-dir")
+            .map(String::from)
+            .or(config.input_dir),
+        output_format: if matches.is_present("output-format") {
+            value_t!(matches, "output-format", OutputFormat)?
+        } else {
+            config.output_format.unwrap_or(OutputFormat::Pretty)
+        },
+        results_log: matches
+            .value_of("results-log")
+            .map(String::from)
+            .or(config.results_log),
+        session: env::var("AOC_SESSION").ok().or(config.session),
+        day_arguments,
+        parse_only: matches.is_present("parse-only"),
+    };
+
+    match matches.subcommand() {
+        ("history", Some(history_args)) => return history_command(history_args, &settings),
+        ("examples", Some(examples_args)) => return examples_command(examples_args, year),
+        ("list", Some(list_args)) => return list_command(list_args, year),
+        ("bench-all", Some(bench_args)) => return bench_all_command(bench_args, year, &settings),
+        _ => (),
+    }
+
     if let (command_name, Some(args)) = matches.subcommand() {
+        let profile = matches.value_of("profile");
         sub_commands
             .get(command_name)
             .ok_or_else::<Error, _>(|| SimpleError::new("No valid subcommand found").into())
             .and_then(|command| {
-                println!("=============Running {:}=============", command.name());
-                let file = match args.subcommand_name() {
-                    Some("part1") => format!("{}/input.txt", command.folder_name()),
-                    Some("part2") => format!("{}/input.txt", command.folder_name()),
-                    _ => format!(
-                        "{}/{}",
-                        command.folder_name(),
-                        value_t_or_exit!(args.value_of("file"), String)
-                    ),
-                };
-
-                let now = Instant::now();
-                let result = command.run(args, &file);
-                let elapsed = now.elapsed();
-                result.map(|r| (r, elapsed))
-            })
-            .map(|(result, elapsed)| {
-                println!("{:#?}", result);
-                println!("Took {:#?} to run", elapsed);
+                if profile.is_some() {
+                    start_profile(command.name());
+                }
+                let result = day_command(command.as_ref(), args, &settings);
+                // Written even when the run fails, since the profile shows how far it got.
+                if let Some(path) = profile {
+                    fs::write(path, finish_profile())?;
+                }
+                result
             })
-            .map(|_| ())
     } else {
         Err(SimpleError::new("No arguments found").into())
     }
 }
+
+fn day_command(command: &dyn Command, args: &ArgMatches, settings: &Settings) -> Result<(), Error> {
+    match args.subcommand() {
+        _ if args.is_present("explain") => explain_command(command),
+        _ if settings.parse_only => parse_only_command(command, args, settings),
+        ("both", Some(both_args)) => both_command(command, both_args, settings),
+        ("validate", Some(validate_args)) => validate_command(command, validate_args, settings),
+        ("generate", Some(generate_args)) => generate_command(command, generate_args),
+        ("fetch", Some(fetch_args)) => fetch_command(command, fetch_args, settings),
+        _ => match file_glob(args) {
+            Some(pattern) => matrix_command(command, args, settings, pattern),
+            None => run_command(command, args, settings),
+        },
+    }
+}
+
+// The arguments that come before the subcommand, shared with the app that reads the year.
+fn with_global_args(app: App<'static, 'static>) -> App<'static, 'static> {
+    app.arg(year_arg())
+        .arg(timeout_arg())
+        .arg(verbose_arg())
+        .arg(deterministic_arg())
+        .arg(seed_arg())
+        .arg(threads_arg())
+        .arg(input_dir_arg())
+        .arg(output_format_arg())
+        .arg(results_log_arg())
+        .arg(profile_arg())
+        .arg(parse_only_arg())
+}
+
+// The year has to be known before the full app can be built, so it's read first with every other
+// argument passed through untouched. Help and the version are left for the full app to print, so
+// the version is only an ordinary flag here.
+fn selected_year(arguments: &[String]) -> Result<&'static Year, Error> {
+    let year = match with_global_args(
+        App::new("Advent of code")
+            .setting(AppSettings::AllowExternalSubcommands)
+            .setting(AppSettings::DisableVersion)
+            .arg(Arg::with_name("version").short("V").long("version")),
+    )
+    .get_matches_from_safe(arguments)
+    {
+        Ok(matches) if matches.is_present("year") => Some(value_t!(matches, "year", usize)?),
+        Ok(_) => None,
+        Err(error) if error.kind == ErrorKind::HelpDisplayed => None,
+        Err(error) => return Err(error.into()),
+    };
+
+    match year {
+        Some(year) => YEARS
+            .iter()
+            .find(|candidate| candidate.year == year)
+            .ok_or_else(|| SimpleError::new(format!("No problems found for {}", year)).into()),
+        None => YEARS
+            .last()
+            .ok_or_else(|| SimpleError::new("No years registered").into()),
+    }
+}
+
+fn year_arg() -> Arg<'static, 'static> {
+    Arg::with_name("year")
+        .long("year")
+        .help("The year of problems to run. Defaults to the latest year.")
+        .takes_value(true)
+}
+
+fn timeout_arg() -> Arg<'static, 'static> {
+    Arg::with_name("timeout")
+        .long("timeout")
+        .value_name("SECONDS")
+        .help("If passed, stops solving with a timeout error once the run takes longer than this.")
+        .takes_value(true)
+}
+
+fn verbose_arg() -> Arg<'static, 'static> {
+    Arg::with_name("verbose")
+        .short("v")
+        .long("verbose")
+        .help("Prints the solvers' diagnostic detail to stderr. Pass twice for more detail.")
+        .multiple(true)
+}
+
+fn deterministic_arg() -> Arg<'static, 'static> {
+    Arg::with_name("deterministic")
+        .long("deterministic")
+        .help("If passed, sorts output that would otherwise follow hash order and leaves out timings, so the same run always prints the same thing.")
+}
+
+fn seed_arg() -> Arg<'static, 'static> {
+    Arg::with_name("seed")
+        .long("seed")
+        .value_name("N")
+        .help("The seed for random modes and input generators that aren't given their own, 0 if not passed. The seed a run used is printed with its results.")
+        .takes_value(true)
+}
+
+fn threads_arg() -> Arg<'static, 'static> {
+    Arg::with_name("threads")
+        .long("threads")
+        .value_name("N")
+        .help("The number of threads for days that split their work, such as the ALU's --inputs-file. Defaults to threads in aoc.toml, then one per core.")
+        .takes_value(true)
+}
+
+// The day's flags from aoc.toml go straight after its name, ahead of any given on the command
+// line, so that the command line ones override them. The part1 and part2 subcommands don't take
+// day flags, so they're turned into --part first. The other subcommands don't use them at all.
+fn with_day_flags(mut arguments: Vec<String>, year: &Year, config: &Config) -> Vec<String> {
+    if let Some(index) = day_position(&arguments, year) {
+        let flags = config.day_flags(&arguments[index]).to_vec();
+        match arguments.get(index + 1).map(String::as_str) {
+            _ if flags.is_empty() => (),
+            Some(part @ ("part1" | "part2")) => {
+                let part = part.trim_start_matches("part").to_string();
+                arguments.splice(
+                    index + 1..index + 2,
+                    flags.into_iter().chain(["--part".to_string(), part]),
+                );
+            }
+            Some(sub_command) if !sub_command.starts_with('-') => (),
+            _ => {
+                arguments.splice(index + 1..index + 1, flags);
+            }
+        }
+    }
+
+    arguments
+}
+
+fn day_position(arguments: &[String], year: &Year) -> Option<usize> {
+    arguments
+        .iter()
+        .enumerate()
+        .skip(1)
+        .find(|(_, argument)| {
+            year.commands
+                .iter()
+                .any(|command| command.name() == argument.as_str())
+        })
+        .map(|(index, _)| index)
+}
+
+fn input_dir_arg() -> Arg<'static, 'static> {
+    Arg::with_name("input-dir")
+        .long("input-dir")
+        .value_name("DIR")
+        .help("The directory holding each day's input folder. Defaults to the current directory.")
+        .takes_value(true)
+}
+
+fn output_format_arg() -> Arg<'static, 'static> {
+    Arg::with_name("output-format")
+        .long("output-format")
+        .help("How results are printed. quiet prints only the answers. Defaults to pretty.")
+        .takes_value(true)
+        .possible_values(OutputFormat::VARIANTS)
+}
+
+fn results_log_arg() -> Arg<'static, 'static> {
+    Arg::with_name("results-log")
+        .long("results-log")
+        .value_name("PATH")
+        .help("If passed, appends every run's answer to this file, such as results.jsonl, for the history subcommand to list.")
+        .takes_value(true)
+}
+
+fn profile_arg() -> Arg<'static, 'static> {
+    Arg::with_name("profile")
+        .long("profile")
+        .value_name("PATH")
+        .help("If passed, times the day's parsing, checking and solving, along with any steps the solver marks, and writes them to this file as collapsed stacks for flame graph tools.")
+        .takes_value(true)
+}
+
+fn parse_only_arg() -> Arg<'static, 'static> {
+    Arg::with_name("parse-only")
+        .long("parse-only")
+        .help("If passed, stops once the day's input is parsed and prints what it was parsed into along with how long the parse took. The day's other arguments are still required, so pass part1 or part2 for days that need more.")
+}
+
+fn history_sub_command() -> App<'static, 'static> {
+    SubCommand::with_name("history")
+        .about("Lists past runs from the results log, or results.jsonl when none is set, flagging answers that changed for the same input.")
+        .arg(
+            Arg::with_name("day")
+                .short("d")
+                .long("day")
+                .help("If passed, only lists runs of this day.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("changed")
+                .short("c")
+                .long("changed")
+                .help("If passed, only lists runs whose answer changed."),
+        )
+}
+
+fn examples_sub_command() -> App<'static, 'static> {
+    SubCommand::with_name("examples")
+        .about(
+            "Solves the examples built into each day and reports which give the expected answer.",
+        )
+        .arg(
+            Arg::with_name("day")
+                .short("d")
+                .long("day")
+                .help("If passed, only runs the examples of this day.")
+                .takes_value(true),
+        )
+}
+
+fn list_sub_command() -> App<'static, 'static> {
+    SubCommand::with_name("list")
+        .about("Lists every day with its folder, day number and the flags it takes beyond the common ones.")
+        .arg(
+            Arg::with_name("json")
+                .short("j")
+                .long("json")
+                .help("If passed, prints the list as a JSON array for other tools to read."),
+        )
+}
+
+fn bench_all_sub_command() -> App<'static, 'static> {
+    SubCommand::with_name("bench-all")
+        .about("Times the solve of both parts of each day on its input, after parsing, and summarizes the results.")
+        .arg(
+            Arg::with_name("day")
+                .short("d")
+                .long("day")
+                .help("If passed, only benchmarks this day.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("samples")
+                .short("s")
+                .long("samples")
+                .help("Number of times to time each part.")
+                .takes_value(true)
+                .default_value(DEFAULT_SAMPLES),
+        )
+        .arg(
+            Arg::with_name("budget")
+                .short("b")
+                .long("budget")
+                .help("Seconds to spend sampling each part before settling for fewer samples.")
+                .takes_value(true)
+                .default_value(DEFAULT_BUDGET_SECONDS),
+        )
+        .arg(
+            Arg::with_name("limit")
+                .short("l")
+                .long("limit")
+                .help("If passed, lists every part whose median takes longer than this many milliseconds and fails, so it can gate performance regressions.")
+                .takes_value(true),
+        )
+}
+
+fn run_command(command: &dyn Command, args: &ArgMatches, settings: &Settings) -> Result<(), Error> {
+    let pretty = settings.output_format == OutputFormat::Pretty;
+    if pretty {
+        println!("=============Running {:}=============", command.name());
+    }
+    let file = input_file(command, args, settings);
+
+    let now = Instant::now();
+    let result = command.run(args, &file);
+    let elapsed = now.elapsed();
+    let result = result?;
+    println!("{:#?}", result);
+    if let Some(seed) = take_used_seed().filter(|_| pretty) {
+        println!("Seeded with {}, pass --seed {} to repeat it", seed, seed);
+    }
+    if pretty && !deterministic() {
+        println!("Took {:#?} to run", elapsed);
+    }
+
+    // The history only tracks answers for input files, so runs on the built in example are left
+    // out of it.
+    if example_requested(args) {
+        return Ok(());
+    }
+
+    record_run(command, part_name(args), &file, &result, elapsed, settings);
+    Ok(())
+}
+
+// Runs the day on each file matching the pattern in its folder, one row per file. A file that
+// fails shows its error in place of an answer and the rest still run.
+fn matrix_command(
+    command: &dyn Command,
+    args: &ArgMatches,
+    settings: &Settings,
+    pattern: &str,
+) -> Result<(), Error> {
+    let pretty = settings.output_format == OutputFormat::Pretty;
+    if pretty {
+        println!(
+            "=============Running {:} on {}=============",
+            command.name(),
+            pattern
+        );
+    }
+    let folder = input_folder(command, settings);
+    let files = matrix::matching_files(&folder, pattern)?;
+    if files.is_empty() {
+        return Err(SimpleError::new(format!("No files in {} match {}", folder, pattern)).into());
+    }
+
+    let rows = files
+        .iter()
+        .map(|name| {
+            let file = format!("{}/{}", folder, name);
+            let now = Instant::now();
+            let result = command.run(args, &file);
+            let elapsed = now.elapsed();
+
+            let answer = match &result {
+                Ok(result) => format!("{:?}", result).replace('\n', ", "),
+                Err(e) => format!("error: {}", e.to_string().lines().next().unwrap_or("")),
+            };
+            if let Ok(result) = &result {
+                record_run(command, part_name(args), &file, result, elapsed, settings);
+            }
+
+            let mut row = vec![name.clone(), answer];
+            if !deterministic() {
+                row.push(format!("{:?}", elapsed));
+            }
+            Ok(row)
+        })
+        .collect::<Result<Vec<Vec<String>>, Error>>()?;
+
+    let headings: &[&str] = if deterministic() {
+        &["File", "Answer"]
+    } else {
+        &["File", "Answer", "Time"]
+    };
+    println!("{}", matrix::table(headings, &rows));
+    Ok(())
+}
+
+// --file-glob, given to the day or to its part1 or part2 subcommand.
+fn file_glob<'a>(args: &'a ArgMatches) -> Option<&'a str> {
+    args.value_of("file-glob").or_else(|| {
+        args.subcommand()
+            .1
+            .and_then(|sub_command| sub_command.value_of("file-glob"))
+    })
+}
+
+fn part_name(args: &ArgMatches) -> &'static str {
+    match selected_part(args) {
+        Some("part1") => "1",
+        Some("part2") => "2",
+        _ => "custom",
+    }
+}
+
+// Nothing is solved or recorded, this only shows how the input was read.
+fn parse_only_command(
+    command: &dyn Command,
+    args: &ArgMatches,
+    settings: &Settings,
+) -> Result<(), Error> {
+    let pretty = settings.output_format == OutputFormat::Pretty;
+    if pretty {
+        println!("=============Parsing {:}=============", command.name());
+    }
+    let file = input_file(command, args, settings);
+
+    let (parsed, elapsed) = command.parse_only(&file)?;
+    println!("{}", parsed);
+    if pretty && !deterministic() {
+        println!("Took {:#?} to parse", elapsed);
+    }
+    Ok(())
+}
+
+fn explain_command(command: &dyn Command) -> Result<(), Error> {
+    println!("=============Explaining {:}=============", command.name());
+    println!("{}", command.long_about());
+    Ok(())
+}
+
+fn both_command(
+    command: &dyn Command,
+    args: &ArgMatches,
+    settings: &Settings,
+) -> Result<(), Error> {
+    let pretty = settings.output_format == OutputFormat::Pretty;
+    if pretty {
+        println!(
+            "=============Running {:} part1 and part2=============",
+            command.name()
+        );
+    }
+    let file = input_file(command, args, settings);
+    let example = example_requested(args);
+    let results = if example {
+        example_input(command).and_then(|input| command.run_both_with_input(&input.to_string()))?
+    } else {
+        command.run_both(&file)?
+    };
+
+    results
+        .iter()
+        .enumerate()
+        .for_each(|(index, (result, elapsed))| {
+            if pretty {
+                println!("Part {}: {:#?}", index + 1, result);
+                if !deterministic() {
+                    println!("Took {:#?} to run", elapsed);
+                }
+            } else {
+                println!("{:#?}", result);
+            }
+
+            if !example {
+                record_run(
+                    command,
+                    &(index + 1).to_string(),
+                    &file,
+                    result,
+                    *elapsed,
+                    settings,
+                );
+            }
+        });
+    Ok(())
+}
+
+// The answer has already been shown, so a log that can't be written is only warned about.
+fn record_run(
+    command: &dyn Command,
+    part: &str,
+    file: &str,
+    result: &CommandResult,
+    elapsed: Duration,
+    settings: &Settings,
+) {
+    let Some(results_log) = &settings.results_log else {
+        return;
+    };
+    let recorded = history::hash_file(file).and_then(|input_hash| {
+        let record = RunRecord::new(
+            command.name(),
+            part,
+            &settings.day_arguments,
+            &input_hash,
+            &format!("{:?}", result),
+            elapsed,
+        );
+        history::append_record(results_log, &record)
+    });
+
+    if let Err(error) = recorded {
+        eprintln!("Couldn't record the run in {}: {}", results_log, error);
+    }
+}
+
+fn history_command(args: &ArgMatches, settings: &Settings) -> Result<(), Error> {
+    let records = history::read_records(
+        settings
+            .results_log
+            .as_deref()
+            .unwrap_or(DEFAULT_RESULTS_LOG),
+    )?;
+    let changed = history::changed_answers(&records);
+
+    let shown: Vec<(&RunRecord, &Option<String>)> = records
+        .iter()
+        .zip(changed.iter())
+        .filter(|(record, _)| args.value_of("day").is_none_or(|day| record.day == day))
+        .filter(|(_, previous)| !args.is_present("changed") || previous.is_some())
+        .collect();
+
+    shown.iter().for_each(|(record, previous)| {
+        let mut line = format!(
+            "{} {} part {} input {}: {} in {:#?}",
+            record.timestamp,
+            record.day,
+            record.part,
+            record.input_hash,
+            record.answer.replace('\n', ", "),
+            record.elapsed
+        );
+        if record.part == "custom" {
+            line.push_str(&format!(" with {}", record.arguments));
+        }
+        if let Some(previous) = previous {
+            line.push_str(&format!(" CHANGED from {}", previous.replace('\n', ", ")));
+        }
+        println!("{}", line);
+    });
+
+    let changes = shown
+        .iter()
+        .filter(|(_, previous)| previous.is_some())
+        .count();
+    if changes > 0 {
+        println!(
+            "The answer changed for the same input in {} of these runs",
+            changes
+        );
+    }
+    Ok(())
+}
+
+fn examples_command(args: &ArgMatches, year: &Year) -> Result<(), Error> {
+    let day = args.value_of("day");
+    let (passed, total) = year
+        .commands
+        .iter()
+        .filter(|command| day.is_none_or(|day| day == command.name()))
+        .flat_map(|command| {
+            command
+                .examples()
+                .iter()
+                .map(move |example| (command, example))
+        })
+        .fold((0usize, 0usize), |(passed, total), (command, example)| {
+            let now = Instant::now();
+            let result = example.solve(command.as_ref());
+            let elapsed = now.elapsed();
+            let pass = match result {
+                Ok(answer) if answer == example.expected => {
+                    println!(
+                        "{} part {}: ok in {:?}",
+                        command.name(),
+                        example.part,
+                        elapsed
+                    );
+                    true
+                }
+                Ok(answer) => {
+                    println!(
+                        "{} part {}: FAILED, expected {} but got {}",
+                        command.name(),
+                        example.part,
+                        example.expected,
+                        answer
+                    );
+                    false
+                }
+                Err(e) => {
+                    println!("{} part {}: FAILED, {}", command.name(), example.part, e);
+                    false
+                }
+            };
+            (passed + pass as usize, total + 1)
+        });
+
+    println!("{} of {} examples passed", passed, total);
+    if passed == total {
+        Ok(())
+    } else {
+        Err(SimpleError::new(format!("{} examples failed", total - passed)).into())
+    }
+}
+
+fn list_command(args: &ArgMatches, year: &Year) -> Result<(), Error> {
+    let listings: Vec<DayListing> = year
+        .commands
+        .iter()
+        .map(|command| DayListing::new(command.as_ref()))
+        .collect();
+
+    if args.is_present("json") {
+        let listings: Vec<serde_json::Value> = listings.iter().map(DayListing::to_json).collect();
+        println!("{}", serde_json::to_string_pretty(&listings)?);
+    } else {
+        listings
+            .iter()
+            .for_each(|listing| println!("{}", listing.describe()));
+    }
+    Ok(())
+}
+
+fn bench_all_command(args: &ArgMatches, year: &Year, settings: &Settings) -> Result<(), Error> {
+    let day = args.value_of("day");
+    let samples = value_t!(args, "samples", usize)?;
+    let budget = Duration::from_secs(value_t!(args, "budget", u64)?);
+    let limit = if args.is_present("limit") {
+        Some(Duration::from_millis(value_t!(args, "limit", u64)?))
+    } else {
+        None
+    };
+
+    let medians: Vec<(String, u8, Duration)> = year
+        .commands
+        .iter()
+        .filter(|command| day.is_none_or(|day| day == command.name()))
+        .flat_map(|command| {
+            let file = input_file(command.as_ref(), args, settings);
+            let input = fs::read_to_string(&file);
+            if input.is_err() {
+                println!("{}: skipped, no input at {}", command.name(), file);
+            }
+
+            input.into_iter().flat_map(move |input| {
+                [1u8, 2u8]
+                    .iter()
+                    .filter_map(|part| match command.prepare(*part, &input) {
+                        Ok(run) => {
+                            let measurement = bench::measure(&run, samples, budget);
+                            println!(
+                                "{} part {}: median {:?}, mean {:?}, min {:?}, max {:?} over {} samples",
+                                command.name(),
+                                part,
+                                measurement.median,
+                                measurement.mean,
+                                measurement.min,
+                                measurement.max,
+                                measurement.samples
+                            );
+                            Some((command.name().to_string(), *part, measurement.median))
+                        }
+                        Err(e) => {
+                            println!("{} part {}: FAILED, {}", command.name(), part, e);
+                            None
+                        }
+                    })
+                    .collect::<Vec<(String, u8, Duration)>>()
+            })
+        })
+        .collect();
+
+    println!(
+        "{} parts benchmarked, medians total {:?}",
+        medians.len(),
+        medians
+            .iter()
+            .map(|(_, _, median)| *median)
+            .sum::<Duration>()
+    );
+
+    match limit.and_then(|limit| bench::slow_report(&medians, limit)) {
+        Some(report) => {
+            println!("{}", report);
+            Err(SimpleError::new("Some parts went over the time limit").into())
+        }
+        None => Ok(()),
+    }
+}
+
+fn validate_command(
+    command: &dyn Command,
+    args: &ArgMatches,
+    settings: &Settings,
+) -> Result<(), Error> {
+    println!("=============Validating {:}=============", command.name());
+    let file = input_file(command, args, settings);
+
+    println!("{}", command.validate(&file)?);
+    Ok(())
+}
+
+// Nothing else is printed so the generated input can be piped straight into another tool.
+fn generate_command(command: &dyn Command, args: &ArgMatches) -> Result<(), Error> {
+    let seed = seed_argument(args, "seed");
+    let size = value_t!(args, "size", usize)?;
+    let input = command.generate(seed, size)?;
+
+    match args.value_of("output") {
+        Some(path) => fs::write(path, input).map_err(|e| e.into()),
+        None => {
+            print!("{}", input);
+            Ok(())
+        }
+    }
+}
+
+// Saves the day's input where the day looks for it by default, so it never overwrites an input
+// that's already there.
+fn fetch_command(
+    command: &dyn Command,
+    args: &ArgMatches,
+    settings: &Settings,
+) -> Result<(), Error> {
+    let file = input_file(command, args, settings);
+    let path = Path::new(&file);
+    if path.exists() {
+        return Err(SimpleError::new(format!("{} already exists", file)).into());
+    }
+
+    let folder = path.parent().unwrap_or_else(|| Path::new("."));
+    if !folder.is_dir() {
+        if !args.is_present("create-dirs") {
+            return Err(SimpleError::new(format!(
+                "The folder {} doesn't exist, pass --create-dirs to create it",
+                folder.display()
+            ))
+            .into());
+        }
+        fs::create_dir_all(folder)?;
+    }
+
+    let session = args
+        .value_of("session")
+        .map(String::from)
+        .or_else(|| settings.session.clone())
+        .ok_or_else::<Error, _>(|| {
+            SimpleError::new(
+                "No session cookie, pass --session or set AOC_SESSION or session in aoc.toml",
+            )
+            .into()
+        })?;
+    let day = list::day_number(command).ok_or_else::<Error, _>(|| {
+        SimpleError::new(format!(
+            "Can't tell which day the folder {} is for",
+            command.input_folder()
+        ))
+        .into()
+    })?;
+
+    let input = ureq::get(&format!(
+        "https://adventofcode.com/{}/day/{}/input",
+        command.year(),
+        day
+    ))
+    .header("Cookie", &format!("session={}", session))
+    .header("User-Agent", "github.com/ktsimpso/adventofcode2021")
+    .call()?
+    .body_mut()
+    .read_to_string()?;
+
+    fs::write(path, input)?;
+    println!("Saved {}", file);
+    Ok(())
+}
+
+fn input_folder(command: &dyn Command, settings: &Settings) -> String {
+    match &settings.input_dir {
+        Some(input_dir) => format!("{}/{}", input_dir, command.input_folder()),
+        None => command.input_folder(),
+    }
+}
+
+fn input_file(command: &dyn Command, args: &ArgMatches, settings: &Settings) -> String {
+    format!(
+        "{}/{}",
+        input_folder(command, settings),
+        args.value_of("file").unwrap_or("input.txt")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day_matches(arguments: Vec<String>) -> Result<ArgMatches<'static>, clap::Error> {
+        YEARS[0]
+            .commands
+            .iter()
+            .fold(App::new("Advent of code"), |app, command| {
+                app.subcommand(command.sub_command())
+            })
+            .get_matches_from_safe(arguments)
+    }
+
+    fn arguments(arguments: &[&str]) -> Vec<String> {
+        arguments
+            .iter()
+            .map(|argument| argument.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn day_flags_work_with_the_part_subcommands() {
+        let config = Config::parse("[flags]\nsmoke-basin = [\"--basin-report\"]\n").unwrap();
+
+        let part1 = with_day_flags(
+            arguments(&["aoc", "smoke-basin", "part1"]),
+            &YEARS[0],
+            &config,
+        );
+        assert_eq!(
+            part1,
+            arguments(&["aoc", "smoke-basin", "--basin-report", "--part", "1"])
+        );
+        let matches = day_matches(part1).unwrap();
+        let (_, day) = matches.subcommand();
+        assert!(day.unwrap().is_present("basin-report"));
+        assert_eq!(selected_part(day.unwrap()), Some("part1"));
+
+        let file = with_day_flags(
+            arguments(&["aoc", "smoke-basin", "--part", "2", "-f", "input.txt"]),
+            &YEARS[0],
+            &config,
+        );
+        assert_eq!(
+            file,
+            arguments(&[
+                "aoc",
+                "smoke-basin",
+                "--basin-report",
+                "--part",
+                "2",
+                "-f",
+                "input.txt"
+            ])
+        );
+        assert!(day_matches(file).is_ok());
+
+        let both = with_day_flags(
+            arguments(&["aoc", "smoke-basin", "both"]),
+            &YEARS[0],
+            &config,
+        );
+        assert_eq!(both, arguments(&["aoc", "smoke-basin", "both"]));
+        assert!(day_matches(both).is_ok());
+    }
+}
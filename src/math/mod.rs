@@ -0,0 +1,4 @@
+pub mod random;
+pub mod rotation;
+pub mod seven_segment;
+pub mod snailfish;
@@ -0,0 +1,47 @@
+/// A splitmix64 generator. It's well distributed for any seed including 0 and fully determined by
+/// the seed, so anything built from it can be reproduced.
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        SplitMix64 { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    /// A value in `0..bound`.
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    /// A value in `low..=high`.
+    pub fn between(&mut self, low: isize, high: isize) -> isize {
+        low + self.below((high - low) as usize + 1) as isize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_values() {
+        let mut first = SplitMix64::new(7u64);
+        let mut second = SplitMix64::new(7u64);
+
+        (0..100).for_each(|_| {
+            let value = first.between(-5isize, 5isize);
+            assert_eq!(value, second.between(-5isize, 5isize));
+            assert!((-5isize..=5isize).contains(&value));
+        });
+    }
+}
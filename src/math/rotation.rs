@@ -0,0 +1,156 @@
+use std::ops::{Add, Neg, Sub};
+
+/// A point or offset on the integer grid in three dimensions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3 {
+    pub x: isize,
+    pub y: isize,
+    pub z: isize,
+}
+
+impl Point3 {
+    pub const ORIGIN: Point3 = Point3 { x: 0, y: 0, z: 0 };
+
+    pub fn new(x: isize, y: isize, z: isize) -> Self {
+        Point3 { x, y, z }
+    }
+
+    pub fn manhattan_distance(&self, other: &Point3) -> isize {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+}
+
+impl Add for Point3 {
+    type Output = Point3;
+
+    fn add(self, other: Point3) -> Point3 {
+        Point3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Point3;
+
+    fn sub(self, other: Point3) -> Point3 {
+        Point3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl Neg for Point3 {
+    type Output = Point3;
+
+    fn neg(self) -> Point3 {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+/// One of the 24 ways to turn the axes without mirroring them, as a 3x3 matrix where every row
+/// and column holds a single 1 or -1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rotation3 {
+    matrix: [[isize; 3]; 3],
+}
+
+impl Rotation3 {
+    pub const IDENTITY: Rotation3 = Rotation3 {
+        matrix: [[1, 0, 0], [0, 1, 0], [0, 0, 1]],
+    };
+
+    /// Every orientation, found as the signed permutations of the axes that keep their handedness.
+    pub fn all() -> Vec<Rotation3> {
+        const PERMUTATIONS: [[usize; 3]; 6] = [
+            [0, 1, 2],
+            [0, 2, 1],
+            [1, 0, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [2, 1, 0],
+        ];
+
+        PERMUTATIONS
+            .iter()
+            .flat_map(|permutation| {
+                (0..8).map(move |signs: usize| {
+                    let mut matrix = [[0isize; 3]; 3];
+                    (0..3).for_each(|row| {
+                        matrix[row][permutation[row]] =
+                            if signs & (1 << row) == 0 { 1 } else { -1 };
+                    });
+                    Rotation3 { matrix }
+                })
+            })
+            .filter(|rotation| rotation.determinant() == 1)
+            .collect()
+    }
+
+    pub fn apply(&self, point: &Point3) -> Point3 {
+        let row = |row: &[isize; 3]| row[0] * point.x + row[1] * point.y + row[2] * point.z;
+        Point3::new(
+            row(&self.matrix[0]),
+            row(&self.matrix[1]),
+            row(&self.matrix[2]),
+        )
+    }
+
+    /// The rotation that applies `other` and then this one.
+    pub fn compose(&self, other: &Rotation3) -> Rotation3 {
+        let mut matrix = [[0isize; 3]; 3];
+        (0..3).for_each(|row| {
+            (0..3).for_each(|column| {
+                matrix[row][column] = (0..3)
+                    .map(|k| self.matrix[row][k] * other.matrix[k][column])
+                    .sum();
+            })
+        });
+        Rotation3 { matrix }
+    }
+
+    /// The rotation that undoes this one. The matrix is orthogonal so that's its transpose.
+    pub fn inverse(&self) -> Rotation3 {
+        let mut matrix = [[0isize; 3]; 3];
+        (0..3).for_each(|row| {
+            (0..3).for_each(|column| matrix[row][column] = self.matrix[column][row])
+        });
+        Rotation3 { matrix }
+    }
+
+    fn determinant(&self) -> isize {
+        let m = &self.matrix;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn all_rotations_form_a_group() {
+        let rotations = Rotation3::all();
+        let unique: HashSet<&Rotation3> = rotations.iter().collect();
+
+        assert_eq!(rotations.len(), 24);
+        assert_eq!(unique.len(), 24);
+        assert!(unique.contains(&Rotation3::IDENTITY));
+        rotations.iter().for_each(|a| {
+            assert_eq!(a.compose(&a.inverse()), Rotation3::IDENTITY);
+            rotations
+                .iter()
+                .for_each(|b| assert!(unique.contains(&a.compose(b))));
+        });
+    }
+
+    #[test]
+    fn compose_applies_right_to_left() {
+        let point = Point3::new(1, 2, 3);
+
+        Rotation3::all().iter().for_each(|a| {
+            Rotation3::all().iter().for_each(|b| {
+                assert_eq!(a.compose(b).apply(&point), a.apply(&b.apply(&point)));
+            })
+        });
+    }
+}
@@ -0,0 +1,319 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use strum_macros::{Display, EnumString, EnumVariantNames};
+
+/// One of the seven signal wires of a scrambled display, named a to g.
+#[derive(
+    Debug, Display, EnumString, EnumVariantNames, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord,
+)]
+#[strum(serialize_all = "kebab_case")]
+pub enum Wire {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+}
+
+/// The wires lit for one digit, in no particular order.
+pub type Pattern = BTreeSet<Wire>;
+
+/// The digit each of a display's ten patterns shows, once its wiring is worked out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigitMap {
+    digits: HashMap<Pattern, usize>,
+}
+
+impl DigitMap {
+    pub fn digit(&self, pattern: &Pattern) -> Option<usize> {
+        self.digits.get(pattern).copied()
+    }
+
+    /// Decodes output patterns in order, failing on the first one that isn't a known digit.
+    pub fn decode(&self, output: &[Pattern]) -> Result<Vec<usize>, DecodeError> {
+        output
+            .iter()
+            .map(|pattern| {
+                self.digit(pattern)
+                    .ok_or_else(|| DecodeError::UnknownOutput {
+                        pattern: pattern.to_owned(),
+                    })
+            })
+            .collect()
+    }
+}
+
+/// Why a display's wiring couldn't be worked out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    NoPattern {
+        digit: usize,
+    },
+    Ambiguous {
+        digit: usize,
+        patterns: Vec<Pattern>,
+    },
+    UnknownOutput {
+        pattern: Pattern,
+    },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::NoPattern { digit } => write!(f, "no pattern fits {}", digit),
+            DecodeError::Ambiguous { digit, patterns } => write!(
+                f,
+                "{} patterns could be {}: {}",
+                patterns.len(),
+                digit,
+                patterns
+                    .iter()
+                    .map(pattern_name)
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            DecodeError::UnknownOutput { pattern } => {
+                write!(f, "output {} matches no digit", pattern_name(pattern))
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// The pattern's wires in order, as they are written in the puzzle input.
+pub fn pattern_name(pattern: &Pattern) -> String {
+    pattern.iter().map(|wire| wire.to_string()).collect()
+}
+
+/// Works out which digit each of the ten unique patterns shows. The unique lengths give 1, 4, 7
+/// and 8, and the rest follow from how they overlap those.
+pub fn decode_line(input: &[BTreeSet<Wire>]) -> Result<DigitMap, DecodeError> {
+    let (one, four, seven, eight, rest) = find_1_4_7_8(input)?;
+    let (six, three, rest) = find_6_3(&one, rest)?;
+    let (nine, rest) = find_9(&three, rest)?;
+    let (zero, rest) = find_0(rest)?;
+    let (five, two) = find_5_2(&six, &nine, rest)?;
+
+    Ok(DigitMap {
+        digits: vec![
+            (zero, 0usize),
+            (one, 1usize),
+            (two, 2usize),
+            (three, 3usize),
+            (four, 4usize),
+            (five, 5usize),
+            (six, 6usize),
+            (seven, 7usize),
+            (eight, 8usize),
+            (nine, 9usize),
+        ]
+        .into_iter()
+        .collect(),
+    })
+}
+
+// The sum, for each digit, of how many of the ten digits light each of its segments. Every digit
+// gets a different sum whatever the wiring, since rewiring doesn't change how often a segment is
+// lit.
+const FREQUENCY_SCORES: [usize; 10] = [42, 17, 34, 39, 30, 37, 41, 25, 49, 45];
+
+/// Works out which digit each of the ten unique patterns shows from how often each wire appears
+/// across all ten. A pattern's digit is the one whose segments add up to the same total.
+pub fn decode_line_by_frequency(input: &[Pattern]) -> Result<DigitMap, DecodeError> {
+    let frequencies: HashMap<Wire, usize> =
+        input
+            .iter()
+            .flatten()
+            .fold(HashMap::new(), |mut frequencies, wire| {
+                *frequencies.entry(*wire).or_default() += 1;
+                frequencies
+            });
+    let score = |pattern: &Pattern| -> usize {
+        pattern
+            .iter()
+            .map(|wire| frequencies.get(wire).copied().unwrap_or(0usize))
+            .sum()
+    };
+
+    Ok(DigitMap {
+        digits: FREQUENCY_SCORES
+            .iter()
+            .enumerate()
+            .map(|(digit, digit_score)| {
+                find_unique(input, digit, |pattern| score(pattern) == *digit_score)
+                    .map(|pattern| (pattern, digit))
+            })
+            .collect::<Result<HashMap<Pattern, usize>, DecodeError>>()?,
+    })
+}
+
+// The one pattern that fits the digit. A line with none, or with several different ones, can't be
+// decoded.
+fn find_unique<'a>(
+    signals: impl IntoIterator<Item = &'a Pattern>,
+    digit: usize,
+    fits: impl Fn(&Pattern) -> bool,
+) -> Result<Pattern, DecodeError> {
+    let candidates: BTreeSet<&Pattern> =
+        signals.into_iter().filter(|signal| fits(signal)).collect();
+
+    match candidates.len() {
+        0 => Err(DecodeError::NoPattern { digit }),
+        1 => Ok(candidates.into_iter().next().unwrap().to_owned()),
+        _ => Err(DecodeError::Ambiguous {
+            digit,
+            patterns: candidates.into_iter().cloned().collect(),
+        }),
+    }
+}
+
+fn find_1_4_7_8(
+    signals: &[Pattern],
+) -> Result<(Pattern, Pattern, Pattern, Pattern, Vec<Pattern>), DecodeError> {
+    let one = find_unique(signals, 1usize, |segment| segment.len() == 2)?;
+    let four = find_unique(signals, 4usize, |segment| segment.len() == 4)?;
+    let seven = find_unique(signals, 7usize, |segment| segment.len() == 3)?;
+    let eight = find_unique(signals, 8usize, |segment| segment.len() == 7)?;
+
+    let rest = signals
+        .iter()
+        .filter(|signal| {
+            **signal != one && **signal != four && **signal != seven && **signal != eight
+        })
+        .map(|signal| signal.to_owned())
+        .collect();
+
+    Ok((one, four, seven, eight, rest))
+}
+
+fn find_6_3(
+    one: &Pattern,
+    signals: Vec<Pattern>,
+) -> Result<(Pattern, Pattern, Vec<Pattern>), DecodeError> {
+    let six = find_unique(&signals, 6usize, |signal| {
+        signal.len() == 6 && signal.intersection(one).count() == 1
+    })?;
+
+    let three = find_unique(&signals, 3usize, |signal| {
+        signal.len() == 5 && signal.intersection(one).count() == 2
+    })?;
+
+    let rest = signals
+        .into_iter()
+        .filter(|signal| *signal != six && *signal != three)
+        .collect();
+
+    Ok((six, three, rest))
+}
+
+fn find_9(three: &Pattern, signals: Vec<Pattern>) -> Result<(Pattern, Vec<Pattern>), DecodeError> {
+    let nine = find_unique(&signals, 9usize, |signal| {
+        signal.len() == 6 && signal.intersection(three).count() == 5
+    })?;
+
+    let rest = signals
+        .into_iter()
+        .filter(|signal| *signal != nine)
+        .collect();
+
+    Ok((nine, rest))
+}
+
+fn find_0(signals: Vec<Pattern>) -> Result<(Pattern, Vec<Pattern>), DecodeError> {
+    let zero = find_unique(&signals, 0usize, |signal| signal.len() == 6)?;
+
+    let rest = signals
+        .into_iter()
+        .filter(|signal| *signal != zero)
+        .collect();
+    Ok((zero, rest))
+}
+
+fn find_5_2(
+    six: &Pattern,
+    nine: &Pattern,
+    signals: Vec<Pattern>,
+) -> Result<(Pattern, Pattern), DecodeError> {
+    let five: Pattern = six
+        .intersection(nine)
+        .map(|signal| signal.to_owned())
+        .collect();
+    if !signals.contains(&five) {
+        return Err(DecodeError::NoPattern { digit: 5usize });
+    }
+
+    let two = find_unique(&signals, 2usize, |signal| {
+        signal.len() == 5 && *signal != five
+    })?;
+
+    Ok((five, two))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn patterns(line: &str) -> Vec<Pattern> {
+        line.split(' ')
+            .map(|pattern| {
+                pattern
+                    .chars()
+                    .map(|wire| Wire::from_str(&wire.to_string()).unwrap())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn decode_line_maps_every_digit() {
+        let digits = decode_line(&patterns(
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab",
+        ))
+        .unwrap();
+
+        assert_eq!(
+            digits.decode(&patterns("cdfeb fcadb cdfeb cdbaf")).unwrap(),
+            vec![5, 3, 5, 3]
+        );
+        assert_eq!(
+            digits.decode(&patterns("cdfeb abcdefg ab ac")).unwrap_err(),
+            DecodeError::UnknownOutput {
+                pattern: patterns("ac").remove(0)
+            }
+        );
+    }
+
+    #[test]
+    fn frequency_decoding_agrees_with_deduction() {
+        let input = patterns("acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab");
+
+        assert_eq!(
+            decode_line_by_frequency(&input).unwrap(),
+            decode_line(&input).unwrap()
+        );
+        assert_eq!(
+            decode_line_by_frequency(&patterns(
+                "ab cd abc abcd abcdefg bcdef acdfg abcdf abcdef bcdefg"
+            ))
+            .unwrap_err(),
+            DecodeError::NoPattern { digit: 2usize }
+        );
+    }
+
+    #[test]
+    fn ambiguous_line_is_an_error() {
+        assert_eq!(
+            decode_line(&patterns(
+                "ab cd abc abcd abcdefg bcdef acdfg abcdf abcdef bcdefg"
+            ))
+            .unwrap_err()
+            .to_string(),
+            "2 patterns could be 1: ab, cd"
+        );
+    }
+}
@@ -0,0 +1,363 @@
+use crate::{parse_usize, AocError};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    combinator::map,
+    sequence::{preceded, separated_pair, terminated},
+    IResult,
+};
+use std::fmt;
+use std::ops::Add;
+use std::str::FromStr;
+
+/// A snailfish number, which is always a pair of elements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnailNumber {
+    pub left: Element,
+    pub right: Element,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Element {
+    Literal(usize),
+    Number(Box<SnailNumber>),
+}
+
+impl SnailNumber {
+    pub fn new(left: Element, right: Element) -> SnailNumber {
+        SnailNumber { left, right }
+    }
+
+    /// Explodes and splits until neither applies, always preferring an explosion.
+    pub fn reduce(self) -> SnailNumber {
+        let mut number = self;
+
+        loop {
+            let (result, did_explode, _, _) = explode(number, 0usize);
+            number = result;
+
+            if did_explode {
+                continue;
+            }
+
+            let (result, did_split) = split(number);
+            number = result;
+
+            if did_split {
+                continue;
+            }
+
+            break;
+        }
+
+        number
+    }
+
+    /// The first place, reading left to right, where the number isn't reduced: a pair nested
+    /// inside four others or a regular number over 9. The offending subtree is printed with it.
+    pub fn invariant_violation(&self) -> Option<String> {
+        violation_in(self, 0usize)
+    }
+
+    pub fn magnitude(&self) -> usize {
+        let left = 3 * magnitude_element(&self.left);
+        let right = 2 * magnitude_element(&self.right);
+
+        left + right
+    }
+}
+
+/// The reduced sum after each number is added in turn, starting with the first number alone.
+pub fn running_sums<I>(numbers: I) -> impl Iterator<Item = SnailNumber>
+where
+    I: IntoIterator<Item = SnailNumber>,
+{
+    numbers
+        .into_iter()
+        .scan(None, |sum: &mut Option<SnailNumber>, number| {
+            let next = match sum.take() {
+                Some(sum) => sum + number,
+                None => number,
+            };
+            *sum = Some(next.clone());
+            Some(next)
+        })
+}
+
+impl Add for SnailNumber {
+    type Output = SnailNumber;
+
+    fn add(self, other: SnailNumber) -> SnailNumber {
+        SnailNumber::new(
+            Element::Number(Box::new(self)),
+            Element::Number(Box::new(other)),
+        )
+        .reduce()
+    }
+}
+
+impl fmt::Display for SnailNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{},{}]", self.left, self.right)
+    }
+}
+
+impl fmt::Display for Element {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Element::Literal(value) => write!(f, "{}", value),
+            Element::Number(number) => write!(f, "{}", number),
+        }
+    }
+}
+
+impl FromStr for SnailNumber {
+    type Err = AocError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match parse_snail_number(input) {
+            Ok(("", number)) => Ok(number),
+            Ok((rest, _)) => Err(AocError::parse(input, rest)),
+            Err(error) => Err(AocError::from_nom(input, error)),
+        }
+    }
+}
+
+pub fn parse_snail_number(input: &str) -> IResult<&str, SnailNumber> {
+    map(
+        separated_pair(
+            preceded(tag("["), parse_element),
+            tag(","),
+            terminated(parse_element, tag("]")),
+        ),
+        |(left, right)| SnailNumber::new(left, right),
+    )(input)
+}
+
+fn parse_element(input: &str) -> IResult<&str, Element> {
+    alt((
+        map(parse_usize, Element::Literal),
+        map(parse_snail_number, |value| Element::Number(Box::new(value))),
+    ))(input)
+}
+
+fn explode(number: SnailNumber, depth: usize) -> (SnailNumber, bool, Option<usize>, Option<usize>) {
+    let (mut left, did_explode, left_carry, right_carry) = explode_element(number.left, &depth);
+
+    if did_explode {
+        let right = match number.right {
+            Element::Literal(value) => match right_carry {
+                Option::Some(carry) => Element::Literal(value + carry),
+                Option::None => Element::Literal(value),
+            },
+            Element::Number(number) => match right_carry {
+                Option::Some(carry) => {
+                    Element::Number(Box::new(add_to_first_available_left(*number, carry)))
+                }
+                Option::None => Element::Number(number),
+            },
+        };
+
+        return (
+            SnailNumber::new(left, right),
+            true,
+            left_carry,
+            Option::None,
+        );
+    }
+
+    let (right, did_explode, left_carry, right_carry) = explode_element(number.right, &depth);
+
+    left = match left_carry {
+        Option::Some(value) => add_to_furthest_available_right(left, value),
+        Option::None => left,
+    };
+
+    (
+        SnailNumber::new(left, right),
+        did_explode,
+        Option::None,
+        right_carry,
+    )
+}
+
+fn explode_element(
+    element: Element,
+    depth: &usize,
+) -> (Element, bool, Option<usize>, Option<usize>) {
+    match element {
+        Element::Literal(value) => (Element::Literal(value), false, Option::None, Option::None),
+        Element::Number(value) => {
+            if depth == &3usize {
+                (
+                    Element::Literal(0usize),
+                    true,
+                    Option::Some(match value.left {
+                        Element::Literal(value) => value,
+                        _ => 0usize,
+                    }),
+                    Option::Some(match value.right {
+                        Element::Literal(value) => value,
+                        _ => 0usize,
+                    }),
+                )
+            } else {
+                let (result, did_explode, left_carry, right_carry) = explode(*value, depth + 1);
+                (
+                    Element::Number(Box::new(result)),
+                    did_explode,
+                    left_carry,
+                    right_carry,
+                )
+            }
+        }
+    }
+}
+
+fn add_to_first_available_left(number: SnailNumber, carry: usize) -> SnailNumber {
+    let left = match number.left {
+        Element::Literal(value) => Element::Literal(value + carry),
+        Element::Number(value) => {
+            Element::Number(Box::new(add_to_first_available_left(*value, carry)))
+        }
+    };
+
+    SnailNumber::new(left, number.right)
+}
+
+fn add_to_furthest_available_right(element: Element, carry: usize) -> Element {
+    match element {
+        Element::Literal(value) => Element::Literal(value + carry),
+        Element::Number(value) => Element::Number(Box::new(SnailNumber::new(
+            value.left,
+            add_to_furthest_available_right(value.right, carry),
+        ))),
+    }
+}
+
+fn split(number: SnailNumber) -> (SnailNumber, bool) {
+    let (left, did_split) = split_element(number.left);
+
+    if did_split {
+        return (SnailNumber::new(left, number.right), true);
+    };
+
+    let (right, did_split) = split_element(number.right);
+
+    (SnailNumber::new(left, right), did_split)
+}
+
+fn split_element(element: Element) -> (Element, bool) {
+    match element {
+        Element::Literal(value) => {
+            if value > 9usize {
+                let remainder = value % 2;
+                (
+                    Element::Number(Box::new(SnailNumber::new(
+                        Element::Literal(value / 2usize),
+                        Element::Literal(value / 2usize + remainder),
+                    ))),
+                    true,
+                )
+            } else {
+                (Element::Literal(value), false)
+            }
+        }
+        Element::Number(value) => {
+            let (result, did_split) = split(*value);
+            (Element::Number(Box::new(result)), did_split)
+        }
+    }
+}
+
+fn violation_in(number: &SnailNumber, depth: usize) -> Option<String> {
+    if depth > 3usize {
+        return Some(format!(
+            "pair {} is nested inside {} other pairs",
+            number, depth
+        ));
+    }
+
+    [&number.left, &number.right]
+        .into_iter()
+        .find_map(|element| match element {
+            Element::Literal(value) if *value > 9usize => {
+                Some(format!("regular number {} is over 9 in {}", value, number))
+            }
+            Element::Literal(_) => None,
+            Element::Number(inner) => violation_in(inner, depth + 1),
+        })
+}
+
+fn magnitude_element(element: &Element) -> usize {
+    match &element {
+        Element::Literal(value) => *value,
+        Element::Number(value) => value.magnitude(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_reduces() {
+        let left: SnailNumber = "[[[[4,3],4],4],[7,[[8,4],9]]]".parse().unwrap();
+        let right: SnailNumber = "[1,1]".parse().unwrap();
+
+        assert_eq!(
+            (left + right).to_string(),
+            "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]"
+        );
+    }
+
+    #[test]
+    fn running_sums_add_in_order() {
+        let numbers = ["[1,1]", "[2,2]", "[3,3]", "[4,4]"]
+            .iter()
+            .map(|number| number.parse::<SnailNumber>().unwrap());
+
+        assert_eq!(
+            running_sums(numbers)
+                .map(|sum| sum.to_string())
+                .collect::<Vec<String>>(),
+            vec![
+                "[1,1]",
+                "[[1,1],[2,2]]",
+                "[[[1,1],[2,2]],[3,3]]",
+                "[[[[1,1],[2,2]],[3,3]],[4,4]]"
+            ]
+        );
+    }
+
+    #[test]
+    fn magnitude() {
+        let number: SnailNumber = "[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]"
+            .parse()
+            .unwrap();
+
+        assert_eq!(number.magnitude(), 3488usize);
+    }
+
+    #[test]
+    fn invariant_violation_finds_the_first_subtree() {
+        let reduced: SnailNumber = "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]".parse().unwrap();
+        let too_deep: SnailNumber = "[[[[[4,3],4],4],[7,[[8,4],9]]],[1,1]]".parse().unwrap();
+        let too_large: SnailNumber = "[[1,[2,13]],[10,4]]".parse().unwrap();
+
+        assert_eq!(reduced.invariant_violation(), None);
+        assert_eq!(
+            too_deep.invariant_violation(),
+            Some("pair [4,3] is nested inside 4 other pairs".to_string())
+        );
+        assert_eq!(
+            too_large.invariant_violation(),
+            Some("regular number 13 is over 9 in [2,13]".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_trailing_input() {
+        assert!("[1,2]]".parse::<SnailNumber>().is_err());
+    }
+}
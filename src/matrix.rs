@@ -0,0 +1,137 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// Whether the name matches the pattern, where * matches any run of characters and ? any one.
+pub fn glob_matches(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0usize, 0usize);
+    // The last * seen and how much of the name it has taken so far, to go back to on a mismatch.
+    let mut star: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    star = Some((star_p, star_n + 1));
+                    p = star_p + 1;
+                    n = star_n + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+// The files in the folder matching the pattern, sorted and relative to the folder. Only the part
+// of the pattern after the last / is matched, anything before it names a folder inside.
+pub fn matching_files(folder: &str, pattern: &str) -> io::Result<Vec<String>> {
+    let (prefix, name_pattern) = match pattern.rsplit_once('/') {
+        Some((directory, name_pattern)) => (format!("{}/", directory), name_pattern),
+        None => (String::new(), pattern),
+    };
+
+    let mut files: Vec<String> = fs::read_dir(Path::new(folder).join(&prefix))?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_file()))
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| glob_matches(name_pattern, name))
+        .map(|name| format!("{}{}", prefix, name))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+// The rows under their headings, with each column as wide as its widest cell.
+pub fn table(headings: &[&str], rows: &[Vec<String>]) -> String {
+    let widths: Vec<usize> = headings
+        .iter()
+        .enumerate()
+        .map(|(column, heading)| {
+            rows.iter()
+                .filter_map(|row| row.get(column))
+                .map(|cell| cell.chars().count())
+                .chain(std::iter::once(heading.chars().count()))
+                .max()
+                .unwrap_or(0usize)
+        })
+        .collect();
+    let line = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, width)| format!("{:width$}", cell, width = width))
+            .collect::<Vec<String>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    std::iter::once(line(headings.to_vec()))
+        .chain(
+            rows.iter()
+                .map(|row| line(row.iter().map(String::as_str).collect())),
+        )
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob_matches_stars_and_single_characters() {
+        assert!(glob_matches("*.txt", "edge_case.txt"));
+        assert!(glob_matches("edge_?.txt", "edge_1.txt"));
+        assert!(glob_matches("*a*e", "sample"));
+        assert!(glob_matches("*", ""));
+        assert!(!glob_matches("edge_?.txt", "edge_10.txt"));
+        assert!(!glob_matches("*.txt", "input.txt.bak"));
+        assert!(!glob_matches("a*b", "ab_"));
+    }
+
+    #[test]
+    fn matching_files_are_sorted_and_relative() {
+        let folder =
+            std::env::temp_dir().join(format!("matrix_matching_files_{}", std::process::id()));
+        fs::create_dir_all(folder.join("edge")).unwrap();
+        ["b.txt", "a.txt", "notes.md", "edge/c.txt"]
+            .iter()
+            .for_each(|name| fs::write(folder.join(name), "").unwrap());
+        let folder = folder.to_str().unwrap();
+
+        assert_eq!(
+            matching_files(folder, "*.txt").unwrap(),
+            vec!["a.txt".to_string(), "b.txt".to_string()]
+        );
+        assert_eq!(
+            matching_files(folder, "edge/*").unwrap(),
+            vec!["edge/c.txt".to_string()]
+        );
+    }
+
+    #[test]
+    fn table_lines_up_columns() {
+        let rows = vec![
+            vec!["input.txt".to_string(), "40".to_string()],
+            vec!["a.txt".to_string(), "315".to_string()],
+        ];
+
+        assert_eq!(
+            table(&["File", "Answer"], &rows),
+            "File       Answer\ninput.txt  40\na.txt      315"
+        );
+    }
+}
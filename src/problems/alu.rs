@@ -0,0 +1,764 @@
+use crate::{
+    default_sub_command, log, parse_isize, selected_part, threads, AocError, CommandResult,
+    Deadline, LogLevel, Problem,
+};
+use clap::{values_t_or_exit, App, Arg, ArgMatches, ErrorKind};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::newline,
+    combinator::{map, value},
+    multi::separated_list0,
+    sequence::{preceded, separated_pair},
+    IResult,
+};
+use std::{fs, thread};
+
+pub const ALU: Problem<AluArgs, Vec<Instruction>> = Problem::new(
+    sub_command,
+    "alu",
+    "day24_alu",
+    parse_arguments,
+    parse_data,
+    run,
+)
+.with_long_about(LONG_ABOUT)
+.with_describe(describe)
+.with_input_check(input_check);
+
+const LONG_ABOUT: &str =
+    "The input is a program for the ALU, which has four registers w, x, y and z that start at \
+    0.\n\n\
+    inp a reads the next input into a. add, mul, div, mod and eql combine a register with \
+    another register or a number. div truncates towards zero and eql stores 1 when both values \
+    are equal. The program checks a 14 digit model number, which is valid when z ends at 0. -i \
+    supplies the inputs. Part 1 and part 2 run the largest and smallest valid model numbers \
+    for the default input. -o optimizes the program first, folding the instructions whose \
+    values are known without the inputs and dropping those that change nothing, then reports the \
+    instruction counts before and after with z.\n\n\
+    --inputs-file checks a batch of model numbers instead, one per line with a digit for each \
+    input. Every line is run through the program, spread over the available cores, and the \
+    lines that leave z at 0 are reported with how many were accepted and rejected. Each line \
+    needs a digit for every inp in the program.\n\n\
+    Example: the binary sample stores the four lowest bits of its input in w, x, y and z.";
+
+#[derive(Debug)]
+pub struct AluArgs {
+    inputs: Vec<isize>,
+    optimize: bool,
+    candidates: Option<Vec<Candidate>>,
+}
+
+// A model number from the inputs file, with the line it was on.
+#[derive(Debug, Clone)]
+struct Candidate {
+    line: usize,
+    digits: Vec<isize>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Variable {
+    W,
+    X,
+    Y,
+    Z,
+}
+
+impl Variable {
+    fn register(&self) -> usize {
+        match self {
+            Variable::W => 0usize,
+            Variable::X => 1usize,
+            Variable::Y => 2usize,
+            Variable::Z => 3usize,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Value {
+    Variable(Variable),
+    Literal(isize),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Instruction {
+    Inp(Variable),
+    Add(Variable, Value),
+    Mul(Variable, Value),
+    Div(Variable, Value),
+    Mod(Variable, Value),
+    Eql(Variable, Value),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ByteCode {
+    Inp(usize),
+    AddRegister(usize, usize),
+    AddLiteral(usize, isize),
+    MulRegister(usize, usize),
+    MulLiteral(usize, isize),
+    DivRegister(usize, usize),
+    DivLiteral(usize, isize),
+    ModRegister(usize, usize),
+    ModLiteral(usize, isize),
+    EqlRegister(usize, usize),
+    EqlLiteral(usize, isize),
+    SetLiteral(usize, isize),
+    CopyRegister(usize, usize),
+}
+
+impl ByteCode {
+    // The register written and the operand, with register operands still to be resolved.
+    fn operation(&self) -> Option<(usize, Operand)> {
+        match *self {
+            ByteCode::Inp(_) | ByteCode::SetLiteral(_, _) | ByteCode::CopyRegister(_, _) => None,
+            ByteCode::AddRegister(a, b)
+            | ByteCode::MulRegister(a, b)
+            | ByteCode::DivRegister(a, b)
+            | ByteCode::ModRegister(a, b)
+            | ByteCode::EqlRegister(a, b) => Some((a, Operand::Register(b))),
+            ByteCode::AddLiteral(a, b)
+            | ByteCode::MulLiteral(a, b)
+            | ByteCode::DivLiteral(a, b)
+            | ByteCode::ModLiteral(a, b)
+            | ByteCode::EqlLiteral(a, b) => Some((a, Operand::Literal(b))),
+        }
+    }
+
+    fn with_literal(&self, literal: isize) -> ByteCode {
+        match *self {
+            ByteCode::AddRegister(a, _) | ByteCode::AddLiteral(a, _) => {
+                ByteCode::AddLiteral(a, literal)
+            }
+            ByteCode::MulRegister(a, _) | ByteCode::MulLiteral(a, _) => {
+                ByteCode::MulLiteral(a, literal)
+            }
+            ByteCode::DivRegister(a, _) | ByteCode::DivLiteral(a, _) => {
+                ByteCode::DivLiteral(a, literal)
+            }
+            ByteCode::ModRegister(a, _) | ByteCode::ModLiteral(a, _) => {
+                ByteCode::ModLiteral(a, literal)
+            }
+            ByteCode::EqlRegister(a, _) | ByteCode::EqlLiteral(a, _) => {
+                ByteCode::EqlLiteral(a, literal)
+            }
+            byte_code => byte_code,
+        }
+    }
+
+    // Applies the operation to known values, or None when it would divide by zero.
+    fn fold(&self, a: isize, b: isize) -> Option<isize> {
+        match self {
+            ByteCode::AddRegister(_, _) | ByteCode::AddLiteral(_, _) => Some(a + b),
+            ByteCode::MulRegister(_, _) | ByteCode::MulLiteral(_, _) => Some(a * b),
+            ByteCode::DivRegister(_, _) | ByteCode::DivLiteral(_, _) => a.checked_div(b),
+            ByteCode::ModRegister(_, _) | ByteCode::ModLiteral(_, _) => a.checked_rem(b),
+            ByteCode::EqlRegister(_, _) | ByteCode::EqlLiteral(_, _) => {
+                Some(if a == b { 1isize } else { 0isize })
+            }
+            _ => None,
+        }
+    }
+
+    fn changes_nothing_with(&self, literal: isize) -> bool {
+        matches!(
+            (self, literal),
+            (ByteCode::AddLiteral(_, _), 0)
+                | (ByteCode::MulLiteral(_, _), 1)
+                | (ByteCode::DivLiteral(_, _), 1)
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Operand {
+    Register(usize),
+    Literal(isize),
+}
+
+// What the optimizer knows about a register. A known value may not have been written to the
+// register yet, in which case it's written just before something reads it.
+#[derive(Debug, Clone, Copy)]
+enum Register {
+    Unknown,
+    Known { value: isize, written: bool },
+}
+
+fn sub_command() -> App<'static, 'static> {
+    default_sub_command(
+        &ALU,
+        "Parses the input program then runs it using the supplied inputs. Prints all register values and returns the value in z.",
+        "Path to the input file. Input should be newline delimited instructions.",
+        "Runs the default program with the largest valid inputs.",
+        "Runs the default program with the smallest valid inputs.",
+    )
+    .arg(
+        Arg::with_name("optimize")
+            .short("o")
+            .long("optimize")
+            .help("If passed, optimizes the program before running it and reports the instruction counts before and after."),
+    )
+    .arg(
+        Arg::with_name("input")
+            .short("i")
+            .help("Inputs to push into the alu program in the order they appear.")
+            .multiple(true)
+            .takes_value(true)
+            .allow_hyphen_values(true)
+            .number_of_values(1),
+    )
+    .arg(
+        Arg::with_name("inputs-file")
+            .short("b")
+            .long("inputs-file")
+            .help("If passed, runs the program for every line of this file, each a model number with a digit per input, and reports the lines that leave z at 0.")
+            .takes_value(true)
+            .conflicts_with("input"),
+    )
+}
+
+fn parse_arguments(arguments: &ArgMatches) -> AluArgs {
+    match selected_part(arguments) {
+        Some("part1") => AluArgs {
+            inputs: vec![9, 9, 8, 9, 3, 9, 9, 9, 2, 9, 1, 9, 6, 7isize],
+            optimize: arguments.is_present("optimize"),
+            candidates: candidates_argument(arguments),
+        },
+        Some("part2") => AluArgs {
+            inputs: vec![3, 4, 1, 7, 1, 9, 1, 1, 1, 8, 1, 2, 1, 1isize],
+            optimize: arguments.is_present("optimize"),
+            candidates: candidates_argument(arguments),
+        },
+        _ => AluArgs {
+            inputs: match arguments.is_present("inputs-file") {
+                true => Vec::new(),
+                false => values_t_or_exit!(arguments.values_of("input"), isize),
+            },
+            optimize: arguments.is_present("optimize"),
+            candidates: candidates_argument(arguments),
+        },
+    }
+}
+
+// An inputs file that can't be read exits the same way an invalid argument value does.
+fn candidates_argument(arguments: &ArgMatches) -> Option<Vec<Candidate>> {
+    arguments.value_of("inputs-file").map(|path| {
+        load_candidates(path).unwrap_or_else(|e| {
+            clap::Error::with_description(
+                &format!("Invalid inputs file {}: {}", path, e),
+                ErrorKind::InvalidValue,
+            )
+            .exit()
+        })
+    })
+}
+
+fn load_candidates(path: &str) -> Result<Vec<Candidate>, AocError> {
+    fs::read_to_string(path)?
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            line.trim()
+                .chars()
+                .map(|digit| digit.to_digit(10).map(|digit| digit as isize))
+                .collect::<Option<Vec<isize>>>()
+                .map(|digits| Candidate {
+                    line: index + 1,
+                    digits,
+                })
+                .ok_or_else(|| AocError::validation(format!("line {} is not a number", index + 1)))
+        })
+        .collect()
+}
+
+fn input_check(arguments: &AluArgs, instructions: &Vec<Instruction>) -> Result<(), AocError> {
+    let reads = instructions
+        .iter()
+        .filter(|instruction| matches!(instruction, Instruction::Inp(_)))
+        .count();
+
+    arguments
+        .candidates
+        .iter()
+        .flatten()
+        .find(|candidate| candidate.digits.len() != reads)
+        .map_or(Ok(()), |candidate| {
+            Err(AocError::validation(format!(
+                "Line {} of the inputs file has {} digits but the program reads {} inputs",
+                candidate.line,
+                candidate.digits.len(),
+                reads
+            )))
+        })
+}
+
+fn run(arguments: AluArgs, instructions: Vec<Instruction>) -> CommandResult {
+    let program = compile(&instructions);
+    let program = if arguments.optimize {
+        optimize(&program)
+    } else {
+        program
+    };
+
+    if let Some(candidates) = arguments.candidates {
+        return check_candidates(&program, &candidates);
+    }
+
+    let [w, x, y, z] = execute(&program, &arguments.inputs);
+
+    log!(LogLevel::Debug, "w: {}, x: {}, y: {}, z: {}", w, x, y, z);
+
+    if arguments.optimize {
+        vec![
+            ("Instructions".to_string(), instructions.len().into()),
+            ("Optimized instructions".to_string(), program.len().into()),
+            ("z".to_string(), z.into()),
+        ]
+        .into()
+    } else {
+        z.into()
+    }
+}
+
+// Propagates the register values that are known without the inputs, starting from all zero.
+// Operations on known values are folded away, register operands with known values become
+// literals, and adding 0, multiplying by 1 or dividing by 1 is dropped. Multiplying by 0 clears
+// the register whatever it held, so a following add becomes a copy. A known value is only written
+// once something needs it in the register, or at the end of the program.
+fn optimize(program: &[ByteCode]) -> Vec<ByteCode> {
+    let mut registers = [Register::Known {
+        value: 0isize,
+        written: true,
+    }; 4];
+    let mut optimized = Vec::new();
+
+    let write = |registers: &mut [Register; 4], optimized: &mut Vec<ByteCode>, a: usize| {
+        if let Register::Known {
+            value,
+            written: false,
+        } = registers[a]
+        {
+            optimized.push(ByteCode::SetLiteral(a, value));
+            registers[a] = Register::Known {
+                value,
+                written: true,
+            };
+        }
+    };
+
+    program.iter().for_each(|byte_code| {
+        let (a, operand) = match byte_code.operation() {
+            Some(operation) => operation,
+            None => {
+                if let ByteCode::Inp(a)
+                | ByteCode::SetLiteral(a, _)
+                | ByteCode::CopyRegister(a, _) = *byte_code
+                {
+                    registers[a] = Register::Unknown;
+                }
+                optimized.push(*byte_code);
+                return;
+            }
+        };
+
+        let literal = match operand {
+            Operand::Literal(literal) => Some(literal),
+            Operand::Register(b) => match registers[b] {
+                Register::Known { value, .. } => Some(value),
+                Register::Unknown => None,
+            },
+        };
+
+        let byte_code = match literal {
+            Some(literal) => byte_code.with_literal(literal),
+            None => *byte_code,
+        };
+
+        match (registers[a], literal) {
+            (Register::Known { value, written }, Some(literal)) => {
+                if let Some(folded) = byte_code.fold(value, literal) {
+                    registers[a] = Register::Known {
+                        value: folded,
+                        written: written && folded == value,
+                    };
+                    return;
+                }
+            }
+            (_, Some(literal)) if byte_code.changes_nothing_with(literal) => return,
+            (_, Some(0)) if matches!(byte_code, ByteCode::MulLiteral(_, _)) => {
+                registers[a] = Register::Known {
+                    value: 0isize,
+                    written: false,
+                };
+                return;
+            }
+            (Register::Known { value: 0, .. }, None)
+                if matches!(byte_code, ByteCode::AddRegister(_, _)) =>
+            {
+                return copy_register(&mut registers, &mut optimized, byte_code);
+            }
+            (Register::Known { value: 1, .. }, None)
+                if matches!(byte_code, ByteCode::MulRegister(_, _)) =>
+            {
+                return copy_register(&mut registers, &mut optimized, byte_code);
+            }
+            _ => (),
+        }
+
+        write(&mut registers, &mut optimized, a);
+        optimized.push(byte_code);
+        registers[a] = Register::Unknown;
+    });
+
+    (0..4).for_each(|a| write(&mut registers, &mut optimized, a));
+    optimized
+}
+
+// Adding a register to 0 or multiplying 1 by it just copies it.
+fn copy_register(
+    registers: &mut [Register; 4],
+    optimized: &mut Vec<ByteCode>,
+    byte_code: ByteCode,
+) {
+    if let Some((a, Operand::Register(b))) = byte_code.operation() {
+        optimized.push(ByteCode::CopyRegister(a, b));
+        registers[a] = Register::Unknown;
+    }
+}
+
+// Flattens the parsed instructions into register indexed operations so execution doesn't need to
+// match on variables or operand kinds for every instruction.
+fn compile(instructions: &[Instruction]) -> Vec<ByteCode> {
+    instructions
+        .iter()
+        .map(|instruction| match instruction {
+            Instruction::Inp(variable) => ByteCode::Inp(variable.register()),
+            Instruction::Add(variable, value) => match value {
+                Value::Variable(other) => {
+                    ByteCode::AddRegister(variable.register(), other.register())
+                }
+                Value::Literal(literal) => ByteCode::AddLiteral(variable.register(), *literal),
+            },
+            Instruction::Mul(variable, value) => match value {
+                Value::Variable(other) => {
+                    ByteCode::MulRegister(variable.register(), other.register())
+                }
+                Value::Literal(literal) => ByteCode::MulLiteral(variable.register(), *literal),
+            },
+            Instruction::Div(variable, value) => match value {
+                Value::Variable(other) => {
+                    ByteCode::DivRegister(variable.register(), other.register())
+                }
+                Value::Literal(literal) => ByteCode::DivLiteral(variable.register(), *literal),
+            },
+            Instruction::Mod(variable, value) => match value {
+                Value::Variable(other) => {
+                    ByteCode::ModRegister(variable.register(), other.register())
+                }
+                Value::Literal(literal) => ByteCode::ModLiteral(variable.register(), *literal),
+            },
+            Instruction::Eql(variable, value) => match value {
+                Value::Variable(other) => {
+                    ByteCode::EqlRegister(variable.register(), other.register())
+                }
+                Value::Literal(literal) => ByteCode::EqlLiteral(variable.register(), *literal),
+            },
+        })
+        .collect()
+}
+
+fn check_candidates(program: &[ByteCode], candidates: &[Candidate]) -> CommandResult {
+    let accepted: Vec<&Candidate> = candidates
+        .iter()
+        .zip(run_batch(program, candidates))
+        .filter(|(_, z)| *z == 0isize)
+        .map(|(candidate, _)| candidate)
+        .collect();
+
+    accepted
+        .iter()
+        .map(|candidate| {
+            (
+                format!("Line {}", candidate.line),
+                candidate
+                    .digits
+                    .iter()
+                    .map(|digit| digit.to_string())
+                    .collect::<String>()
+                    .into(),
+            )
+        })
+        .chain(vec![
+            ("Accepted".to_string(), accepted.len().into()),
+            (
+                "Rejected".to_string(),
+                (candidates.len() - accepted.len()).into(),
+            ),
+        ])
+        .collect::<Vec<(String, CommandResult)>>()
+        .into()
+}
+
+// z for every candidate, in order. The candidates are split into a chunk per thread. Each chunk
+// stops early once the deadline passes, leaving its remaining candidates out.
+fn run_batch(program: &[ByteCode], candidates: &[Candidate]) -> Vec<isize> {
+    let chunk_size = candidates.len().div_ceil(threads()).max(1usize);
+    let deadline = Deadline::current();
+
+    thread::scope(|scope| {
+        candidates
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    deadline.set_current();
+                    chunk
+                        .iter()
+                        .take_while(|_| !Deadline::current().expired())
+                        .map(|candidate| execute(program, &candidate.digits)[3])
+                        .collect::<Vec<isize>>()
+                })
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("The program runs without panicking"))
+            .collect()
+    })
+}
+
+fn execute(program: &[ByteCode], inputs: &[isize]) -> [isize; 4] {
+    let mut registers = [0isize; 4];
+    let mut inputs = inputs.iter();
+
+    program.iter().for_each(|byte_code| match *byte_code {
+        ByteCode::Inp(a) => {
+            registers[a] = *inputs.next().expect("Enough inputs for program");
+        }
+        ByteCode::AddRegister(a, b) => registers[a] += registers[b],
+        ByteCode::AddLiteral(a, b) => registers[a] += b,
+        ByteCode::MulRegister(a, b) => registers[a] *= registers[b],
+        ByteCode::MulLiteral(a, b) => registers[a] *= b,
+        ByteCode::DivRegister(a, b) => registers[a] /= registers[b],
+        ByteCode::DivLiteral(a, b) => registers[a] /= b,
+        ByteCode::ModRegister(a, b) => registers[a] %= registers[b],
+        ByteCode::ModLiteral(a, b) => registers[a] %= b,
+        ByteCode::EqlRegister(a, b) => {
+            registers[a] = if registers[a] == registers[b] {
+                1isize
+            } else {
+                0isize
+            }
+        }
+        ByteCode::EqlLiteral(a, b) => {
+            registers[a] = if registers[a] == b { 1isize } else { 0isize }
+        }
+        ByteCode::SetLiteral(a, b) => registers[a] = b,
+        ByteCode::CopyRegister(a, b) => registers[a] = registers[b],
+    });
+
+    registers
+}
+
+fn describe(instructions: &Vec<Instruction>) -> String {
+    format!(
+        "{} instructions reading {} inputs",
+        instructions.len(),
+        instructions
+            .iter()
+            .filter(|instruction| matches!(instruction, Instruction::Inp(_)))
+            .count()
+    )
+}
+
+fn parse_data(input: &String) -> IResult<&str, Vec<Instruction>> {
+    separated_list0(newline, parse_instruction)(input)
+}
+
+fn parse_instruction(input: &str) -> IResult<&str, Instruction> {
+    alt((
+        parse_inp, parse_add, parse_mul, parse_div, parse_mod, parse_eql,
+    ))(input)
+}
+
+fn parse_inp(input: &str) -> IResult<&str, Instruction> {
+    map(preceded(tag("inp "), parse_variable), |variable| {
+        Instruction::Inp(variable)
+    })(input)
+}
+
+fn parse_add(input: &str) -> IResult<&str, Instruction> {
+    map(
+        preceded(
+            tag("add "),
+            separated_pair(parse_variable, tag(" "), parse_value),
+        ),
+        |(variable, value)| Instruction::Add(variable, value),
+    )(input)
+}
+
+fn parse_mul(input: &str) -> IResult<&str, Instruction> {
+    map(
+        preceded(
+            tag("mul "),
+            separated_pair(parse_variable, tag(" "), parse_value),
+        ),
+        |(variable, value)| Instruction::Mul(variable, value),
+    )(input)
+}
+
+fn parse_div(input: &str) -> IResult<&str, Instruction> {
+    map(
+        preceded(
+            tag("div "),
+            separated_pair(parse_variable, tag(" "), parse_value),
+        ),
+        |(variable, value)| Instruction::Div(variable, value),
+    )(input)
+}
+
+fn parse_mod(input: &str) -> IResult<&str, Instruction> {
+    map(
+        preceded(
+            tag("mod "),
+            separated_pair(parse_variable, tag(" "), parse_value),
+        ),
+        |(variable, value)| Instruction::Mod(variable, value),
+    )(input)
+}
+
+fn parse_eql(input: &str) -> IResult<&str, Instruction> {
+    map(
+        preceded(
+            tag("eql "),
+            separated_pair(parse_variable, tag(" "), parse_value),
+        ),
+        |(variable, value)| Instruction::Eql(variable, value),
+    )(input)
+}
+
+fn parse_value(input: &str) -> IResult<&str, Value> {
+    alt((
+        map(parse_variable, |variable| Value::Variable(variable)),
+        map(parse_isize, |value| Value::Literal(value)),
+    ))(input)
+}
+
+fn parse_variable(input: &str) -> IResult<&str, Variable> {
+    alt((
+        value(Variable::W, tag("w")),
+        value(Variable::X, tag("x")),
+        value(Variable::Y, tag("y")),
+        value(Variable::Z, tag("z")),
+    ))(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{run_problem_with_input, scratch_path};
+
+    const SAMPLE: &str = include_str!("../../day24_alu/sample.txt");
+    const SAMPLE2: &str = include_str!("../../day24_alu/sample2.txt");
+    const SAMPLE3: &str = include_str!("../../day24_alu/sample3.txt");
+
+    #[test]
+    fn negate_sample() {
+        assert_eq!(
+            run_problem_with_input(&ALU, SAMPLE, &["-f", "sample.txt", "-i", "5"]).unwrap(),
+            0isize.into()
+        );
+    }
+
+    #[test]
+    fn triple_sample() {
+        assert_eq!(
+            run_problem_with_input(&ALU, SAMPLE2, &["-f", "sample2.txt", "-i", "2", "-i", "6"])
+                .unwrap(),
+            1isize.into()
+        );
+    }
+
+    #[test]
+    fn optimize_binary_sample() {
+        assert_eq!(
+            run_problem_with_input(&ALU, SAMPLE3, &["-f", "sample3.txt", "-o", "-i", "11"])
+                .unwrap(),
+            vec![
+                ("Instructions".to_string(), 11usize.into()),
+                ("Optimized instructions".to_string(), 11usize.into()),
+                ("z".to_string(), 1isize.into()),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn optimize_folds_known_values() {
+        let program = "inp w\nmul x 0\nadd x 5\nmul x 2\nadd y x\neql y 10\nmul z 0\nadd z w\nmul z y\nadd z 0";
+        let (_, instructions) = parse_data(&program.to_string()).unwrap();
+        let program = compile(&instructions);
+        let optimized = optimize(&program);
+
+        assert_eq!(optimized.len(), 4);
+        (-3..=3).for_each(|input| {
+            assert_eq!(execute(&optimized, &[input]), execute(&program, &[input]));
+        });
+    }
+
+    #[test]
+    fn inputs_file_sample() {
+        let inputs = scratch_path("alu_inputs_file_sample.txt");
+        std::fs::write(&inputs, "26\n27\n\n13\n31\n").unwrap();
+        let inputs = inputs.to_str().unwrap();
+
+        assert_eq!(
+            run_problem_with_input(&ALU, SAMPLE2, &["-f", "sample2.txt", "-b", inputs]).unwrap(),
+            vec![
+                ("Line 2".to_string(), "27".to_string().into()),
+                ("Line 5".to_string(), "31".to_string().into()),
+                ("Accepted".to_string(), 2usize.into()),
+                ("Rejected".to_string(), 2usize.into()),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn inputs_file_needs_a_digit_per_input() {
+        let inputs = scratch_path("alu_inputs_file_needs_a_digit_per_input.txt");
+        std::fs::write(&inputs, "26\n269\n").unwrap();
+        let inputs = inputs.to_str().unwrap();
+
+        assert_eq!(
+            run_problem_with_input(&ALU, SAMPLE2, &["-f", "sample2.txt", "-b", inputs])
+                .unwrap_err()
+                .to_string(),
+            "Line 2 of the inputs file has 3 digits but the program reads 2 inputs"
+        );
+    }
+
+    #[test]
+    fn run_batch_stops_at_the_deadline() {
+        let (_, instructions) = parse_data(&SAMPLE2.to_string()).unwrap();
+        let program = compile(&instructions);
+        let candidates: Vec<Candidate> = (1..=100)
+            .map(|line| Candidate {
+                line,
+                digits: vec![2, 6],
+            })
+            .collect();
+
+        assert_eq!(run_batch(&program, &candidates).len(), 100);
+        Deadline::after(std::time::Duration::from_secs(0u64)).set_current();
+        assert!(run_batch(&program, &candidates).is_empty());
+    }
+
+    #[test]
+    fn binary_sample() {
+        assert_eq!(
+            run_problem_with_input(&ALU, SAMPLE3, &["-f", "sample3.txt", "-i", "11"]).unwrap(),
+            1isize.into()
+        );
+    }
+}
@@ -0,0 +1,725 @@
+use crate::{
+    default_sub_command, selected_part, AocError, CommandResult, Deadline, Example, Problem,
+};
+use clap::{value_t_or_exit, App, Arg, ArgMatches};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{line_ending, multispace0, space0},
+    combinator::{map, opt, value},
+    multi::{count, many0, many1, separated_list1},
+    sequence::{delimited, preceded, terminated, tuple},
+    IResult,
+};
+use std::{
+    cmp::min,
+    collections::{BTreeMap, HashMap, HashSet},
+};
+use strum::VariantNames;
+use strum_macros::{Display, EnumString, EnumVariantNames};
+
+pub const AMPHIPOD: Problem<AmphipodArgs, Burrow> = Problem::new(
+    sub_command,
+    "amphipod",
+    "day23_amphipod",
+    parse_arguments,
+    parse_data,
+    run,
+)
+.with_long_about(LONG_ABOUT)
+.with_examples(EXAMPLES)
+.with_describe(describe)
+.with_input_check(input_check);
+
+const LONG_ABOUT: &str =
+    "The input is a burrow with a hallway and four side rooms holding amphipods of types A, B, \
+    C and D.\n\n\
+    Each type has to end up in its own room, A to D from left to right. Moving one step costs \
+    1, 10, 100 or 1000 energy depending on the type. Amphipods never stop outside a room, only \
+    enter their own room when it holds no other type, and once in the hallway stay put until \
+    they can move into their room. The answer is the least energy needed. -a unfolds the two \
+    extra rows from part 2 into the rooms.\n\n\
+    The burrow can be drawn as in the puzzle, with any number of rows and amphipods already in \
+    the hallway, or given on one line as the flat state. That's the 11 hallway spaces then each \
+    row of the rooms from the top down, split by /, with . for empty spaces. The hallway can be \
+    left out when it's empty, so the sample is BCBD/ADCA. The format is worked out from the \
+    input, and -i insists on one. validate prints any burrow as a flat state to share.\n\n\
+    Example: the sample needs 12521 energy, or 44169 with the extra rows.";
+
+const SAMPLE: &str = include_str!("../../day23_amphipod/sample.txt");
+
+const EXAMPLES: &[Example] = &[
+    Example::new(SAMPLE, 1, "12521"),
+    Example::new(SAMPLE, 2, "44169"),
+];
+
+#[derive(Debug)]
+pub struct AmphipodArgs {
+    additional_rows: bool,
+    format: Format,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "kebab_case")]
+enum Format {
+    Auto,
+    Diagram,
+    Flat,
+}
+
+// A burrow as it was read, which may be part way through the game.
+#[derive(Debug, Clone)]
+pub struct Burrow {
+    format: Format,
+    // The 11 hallway spaces from left to right.
+    hallway: Vec<Option<Amphipod>>,
+    // The four rooms' spaces a row at a time, from the top down.
+    rows: Vec<Vec<Option<Amphipod>>>,
+}
+
+const ROOMS: [Amphipod; 4] = [
+    Amphipod::Amber,
+    Amphipod::Bronze,
+    Amphipod::Copper,
+    Amphipod::Desert,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Amphipod {
+    Amber,
+    Bronze,
+    Copper,
+    Desert,
+}
+
+impl Amphipod {
+    fn multiplier(&self) -> usize {
+        match self {
+            Amphipod::Amber => 1usize,
+            Amphipod::Bronze => 10usize,
+            Amphipod::Copper => 100usize,
+            Amphipod::Desert => 1000usize,
+        }
+    }
+
+    fn letter(&self) -> char {
+        match self {
+            Amphipod::Amber => 'A',
+            Amphipod::Bronze => 'B',
+            Amphipod::Copper => 'C',
+            Amphipod::Desert => 'D',
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct AmphipodGame {
+    block_depth: usize,
+    buffers: BTreeMap<BufferLocation, Amphipod>,
+    blocks: BTreeMap<Amphipod, Vec<Amphipod>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum BufferLocation {
+    FarLeft,
+    Left,
+    AB,
+    BC,
+    CD,
+    Right,
+    FarRight,
+}
+
+impl BufferLocation {
+    // The hallway spaces that aren't in front of a room, the only ones an amphipod can stop in.
+    fn from_hallway(space: usize) -> Option<BufferLocation> {
+        match space {
+            0 => Some(BufferLocation::FarLeft),
+            1 => Some(BufferLocation::Left),
+            3 => Some(BufferLocation::AB),
+            5 => Some(BufferLocation::BC),
+            7 => Some(BufferLocation::CD),
+            9 => Some(BufferLocation::Right),
+            10 => Some(BufferLocation::FarRight),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum Node {
+    Buffer(BufferLocation),
+    Block(Amphipod),
+}
+
+impl Node {
+    fn get_adjacent_nodes(&self) -> Vec<(Node, usize)> {
+        match self {
+            Node::Buffer(location) => match location {
+                BufferLocation::FarLeft => vec![(Node::Buffer(BufferLocation::Left), 1)],
+                BufferLocation::Left => vec![
+                    (Node::Buffer(BufferLocation::FarLeft), 1),
+                    (Node::Buffer(BufferLocation::AB), 2),
+                    (Node::Block(Amphipod::Amber), 2),
+                ],
+                BufferLocation::AB => vec![
+                    (Node::Buffer(BufferLocation::Left), 2),
+                    (Node::Buffer(BufferLocation::BC), 2),
+                    (Node::Block(Amphipod::Amber), 2),
+                    (Node::Block(Amphipod::Bronze), 2),
+                ],
+                BufferLocation::BC => vec![
+                    (Node::Buffer(BufferLocation::AB), 2),
+                    (Node::Buffer(BufferLocation::CD), 2),
+                    (Node::Block(Amphipod::Bronze), 2),
+                    (Node::Block(Amphipod::Copper), 2),
+                ],
+                BufferLocation::CD => vec![
+                    (Node::Buffer(BufferLocation::BC), 2),
+                    (Node::Buffer(BufferLocation::Right), 2),
+                    (Node::Block(Amphipod::Copper), 2),
+                    (Node::Block(Amphipod::Desert), 2),
+                ],
+                BufferLocation::Right => vec![
+                    (Node::Buffer(BufferLocation::FarRight), 1),
+                    (Node::Buffer(BufferLocation::CD), 2),
+                    (Node::Block(Amphipod::Desert), 2),
+                ],
+                BufferLocation::FarRight => vec![(Node::Buffer(BufferLocation::Right), 1)],
+            },
+            Node::Block(amphipod) => match amphipod {
+                Amphipod::Amber => vec![
+                    (Node::Buffer(BufferLocation::Left), 2),
+                    (Node::Buffer(BufferLocation::AB), 2),
+                ],
+                Amphipod::Bronze => vec![
+                    (Node::Buffer(BufferLocation::AB), 2),
+                    (Node::Buffer(BufferLocation::BC), 2),
+                ],
+                Amphipod::Copper => vec![
+                    (Node::Buffer(BufferLocation::BC), 2),
+                    (Node::Buffer(BufferLocation::CD), 2),
+                ],
+                Amphipod::Desert => vec![
+                    (Node::Buffer(BufferLocation::CD), 2),
+                    (Node::Buffer(BufferLocation::Right), 2),
+                ],
+            },
+        }
+    }
+}
+
+fn sub_command() -> App<'static, 'static> {
+    default_sub_command(
+        &AMPHIPOD,
+        "Finds solutions to the amphipod game, returns the solution value with the lowest energy.",
+        "Path to the input file. Input should be the intial setup of the game.",
+        "Finds the result of the gane for the default input.",
+        "Finds the result of the gane for the default input with additional rows added.",
+    )
+    .arg(
+        Arg::with_name("additional-rows")
+            .short("a")
+            .help("If passed, adds two more rows to the amphipod game."),
+    )
+    .arg(
+        Arg::with_name("format")
+            .short("i")
+            .long("format")
+            .help(
+                "The format of the input. The formats available are as follows:\n\n\
+            auto: Works out the format from the input.\n\n\
+            diagram: The burrow drawn as in the puzzle.\n\n\
+            flat: The hallway and rows on one line, such as ...B......./BC.D/ADCA.\n\n",
+            )
+            .takes_value(true)
+            .possible_values(Format::VARIANTS)
+            .default_value("auto"),
+    )
+}
+
+fn parse_arguments(arguments: &ArgMatches) -> AmphipodArgs {
+    match selected_part(arguments) {
+        Some("part1") => AmphipodArgs {
+            additional_rows: false,
+            format: value_t_or_exit!(arguments.value_of("format"), Format),
+        },
+        Some("part2") => AmphipodArgs {
+            additional_rows: true,
+            format: value_t_or_exit!(arguments.value_of("format"), Format),
+        },
+        _ => AmphipodArgs {
+            additional_rows: arguments.is_present("additional-rows"),
+            format: value_t_or_exit!(arguments.value_of("format"), Format),
+        },
+    }
+}
+
+// The search can only finish on a burrow the rules could have led to, with each room full of its
+// own type at the end.
+fn input_check(arguments: &AmphipodArgs, burrow: &Burrow) -> Result<(), AocError> {
+    if arguments.format != Format::Auto && arguments.format != burrow.format {
+        return Err(AocError::validation(format!(
+            "The input is in the {} format but --format {} was passed",
+            burrow.format, arguments.format
+        )));
+    }
+
+    if let Some((space, amphipod)) = burrow
+        .hallway
+        .iter()
+        .enumerate()
+        .filter_map(|(space, amphipod)| amphipod.map(|amphipod| (space, amphipod)))
+        .find(|(space, _)| BufferLocation::from_hallway(*space).is_none())
+    {
+        return Err(AocError::validation(format!(
+            "{} is stopped in hallway space {}, in front of a room",
+            amphipod.letter(),
+            space + 1
+        )));
+    }
+
+    if let Some(room) = ROOMS.iter().enumerate().find_map(|(index, room)| {
+        burrow
+            .rows
+            .windows(2)
+            .any(|rows| rows[0][index].is_some() && rows[1][index].is_none())
+            .then_some(room)
+    }) {
+        return Err(AocError::validation(format!(
+            "Room {} has an empty space below an amphipod",
+            room.letter()
+        )));
+    }
+
+    if arguments.additional_rows && burrow.rows.len() != 2 {
+        return Err(AocError::validation(format!(
+            "The extra rows go into rooms 2 deep, but these rooms are {} deep",
+            burrow.rows.len()
+        )));
+    }
+
+    let amphipods = burrow
+        .hallway
+        .iter()
+        .chain(burrow.rows.iter().flatten())
+        .flatten();
+    match ROOMS.iter().find_map(|room| {
+        let count = amphipods
+            .clone()
+            .filter(|amphipod| *amphipod == room)
+            .count();
+        (count != burrow.rows.len()).then_some((room, count))
+    }) {
+        Some((room, count)) => Err(AocError::validation(format!(
+            "There are {} {} amphipods but each room holds {}",
+            count,
+            room.letter(),
+            burrow.rows.len()
+        ))),
+        None => Ok(()),
+    }
+}
+
+// Rooms are stacks from the bottom up. The extra rows go between the first and second rows.
+fn starting_game(burrow: &Burrow, additional_rows: bool) -> AmphipodGame {
+    let mut rows = burrow.rows.clone();
+    if additional_rows {
+        rows.splice(
+            1..1,
+            [
+                [
+                    Amphipod::Desert,
+                    Amphipod::Copper,
+                    Amphipod::Bronze,
+                    Amphipod::Amber,
+                ],
+                [
+                    Amphipod::Desert,
+                    Amphipod::Bronze,
+                    Amphipod::Amber,
+                    Amphipod::Copper,
+                ],
+            ]
+            .iter()
+            .map(|row| row.iter().copied().map(Some).collect()),
+        );
+    }
+
+    AmphipodGame {
+        block_depth: rows.len(),
+        buffers: burrow
+            .hallway
+            .iter()
+            .enumerate()
+            .filter_map(|(space, amphipod)| {
+                amphipod.and_then(|amphipod| {
+                    BufferLocation::from_hallway(space).map(|location| (location, amphipod))
+                })
+            })
+            .collect(),
+        blocks: ROOMS
+            .iter()
+            .enumerate()
+            .map(|(index, room)| {
+                (
+                    *room,
+                    rows.iter().rev().filter_map(|row| row[index]).collect(),
+                )
+            })
+            .collect(),
+    }
+}
+
+fn run(arguments: AmphipodArgs, burrow: Burrow) -> CommandResult {
+    let game = starting_game(&burrow, arguments.additional_rows);
+
+    let mut games = HashMap::from([(game, 0usize)]);
+    let mut lowest_energy = usize::MAX;
+    let mut losers = HashSet::new();
+
+    let deadline = Deadline::current();
+
+    while !games.is_empty() && !deadline.expired() {
+        let new_games: Vec<(AmphipodGame, usize)> = games
+            .into_iter()
+            .filter_map(|(game, energy)| {
+                let moves = get_all_valid_moves(&game, energy);
+                if moves.len() > 0 {
+                    Option::Some(moves)
+                } else {
+                    losers.insert(game);
+                    Option::None
+                }
+            })
+            .flatten()
+            .filter(|(game, energy)| {
+                let winner = is_game_winner(&game);
+
+                if winner {
+                    lowest_energy = min(lowest_energy, *energy);
+                }
+
+                !winner
+            })
+            .collect();
+        games = new_games
+            .into_iter()
+            .fold(HashMap::new(), |mut acc, (game, energy)| {
+                if losers.contains(&game) {
+                    return acc;
+                }
+                let result = min(*acc.get(&game).unwrap_or(&energy), energy);
+                acc.insert(game, result);
+                acc
+            });
+    }
+
+    lowest_energy.into()
+}
+
+fn is_block_valid(amphipod: &Amphipod, block: &Vec<Amphipod>) -> bool {
+    block.iter().all(|item| match item {
+        x if x == amphipod => true,
+        _ => false,
+    })
+}
+
+fn is_game_winner(game: &AmphipodGame) -> bool {
+    game.blocks
+        .iter()
+        .all(|(amphipod, block)| is_block_valid(amphipod, block))
+        && game.buffers.is_empty()
+}
+
+fn get_all_valid_moves(game: &AmphipodGame, energy: usize) -> HashMap<AmphipodGame, usize> {
+    let mut valid_moves: HashMap<AmphipodGame, usize> = game
+        .blocks
+        .keys()
+        .map(|block| Node::Block(*block))
+        .map(|node| get_valid_moves(&game, energy, node))
+        .fold(HashMap::new(), |mut acc, moves| {
+            moves.into_iter().for_each(|(game, energy)| {
+                let result = min(*acc.get(&game).unwrap_or(&energy), energy);
+                acc.insert(game, result);
+            });
+            acc
+        });
+    valid_moves = game
+        .buffers
+        .keys()
+        .map(|location| Node::Buffer(*location))
+        .map(|node| get_valid_moves(&game, energy, node))
+        .fold(valid_moves, |mut acc, moves| {
+            moves.into_iter().for_each(|(game, energy)| {
+                let result = min(*acc.get(&game).unwrap_or(&energy), energy);
+                acc.insert(game, result);
+            });
+            acc
+        });
+
+    valid_moves
+}
+
+fn get_valid_moves(game: &AmphipodGame, energy: usize, node: Node) -> HashMap<AmphipodGame, usize> {
+    let (move_amphipod, base_cost, new_base_game, can_go_to_buffer) = match &node {
+        Node::Block(amphipod) => {
+            let block = game.blocks.get(&amphipod).expect("Block exists");
+            if is_block_valid(&amphipod, block) {
+                return HashMap::new();
+            }
+
+            let mut new_base_game = game.clone();
+            let new_block = new_base_game
+                .blocks
+                .get_mut(amphipod)
+                .expect("Block exists");
+            new_block.pop();
+
+            (
+                block
+                    .last()
+                    .expect("Empty a block would trigger valid check"),
+                game.block_depth - block.len(),
+                new_base_game,
+                true,
+            )
+        }
+        Node::Buffer(location) => {
+            let buffer = game.buffers.get(&location);
+            if buffer.is_none() {
+                return HashMap::new();
+            }
+
+            let mut new_base_game = game.clone();
+            new_base_game.buffers.remove(location);
+
+            (buffer.expect("None checked"), 0usize, new_base_game, false)
+        }
+    };
+
+    let mut queue: Vec<(Node, usize)> = node
+        .get_adjacent_nodes()
+        .into_iter()
+        .map(|(node, cost)| (node, cost + base_cost))
+        .collect();
+    let mut seen = HashSet::from([node]);
+    queue
+        .iter()
+        .map(|(node, _)| node.to_owned())
+        .for_each(|node| {
+            seen.insert(node);
+        });
+    let mut games = HashMap::new();
+
+    while let Some((node, cost)) = queue.pop() {
+        match node {
+            Node::Block(amphipod) => {
+                if move_amphipod == &amphipod {
+                    let block = game.blocks.get(&amphipod).expect("Block exists");
+                    if is_block_valid(&amphipod, block) {
+                        let mut new_game = new_base_game.clone();
+                        let new_block = new_game.blocks.get_mut(&amphipod).expect("Block exists");
+                        new_block.push(*move_amphipod);
+
+                        let final_cost = game.block_depth - new_block.len() + cost;
+                        let final_energy = energy + final_cost * move_amphipod.multiplier();
+                        games.insert(new_game, final_energy);
+                    }
+                }
+            }
+            Node::Buffer(location) => {
+                if !game.buffers.contains_key(&location) {
+                    if can_go_to_buffer {
+                        let mut new_game = new_base_game.clone();
+                        new_game.buffers.insert(location, *move_amphipod);
+                        let final_energy = energy + cost * move_amphipod.multiplier();
+                        games.insert(new_game, final_energy);
+                    }
+
+                    let new_nodes: Vec<(Node, usize)> = node
+                        .get_adjacent_nodes()
+                        .into_iter()
+                        .filter(|(node, _)| !seen.contains(node))
+                        .map(|(node, node_cost)| (node, cost + node_cost))
+                        .collect();
+
+                    new_nodes.iter().for_each(|(node, cost)| {
+                        queue.push((*node, *cost));
+                        seen.insert(*node);
+                    });
+                }
+            }
+        }
+
+        seen.insert(node);
+    }
+
+    games
+}
+
+fn describe(burrow: &Burrow) -> String {
+    format!(
+        "Burrow {} rows deep with {} amphipods in the hallway, from a {}\nAs a flat state: {}",
+        burrow.rows.len(),
+        burrow.hallway.iter().flatten().count(),
+        burrow.format,
+        flat_state(burrow)
+    )
+}
+
+fn flat_state(burrow: &Burrow) -> String {
+    let spaces = |spaces: &[Option<Amphipod>]| -> String {
+        spaces
+            .iter()
+            .map(|space| space.map(|amphipod| amphipod.letter()).unwrap_or('.'))
+            .collect()
+    };
+
+    std::iter::once(spaces(&burrow.hallway))
+        .chain(burrow.rows.iter().map(|row| spaces(row)))
+        .collect::<Vec<String>>()
+        .join("/")
+}
+
+fn parse_data(input: &String) -> IResult<&str, Burrow> {
+    terminated(alt((parse_diagram, parse_flat)), multispace0)(input)
+}
+
+fn parse_diagram(input: &str) -> IResult<&str, Burrow> {
+    map(
+        tuple((
+            terminated(tag("#############"), line_ending),
+            terminated(
+                delimited(tag("#"), count(parse_space, 11), tag("#")),
+                line_ending,
+            ),
+            separated_list1(line_ending, parse_room_row),
+            opt(preceded(line_ending, preceded(space0, tag("#########")))),
+        )),
+        |(_, hallway, rows, _)| Burrow {
+            format: Format::Diagram,
+            hallway,
+            rows,
+        },
+    )(input)
+}
+
+// The first row is walled by ### either side and the rest by a single #.
+fn parse_room_row(input: &str) -> IResult<&str, Vec<Option<Amphipod>>> {
+    delimited(
+        preceded(space0, many1(tag("#"))),
+        count(terminated(parse_space, tag("#")), 4),
+        terminated(many0(tag("#")), space0),
+    )(input)
+}
+
+fn parse_flat(input: &str) -> IResult<&str, Burrow> {
+    map(
+        tuple((
+            opt(terminated(count(parse_space, 11), tag("/"))),
+            separated_list1(tag("/"), count(parse_space, 4)),
+        )),
+        |(hallway, rows)| Burrow {
+            format: Format::Flat,
+            hallway: hallway.unwrap_or_else(|| vec![None; 11]),
+            rows,
+        },
+    )(input)
+}
+
+fn parse_space(input: &str) -> IResult<&str, Option<Amphipod>> {
+    alt((map(parse_amphipod, Some), value(None, tag("."))))(input)
+}
+
+fn parse_amphipod(input: &str) -> IResult<&str, Amphipod> {
+    alt((
+        value(Amphipod::Amber, tag("A")),
+        value(Amphipod::Bronze, tag("B")),
+        value(Amphipod::Copper, tag("C")),
+        value(Amphipod::Desert, tag("D")),
+    ))(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{run_problem_with_input, Command};
+    use std::time::Duration;
+
+    #[test]
+    fn part1_sample() {
+        assert_eq!(
+            run_problem_with_input(&AMPHIPOD, SAMPLE, &["part1"]).unwrap(),
+            12521usize.into()
+        );
+    }
+
+    #[test]
+    fn part2_sample() {
+        assert_eq!(
+            run_problem_with_input(&AMPHIPOD, SAMPLE, &["part2"]).unwrap(),
+            44169usize.into()
+        );
+    }
+    #[test]
+    fn flat_sample() {
+        assert_eq!(
+            run_problem_with_input(&AMPHIPOD, "BCBD/ADCA\n", &["part1"]).unwrap(),
+            12521usize.into()
+        );
+        assert_eq!(
+            AMPHIPOD.validate_input(&SAMPLE.to_string()).unwrap(),
+            "5 lines\nBurrow 2 rows deep with 0 amphipods in the hallway, from a diagram\n\
+            As a flat state: .........../BCBD/ADCA"
+        );
+    }
+
+    #[test]
+    fn midgame_sample() {
+        let diagram = "#############\n#...B.......#\n###B#C#.#D###\n  #A#D#C#A#\n  #########\n";
+
+        assert_eq!(
+            run_problem_with_input(&AMPHIPOD, diagram, &["part1"]).unwrap(),
+            12481usize.into()
+        );
+        assert_eq!(
+            run_problem_with_input(&AMPHIPOD, "...B......./BC.D/ADCA", &["part1"]).unwrap(),
+            12481usize.into()
+        );
+        assert_eq!(
+            run_problem_with_input(&AMPHIPOD, diagram, &["--part", "1", "-i", "flat"])
+                .unwrap_err()
+                .to_string(),
+            "The input is in the diagram format but --format flat was passed"
+        );
+    }
+
+    #[test]
+    fn impossible_burrows_are_rejected() {
+        [
+            (
+                "..B......../BC.D/ADCA",
+                "B is stopped in hallway space 3, in front of a room",
+            ),
+            ("BC.D/ADCA", "There are 1 B amphipods but each room holds 2"),
+            ("BCBD/AD.A", "Room C has an empty space below an amphipod"),
+        ]
+        .iter()
+        .for_each(|(burrow, error)| {
+            assert_eq!(
+                run_problem_with_input(&AMPHIPOD, burrow, &["part1"])
+                    .unwrap_err()
+                    .to_string(),
+                *error
+            )
+        });
+    }
+
+    #[test]
+    fn timeout_sample() {
+        Deadline::after(Duration::from_secs(0u64)).set_current();
+
+        assert!(run_problem_with_input(&AMPHIPOD, SAMPLE, &["part2"]).is_err());
+    }
+}
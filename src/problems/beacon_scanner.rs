@@ -0,0 +1,628 @@
+use crate::rotation::{Point3, Rotation3};
+use crate::{
+    default_sub_command, log, parse_isize, parse_usize, profile_scope, report_output,
+    selected_part, AocError, CommandResult, Counter, Deadline, Example, IteratorExt, LogLevel,
+    Problem,
+};
+use clap::{value_t_or_exit, App, Arg, ArgMatches};
+use nom::{
+    bytes::complete::tag,
+    character::complete::newline,
+    combinator::{map, map_opt, opt, verify},
+    multi::separated_list0,
+    sequence::{delimited, preceded, terminated, tuple},
+    IResult,
+};
+use std::{
+    cmp::min,
+    collections::{HashMap, HashSet, VecDeque},
+    fs::File,
+    io::{self, BufWriter, Write},
+};
+use strum::VariantNames;
+use strum_macros::{EnumString, EnumVariantNames};
+
+pub const BEACON_SCANNER: Problem<BeaconScannerArgs, Vec<Scanner>> = Problem::new(
+    sub_command,
+    "beacon-scanner",
+    "day19_beacon_scanner",
+    parse_arguments,
+    parse_data,
+    run,
+)
+.with_long_about(LONG_ABOUT)
+.with_examples(EXAMPLES)
+.with_describe(describe)
+.with_input_check(input_check);
+
+const LONG_ABOUT: &str =
+    "Each scanner reports the beacons it sees relative to itself, in one of 24 unknown \
+    orientations.\n\n\
+    Two scanners overlap when at least 12 beacons line up after rotating and moving one of \
+    them. Scanners are paired up by the beacon distances they share, and aligned along a \
+    spanning tree of those pairs to build a single map. beacon-count counts the unique beacons. \
+    max-scanner-distance finds the largest manhattan distance between any two scanners.\n\n\
+    Beacons can also be given as x,y for scanners on a plane, like the warm up example. Those \
+    scanners only turn about the vertical axis, so there are 4 orientations, and 3 beacons \
+    lining up is enough for them to overlap. -m sets how many beacons have to line up.\n\n\
+    Noisy scanners can miss beacons another scanner sees. -t allows that many beacons of an \
+    overlap to go unmatched, so two scanners overlap once -m less -t beacons line up. With -v \
+    the number of beacons that lined up is written for each pair of scanners aligned.\n\n\
+    Example: the sample has 79 beacons and a largest distance of 3621.";
+
+const SAMPLE: &str = include_str!("../../day19_beacon_scanner/sample.txt");
+
+const EXAMPLES: &[Example] = &[
+    Example::new(SAMPLE, 1, "79"),
+    Example::new(SAMPLE, 2, "3621"),
+];
+
+#[derive(Debug)]
+pub struct BeaconScannerArgs {
+    signal: Signal,
+    dump_beacons: Option<String>,
+    min_overlap: Option<usize>,
+    tolerance: usize,
+}
+
+#[derive(Debug, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "kebab_case")]
+enum Signal {
+    BeaconCount,
+    MaxScannerDistance,
+}
+
+// Scanners on a plane have 2 dimensions, with every z at 0.
+#[derive(Debug, Clone)]
+pub struct Scanner {
+    beacons: Vec<Point3>,
+    dimensions: usize,
+}
+
+// Moves points seen by one scanner into another's frame: rotate, then add the offset.
+#[derive(Debug, Clone, Copy)]
+struct Alignment {
+    rotation: Rotation3,
+    offset: Point3,
+}
+
+impl Alignment {
+    const IDENTITY: Alignment = Alignment {
+        rotation: Rotation3::IDENTITY,
+        offset: Point3::ORIGIN,
+    };
+
+    fn apply(&self, point: &Point3) -> Point3 {
+        self.rotation.apply(point) + self.offset
+    }
+
+    // The alignment that applies other and then this one.
+    fn compose(&self, other: &Alignment) -> Alignment {
+        Alignment {
+            rotation: self.rotation.compose(&other.rotation),
+            offset: self.apply(&other.offset),
+        }
+    }
+
+    fn inverse(&self) -> Alignment {
+        let rotation = self.rotation.inverse();
+        Alignment {
+            rotation,
+            offset: -rotation.apply(&self.offset),
+        }
+    }
+}
+
+// Union-find over scanner indices, tracking which scanners have been aligned with each other.
+struct DisjointSet {
+    parents: Vec<usize>,
+}
+
+impl DisjointSet {
+    fn new(size: usize) -> Self {
+        DisjointSet {
+            parents: (0..size).collect(),
+        }
+    }
+
+    fn find(&mut self, item: usize) -> usize {
+        let parent = self.parents[item];
+        if parent == item {
+            item
+        } else {
+            let root = self.find(parent);
+            self.parents[item] = root;
+            root
+        }
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find(a), self.find(b));
+        self.parents[b] = a;
+    }
+}
+
+fn sub_command() -> App<'static, 'static> {
+    default_sub_command(
+        &BEACON_SCANNER,
+        "Coorilates all beacon and scanner signals and computes stats.",
+        "Path to the input file. Input should be scanner results.",
+        "Finds the total number of beacons for the default input.",
+        "Finds the largest distance between two scanners for the default input.",
+    ).arg(
+        Arg::with_name("signal")
+            .short("s")
+            .help(
+                "The signal to determine. The questions available are as follows:\n\n\
+            beacon-count: Returns the total number of beacons.\n\n\
+            max-scanner-distance: Returns the maximum hamiltonian distance between all scanners.\n\n",
+            )
+            .takes_value(true)
+            .possible_values(&Signal::VARIANTS)
+            .required_unless_one(&["part", "explain"]),
+    )
+    .arg(
+        Arg::with_name("dump-beacons")
+            .short("d")
+            .long("dump-beacons")
+            .help("If passed, writes the aligned beacon coordinates to the given path, sorted and one x,y,z triple per line.")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("min-overlap")
+            .short("m")
+            .long("min-overlap")
+            .help("The number of beacons that have to line up for two scanners to overlap. Defaults to 12, or 3 for scanners on a plane.")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("tolerance")
+            .short("t")
+            .long("tolerance")
+            .help("The number of beacons in an overlap that are allowed not to line up, for noisy scanners. Defaults to 0.")
+            .takes_value(true),
+    )
+}
+
+fn parse_arguments(arguments: &ArgMatches) -> BeaconScannerArgs {
+    match selected_part(arguments) {
+        Some("part1") => BeaconScannerArgs {
+            signal: Signal::BeaconCount,
+            dump_beacons: arguments.value_of("dump-beacons").map(String::from),
+            min_overlap: min_overlap_argument(arguments),
+            tolerance: tolerance_argument(arguments),
+        },
+        Some("part2") => BeaconScannerArgs {
+            signal: Signal::MaxScannerDistance,
+            dump_beacons: arguments.value_of("dump-beacons").map(String::from),
+            min_overlap: min_overlap_argument(arguments),
+            tolerance: tolerance_argument(arguments),
+        },
+        _ => BeaconScannerArgs {
+            signal: value_t_or_exit!(arguments.value_of("signal"), Signal),
+            dump_beacons: arguments.value_of("dump-beacons").map(String::from),
+            min_overlap: min_overlap_argument(arguments),
+            tolerance: tolerance_argument(arguments),
+        },
+    }
+}
+
+fn min_overlap_argument(arguments: &ArgMatches) -> Option<usize> {
+    arguments
+        .value_of("min-overlap")
+        .map(|_| value_t_or_exit!(arguments.value_of("min-overlap"), usize))
+}
+
+fn tolerance_argument(arguments: &ArgMatches) -> usize {
+    arguments.value_of("tolerance").map_or(0usize, |_| {
+        value_t_or_exit!(arguments.value_of("tolerance"), usize)
+    })
+}
+
+fn input_check(arguments: &BeaconScannerArgs, scanners: &Vec<Scanner>) -> Result<(), AocError> {
+    let min_overlap = min_overlap(arguments, dimensions(scanners));
+    if arguments.tolerance >= min_overlap {
+        return Err(AocError::validation(format!(
+            "A tolerance of {} leaves no beacons to line up when {} have to",
+            arguments.tolerance, min_overlap
+        )));
+    }
+
+    Ok(())
+}
+
+fn min_overlap(arguments: &BeaconScannerArgs, dimensions: usize) -> usize {
+    arguments
+        .min_overlap
+        .unwrap_or(if dimensions == 2 { 3usize } else { 12usize })
+}
+
+fn run(arguments: BeaconScannerArgs, scanners: Vec<Scanner>) -> CommandResult {
+    let dimensions = dimensions(&scanners);
+    let matches_needed = min_overlap(&arguments, dimensions) - arguments.tolerance;
+    let alignments = align_scanners(&scanners, dimensions, matches_needed);
+
+    let _profile = profile_scope("merge beacons");
+    let beacons: HashSet<Point3> = alignments
+        .iter()
+        .flat_map(|(index, alignment)| {
+            scanners[*index]
+                .beacons
+                .iter()
+                .map(|beacon| alignment.apply(beacon))
+        })
+        .collect();
+    let scanner_points: Vec<Point3> = alignments
+        .values()
+        .map(|alignment| alignment.apply(&Point3::ORIGIN))
+        .collect();
+
+    if let Some(path) = arguments.dump_beacons {
+        report_output(&path, dump_beacons(&beacons, &path, dimensions));
+    }
+
+    match arguments.signal {
+        Signal::BeaconCount => beacons.len().into(),
+        Signal::MaxScannerDistance => scanner_points
+            .iter()
+            .combinations2()
+            .map(|(left, right)| left.manhattan_distance(right))
+            .max()
+            .unwrap_or(0isize)
+            .into(),
+    }
+}
+
+// The scanners all have the same dimensions, which the parser checks.
+fn dimensions(scanners: &[Scanner]) -> usize {
+    scanners
+        .first()
+        .map(|scanner| scanner.dimensions)
+        .unwrap_or(3usize)
+}
+
+fn dump_beacons(beacons: &HashSet<Point3>, path: &str, dimensions: usize) -> Result<(), io::Error> {
+    let mut sorted_beacons: Vec<&Point3> = beacons.iter().collect();
+    sorted_beacons.sort();
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    sorted_beacons
+        .iter()
+        .try_for_each(|beacon| match dimensions {
+            2 => writeln!(writer, "{},{}", beacon.x, beacon.y),
+            _ => writeln!(writer, "{},{},{}", beacon.x, beacon.y, beacon.z),
+        })?;
+    writer.flush()
+}
+
+// Pairs of scanners sharing enough beacon distances probably overlap. Trying those pairs from the
+// most shared distances down, and only aligning the ones that join two separate groups, builds a
+// spanning tree. Each scanner is then placed by composing the alignments on its way to scanner 0
+// instead of being searched for against every beacon found so far. Scanners that can't be reached
+// from scanner 0 are left out. Scanners on a plane only turn about the z axis. matches_needed is
+// the overlap less any tolerance for unmatched beacons.
+fn align_scanners(
+    scanners: &[Scanner],
+    dimensions: usize,
+    matches_needed: usize,
+) -> HashMap<usize, Alignment> {
+    let _profile = profile_scope("align scanners");
+    let up = Point3::new(0, 0, 1);
+    let rotations: Vec<Rotation3> = Rotation3::all()
+        .into_iter()
+        .filter(|rotation| dimensions == 3 || rotation.apply(&up) == up)
+        .collect();
+    // Every pair of overlapping beacons is the same distance apart in both scanners.
+    let overlapping_distances = matches_needed * matches_needed.saturating_sub(1) / 2;
+    let mut pairs: Vec<(usize, usize, usize)> = {
+        let _profile = profile_scope("fingerprint");
+        let fingerprints: Vec<Counter<isize>> = scanners.iter().map(fingerprint).collect();
+        (0..scanners.len())
+            .flat_map(|i| ((i + 1)..scanners.len()).map(move |j| (i, j)))
+            .map(|(i, j)| (shared_distances(&fingerprints[i], &fingerprints[j]), i, j))
+            .filter(|(shared, _, _)| *shared >= overlapping_distances)
+            .collect()
+    };
+    pairs.sort_by(|a, b| b.cmp(a));
+
+    let mut groups = DisjointSet::new(scanners.len());
+    let mut tree: HashMap<usize, Vec<(usize, Alignment)>> = HashMap::new();
+    let deadline = Deadline::current();
+    pairs
+        .into_iter()
+        .take_while(|_| !deadline.expired())
+        .for_each(|(_, i, j)| {
+            if groups.find(i) != groups.find(j) {
+                let _profile = profile_scope("align pair");
+                if let Some((alignment, matched)) =
+                    align_pair(&scanners[i], &scanners[j], &rotations, matches_needed)
+                {
+                    log!(
+                        LogLevel::Debug,
+                        "Scanners {} and {}: {} beacons matched",
+                        i,
+                        j,
+                        matched
+                    );
+                    groups.union(i, j);
+                    tree.entry(i).or_default().push((j, alignment));
+                    tree.entry(j).or_default().push((i, alignment.inverse()));
+                }
+            }
+        });
+
+    let _profile = profile_scope("place scanners");
+    let mut alignments = HashMap::new();
+    let mut queue = VecDeque::new();
+    if !scanners.is_empty() {
+        alignments.insert(0usize, Alignment::IDENTITY);
+        queue.push_back(0usize);
+    }
+
+    while let Some(current) = queue.pop_front() {
+        let current_alignment = alignments[&current];
+        tree.get(&current)
+            .into_iter()
+            .flatten()
+            .for_each(|(neighbour, alignment)| {
+                if !alignments.contains_key(neighbour) {
+                    alignments.insert(*neighbour, current_alignment.compose(alignment));
+                    queue.push_back(*neighbour);
+                }
+            });
+    }
+
+    alignments
+}
+
+fn fingerprint(scanner: &Scanner) -> Counter<isize> {
+    scanner
+        .beacons
+        .iter()
+        .enumerate()
+        .flat_map(|(index, a)| {
+            scanner.beacons[(index + 1)..]
+                .iter()
+                .map(move |b| squared_distance(a, b))
+        })
+        .collect()
+}
+
+fn shared_distances(a: &Counter<isize>, b: &Counter<isize>) -> usize {
+    a.iter()
+        .map(|(distance, count)| min(*count, b.get(distance)))
+        .sum()
+}
+
+// Finds how to move the scanner's beacons into the reference's frame so at least matches_needed
+// line up, along with how many do.
+fn align_pair(
+    reference: &Scanner,
+    scanner: &Scanner,
+    rotations: &[Rotation3],
+    matches_needed: usize,
+) -> Option<(Alignment, usize)> {
+    rotations.iter().find_map(|rotation| {
+        let rotated: Vec<Point3> = scanner
+            .beacons
+            .iter()
+            .map(|beacon| rotation.apply(beacon))
+            .collect();
+        let offsets: Counter<Point3> = reference
+            .beacons
+            .iter()
+            .flat_map(|a| rotated.iter().map(move |b| *a - *b))
+            .collect();
+
+        offsets
+            .iter()
+            .find(|(_, count)| **count >= matches_needed)
+            .map(|(offset, count)| {
+                (
+                    Alignment {
+                        rotation: *rotation,
+                        offset: *offset,
+                    },
+                    *count,
+                )
+            })
+    })
+}
+
+fn squared_distance(point1: &Point3, point2: &Point3) -> isize {
+    let dx = point2.x - point1.x;
+    let dy = point2.y - point1.y;
+    let dz = point2.z - point1.z;
+    (dx * dx) + (dy * dy) + (dz * dz)
+}
+
+fn describe(scanners: &Vec<Scanner>) -> String {
+    let mut description = format!(
+        "{} scanners{} detecting between {} and {} beacons",
+        scanners.len(),
+        if dimensions(scanners) == 2 {
+            " on a plane"
+        } else {
+            ""
+        },
+        scanners
+            .iter()
+            .map(|scanner| scanner.beacons.len())
+            .min()
+            .unwrap_or(0usize),
+        scanners
+            .iter()
+            .map(|scanner| scanner.beacons.len())
+            .max()
+            .unwrap_or(0usize)
+    );
+
+    scanners
+        .iter()
+        .enumerate()
+        .filter(|(_, scanner)| scanner.dimensions == 3 && scanner.beacons.len() < 12)
+        .for_each(|(index, _)| {
+            description.push_str(&format!(
+                "\nAnomaly: scanner {} detects fewer than 12 beacons and can't be aligned",
+                index
+            ))
+        });
+
+    description
+}
+
+fn parse_data(input: &String) -> IResult<&str, Vec<Scanner>> {
+    verify(
+        separated_list0(tag("\n\n"), parse_scanner),
+        |scanners: &Vec<Scanner>| {
+            scanners
+                .iter()
+                .all(|scanner| scanner.dimensions == dimensions(scanners))
+        },
+    )(input)
+}
+
+// A scanner's dimensions come from its beacons, which all need the same number of coordinates.
+fn parse_scanner(input: &str) -> IResult<&str, Scanner> {
+    map_opt(
+        tuple((
+            terminated(parse_scanner_number, newline),
+            separated_list0(newline, parse_point),
+        )),
+        |(_, points)| {
+            let dimensions = points.first().map_or(3usize, |(_, dimensions)| *dimensions);
+            points
+                .iter()
+                .all(|(_, point_dimensions)| *point_dimensions == dimensions)
+                .then(|| Scanner {
+                    beacons: points.into_iter().map(|(point, _)| point).collect(),
+                    dimensions,
+                })
+        },
+    )(input)
+}
+
+fn parse_scanner_number(input: &str) -> IResult<&str, usize> {
+    delimited(tag("--- scanner "), parse_usize, tag(" ---"))(input)
+}
+
+fn parse_point(input: &str) -> IResult<&str, (Point3, usize)> {
+    map(
+        tuple((
+            terminated(parse_isize, tag(",")),
+            parse_isize,
+            opt(preceded(tag(","), parse_isize)),
+        )),
+        |(x, y, z)| match z {
+            Some(z) => (Point3::new(x, y, z), 3usize),
+            None => (Point3::new(x, y, 0), 2usize),
+        },
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{run_problem_with_input, scratch_path, Command};
+
+    #[test]
+    fn part1_sample() {
+        assert_eq!(
+            run_problem_with_input(&BEACON_SCANNER, SAMPLE, &["part1"]).unwrap(),
+            79usize.into()
+        );
+    }
+
+    #[test]
+    fn part2_sample() {
+        assert_eq!(
+            run_problem_with_input(&BEACON_SCANNER, SAMPLE, &["part2"]).unwrap(),
+            3621isize.into()
+        );
+    }
+
+    #[test]
+    fn dump_beacons_sample() {
+        let beacon_file = scratch_path("beacon_scanner_dump_beacons_sample.txt");
+        let beacon_file = beacon_file.to_str().unwrap();
+
+        assert_eq!(
+            run_problem_with_input(&BEACON_SCANNER, SAMPLE, &["--part", "1", "-d", beacon_file])
+                .unwrap(),
+            79usize.into()
+        );
+
+        let beacons = std::fs::read_to_string(beacon_file).unwrap();
+        assert_eq!(beacons.lines().count(), 79);
+        assert_eq!(beacons.lines().next(), Some("-892,524,684"));
+        assert_eq!(beacons.lines().last(), Some("1994,-1805,1792"));
+    }
+
+    #[test]
+    fn unwritable_beacon_file_is_an_error() {
+        let beacon_file = scratch_path("beacon_scanner_missing").join("beacons.txt");
+        let beacon_file = beacon_file.to_str().unwrap();
+        let error =
+            run_problem_with_input(&BEACON_SCANNER, SAMPLE, &["--part", "1", "-d", beacon_file])
+                .unwrap_err();
+
+        assert!(matches!(error, AocError::Io(_)));
+        assert!(error
+            .to_string()
+            .contains(&format!("{} can't be written", beacon_file)));
+    }
+
+    #[test]
+    fn tolerance_sample() {
+        // Scanner 0 misses 2 of the 12 beacons it shares with scanner 1, which leaves it unaligned
+        // with only its own 23 beacons unless the 2 are tolerated.
+        let noisy = SAMPLE
+            .replacen("-537,-823,-458\n", "", 1)
+            .replacen("-618,-824,-621\n", "", 1);
+
+        assert_eq!(
+            run_problem_with_input(&BEACON_SCANNER, &noisy, &["part1"]).unwrap(),
+            23usize.into()
+        );
+        assert_eq!(
+            run_problem_with_input(&BEACON_SCANNER, &noisy, &["--part", "1", "-t", "2"]).unwrap(),
+            79usize.into()
+        );
+        assert_eq!(
+            run_problem_with_input(&BEACON_SCANNER, &noisy, &["--part", "2", "-t", "2"]).unwrap(),
+            3621isize.into()
+        );
+        assert_eq!(
+            run_problem_with_input(&BEACON_SCANNER, SAMPLE, &["--part", "1", "-t", "12"])
+                .unwrap_err()
+                .to_string(),
+            "A tolerance of 12 leaves no beacons to line up when 12 have to"
+        );
+    }
+
+    const PLANAR_SAMPLE: &str =
+        "--- scanner 0 ---\n0,2\n4,1\n3,3\n\n--- scanner 1 ---\n-1,-1\n-5,0\n-2,1\n";
+
+    #[test]
+    fn planar_sample() {
+        // The same scanner 1 turned a quarter turn.
+        let turned = PLANAR_SAMPLE.replace("-1,-1\n-5,0\n-2,1", "1,-1\n0,-5\n-1,-2");
+
+        [PLANAR_SAMPLE, turned.as_str()].iter().for_each(|input| {
+            assert_eq!(
+                run_problem_with_input(&BEACON_SCANNER, input, &["part1"]).unwrap(),
+                3usize.into()
+            );
+            assert_eq!(
+                run_problem_with_input(&BEACON_SCANNER, input, &["part2"]).unwrap(),
+                7isize.into()
+            );
+        });
+        assert_eq!(
+            BEACON_SCANNER
+                .validate_input(&PLANAR_SAMPLE.to_string())
+                .unwrap(),
+            "9 lines\n2 scanners on a plane detecting between 3 and 3 beacons"
+        );
+    }
+}
@@ -0,0 +1,733 @@
+use crate::{
+    count_result, default_sub_command, selected_part, AocError, CommandResult, Example, Problem,
+};
+use clap::{value_t_or_exit, App, Arg, ArgMatches};
+use nom::bytes::complete::take_until;
+use nom::character::complete::newline;
+use nom::combinator::map_opt;
+use nom::multi::separated_list0;
+use nom::IResult;
+use num_bigint::BigUint;
+use std::cmp::Ordering;
+use std::io::BufRead;
+use strum::VariantNames;
+use strum_macros::{EnumString, EnumVariantNames};
+
+use std::collections::BTreeSet;
+pub const BINARY_DIAGNOSTIC: Problem<BinaryDiagnosticArgs, Vec<Reading>> = Problem::new(
+    sub_command,
+    "binary-diagnostic",
+    "day3_binary_diagnostic",
+    parse_arguments,
+    parse_readings,
+    run,
+)
+.with_long_about(LONG_ABOUT)
+.with_examples(EXAMPLES)
+.with_describe(describe)
+.with_input_check(input_check)
+.with_stream(stream);
+
+const LONG_ABOUT: &str = "The input is a list of binary numbers of equal width.\n\n\
+    power-consumption builds a gamma rate from the most common bit in each position and an \
+    epsilon rate from the least common bit, then multiplies them. life-support keeps filtering \
+    the numbers by the most common bit (oxygen) or least common bit (CO2 scrubber) one \
+    position at a time from the left until one number is left, then multiplies the two \
+    ratings.\n\n\
+    When a position has as many ones as zeros, -t decides which bit counts as the most common: \
+    one (the default, as the puzzle does), zero, or error to stop with the position of the \
+    tie.\n\n\
+    -r reads the numbers in another radix, from 2 up to 36 with letters past 9, and the \
+    diagnostics use the most or least common digit in each position instead. Only digits that \
+    appear in a position are counted. On a tie, one picks the highest digit for the most common \
+    and the lowest for the least common, and zero the other way around. The numbers can be as \
+    wide as they like.\n\n\
+    Example: the sample gives 198 for power consumption and 230 for life support.";
+
+const SAMPLE: &str = include_str!("../../day3_binary_diagnostic/sample.txt");
+
+const EXAMPLES: &[Example] = &[
+    Example::new(SAMPLE, 1, "198"),
+    Example::new(SAMPLE, 2, "230"),
+];
+
+const MAX_RADIX: u32 = 36;
+
+#[derive(Debug)]
+pub struct BinaryDiagnosticArgs {
+    diagnostic: Diagnostic,
+    tie_break: TieBreak,
+    radix: u32,
+}
+
+#[derive(Debug, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "kebab_case")]
+enum Diagnostic {
+    PowerConsumption,
+    LifeSupport,
+}
+
+#[derive(Debug, Clone, Copy, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "kebab_case")]
+enum TieBreak {
+    One,
+    Zero,
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Commonness {
+    Most,
+    Least,
+}
+
+// The digits of one number, most significant first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reading {
+    digits: Vec<u8>,
+}
+
+impl Reading {
+    // Positions are counted from 0 at the rightmost digit. Shorter numbers read as zero past their
+    // own width.
+    fn digit_at(&self, position: usize) -> u8 {
+        self.digits
+            .len()
+            .checked_sub(position + 1)
+            .map(|index| self.digits[index])
+            .unwrap_or(0)
+    }
+}
+
+fn sub_command() -> App<'static, 'static> {
+    default_sub_command(
+        &BINARY_DIAGNOSTIC,
+        "Parses the binary to find diagnotics",
+        "Path to the input file. Each line should contain a binary number.",
+        "Finds power consumption.",
+        "Finds the life support rating",
+    )
+    .arg(
+        Arg::with_name("diagnostic")
+            .short("d")
+            .help("The diagnostic requested. The diagnostics available are as follows:\n\n\
+            power-consumption: Finds the gamma rate and the epsilon rate and multiplies them.\n\n\
+            life-support: Finds the oxygen rating and the CO2 scrubber rating and multiplies them.\n\n")
+            .takes_value(true)
+            .possible_values(&Diagnostic::VARIANTS)
+            .required_unless_one(&["part", "explain"]),
+    )
+    .arg(
+        Arg::with_name("tie-break")
+            .short("t")
+            .long("tie-break")
+            .help("The most common bit when there are as many ones as zeros, or error to fail on a tie. Past radix 2, one favors the highest digit and zero the lowest.")
+            .takes_value(true)
+            .possible_values(TieBreak::VARIANTS)
+            .default_value("one"),
+    )
+    .arg(
+        Arg::with_name("radix")
+            .short("r")
+            .long("radix")
+            .help("The radix the numbers are written in, from 2 to 36.")
+            .takes_value(true)
+            .default_value("2"),
+    )
+}
+
+fn parse_arguments(arguments: &ArgMatches) -> BinaryDiagnosticArgs {
+    match selected_part(arguments) {
+        Some("part1") => BinaryDiagnosticArgs {
+            diagnostic: Diagnostic::PowerConsumption,
+            tie_break: value_t_or_exit!(arguments.value_of("tie-break"), TieBreak),
+            radix: value_t_or_exit!(arguments.value_of("radix"), u32),
+        },
+        Some("part2") => BinaryDiagnosticArgs {
+            diagnostic: Diagnostic::LifeSupport,
+            tie_break: value_t_or_exit!(arguments.value_of("tie-break"), TieBreak),
+            radix: value_t_or_exit!(arguments.value_of("radix"), u32),
+        },
+        _ => BinaryDiagnosticArgs {
+            diagnostic: value_t_or_exit!(arguments.value_of("diagnostic"), Diagnostic),
+            tie_break: value_t_or_exit!(arguments.value_of("tie-break"), TieBreak),
+            radix: value_t_or_exit!(arguments.value_of("radix"), u32),
+        },
+    }
+}
+
+// Digits are only checked against the radix here, since parsing doesn't know it. Ties can only
+// fail with --tie-break error, and then only once the ratings are worked out, so this runs the
+// diagnostic ahead of time in that case.
+fn input_check(arguments: &BinaryDiagnosticArgs, readings: &Vec<Reading>) -> Result<(), AocError> {
+    check_radix(arguments.radix)?;
+    readings
+        .iter()
+        .enumerate()
+        .try_for_each(|(line, reading)| check_digits(reading, arguments.radix, line + 1))?;
+
+    match arguments.tie_break {
+        TieBreak::Error => diagnose(arguments, readings).map(|_| ()),
+        _ => Ok(()),
+    }
+}
+
+fn check_radix(radix: u32) -> Result<(), AocError> {
+    if (2..=MAX_RADIX).contains(&radix) {
+        Ok(())
+    } else {
+        Err(AocError::validation(format!(
+            "The radix must be from 2 to {}, not {}",
+            MAX_RADIX, radix
+        )))
+    }
+}
+
+fn check_digits(reading: &Reading, radix: u32, line: usize) -> Result<(), AocError> {
+    match reading
+        .digits
+        .iter()
+        .find(|digit| u32::from(**digit) >= radix)
+    {
+        Some(digit) => Err(AocError::validation(format!(
+            "Line {} has the digit {}, which radix {} doesn't have",
+            line,
+            digit_name(*digit),
+            radix
+        ))),
+        None => Ok(()),
+    }
+}
+
+fn run(arguments: BinaryDiagnosticArgs, readings: Vec<Reading>) -> CommandResult {
+    let (metric1, metric2) =
+        diagnose(&arguments, &readings).expect("Ties are checked by input_check");
+
+    count_result(metric1 * metric2)
+}
+
+fn diagnose(
+    arguments: &BinaryDiagnosticArgs,
+    readings: &[Reading],
+) -> Result<(BigUint, BigUint), AocError> {
+    let tie_break = arguments.tie_break;
+    let radix = arguments.radix;
+    Ok(match arguments.diagnostic {
+        Diagnostic::PowerConsumption => (
+            combine_common_digits(readings, radix, tie_break, Commonness::Most)?,
+            combine_common_digits(readings, radix, tie_break, Commonness::Least)?,
+        ),
+        Diagnostic::LifeSupport => (
+            filter_by_common_digits(readings, radix, tie_break, Commonness::Most)?,
+            filter_by_common_digits(readings, radix, tie_break, Commonness::Least)?,
+        ),
+    })
+}
+
+fn stream(
+    arguments: BinaryDiagnosticArgs,
+    reader: &mut dyn BufRead,
+) -> Result<CommandResult, AocError> {
+    check_radix(arguments.radix)?;
+    let (metric1, metric2) = match arguments.diagnostic {
+        Diagnostic::PowerConsumption => {
+            stream_power_consumption(reader, arguments.radix, arguments.tie_break)?
+        }
+        Diagnostic::LifeSupport => {
+            stream_life_support(reader, arguments.radix, arguments.tie_break)?
+        }
+    };
+
+    Ok(count_result(metric1 * metric2))
+}
+
+fn stream_readings<'a>(
+    reader: &'a mut dyn BufRead,
+    radix: u32,
+) -> impl Iterator<Item = Result<Reading, AocError>> + 'a {
+    reader
+        .lines()
+        .map(|line| line.map_err(|e| e.into()))
+        .filter(|line: &Result<String, AocError>| {
+            line.as_ref().map(|line| !line.is_empty()).unwrap_or(true)
+        })
+        .enumerate()
+        .map(move |(index, line)| {
+            let reading = parse_reading_line(&line?)?;
+            check_digits(&reading, radix, index + 1)?;
+            Ok(reading)
+        })
+}
+
+// Only the count of each digit in each position is kept, indexed from the rightmost position.
+fn stream_power_consumption(
+    reader: &mut dyn BufRead,
+    radix: u32,
+    tie_break: TieBreak,
+) -> Result<(BigUint, BigUint), AocError> {
+    let counts = stream_readings(reader, radix).try_fold(
+        Vec::<Vec<usize>>::new(),
+        |mut counts, reading| -> Result<Vec<Vec<usize>>, AocError> {
+            let reading = reading?;
+            if counts.len() < reading.digits.len() {
+                counts.resize(reading.digits.len(), vec![0usize; radix as usize]);
+            }
+
+            counts
+                .iter_mut()
+                .enumerate()
+                .for_each(|(position, count)| count[reading.digit_at(position) as usize] += 1);
+            Ok(counts)
+        },
+    )?;
+
+    Ok((
+        combine_counts(&counts, radix, tie_break, Commonness::Most)?,
+        combine_counts(&counts, radix, tie_break, Commonness::Least)?,
+    ))
+}
+
+// Only the value of each number is kept, and the numbers are then sorted. Every step of a filter
+// keeps numbers that agree on all the digits to the left, so the ones it wants are a run of the
+// sorted numbers.
+fn stream_life_support(
+    reader: &mut dyn BufRead,
+    radix: u32,
+    tie_break: TieBreak,
+) -> Result<(BigUint, BigUint), AocError> {
+    let (width, mut values) = stream_readings(reader, radix).try_fold(
+        (0usize, StreamedValues::Small(Vec::new())),
+        |(width, values), reading| -> Result<(usize, StreamedValues), AocError> {
+            let reading = reading?;
+            Ok((
+                width.max(reading.digits.len()),
+                values.push(value(&reading.digits, radix)),
+            ))
+        },
+    )?;
+
+    values.sort();
+    let filter = |commonness| match &values {
+        StreamedValues::Small(values) => {
+            let digit_at = |value: &u64, position: usize| {
+                u32::try_from(position)
+                    .ok()
+                    .and_then(|position| u64::from(radix).checked_pow(position))
+                    .map_or(0u8, |power| (value / power % u64::from(radix)) as u8)
+            };
+            filter_sorted_by_common_digits(values, width, radix, tie_break, commonness, digit_at)
+                .map(|value| value.map(|value| BigUint::from(*value)))
+        }
+        StreamedValues::Big(values) => {
+            let digit_at = |value: &BigUint, position: usize| {
+                let digit = value / BigUint::from(radix).pow(position as u32) % radix;
+                digit.to_u32_digits().first().copied().unwrap_or(0) as u8
+            };
+            filter_sorted_by_common_digits(values, width, radix, tie_break, commonness, digit_at)
+                .map(|value| value.cloned())
+        }
+    };
+
+    Ok((
+        filter(Commonness::Most)?.unwrap_or_default(),
+        filter(Commonness::Least)?.unwrap_or_default(),
+    ))
+}
+
+// The numbers read so far. They are kept as u64s, which covers any usual input, until one doesn't
+// fit.
+enum StreamedValues {
+    Small(Vec<u64>),
+    Big(Vec<BigUint>),
+}
+
+impl StreamedValues {
+    fn sort(&mut self) {
+        match self {
+            StreamedValues::Small(values) => values.sort_unstable(),
+            StreamedValues::Big(values) => values.sort_unstable(),
+        }
+    }
+
+    fn push(self, value: BigUint) -> StreamedValues {
+        match (self, u64::try_from(&value)) {
+            (StreamedValues::Small(mut values), Ok(small)) => {
+                values.push(small);
+                StreamedValues::Small(values)
+            }
+            (StreamedValues::Small(values), Err(_)) => {
+                let mut values: Vec<BigUint> = values.into_iter().map(BigUint::from).collect();
+                values.push(value);
+                StreamedValues::Big(values)
+            }
+            (StreamedValues::Big(mut values), _) => {
+                values.push(value);
+                StreamedValues::Big(values)
+            }
+        }
+    }
+}
+
+fn describe(readings: &Vec<Reading>) -> String {
+    let widths: BTreeSet<usize> = readings
+        .iter()
+        .map(|reading| reading.digits.len())
+        .collect();
+    let mut description = format!(
+        "{} numbers of {} digits",
+        readings.len(),
+        widths.iter().next().unwrap_or(&0usize)
+    );
+
+    if widths.len() > 1 {
+        description.push_str(&format!(
+            "\nAnomaly: numbers have differing widths {:?}",
+            widths
+        ));
+    }
+
+    description
+}
+
+fn parse_readings(file: &String) -> IResult<&str, Vec<Reading>> {
+    separated_list0(newline, map_opt(take_until("\n"), parse_digits))(file)
+}
+
+fn parse_digits(line: &str) -> Option<Reading> {
+    if line.is_empty() {
+        return None;
+    }
+
+    line.chars()
+        .map(|digit| digit.to_digit(MAX_RADIX).map(|digit| digit as u8))
+        .collect::<Option<Vec<u8>>>()
+        .map(|digits| Reading { digits })
+}
+
+fn parse_reading_line(line: &str) -> Result<Reading, AocError> {
+    parse_digits(line)
+        .ok_or_else(|| AocError::validation(format!("{} isn't a number in any radix", line)))
+}
+
+fn digit_name(digit: u8) -> char {
+    std::char::from_digit(u32::from(digit), MAX_RADIX).unwrap_or('?')
+}
+
+fn value(digits: &[u8], radix: u32) -> BigUint {
+    BigUint::from_radix_be(digits, radix).unwrap_or_default()
+}
+
+fn width(readings: &[Reading]) -> usize {
+    readings
+        .iter()
+        .map(|reading| reading.digits.len())
+        .max()
+        .unwrap_or(0)
+}
+
+fn counts_at_position(readings: &[Reading], position: usize, radix: u32) -> Vec<usize> {
+    readings
+        .iter()
+        .fold(vec![0usize; radix as usize], |mut counts, reading| {
+            counts[reading.digit_at(position) as usize] += 1;
+            counts
+        })
+}
+
+// The most or least common of the digits that appear, from the count of each digit. In radix 2
+// the least common is the other bit, so ties go the opposite way to the most common. Positions
+// are counted from 1 at the rightmost digit in the error.
+fn common_digit(
+    counts: &[usize],
+    position: usize,
+    tie_break: TieBreak,
+    commonness: Commonness,
+) -> Result<u8, AocError> {
+    let present = counts
+        .iter()
+        .enumerate()
+        .filter(|(_, count)| **count > 0)
+        .map(|(digit, count)| (digit as u8, *count));
+    let extreme = match commonness {
+        Commonness::Most => present.clone().map(|(_, count)| count).max(),
+        Commonness::Least => present.clone().map(|(_, count)| count).min(),
+    }
+    .unwrap_or(0);
+    let tied: Vec<u8> = present
+        .filter(|(_, count)| *count == extreme)
+        .map(|(digit, _)| digit)
+        .collect();
+
+    match (tied.len().cmp(&1), tie_break, commonness) {
+        (Ordering::Less, _, _) => Ok(0),
+        (Ordering::Equal, _, _) => Ok(tied[0]),
+        (Ordering::Greater, TieBreak::One, Commonness::Most)
+        | (Ordering::Greater, TieBreak::Zero, Commonness::Least) => Ok(tied[tied.len() - 1]),
+        (Ordering::Greater, TieBreak::Zero, Commonness::Most)
+        | (Ordering::Greater, TieBreak::One, Commonness::Least) => Ok(tied[0]),
+        (Ordering::Greater, TieBreak::Error, _) => Err(AocError::validation(format!(
+            "Digit {} from the right has {} each of {}, pass --tie-break one or zero to settle the tie",
+            position + 1,
+            extreme,
+            tied.iter()
+                .map(|digit| digit_name(*digit).to_string())
+                .collect::<Vec<String>>()
+                .join(" and ")
+        ))),
+    }
+}
+
+fn combine_common_digits(
+    readings: &[Reading],
+    radix: u32,
+    tie_break: TieBreak,
+    commonness: Commonness,
+) -> Result<BigUint, AocError> {
+    let counts: Vec<Vec<usize>> = (0..width(readings))
+        .map(|position| counts_at_position(readings, position, radix))
+        .collect();
+    combine_counts(&counts, radix, tie_break, commonness)
+}
+
+fn combine_counts(
+    counts: &[Vec<usize>],
+    radix: u32,
+    tie_break: TieBreak,
+    commonness: Commonness,
+) -> Result<BigUint, AocError> {
+    let digits = counts
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(position, counts)| common_digit(counts, position, tie_break, commonness))
+        .collect::<Result<Vec<u8>, AocError>>()?;
+    Ok(value(&digits, radix))
+}
+
+fn filter_by_common_digits(
+    readings: &[Reading],
+    radix: u32,
+    tie_break: TieBreak,
+    commonness: Commonness,
+) -> Result<BigUint, AocError> {
+    let mut position = width(readings);
+    let mut filtered_readings = readings.to_vec();
+
+    while filtered_readings.len() > 1 && position > 0 {
+        position -= 1;
+        let common = common_digit(
+            &counts_at_position(&filtered_readings, position, radix),
+            position,
+            tie_break,
+            commonness,
+        )?;
+        filtered_readings.retain(|reading| reading.digit_at(position) == common);
+    }
+
+    Ok(filtered_readings
+        .first()
+        .map(|reading| value(&reading.digits, radix))
+        .unwrap_or_default())
+}
+
+// digit_at gives the digit of a number at a position, counted from 0 at the rightmost digit.
+fn filter_sorted_by_common_digits<T: Ord>(
+    values: &[T],
+    width: usize,
+    radix: u32,
+    tie_break: TieBreak,
+    commonness: Commonness,
+    digit_at: impl Fn(&T, usize) -> u8,
+) -> Result<Option<&T>, AocError> {
+    let mut filtered_values = values;
+
+    for position in (0..width).rev() {
+        if filtered_values.len() <= 1 {
+            break;
+        }
+
+        let starts: Vec<usize> = (0..=radix as u8)
+            .map(|digit| filtered_values.partition_point(|value| digit_at(value, position) < digit))
+            .collect();
+        let counts: Vec<usize> = starts.windows(2).map(|run| run[1] - run[0]).collect();
+        let common = common_digit(&counts, position, tie_break, commonness)? as usize;
+        filtered_values = &filtered_values[starts[common]..starts[common + 1]];
+    }
+
+    Ok(filtered_values.first())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_problem_with_input;
+
+    #[test]
+    fn part1_sample() {
+        assert_eq!(
+            run_problem_with_input(&BINARY_DIAGNOSTIC, SAMPLE, &["part1"]).unwrap(),
+            198usize.into()
+        );
+    }
+
+    #[test]
+    fn part2_sample() {
+        assert_eq!(
+            run_problem_with_input(&BINARY_DIAGNOSTIC, SAMPLE, &["part2"]).unwrap(),
+            230usize.into()
+        );
+    }
+
+    #[test]
+    fn stream_power_consumption_sample() {
+        assert_eq!(
+            stream(
+                BinaryDiagnosticArgs {
+                    diagnostic: Diagnostic::PowerConsumption,
+                    tie_break: TieBreak::One,
+                    radix: 2,
+                },
+                &mut SAMPLE.as_bytes(),
+            )
+            .unwrap(),
+            198usize.into()
+        );
+    }
+
+    #[test]
+    fn stream_life_support_sample() {
+        assert_eq!(
+            stream(
+                BinaryDiagnosticArgs {
+                    diagnostic: Diagnostic::LifeSupport,
+                    tie_break: TieBreak::One,
+                    radix: 2,
+                },
+                &mut SAMPLE.as_bytes(),
+            )
+            .unwrap(),
+            230usize.into()
+        );
+    }
+
+    #[test]
+    fn stream_life_support_past_64_bits() {
+        let input: String = SAMPLE
+            .lines()
+            .enumerate()
+            .map(|(index, line)| {
+                let prefix = if index % 3 == 0 { "1" } else { "0" };
+                format!("{}{}{}\n", prefix, "0".repeat(69), line)
+            })
+            .collect();
+
+        assert_eq!(
+            stream(
+                BinaryDiagnosticArgs {
+                    diagnostic: Diagnostic::LifeSupport,
+                    tie_break: TieBreak::One,
+                    radix: 2,
+                },
+                &mut input.as_bytes(),
+            )
+            .unwrap(),
+            run_problem_with_input(&BINARY_DIAGNOSTIC, &input, &["part2"]).unwrap()
+        );
+    }
+
+    #[test]
+    fn tie_break_zero_sample() {
+        assert_eq!(
+            run_problem_with_input(&BINARY_DIAGNOSTIC, SAMPLE, &["--part", "2", "-t", "zero"])
+                .unwrap(),
+            330usize.into()
+        );
+        assert_eq!(
+            stream(
+                BinaryDiagnosticArgs {
+                    diagnostic: Diagnostic::LifeSupport,
+                    tie_break: TieBreak::Zero,
+                    radix: 2,
+                },
+                &mut SAMPLE.as_bytes(),
+            )
+            .unwrap(),
+            330usize.into()
+        );
+    }
+
+    #[test]
+    fn tie_break_error_reports_the_tie() {
+        let error =
+            run_problem_with_input(&BINARY_DIAGNOSTIC, SAMPLE, &["--part", "2", "-t", "error"])
+                .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Digit 1 from the right has 1 each of 0 and 1, pass --tie-break one or zero to settle the tie"
+        );
+        assert_eq!(
+            run_problem_with_input(&BINARY_DIAGNOSTIC, SAMPLE, &["--part", "1", "-t", "error"])
+                .unwrap(),
+            198usize.into()
+        );
+    }
+
+    #[test]
+    fn ternary_sample() {
+        let input = "210\n201\n111\n220\n012\n";
+
+        // Gamma is 211 and epsilon 002, oxygen is 220 and CO2 012.
+        [(1, 44usize), (2, 120usize)]
+            .iter()
+            .for_each(|(part, expected)| {
+                let part = part.to_string();
+                assert_eq!(
+                    run_problem_with_input(
+                        &BINARY_DIAGNOSTIC,
+                        input,
+                        &["--part", &part, "-r", "3"]
+                    )
+                    .unwrap(),
+                    (*expected).into()
+                );
+                assert_eq!(
+                    stream(
+                        BinaryDiagnosticArgs {
+                            diagnostic: if part == "1" {
+                                Diagnostic::PowerConsumption
+                            } else {
+                                Diagnostic::LifeSupport
+                            },
+                            tie_break: TieBreak::One,
+                            radix: 3,
+                        },
+                        &mut input.as_bytes(),
+                    )
+                    .unwrap(),
+                    (*expected).into()
+                );
+            });
+        assert_eq!(
+            run_problem_with_input(&BINARY_DIAGNOSTIC, input, &["part1"])
+                .unwrap_err()
+                .to_string(),
+            "Line 1 has the digit 2, which radix 2 doesn't have"
+        );
+    }
+
+    #[test]
+    fn wide_numbers_are_not_capped() {
+        let input = format!(
+            "1{}\n1{}\n0{}\n",
+            "0".repeat(70),
+            "1".repeat(70),
+            "0".repeat(70)
+        );
+
+        assert_eq!(
+            run_problem_with_input(&BINARY_DIAGNOSTIC, &input, &["part1"]).unwrap(),
+            ((BigUint::from(1u8) << 70usize) * ((BigUint::from(1u8) << 70usize) - 1u8)).into()
+        );
+    }
+}
@@ -0,0 +1,913 @@
+use crate::{
+    check_grid, default_sub_command, describe_grid, parse_digit_grid, report_output, selected_part,
+    AocError, BitGrid, CommandResult, Deadline, Example, Grid, Problem,
+};
+use clap::{value_t_or_exit, App, Arg, ArgMatches, ErrorKind};
+use nom::IResult;
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, BinaryHeap, HashMap};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+pub const CHITON: Problem<ChitonArgs, Grid<usize>> = Problem::new(
+    sub_command,
+    "chiton",
+    "day15_chiton",
+    parse_arguments,
+    parse_data,
+    run,
+)
+.with_long_about(LONG_ABOUT)
+.with_examples(EXAMPLES)
+.with_describe(describe)
+.with_input_check(input_check)
+.with_cross_check(cross_check_arguments);
+
+const LONG_ABOUT: &str = "The input is a grid of risk levels.\n\n\
+    The answer is the lowest total risk of a path from the top left to the bottom right, \
+    moving up, down, left or right. The starting cell isn't counted. -e tiles the map that \
+    many times in each direction. Each tile to the right or down adds 1 to every risk level, \
+    wrapping from 9 back to 1. Part 1 uses -e 1 and part 2 uses -e 5. Each risk level is worked \
+    out from the input as the search reaches it, and -x builds the whole expanded map first \
+    instead. -b searches from both corners at once until the searches meet, which settles fewer \
+    points on large maps. --cross-check runs the other search on the other kind of map and \
+    compares the answers.\n\n\
+    The expanded map can be edited before the search. -i inverts every risk level, so 1 becomes \
+    9 and 9 becomes 1. -m multiplies every risk level by a factor, rounding to the nearest \
+    whole number. -c MIN,MAX then makes any cell whose risk is outside that range impassable, \
+    so -c 1,8 walls off every 9. When no path is left the answer is Unreachable.\n\n\
+    Example: the sample has a lowest risk of 40, or 315 when expanded 5 times.";
+
+const SAMPLE: &str = include_str!("../../day15_chiton/sample.txt");
+
+const SAMPLE2: &str = include_str!("../../day15_chiton/sample2.txt");
+
+const EXAMPLES: &[Example] = &[
+    Example::new(SAMPLE, 1, "40"),
+    Example::new(SAMPLE, 2, "315"),
+    Example::new(SAMPLE2, 1, "315"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+
+struct Point {
+    x: usize,
+    y: usize,
+}
+
+#[derive(Debug)]
+pub struct ChitonArgs {
+    expand: usize,
+    bidirectional: bool,
+    show_path: bool,
+    path_file: Option<String>,
+    clamp: Option<(usize, usize)>,
+    invert: bool,
+    scale: Option<f64>,
+    materialize: bool,
+}
+
+// Where the searches look up the risk of entering a point. None means it's impassable.
+trait RiskMap {
+    fn risk(&self, point: &Point) -> Option<usize>;
+}
+
+impl RiskMap for HashMap<Point, usize> {
+    fn risk(&self, point: &Point) -> Option<usize> {
+        self.get(point).copied()
+    }
+}
+
+// The expanded map, worked out a point at a time from the input tile rather than built up front.
+struct TiledRisks<'a> {
+    tile: &'a Grid<usize>,
+    rows: usize,
+    columns: usize,
+    arguments: &'a ChitonArgs,
+}
+
+impl RiskMap for TiledRisks<'_> {
+    // Each tile right or down adds 1, wrapping from 9 back to 1. The first tile is left as it is.
+    fn risk(&self, point: &Point) -> Option<usize> {
+        let base = self.tile[point.y % self.rows][point.x % self.columns];
+        let tiles = point.x / self.columns + point.y / self.rows;
+        let cost = if tiles == 0 {
+            base
+        } else {
+            (base + tiles + 8) % 9 + 1
+        };
+        edit_risk(cost, self.arguments)
+    }
+}
+
+fn sub_command() -> App<'static, 'static> {
+    default_sub_command(
+        &CHITON,
+        "Finds the least risky path from top left to bottom right then sums the risk.",
+        "Path to the input file. Input should be newline delimited ranges of integers.",
+        "Searches the default input for the path with the lowest risk level.",
+        "Expands the default input by 5x then finds the path with the lowest risk level.",
+    )    .arg(
+        Arg::with_name("expand")
+            .short("e")
+            .help("The multiplier on the input size. 1 will use the input directly, 5 will expand the input by 5 etc.")
+            .takes_value(true)
+            .required_unless_one(&["part", "explain"]),
+    )
+    .arg(
+        Arg::with_name("bidirectional")
+            .short("b")
+            .long("bidirectional")
+            .help("If passed, searches from the start and the end at once, stopping where they meet."),
+    )
+    .arg(
+        Arg::with_name("show-path")
+            .short("s")
+            .long("show-path")
+            .help("If passed, lists the risk map with the lowest risk path highlighted before the answer."),
+    )
+    .arg(
+        Arg::with_name("path-file")
+            .short("p")
+            .long("path-file")
+            .help("If passed, writes the coordinates of the lowest risk path to the given path, one x,y pair per line.")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("clamp")
+            .short("c")
+            .long("clamp")
+            .help("If passed as MIN,MAX, cells with a risk level outside the range become impassable.")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("invert")
+            .short("i")
+            .long("invert")
+            .help("If passed, inverts every risk level so 1 becomes 9 and 9 becomes 1."),
+    )
+    .arg(
+        Arg::with_name("scale")
+            .short("m")
+            .long("scale")
+            .help("If passed, multiplies every risk level by the given factor, rounding to the nearest whole number.")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("materialize")
+            .short("x")
+            .long("materialize")
+            .help("If passed, builds the whole expanded map before searching instead of working out each risk level as it's reached."),
+    )
+}
+
+fn parse_arguments(arguments: &ArgMatches) -> ChitonArgs {
+    match selected_part(arguments) {
+        Some("part1") => ChitonArgs {
+            expand: 1usize,
+            bidirectional: arguments.is_present("bidirectional"),
+            show_path: arguments.is_present("show-path"),
+            path_file: arguments.value_of("path-file").map(String::from),
+            clamp: clamp_argument(arguments),
+            invert: arguments.is_present("invert"),
+            scale: scale_argument(arguments),
+            materialize: arguments.is_present("materialize"),
+        },
+        Some("part2") => ChitonArgs {
+            expand: 5usize,
+            bidirectional: arguments.is_present("bidirectional"),
+            show_path: arguments.is_present("show-path"),
+            path_file: arguments.value_of("path-file").map(String::from),
+            clamp: clamp_argument(arguments),
+            invert: arguments.is_present("invert"),
+            scale: scale_argument(arguments),
+            materialize: arguments.is_present("materialize"),
+        },
+        _ => ChitonArgs {
+            expand: value_t_or_exit!(arguments.value_of("expand"), usize),
+            bidirectional: arguments.is_present("bidirectional"),
+            show_path: arguments.is_present("show-path"),
+            path_file: arguments.value_of("path-file").map(String::from),
+            clamp: clamp_argument(arguments),
+            invert: arguments.is_present("invert"),
+            scale: scale_argument(arguments),
+            materialize: arguments.is_present("materialize"),
+        },
+    }
+}
+
+fn clamp_argument(arguments: &ArgMatches) -> Option<(usize, usize)> {
+    arguments.value_of("clamp").map(|clamp| {
+        clamp
+            .split_once(',')
+            .and_then(|(min, max)| Some((min.parse().ok()?, max.parse().ok()?)))
+            .unwrap_or_else(|| {
+                clap::Error::with_description(
+                    "The clamp should be MIN,MAX, such as 1,8",
+                    ErrorKind::InvalidValue,
+                )
+                .exit()
+            })
+    })
+}
+
+fn scale_argument(arguments: &ArgMatches) -> Option<f64> {
+    arguments
+        .value_of("scale")
+        .map(|_| value_t_or_exit!(arguments.value_of("scale"), f64))
+}
+
+fn input_check(arguments: &ChitonArgs, cavern: &Grid<usize>) -> Result<(), AocError> {
+    check_grid(cavern)?;
+
+    if let Some((min, max)) = arguments.clamp {
+        if min > max {
+            return Err(AocError::validation(format!(
+                "The clamp {},{} is empty, the minimum is above the maximum",
+                min, max
+            )));
+        }
+    }
+
+    match arguments.scale {
+        Some(scale) if !scale.is_finite() || scale < 0f64 => Err(AocError::validation(format!(
+            "The scale {} should be a number of at least 0",
+            scale
+        ))),
+        _ => Ok(()),
+    }
+}
+
+fn run(arguments: ChitonArgs, cavern: Vec<Vec<usize>>) -> CommandResult {
+    let rows = cavern.len();
+    let columns = cavern.first().unwrap().len();
+
+    if arguments.materialize {
+        let (points_to_cost, row_max, column_max) = materialize(&cavern, &arguments);
+        find_lowest_risk(&points_to_cost, row_max, column_max, &arguments)
+    } else {
+        let risks = TiledRisks {
+            tile: &cavern,
+            rows,
+            columns,
+            arguments: &arguments,
+        };
+        find_lowest_risk(
+            &risks,
+            rows * arguments.expand,
+            columns * arguments.expand,
+            &arguments,
+        )
+    }
+}
+
+// The whole expanded and edited map, along with its size.
+fn materialize(
+    cavern: &Grid<usize>,
+    arguments: &ChitonArgs,
+) -> (HashMap<Point, usize>, usize, usize) {
+    let row_max = cavern.len();
+    let column_max = cavern.first().unwrap().len();
+
+    let points_to_cost = (0..row_max)
+        .map(|y| {
+            (0..column_max)
+                .map(|x| {
+                    (
+                        Point { x: x, y: y },
+                        *cavern.get(y).unwrap().get(x).unwrap(),
+                    )
+                })
+                .collect()
+        })
+        .fold(HashMap::new(), |mut acc, row: Vec<(Point, usize)>| {
+            acc.extend(row.into_iter());
+            acc
+        });
+
+    let (points_to_cost, row_max, column_max) =
+        expand_points_field(points_to_cost, row_max, column_max, &arguments.expand);
+
+    (edit_risks(points_to_cost, arguments), row_max, column_max)
+}
+
+fn find_lowest_risk(
+    risks: &impl RiskMap,
+    row_max: usize,
+    column_max: usize,
+    arguments: &ChitonArgs,
+) -> CommandResult {
+    let end = Point {
+        x: column_max - 1,
+        y: row_max - 1,
+    };
+
+    let lowest = if arguments.bidirectional {
+        lowest_risk_bidirectional(risks, row_max, column_max, end)
+    } else {
+        lowest_risk(risks, row_max, column_max, end)
+    };
+    let (cost, path) = match lowest {
+        Some(lowest) => lowest,
+        None => return "Unreachable".to_string().into(),
+    };
+
+    if let Some(path_file) = &arguments.path_file {
+        report_output(path_file, write_path(&path, path_file));
+    }
+
+    if arguments.show_path {
+        vec![
+            (
+                "Path".to_string(),
+                display_path(risks, &path, row_max, column_max).into(),
+            ),
+            ("Lowest risk".to_string(), cost.into()),
+        ]
+        .into()
+    } else {
+        cost.into()
+    }
+}
+
+// The alternate run leaves the path file to the main run. With -s it keeps the same search and
+// only swaps the map, since the two searches can settle on different paths of the same risk.
+fn cross_check_arguments(arguments: &ArgMatches) -> ChitonArgs {
+    let arguments = parse_arguments(arguments);
+    ChitonArgs {
+        bidirectional: if arguments.show_path {
+            arguments.bidirectional
+        } else {
+            !arguments.bidirectional
+        },
+        materialize: !arguments.materialize,
+        path_file: None,
+        ..arguments
+    }
+}
+
+// Impassable cells are left out of the map. Inverting, then scaling, then clamping.
+fn edit_risks(
+    points_to_cost: HashMap<Point, usize>,
+    arguments: &ChitonArgs,
+) -> HashMap<Point, usize> {
+    points_to_cost
+        .into_iter()
+        .filter_map(|(point, cost)| edit_risk(cost, arguments).map(|cost| (point, cost)))
+        .collect()
+}
+
+fn edit_risk(cost: usize, arguments: &ChitonArgs) -> Option<usize> {
+    let cost = if arguments.invert { 10 - cost } else { cost };
+    let cost = match arguments.scale {
+        Some(scale) => (cost as f64 * scale).round() as usize,
+        None => cost,
+    };
+
+    arguments
+        .clamp
+        .is_none_or(|(min, max)| (min..=max).contains(&cost))
+        .then_some(cost)
+}
+
+// None when an end is impassable or walled off from the other, or when the deadline passes first.
+fn lowest_risk(
+    risks: &impl RiskMap,
+    row_max: usize,
+    column_max: usize,
+    end: Point,
+) -> Option<(usize, Vec<Point>)> {
+    let mut visited_points = BitGrid::new(row_max, column_max);
+
+    let mut current = Point {
+        x: 0usize,
+        y: 0usize,
+    };
+    if risks.risk(&current).is_none() || risks.risk(&end).is_none() {
+        return None;
+    }
+    let mut costs = HashMap::new();
+    let mut previous_points = HashMap::new();
+    let mut unvisited_costs = BTreeSet::new();
+    costs.insert(current, 0usize);
+    unvisited_costs.insert((0usize, current));
+    let deadline = Deadline::current();
+
+    loop {
+        if deadline.expired() {
+            return None;
+        }
+        let current_cost = *costs.get(&current).unwrap();
+        get_adjacent_points(&(row_max), &(column_max), &current)
+            .iter()
+            .filter(|point| !visited_points.contains(point.y, point.x))
+            .filter_map(|point| risks.risk(point).map(|cost| (point, cost)))
+            .for_each(|(point, cost)| {
+                let potential_new_cost = current_cost + cost;
+                let new_cost = match costs.get(point) {
+                    Some(old_cost) => {
+                        unvisited_costs.remove(&(*old_cost, *point));
+                        if *old_cost < potential_new_cost {
+                            *old_cost
+                        } else {
+                            previous_points.insert(*point, current);
+                            potential_new_cost
+                        }
+                    }
+                    None => {
+                        previous_points.insert(*point, current);
+                        potential_new_cost
+                    }
+                };
+                costs.insert(*point, new_cost);
+                unvisited_costs.insert((new_cost, *point));
+            });
+
+        visited_points.insert(current.y, current.x);
+        unvisited_costs.remove(&(current_cost, current));
+
+        let result = unvisited_costs.first();
+
+        if let Some((_, next_point)) = result {
+            current = *next_point;
+        } else {
+            break;
+        }
+    }
+
+    costs
+        .get(&end)
+        .map(|cost| (*cost, reconstruct_path(&previous_points, end)))
+}
+
+// One direction of the bidirectional search, with a heap of points to settle that may still hold
+// stale entries for points already settled at a lower cost.
+struct Search {
+    costs: HashMap<Point, usize>,
+    previous_points: HashMap<Point, Point>,
+    settled: BitGrid,
+    queue: BinaryHeap<Reverse<(usize, Point)>>,
+}
+
+impl Search {
+    fn new(start: Point, row_max: usize, column_max: usize) -> Search {
+        let mut search = Search {
+            costs: HashMap::new(),
+            previous_points: HashMap::new(),
+            settled: BitGrid::new(row_max, column_max),
+            queue: BinaryHeap::new(),
+        };
+        search.costs.insert(start, 0usize);
+        search.queue.push(Reverse((0usize, start)));
+        search
+    }
+
+    fn lowest_queued(&self) -> Option<usize> {
+        self.queue.peek().map(|Reverse((cost, _))| *cost)
+    }
+}
+
+// Runs a search from each corner, always advancing the one with the cheaper next point. Moving
+// backwards off a point costs its risk, the same as entering it going forwards. Once the two
+// cheapest queued costs add up to at least the best path found through a point both searches have
+// reached, no better path is left.
+fn lowest_risk_bidirectional(
+    risks: &impl RiskMap,
+    row_max: usize,
+    column_max: usize,
+    end: Point,
+) -> Option<(usize, Vec<Point>)> {
+    let start = Point {
+        x: 0usize,
+        y: 0usize,
+    };
+    if risks.risk(&start).is_none() || risks.risk(&end).is_none() {
+        return None;
+    }
+    let mut searches = [
+        Search::new(start, row_max, column_max),
+        Search::new(end, row_max, column_max),
+    ];
+    let mut best = if start == end {
+        Some((0usize, start))
+    } else {
+        None
+    };
+    let deadline = Deadline::current();
+
+    while let (Some(forward), Some(backward)) =
+        (searches[0].lowest_queued(), searches[1].lowest_queued())
+    {
+        if best.is_some_and(|(cost, _)| forward + backward >= cost) {
+            break;
+        }
+        if deadline.expired() {
+            return None;
+        }
+
+        let direction = if forward <= backward { 0usize } else { 1usize };
+        let Reverse((current_cost, current)) = searches[direction].queue.pop().unwrap();
+        if !searches[direction].settled.insert(current.y, current.x) {
+            continue;
+        }
+
+        get_adjacent_points(&row_max, &column_max, &current)
+            .into_iter()
+            .filter(|point| risks.risk(point).is_some())
+            .for_each(|point| {
+                let step = if direction == 0 { point } else { current };
+                let new_cost = current_cost + risks.risk(&step).unwrap();
+                let search = &mut searches[direction];
+                if search
+                    .costs
+                    .get(&point)
+                    .is_some_and(|cost| *cost <= new_cost)
+                {
+                    return;
+                }
+
+                search.costs.insert(point, new_cost);
+                search.previous_points.insert(point, current);
+                search.queue.push(Reverse((new_cost, point)));
+
+                if let Some(other_cost) = searches[1 - direction].costs.get(&point) {
+                    let total = new_cost + other_cost;
+                    if best.is_none_or(|(cost, _)| total < cost) {
+                        best = Some((total, point));
+                    }
+                }
+            });
+    }
+
+    let (cost, meeting) = best?;
+    let mut path = reconstruct_path(&searches[0].previous_points, meeting);
+    let mut next = meeting;
+    while let Some(point) = searches[1].previous_points.get(&next) {
+        path.push(*point);
+        next = *point;
+    }
+
+    Some((cost, path))
+}
+
+fn reconstruct_path(previous_points: &HashMap<Point, Point>, end: Point) -> Vec<Point> {
+    let mut path = vec![end];
+
+    while let Some(previous) = previous_points.get(path.last().unwrap()) {
+        path.push(*previous);
+    }
+
+    path.reverse();
+    path
+}
+
+fn display_path(risks: &impl RiskMap, path: &[Point], row_max: usize, column_max: usize) -> String {
+    let mut path_points = BitGrid::new(row_max, column_max);
+    path.iter().for_each(|point| {
+        path_points.insert(point.y, point.x);
+    });
+
+    (0..row_max)
+        .map(|y| {
+            (0..column_max)
+                .map(|x| Point { x, y })
+                .map(|point| match risks.risk(&point) {
+                    Some(cost) if path_points.contains(point.y, point.x) => {
+                        format!("\x1b[1;31m{}\x1b[0m", cost)
+                    }
+                    Some(cost) => cost.to_string(),
+                    None => "#".to_string(),
+                })
+                .collect::<Vec<String>>()
+                .join("")
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn write_path(path: &[Point], path_file: &str) -> Result<(), io::Error> {
+    let mut writer = BufWriter::new(File::create(path_file)?);
+    path.iter()
+        .try_for_each(|point| writeln!(writer, "{},{}", point.x, point.y))?;
+    writer.flush()
+}
+
+fn expand_points_field(
+    mut points_to_cost: HashMap<Point, usize>,
+    mut max_row_size: usize,
+    mut max_column_size: usize,
+    expand: &usize,
+) -> (HashMap<Point, usize>, usize, usize) {
+    let mut first_row = vec![points_to_cost];
+
+    for i in 1..*expand {
+        let seed = first_row.get(i - 1).unwrap();
+        let next = get_next_set_of_points(&max_row_size, &max_column_size, &seed, true);
+        first_row.push(next);
+    }
+
+    points_to_cost = first_row
+        .iter()
+        .map(|column| {
+            let mut first_column = vec![column.to_owned()];
+
+            for i in 1..*expand {
+                let seed = first_column.get(i - 1).unwrap();
+                let next = get_next_set_of_points(&max_row_size, &max_column_size, &seed, false);
+                first_column.push(next);
+            }
+
+            first_column
+                .iter()
+                .fold(HashMap::new(), |mut acc: HashMap<Point, usize>, segment| {
+                    acc.extend(segment);
+                    acc
+                })
+        })
+        .fold(HashMap::new(), |mut acc, segment| {
+            acc.extend(segment);
+            acc
+        });
+    max_row_size *= *expand;
+    max_column_size *= *expand;
+    (points_to_cost, max_row_size, max_column_size)
+}
+
+fn get_next_set_of_points(
+    max_row_size: &usize,
+    max_column_size: &usize,
+    seed: &HashMap<Point, usize>,
+    scale_x: bool,
+) -> HashMap<Point, usize> {
+    seed.iter()
+        .map(|(point, cost)| {
+            let new_cost = if cost == &9usize {
+                1usize
+            } else {
+                *cost + 1usize
+            };
+
+            let new_point = if scale_x {
+                Point {
+                    x: point.x + max_column_size,
+                    y: point.y,
+                }
+            } else {
+                Point {
+                    x: point.x,
+                    y: point.y + max_row_size,
+                }
+            };
+            (new_point, new_cost)
+        })
+        .collect()
+}
+
+fn get_adjacent_points(max_row_size: &usize, max_column_size: &usize, start: &Point) -> Vec<Point> {
+    let x = start.x;
+    let y = start.y;
+    let mut adjacents = Vec::new();
+    if y > 0usize {
+        adjacents.push(Point { x: x, y: y - 1 });
+    }
+
+    if y < (*max_row_size - 1usize) {
+        adjacents.push(Point { x: x, y: y + 1 });
+    }
+
+    if x > 0usize {
+        adjacents.push(Point { x: x - 1, y: y });
+    }
+
+    if x < (*max_column_size - 1usize) {
+        adjacents.push(Point { x: x + 1, y: y });
+    }
+
+    adjacents
+}
+
+fn describe(cavern: &Vec<Vec<usize>>) -> String {
+    describe_grid(cavern)
+}
+
+fn parse_data(input: &String) -> IResult<&str, Grid<usize>> {
+    parse_digit_grid(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{run_problem_with_input, scratch_path, Command};
+
+    #[test]
+    fn part1_sample() {
+        assert_eq!(
+            run_problem_with_input(&CHITON, SAMPLE, &["part1"]).unwrap(),
+            40usize.into()
+        );
+    }
+
+    #[test]
+    fn part2_sample() {
+        assert_eq!(
+            run_problem_with_input(&CHITON, SAMPLE, &["part2"]).unwrap(),
+            315usize.into()
+        );
+    }
+
+    #[test]
+    fn part1_expanded_sample() {
+        assert_eq!(
+            run_problem_with_input(&CHITON, SAMPLE2, &["part1"]).unwrap(),
+            315usize.into()
+        );
+    }
+
+    #[test]
+    fn bidirectional_sample() {
+        assert_eq!(
+            run_problem_with_input(&CHITON, SAMPLE, &["--part", "1", "-b"]).unwrap(),
+            40usize.into()
+        );
+        assert_eq!(
+            run_problem_with_input(&CHITON, SAMPLE, &["--part", "2", "-b", "--cross-check"])
+                .unwrap(),
+            315usize.into()
+        );
+    }
+
+    #[test]
+    fn tiled_risks_match_the_materialized_map() {
+        let cavern = parse_data(&SAMPLE.to_string()).unwrap().1;
+        let arguments = ChitonArgs {
+            expand: 5,
+            bidirectional: false,
+            show_path: false,
+            path_file: None,
+            clamp: Some((2, 8)),
+            invert: true,
+            scale: None,
+            materialize: true,
+        };
+        let (points_to_cost, row_max, column_max) = materialize(&cavern, &arguments);
+        let risks = TiledRisks {
+            tile: &cavern,
+            rows: 10,
+            columns: 10,
+            arguments: &arguments,
+        };
+
+        assert_eq!((row_max, column_max), (50, 50));
+        (0..row_max)
+            .flat_map(|y| (0..column_max).map(move |x| Point { x, y }))
+            .for_each(|point| assert_eq!(risks.risk(&point), points_to_cost.risk(&point)));
+        assert_eq!(
+            run_problem_with_input(&CHITON, SAMPLE, &["--part", "2", "-x", "--cross-check"])
+                .unwrap(),
+            315usize.into()
+        );
+    }
+
+    #[test]
+    fn show_path_is_part_of_the_result() {
+        let shown: CommandResult = vec![
+            (
+                "Path".to_string(),
+                "\x1b[1;31m1\x1b[0m9\n\x1b[1;31m1\x1b[0m\x1b[1;31m1\x1b[0m"
+                    .to_string()
+                    .into(),
+            ),
+            ("Lowest risk".to_string(), 2usize.into()),
+        ]
+        .into();
+
+        assert_eq!(
+            run_problem_with_input(&CHITON, "19\n11\n", &["--part", "1", "-s", "--cross-check"])
+                .unwrap(),
+            shown
+        );
+        assert_eq!(
+            run_problem_with_input(
+                &CHITON,
+                "19\n11\n",
+                &["--part", "1", "-s", "-b", "--cross-check"]
+            )
+            .unwrap(),
+            shown
+        );
+    }
+
+    #[test]
+    fn bidirectional_path_file_sample() {
+        let path_file = scratch_path("chiton_bidirectional_path_file_sample.txt");
+        let path_file = path_file.to_str().unwrap();
+
+        run_problem_with_input(&CHITON, SAMPLE, &["--part", "1", "-b", "-p", path_file]).unwrap();
+
+        let path = std::fs::read_to_string(path_file).unwrap();
+        assert_eq!(path.lines().next(), Some("0,0"));
+        assert_eq!(path.lines().last(), Some("9,9"));
+        assert_eq!(path.lines().count(), 19);
+    }
+
+    #[test]
+    fn edited_sample() {
+        assert_eq!(
+            run_problem_with_input(&CHITON, SAMPLE, &["--part", "1", "-m", "2"]).unwrap(),
+            80usize.into()
+        );
+        assert_eq!(
+            run_problem_with_input(&CHITON, SAMPLE, &["--part", "1", "-i", "--cross-check"])
+                .unwrap(),
+            81usize.into()
+        );
+        assert_eq!(
+            run_problem_with_input(
+                &CHITON,
+                SAMPLE,
+                &["--part", "1", "-c", "1,8", "--cross-check"]
+            )
+            .unwrap(),
+            40usize.into()
+        );
+        assert_eq!(
+            run_problem_with_input(
+                &CHITON,
+                SAMPLE,
+                &["--part", "1", "-c", "1,5", "--cross-check"]
+            )
+            .unwrap(),
+            "Unreachable".to_string().into()
+        );
+    }
+
+    #[test]
+    fn empty_clamp_is_rejected() {
+        let error =
+            run_problem_with_input(&CHITON, SAMPLE, &["--part", "1", "-c", "8,1"]).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "The clamp 8,1 is empty, the minimum is above the maximum"
+        );
+    }
+
+    #[test]
+    fn validate_sample() {
+        assert_eq!(
+            CHITON.validate_input(&SAMPLE.to_string()).unwrap(),
+            "10 lines\nGrid of 10 rows by 10 columns"
+        );
+    }
+
+    #[test]
+    fn validate_ragged_sample() {
+        assert!(CHITON
+            .validate_input(&"123\n45\n678".to_string())
+            .unwrap()
+            .contains("Anomaly: rows 2 differ in length from the first row"));
+    }
+
+    #[test]
+    fn path_file_sample() {
+        let path_file = scratch_path("chiton_path_file_sample.txt");
+        let path_file = path_file.to_str().unwrap();
+
+        assert_eq!(
+            run_problem_with_input(&CHITON, SAMPLE, &["--part", "1", "-p", path_file]).unwrap(),
+            40usize.into()
+        );
+
+        let path = std::fs::read_to_string(path_file).unwrap();
+        assert_eq!(path.lines().next(), Some("0,0"));
+        assert_eq!(path.lines().last(), Some("9,9"));
+        assert_eq!(path.lines().count(), 19);
+    }
+
+    #[test]
+    fn unwritable_path_file_is_an_error() {
+        let path_file = scratch_path("chiton_missing").join("path.txt");
+        let path_file = path_file.to_str().unwrap();
+        let error =
+            run_problem_with_input(&CHITON, SAMPLE, &["--part", "1", "-p", path_file]).unwrap_err();
+
+        assert!(matches!(error, AocError::Io(_)));
+        assert!(error
+            .to_string()
+            .contains(&format!("{} can't be written", path_file)));
+    }
+
+    #[test]
+    fn timeout_sample() {
+        Deadline::after(std::time::Duration::from_secs(0u64)).set_current();
+
+        assert!(matches!(
+            run_problem_with_input(&CHITON, SAMPLE, &["part2"]),
+            Err(AocError::Timeout)
+        ));
+        assert!(matches!(
+            run_problem_with_input(&CHITON, SAMPLE, &["--part", "2", "-b"]),
+            Err(AocError::Timeout)
+        ));
+    }
+}
@@ -0,0 +1,739 @@
+use crate::random::SplitMix64;
+use crate::{
+    count_result, default_sub_command, parse_usize, report_output, seed_argument, seeded_random,
+    selected_part, AocError, CommandResult, Counter, Example, Problem,
+};
+use clap::{value_t_or_exit, App, Arg, ArgMatches};
+use nom::{
+    bytes::complete::tag,
+    character::complete::newline,
+    combinator::map,
+    sequence::{preceded, terminated, tuple},
+    IResult,
+};
+use num_bigint::{BigInt, BigUint};
+use num_rational::BigRational;
+use std::{
+    cmp::max,
+    collections::HashMap,
+    fmt::Display,
+    fs::File,
+    io::{self, BufWriter, Write},
+    iter::Cycle,
+    ops::{AddAssign, Mul, RangeInclusive},
+};
+use strum::VariantNames;
+use strum_macros::{EnumString, EnumVariantNames};
+
+pub const DIRAC_DICE: Problem<DiracDiceArgs, (Player, Player)> = Problem::new(
+    sub_command,
+    "dirac-dice",
+    "day21_dirac_dice",
+    parse_arguments,
+    parse_data,
+    run,
+)
+.with_long_about(LONG_ABOUT)
+.with_examples(EXAMPLES)
+.with_describe(describe)
+.with_input_check(input_check);
+
+const LONG_ABOUT: &str =
+    "The input gives each player's starting space on a circular board of 10 spaces.\n\n\
+    Players take turns rolling three times, moving forward by the total and scoring the space \
+    they land on. deterministic uses a die that rolls 1, 2, 3 and so on up to 100. The first \
+    to 1000 wins and the answer is the loser's score times the number of rolls. dirac uses a \
+    three sided die that splits the universe on every roll. The first to 21 wins and the \
+    answer is the number of universes won by the player who wins more. -o all-wins reports \
+    both players' wins instead and -o probabilities gives each player's chance of winning as an \
+    exact fraction. -w changes the score needed to win, and the dirac counts grow past 64 bits \
+    for high scores. random plays -t games to 21 with a random three sided die \
+    seeded by -s, or else the global --seed, and reports each player's win rate.\n\n\
+    -c writes a CSV with a row for each turn, starting before the first, giving the number of \
+    universes still being played and each player's wins so far. For dirac games it shows how \
+    fast the universes multiply and where another solver's counts first differ. Random games \
+    can't be exported since they play many games.\n\n\
+    Example: starting at 4 and 8 gives 739785, and player 1 wins in 444356092776315 universes.";
+
+const SAMPLE: &str = include_str!("../../day21_dirac_dice/sample.txt");
+
+const EXAMPLES: &[Example] = &[
+    Example::new(SAMPLE, 1, "739785"),
+    Example::new(SAMPLE, 2, "444356092776315"),
+];
+
+#[derive(Debug)]
+pub struct DiracDiceArgs {
+    game_type: GameType,
+    output: Output,
+    winning_score: Option<usize>,
+    seed: u64,
+    trials: usize,
+    csv: Option<String>,
+}
+
+#[derive(Debug, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "kebab_case")]
+enum GameType {
+    Deterministic,
+    Dirac,
+    Random,
+}
+
+#[derive(Debug, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "kebab_case")]
+enum Output {
+    MostWins,
+    AllWins,
+    Probabilities,
+}
+
+trait Die {
+    /// Rolls the die three times, returning each possible total with the number of universes it
+    /// appears in.
+    fn roll_turn(&mut self) -> Vec<(usize, usize)>;
+}
+
+struct DeterministicDie {
+    faces: Cycle<RangeInclusive<usize>>,
+}
+
+impl DeterministicDie {
+    fn new(sides: usize) -> DeterministicDie {
+        DeterministicDie {
+            faces: (1..=sides).cycle(),
+        }
+    }
+}
+
+impl Die for DeterministicDie {
+    fn roll_turn(&mut self) -> Vec<(usize, usize)> {
+        let total = (0..3).fold(0usize, |total, _| {
+            total + self.faces.next().expect("infinite iterator")
+        });
+        vec![(total, 1usize)]
+    }
+}
+
+struct RandomDie {
+    sides: usize,
+    random: SplitMix64,
+}
+
+impl RandomDie {
+    fn new(sides: usize, seed: u64) -> RandomDie {
+        RandomDie {
+            sides,
+            random: seeded_random(seed),
+        }
+    }
+
+    fn roll(&mut self) -> usize {
+        self.random.below(self.sides) + 1
+    }
+}
+
+impl Die for RandomDie {
+    fn roll_turn(&mut self) -> Vec<(usize, usize)> {
+        let total = (0..3).fold(0usize, |total, _| total + self.roll());
+        vec![(total, 1usize)]
+    }
+}
+
+struct QuantumDie {
+    outcomes: Vec<(usize, usize)>,
+}
+
+impl QuantumDie {
+    fn new(sides: usize) -> QuantumDie {
+        let totals: Counter<usize> = (1..=sides)
+            .flat_map(|first| {
+                (1..=sides)
+                    .flat_map(move |second| (1..=sides).map(move |third| first + second + third))
+            })
+            .collect();
+        let mut outcomes: Vec<(usize, usize)> = totals.into_iter().collect();
+        outcomes.sort();
+
+        QuantumDie { outcomes }
+    }
+}
+
+impl Die for QuantumDie {
+    fn roll_turn(&mut self) -> Vec<(usize, usize)> {
+        self.outcomes.clone()
+    }
+}
+
+// Universe counts are generic so dirac games can count in big integers, which high winning scores
+// need.
+struct GameOutcome<C> {
+    player1_wins: C,
+    player2_wins: C,
+    losing_score: usize,
+    rolls: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Player {
+    starting_position: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+struct PlayerUniverse {
+    player1_position: usize,
+    player2_position: usize,
+    player1_score: usize,
+    player2_score: usize,
+}
+
+fn sub_command() -> App<'static, 'static> {
+    default_sub_command(
+        &DIRAC_DICE,
+        "Plays a dice game then calculates a value based on the result.",
+        "Path to the input file. Input should be newline delimited players and their starting positions.",
+        "Plays the game with the default input with a detmerinistic die, then multplies the loser's score by the number of rolls.",
+        "Plays the game with the default input with dirac dice. Then outputs the total number of universes where the player that won the most won.",
+    ).arg(
+        Arg::with_name("game-type")
+            .short("g")
+            .help(
+                "The type of game to play. The games available are as follows:\n\n\
+            deterministic: Uses a d100 which always rolls one higher.\n\n\
+            dirac: Uses a d3 dirac die and finds the results for all universes.\n\n\
+            random: Uses a random d3 and reports win rates over a number of trials.\n\n",
+            )
+            .takes_value(true)
+            .possible_values(&GameType::VARIANTS)
+            .required_unless_one(&["part", "explain"]),
+    )
+    .arg(
+        Arg::with_name("output")
+            .short("o")
+            .long("output")
+            .help(
+                "What to report for dirac games. The outputs available are as follows:\n\n\
+            most-wins: The number of universes won by the player that won the most.\n\n\
+            all-wins: The universes won by each player and the total number of universes.\n\n\
+            probabilities: Each player's chance of winning as a reduced fraction.\n\n",
+            )
+            .takes_value(true)
+            .possible_values(Output::VARIANTS)
+            .default_value("most-wins"),
+    )
+    .arg(
+        Arg::with_name("winning-score")
+            .short("w")
+            .long("winning-score")
+            .help("The score needed to win. Defaults to 1000 for deterministic games and 21 otherwise.")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("seed")
+            .short("s")
+            .long("seed")
+            .help("The seed for random games. Defaults to the global --seed.")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("trials")
+            .short("t")
+            .long("trials")
+            .help("The number of games to play for random games.")
+            .takes_value(true)
+            .default_value("1000"),
+    )
+    .arg(
+        Arg::with_name("csv")
+            .short("c")
+            .long("csv")
+            .help("If passed, writes the universes still being played and each player's wins after every turn to the given path as CSV.")
+            .takes_value(true),
+    )
+}
+
+fn parse_arguments(arguments: &ArgMatches) -> DiracDiceArgs {
+    match selected_part(arguments) {
+        Some("part1") => DiracDiceArgs {
+            game_type: GameType::Deterministic,
+            output: Output::MostWins,
+            winning_score: winning_score_argument(arguments),
+            seed: 0u64,
+            trials: 0usize,
+            csv: arguments.value_of("csv").map(String::from),
+        },
+        Some("part2") => DiracDiceArgs {
+            game_type: GameType::Dirac,
+            output: Output::MostWins,
+            winning_score: winning_score_argument(arguments),
+            seed: 0u64,
+            trials: 0usize,
+            csv: arguments.value_of("csv").map(String::from),
+        },
+        _ => DiracDiceArgs {
+            game_type: value_t_or_exit!(arguments.value_of("game-type"), GameType),
+            output: value_t_or_exit!(arguments.value_of("output"), Output),
+            winning_score: winning_score_argument(arguments),
+            seed: seed_argument(arguments, "seed"),
+            trials: value_t_or_exit!(arguments.value_of("trials"), usize),
+            csv: arguments.value_of("csv").map(String::from),
+        },
+    }
+}
+
+fn winning_score_argument(arguments: &ArgMatches) -> Option<usize> {
+    if arguments.is_present("winning-score") {
+        Some(value_t_or_exit!(arguments.value_of("winning-score"), usize))
+    } else {
+        None
+    }
+}
+
+fn input_check(arguments: &DiracDiceArgs, _players: &(Player, Player)) -> Result<(), AocError> {
+    match (&arguments.game_type, &arguments.csv) {
+        (GameType::Random, Some(_)) => Err(AocError::validation(
+            "--csv can only export deterministic and dirac games",
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn run(arguments: DiracDiceArgs, players: (Player, Player)) -> CommandResult {
+    let winning_score = arguments
+        .winning_score
+        .unwrap_or(match arguments.game_type {
+            GameType::Deterministic => 1000usize,
+            _ => 21usize,
+        });
+
+    let csv = arguments.csv.as_deref();
+
+    match (arguments.game_type, arguments.output) {
+        (GameType::Deterministic, _) => {
+            let outcome: GameOutcome<usize> = play_recorded(
+                &players,
+                &mut DeterministicDie::new(100usize),
+                winning_score,
+                csv,
+            );
+            (outcome.losing_score * outcome.rolls).into()
+        }
+        (GameType::Dirac, Output::MostWins) => {
+            let outcome: GameOutcome<BigUint> =
+                play_recorded(&players, &mut QuantumDie::new(3usize), winning_score, csv);
+            count_result(max(outcome.player1_wins, outcome.player2_wins))
+        }
+        (GameType::Dirac, Output::AllWins) => {
+            let outcome: GameOutcome<BigUint> =
+                play_recorded(&players, &mut QuantumDie::new(3usize), winning_score, csv);
+            let total = &outcome.player1_wins + &outcome.player2_wins;
+            vec![
+                (
+                    "Player 1 wins".to_string(),
+                    count_result(outcome.player1_wins),
+                ),
+                (
+                    "Player 2 wins".to_string(),
+                    count_result(outcome.player2_wins),
+                ),
+                ("Total universes".to_string(), count_result(total)),
+            ]
+            .into()
+        }
+        (GameType::Dirac, Output::Probabilities) => {
+            let outcome: GameOutcome<BigUint> =
+                play_recorded(&players, &mut QuantumDie::new(3usize), winning_score, csv);
+            let total = BigInt::from(&outcome.player1_wins + &outcome.player2_wins);
+            let probability = |wins: BigUint| {
+                let probability = BigRational::new(BigInt::from(wins), total.clone());
+                format!("{}/{}", probability.numer(), probability.denom()).into()
+            };
+            vec![
+                (
+                    "Player 1 win probability".to_string(),
+                    probability(outcome.player1_wins),
+                ),
+                (
+                    "Player 2 win probability".to_string(),
+                    probability(outcome.player2_wins),
+                ),
+            ]
+            .into()
+        }
+        (GameType::Random, _) => {
+            let mut die = RandomDie::new(3usize, arguments.seed);
+            let (player1_wins, player2_wins) = (0..arguments.trials)
+                .map(|_| -> GameOutcome<usize> {
+                    play_game(&players, &mut die, winning_score, &mut |_, _, _| {})
+                })
+                .fold((0usize, 0usize), |(player1_wins, player2_wins), outcome| {
+                    (
+                        player1_wins + outcome.player1_wins,
+                        player2_wins + outcome.player2_wins,
+                    )
+                });
+
+            vec![
+                ("Trials".to_string(), arguments.trials.into()),
+                ("Player 1 wins".to_string(), player1_wins.into()),
+                ("Player 2 wins".to_string(), player2_wins.into()),
+                (
+                    "Player 1 win percentage".to_string(),
+                    (player1_wins * 100 / max(arguments.trials, 1usize)).into(),
+                ),
+                (
+                    "Player 2 win percentage".to_string(),
+                    (player2_wins * 100 / max(arguments.trials, 1usize)).into(),
+                ),
+            ]
+            .into()
+        }
+    }
+}
+
+// on_turn is given the universes still being played and the outcome so far, once before the first
+// turn and again after every turn.
+fn play_game<C>(
+    players: &(Player, Player),
+    die: &mut impl Die,
+    winning_score: usize,
+    on_turn: &mut impl FnMut(usize, &HashMap<PlayerUniverse, C>, &GameOutcome<C>),
+) -> GameOutcome<C>
+where
+    C: Clone + Default + From<usize> + AddAssign + Mul<usize, Output = C>,
+{
+    let (player1, player2) = players;
+
+    let mut games = HashMap::new();
+    games.insert(
+        PlayerUniverse {
+            player1_position: player1.starting_position - 1,
+            player2_position: player2.starting_position - 1,
+            player1_score: 0usize,
+            player2_score: 0usize,
+        },
+        C::from(1usize),
+    );
+
+    let mut outcome = GameOutcome {
+        player1_wins: C::default(),
+        player2_wins: C::default(),
+        losing_score: 0usize,
+        rolls: 0usize,
+    };
+    let mut turn = 0usize;
+    let mut player1_turn = true;
+    on_turn(turn, &games, &outcome);
+
+    while !games.is_empty() {
+        let die_outcomes = die.roll_turn();
+        outcome.rolls += 3;
+        games = take_turn(&games, &die_outcomes, player1_turn);
+
+        let winning_games: HashMap<PlayerUniverse, C> = games
+            .iter()
+            .filter(|(game, _)| game.score(player1_turn) >= winning_score)
+            .map(|(game, count)| (*game, count.clone()))
+            .collect();
+        winning_games.into_iter().for_each(|(game, count)| {
+            games.remove(&game);
+            if player1_turn {
+                outcome.player1_wins += count;
+            } else {
+                outcome.player2_wins += count;
+            }
+            outcome.losing_score = game.score(!player1_turn);
+        });
+
+        turn += 1;
+        player1_turn = !player1_turn;
+        on_turn(turn, &games, &outcome);
+    }
+
+    outcome
+}
+
+// Moves the player whose turn it is by every total the die can roll in every universe.
+fn take_turn<C>(
+    games: &HashMap<PlayerUniverse, C>,
+    die_outcomes: &[(usize, usize)],
+    player1_turn: bool,
+) -> HashMap<PlayerUniverse, C>
+where
+    C: Clone + Default + AddAssign + Mul<usize, Output = C>,
+{
+    games
+        .iter()
+        .flat_map(|(game, count)| {
+            die_outcomes.iter().map(move |(die_roll, die_count)| {
+                (
+                    game.moved(player1_turn, *die_roll),
+                    count.clone() * *die_count,
+                )
+            })
+        })
+        .fold(HashMap::new(), |mut acc, (game, count)| {
+            *acc.entry(game).or_default() += count;
+            acc
+        })
+}
+
+impl PlayerUniverse {
+    fn score(&self, player1: bool) -> usize {
+        if player1 {
+            self.player1_score
+        } else {
+            self.player2_score
+        }
+    }
+
+    fn moved(&self, player1: bool, die_roll: usize) -> PlayerUniverse {
+        if player1 {
+            let player1_position = (self.player1_position + die_roll) % 10;
+            PlayerUniverse {
+                player1_position,
+                player1_score: self.player1_score + player1_position + 1,
+                ..*self
+            }
+        } else {
+            let player2_position = (self.player2_position + die_roll) % 10;
+            PlayerUniverse {
+                player2_position,
+                player2_score: self.player2_score + player2_position + 1,
+                ..*self
+            }
+        }
+    }
+}
+
+// Plays the game, writing a row to the CSV at path after every turn when there is one. The CSV is
+// opened once, and after the first failed write the rest of its rows are skipped.
+fn play_recorded<C>(
+    players: &(Player, Player),
+    die: &mut impl Die,
+    winning_score: usize,
+    csv: Option<&str>,
+) -> GameOutcome<C>
+where
+    C: Clone + Default + From<usize> + AddAssign + Mul<usize, Output = C> + Display,
+{
+    let path = match csv {
+        Some(path) => path,
+        None => return play_game(players, die, winning_score, &mut |_, _, _| {}),
+    };
+
+    let mut written = create_csv(path);
+    let outcome = play_game(players, die, winning_score, &mut |turn, games, outcome| {
+        if let Ok(writer) = &mut written {
+            if let Err(error) = write_csv_row(writer, turn, games, outcome) {
+                written = Err(error);
+            }
+        }
+    });
+    report_output(path, written.and_then(|mut writer| writer.flush()));
+
+    outcome
+}
+
+fn create_csv(path: &str) -> Result<BufWriter<File>, io::Error> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    writeln!(writer, "turn,live_universes,player1_wins,player2_wins")?;
+    Ok(writer)
+}
+
+fn write_csv_row<C>(
+    writer: &mut BufWriter<File>,
+    turn: usize,
+    games: &HashMap<PlayerUniverse, C>,
+    outcome: &GameOutcome<C>,
+) -> Result<(), io::Error>
+where
+    C: Clone + Default + AddAssign + Display,
+{
+    let live = games.values().fold(C::default(), |mut total, count| {
+        total += count.clone();
+        total
+    });
+    writeln!(
+        writer,
+        "{},{},{},{}",
+        turn, live, outcome.player1_wins, outcome.player2_wins
+    )
+}
+
+fn describe(players: &(Player, Player)) -> String {
+    let (player1, player2) = players;
+    let mut description = format!(
+        "Players start at positions {} and {}",
+        player1.starting_position, player2.starting_position
+    );
+
+    if [player1, player2]
+        .iter()
+        .any(|player| player.starting_position < 1 || player.starting_position > 10)
+    {
+        description.push_str("\nAnomaly: starting positions should be between 1 and 10");
+    }
+
+    description
+}
+
+fn parse_data(input: &String) -> IResult<&str, (Player, Player)> {
+    tuple((
+        terminated(parse_player, newline),
+        terminated(parse_player, newline),
+    ))(input)
+}
+
+fn parse_player(input: &str) -> IResult<&str, Player> {
+    map(
+        tuple((
+            preceded(tag("Player "), parse_usize),
+            preceded(tag(" starting position: "), parse_usize),
+        )),
+        |(_, starting_position)| Player {
+            starting_position: starting_position,
+        },
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{run_problem_with_input, scratch_path, take_used_seed};
+
+    #[test]
+    fn part1_sample() {
+        assert_eq!(
+            run_problem_with_input(&DIRAC_DICE, SAMPLE, &["part1"]).unwrap(),
+            739785usize.into()
+        );
+    }
+
+    #[test]
+    fn part2_sample() {
+        assert_eq!(
+            run_problem_with_input(&DIRAC_DICE, SAMPLE, &["part2"]).unwrap(),
+            444356092776315usize.into()
+        );
+    }
+
+    #[test]
+    fn all_wins_sample() {
+        assert_eq!(
+            run_problem_with_input(
+                &DIRAC_DICE,
+                SAMPLE,
+                &["-f", "sample.txt", "-g", "dirac", "-o", "all-wins"]
+            )
+            .unwrap(),
+            vec![
+                ("Player 1 wins".to_string(), 444356092776315usize.into()),
+                ("Player 2 wins".to_string(), 341960390180808usize.into()),
+                ("Total universes".to_string(), 786316482957123usize.into()),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn probabilities_sample() {
+        assert_eq!(
+            run_problem_with_input(
+                &DIRAC_DICE,
+                SAMPLE,
+                &["-f", "sample.txt", "-g", "dirac", "-o", "probabilities"]
+            )
+            .unwrap(),
+            vec![
+                (
+                    "Player 1 win probability".to_string(),
+                    "148118697592105/262105494319041".to_string().into()
+                ),
+                (
+                    "Player 2 win probability".to_string(),
+                    "113986796726936/262105494319041".to_string().into()
+                ),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn csv_sample() {
+        let path = scratch_path("dirac_dice_csv_sample.csv");
+        let path = path.to_str().unwrap();
+
+        run_problem_with_input(
+            &DIRAC_DICE,
+            SAMPLE,
+            &["-f", "sample.txt", "-g", "dirac", "-c", path],
+        )
+        .unwrap();
+        let csv = std::fs::read_to_string(path).unwrap();
+        let rows: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(
+            rows[..4],
+            [
+                "turn,live_universes,player1_wins,player2_wins",
+                "0,1,0,0",
+                "1,27,0,0",
+                "2,729,0,0"
+            ]
+        );
+        assert!(rows
+            .last()
+            .unwrap()
+            .ends_with(",0,444356092776315,341960390180808"));
+    }
+
+    #[test]
+    fn unwritable_csv_is_an_error() {
+        let path = scratch_path("dirac_dice_missing").join("games.csv");
+        let path = path.to_str().unwrap();
+        let error = run_problem_with_input(
+            &DIRAC_DICE,
+            SAMPLE,
+            &["-f", "sample.txt", "-g", "dirac", "-c", path],
+        )
+        .unwrap_err();
+
+        assert!(matches!(error, AocError::Io(_)));
+        assert!(error
+            .to_string()
+            .contains(&format!("{} can't be written", path)));
+    }
+
+    #[test]
+    fn csv_rejects_random_games() {
+        assert!(run_problem_with_input(
+            &DIRAC_DICE,
+            SAMPLE,
+            &["-f", "sample.txt", "-g", "random", "-c", "unused.csv"]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn high_winning_score_exceeds_64_bits() {
+        let arguments = ["-f", "sample.txt", "-g", "dirac", "-w", "30"];
+
+        assert_eq!(
+            run_problem_with_input(&DIRAC_DICE, SAMPLE, &arguments).unwrap(),
+            BigUint::from(455119916668356680878u128).into()
+        );
+    }
+
+    #[test]
+    fn random_sample_is_seeded() {
+        let arguments = ["-f", "sample.txt", "-g", "random", "-s", "7", "-t", "200"];
+
+        assert_eq!(
+            run_problem_with_input(&DIRAC_DICE, SAMPLE, &arguments).unwrap(),
+            run_problem_with_input(&DIRAC_DICE, SAMPLE, &arguments).unwrap()
+        );
+        assert_eq!(take_used_seed(), Some(7u64));
+        assert_eq!(take_used_seed(), None);
+    }
+}
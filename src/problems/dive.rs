@@ -0,0 +1,591 @@
+use crate::{
+    default_sub_command, log, parse_usize, selected_part, AocError, CommandResult, Direction,
+    Example, LogLevel, Problem,
+};
+use clap::{value_t_or_exit, App, Arg, ArgMatches};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{newline, not_line_ending},
+    combinator::{all_consuming, map, value, verify},
+    multi::separated_list0,
+    sequence::{preceded, separated_pair},
+    IResult,
+};
+use strum::VariantNames;
+use strum_macros::{EnumString, EnumVariantNames};
+
+pub const DIVE: Problem<DiveArgs, DiveInput> = Problem::new(
+    sub_command,
+    "dive",
+    "day2_dive",
+    parse_arguments,
+    parse_commands,
+    run,
+)
+.with_long_about(LONG_ABOUT)
+.with_examples(EXAMPLES)
+.with_describe(describe)
+.with_input_check(input_check);
+
+const LONG_ABOUT: &str =
+    "The input is a list of commands. forward X moves X horizontally, down X and up X change \
+    the depth.\n\n\
+    Part 1 applies the commands directly and multiplies the final horizontal position by the \
+    final depth. With -a the commands steer instead: down and up change the aim, and forward X \
+    moves X horizontally and aim times X deeper.\n\n\
+    --mode waypoint steers a waypoint instead, which starts 1 forward of the submarine. down X \
+    and up X move the waypoint, left X and right X turn it X degrees around the submarine, and \
+    forward X moves the submarine X times the way to the waypoint. Without turns this is the \
+    same as aim. Turns are only accepted in waypoint mode and must be a multiple of 90 \
+    degrees.\n\n\
+    A course that takes the submarine, or its aim, above the surface stops the run with the \
+    line that does it.\n\n\
+    A line that isn't a valid command stops the run with its line number. -s skips those lines \
+    instead and uses the rest. -v lists the lines it skipped. Blank lines and anything after a # \
+    are ignored, so course files can be annotated.\n\n\
+    With -c the answer is instead the total distance commanded in each direction.\n\n\
+    Example: forward 5, down 5, forward 8, up 3, down 8, forward 2 gives 150, or 900 with aim.";
+
+const SAMPLE: &str = include_str!("../../day2_dive/sample.txt");
+
+const EXAMPLES: &[Example] = &[
+    Example::new(SAMPLE, 1, "150"),
+    Example::new(SAMPLE, 2, "900"),
+];
+
+#[derive(Debug)]
+pub struct DiveArgs {
+    mode: NavigationMode,
+    skip_invalid: bool,
+    course_summary: bool,
+}
+
+#[derive(Debug, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "kebab_case")]
+enum NavigationMode {
+    Direct,
+    Aim,
+    Waypoint,
+}
+
+// Seen from the side, so forward is east and the depth grows to the south.
+#[derive(Debug, Clone)]
+pub enum SubmarineCommand {
+    Move {
+        direction: Direction,
+        magnitude: usize,
+    },
+    // Quarter turns clockwise, so right 90 is 1 and left 90 is 3.
+    Turn(usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct DiveInput {
+    commands: Vec<SubmarineCommand>,
+    // The line each command is on, for errors about a command.
+    lines: Vec<usize>,
+    invalid_lines: Vec<(usize, String)>,
+}
+
+fn sub_command() -> App<'static, 'static> {
+    default_sub_command(
+        &DIVE,
+        "Finds the final position of the sub starting at 0,0 then returns the multiple of the tuple.",
+        "Path to the input file. Each line should contain a direction followed by a number.",
+        "Finds the postion for the default input without aim.",
+        "Finds the postion for the default input with aim.",
+    )
+    .arg(
+        Arg::with_name("aim")
+        .short("a")
+        .help("If passed, takes submarine aim into account when determining position.")
+        .conflicts_with("mode")
+    )
+    .arg(
+        Arg::with_name("mode")
+            .short("m")
+            .long("mode")
+            .help(
+                "How the commands steer the submarine. The modes available are as follows:\n\n\
+            direct: Down and up change the depth.\n\n\
+            aim: Down and up change the aim, which forward follows.\n\n\
+            waypoint: Down and up move a waypoint, left and right turn it, and forward heads towards it.\n\n",
+            )
+            .takes_value(true)
+            .possible_values(NavigationMode::VARIANTS),
+    )
+    .arg(
+        Arg::with_name("skip-invalid")
+            .short("s")
+            .long("skip-invalid")
+            .help("If passed, skips lines that aren't valid commands instead of failing. -v lists them."),
+    )
+    .arg(
+        Arg::with_name("course-summary")
+            .short("c")
+            .long("course-summary")
+            .help("If passed, gives the total distance commanded in each direction instead of the position."),
+    )
+}
+
+fn parse_arguments(arguments: &ArgMatches) -> DiveArgs {
+    match selected_part(arguments) {
+        Some("part1") => DiveArgs {
+            mode: NavigationMode::Direct,
+            skip_invalid: arguments.is_present("skip-invalid"),
+            course_summary: arguments.is_present("course-summary"),
+        },
+        Some("part2") => DiveArgs {
+            mode: NavigationMode::Aim,
+            skip_invalid: arguments.is_present("skip-invalid"),
+            course_summary: arguments.is_present("course-summary"),
+        },
+        _ => DiveArgs {
+            mode: mode_argument(arguments),
+            skip_invalid: arguments.is_present("skip-invalid"),
+            course_summary: arguments.is_present("course-summary"),
+        },
+    }
+}
+
+fn mode_argument(arguments: &ArgMatches) -> NavigationMode {
+    if arguments.is_present("mode") {
+        value_t_or_exit!(arguments.value_of("mode"), NavigationMode)
+    } else if arguments.is_present("aim") {
+        NavigationMode::Aim
+    } else {
+        NavigationMode::Direct
+    }
+}
+
+fn input_check(arguments: &DiveArgs, input: &DiveInput) -> Result<(), AocError> {
+    match input.invalid_lines.first() {
+        Some((line_number, line)) if !arguments.skip_invalid => Err(AocError::validation(
+            format!("Line {} is not a valid command: {}", line_number, line),
+        ))?,
+        _ => (),
+    }
+
+    let has_turns = input
+        .commands
+        .iter()
+        .any(|command| matches!(command, SubmarineCommand::Turn(_)));
+    if has_turns && !matches!(arguments.mode, NavigationMode::Waypoint) && !arguments.course_summary
+    {
+        Err(AocError::validation(
+            "Left and right only turn the waypoint, pass --mode waypoint",
+        ))?;
+    }
+
+    if !arguments.course_summary {
+        let surfaced = match arguments.mode {
+            NavigationMode::Waypoint => follow_waypoint(&input.commands).err(),
+            _ => determine_position(&input.commands, &arguments.mode).err(),
+        };
+        if let Some(index) = surfaced {
+            Err(AocError::validation(format!(
+                "Line {} takes the submarine above the surface",
+                input.lines[index]
+            )))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn run(arguments: DiveArgs, input: DiveInput) -> CommandResult {
+    input.invalid_lines.iter().for_each(|(line_number, line)| {
+        log!(LogLevel::Debug, "Skipped line {}: {}", line_number, line)
+    });
+
+    if arguments.course_summary {
+        return course_summary(&input.commands);
+    }
+
+    if let NavigationMode::Waypoint = arguments.mode {
+        let (horizontal, depth) = follow_waypoint(&input.commands)
+            .expect("Courses above the surface are rejected by the input check");
+        return (horizontal * depth).into();
+    }
+
+    let (horizontal, depth) = determine_position(&input.commands, &arguments.mode)
+        .expect("Courses above the surface are rejected by the input check");
+    (horizontal * depth).into()
+}
+
+fn course_summary(commands: &[SubmarineCommand]) -> CommandResult {
+    let (forward, down, up) = commands.iter().fold(
+        (0usize, 0usize, 0usize),
+        |(forward, down, up), command| match command {
+            SubmarineCommand::Move {
+                direction: Direction::East,
+                magnitude,
+            } => (forward + magnitude, down, up),
+            SubmarineCommand::Move {
+                direction: Direction::South,
+                magnitude,
+            } => (forward, down + magnitude, up),
+            SubmarineCommand::Move {
+                direction: Direction::North,
+                magnitude,
+            } => (forward, down, up + magnitude),
+            _ => (forward, down, up),
+        },
+    );
+
+    vec![
+        ("Forward".to_string(), forward.into()),
+        ("Down".to_string(), down.into()),
+        ("Up".to_string(), up.into()),
+    ]
+    .into()
+}
+
+fn describe(input: &DiveInput) -> String {
+    let commands = &input.commands;
+    let (forward, down, up, turns) = commands.iter().fold(
+        (0usize, 0usize, 0usize, 0usize),
+        |(forward, down, up, turns), command| match command {
+            SubmarineCommand::Move {
+                direction: Direction::East,
+                ..
+            } => (forward + 1, down, up, turns),
+            SubmarineCommand::Move {
+                direction: Direction::South,
+                ..
+            } => (forward, down + 1, up, turns),
+            SubmarineCommand::Move {
+                direction: Direction::North,
+                ..
+            } => (forward, down, up + 1, turns),
+            SubmarineCommand::Turn(_) => (forward, down, up, turns + 1),
+            _ => (forward, down, up, turns),
+        },
+    );
+
+    let mut description = format!(
+        "{} commands: {} forward, {} down, {} up",
+        commands.len(),
+        forward,
+        down,
+        up
+    );
+    if turns > 0 {
+        description.push_str(&format!(", {} turns", turns));
+    }
+
+    input.invalid_lines.iter().for_each(|(line_number, line)| {
+        description.push_str(&format!(
+            "\nAnomaly: line {} is not a valid command: {}",
+            line_number, line
+        ))
+    });
+
+    description
+}
+
+// Every line is read so that invalid commands can be reported by line number. Comments starting
+// with # are dropped and the blank lines left behind are ignored.
+fn parse_commands(input: &String) -> IResult<&str, DiveInput> {
+    map(
+        separated_list0(newline, not_line_ending),
+        |lines: Vec<&str>| {
+            lines
+                .into_iter()
+                .enumerate()
+                .map(|(index, line)| (index, strip_comment(line)))
+                .filter(|(_, line)| !line.is_empty())
+                .fold(
+                    DiveInput {
+                        commands: Vec::new(),
+                        lines: Vec::new(),
+                        invalid_lines: Vec::new(),
+                    },
+                    |mut input, (index, line)| {
+                        match all_consuming(parse_command)(line) {
+                            Ok((_, command)) => {
+                                input.commands.push(command);
+                                input.lines.push(index + 1);
+                            }
+                            Err(_) => input.invalid_lines.push((index + 1, line.to_string())),
+                        }
+                        input
+                    },
+                )
+        },
+    )(input)
+}
+
+fn strip_comment(line: &str) -> &str {
+    line.split('#').next().unwrap_or_default().trim()
+}
+
+fn parse_command(input: &str) -> IResult<&str, SubmarineCommand> {
+    alt((
+        map(
+            separated_pair(parse_heading, tag(" "), parse_usize),
+            |(direction, magnitude)| SubmarineCommand::Move {
+                direction,
+                magnitude,
+            },
+        ),
+        map(
+            alt((
+                preceded(tag("right "), parse_quarters),
+                map(preceded(tag("left "), parse_quarters), |quarters| {
+                    (4 - quarters) % 4
+                }),
+            )),
+            SubmarineCommand::Turn,
+        ),
+    ))(input)
+}
+
+fn parse_quarters(input: &str) -> IResult<&str, usize> {
+    map(
+        verify(parse_usize, |degrees| degrees % 90 == 0),
+        |degrees| degrees / 90 % 4,
+    )(input)
+}
+
+fn parse_heading(input: &str) -> IResult<&str, Direction> {
+    alt((
+        value(Direction::East, tag("forward")),
+        value(Direction::South, tag("down")),
+        value(Direction::North, tag("up")),
+    ))(input)
+}
+
+// The index of the first command that takes the submarine, or its aim, above the surface is given
+// instead when there is one.
+fn determine_position(
+    commands: &[SubmarineCommand],
+    mode: &NavigationMode,
+) -> Result<(usize, usize), usize> {
+    let position_func = if let NavigationMode::Aim = mode {
+        update_position_with_aim
+    } else {
+        update_position_no_aim
+    };
+    let (horizontal, depth, _) = commands
+        .iter()
+        .enumerate()
+        .try_fold((0, 0, 0), |position, (index, command)| {
+            position_func(position, command).ok_or(index)
+        })?;
+    Ok((horizontal, depth))
+}
+
+fn update_position_no_aim(
+    (horizontal, depth, aim): (usize, usize, usize),
+    command: &SubmarineCommand,
+) -> Option<(usize, usize, usize)> {
+    match command {
+        SubmarineCommand::Move {
+            direction: Direction::East,
+            magnitude,
+        } => Some((horizontal + magnitude, depth, aim)),
+        SubmarineCommand::Move {
+            direction: Direction::South,
+            magnitude,
+        } => Some((horizontal, depth + magnitude, aim)),
+        SubmarineCommand::Move {
+            direction: Direction::North,
+            magnitude,
+        } => Some((horizontal, depth.checked_sub(*magnitude)?, aim)),
+        _ => Some((horizontal, depth, aim)),
+    }
+}
+
+// Down and up only turn the aim. Moving forward takes the submarine aim deeper per space.
+fn update_position_with_aim(
+    (horizontal, depth, aim): (usize, usize, usize),
+    command: &SubmarineCommand,
+) -> Option<(usize, usize, usize)> {
+    match command {
+        SubmarineCommand::Move {
+            direction: Direction::East,
+            magnitude,
+        } => Some((horizontal + magnitude, depth + aim * magnitude, aim)),
+        SubmarineCommand::Move {
+            direction: Direction::South,
+            magnitude,
+        } => Some((horizontal, depth, aim + magnitude)),
+        SubmarineCommand::Move {
+            direction: Direction::North,
+            magnitude,
+        } => Some((horizontal, depth, aim.checked_sub(*magnitude)?)),
+        _ => Some((horizontal, depth, aim)),
+    }
+}
+
+// The waypoint is kept relative to the submarine, so it comes along on every move forward. The
+// submarine can end up behind where it started, so the position is signed. The index of the first
+// command that takes it above the surface is given instead when there is one.
+fn follow_waypoint(commands: &[SubmarineCommand]) -> Result<(isize, isize), usize> {
+    let (horizontal, depth, _) = commands.iter().enumerate().try_fold(
+        (0isize, 0isize, (1isize, 0isize)),
+        |(horizontal, depth, waypoint), (index, command)| match command {
+            SubmarineCommand::Move {
+                direction: Direction::East,
+                magnitude,
+            } => {
+                let magnitude = *magnitude as isize;
+                let depth = depth + waypoint.1 * magnitude;
+                if depth < 0 {
+                    return Err(index);
+                }
+                Ok((horizontal + waypoint.0 * magnitude, depth, waypoint))
+            }
+            SubmarineCommand::Move {
+                direction,
+                magnitude,
+            } => {
+                let (dx, dy) = direction.to_vector();
+                let magnitude = *magnitude as isize;
+                Ok((
+                    horizontal,
+                    depth,
+                    (waypoint.0 + dx * magnitude, waypoint.1 + dy * magnitude),
+                ))
+            }
+            SubmarineCommand::Turn(quarters) => Ok((
+                horizontal,
+                depth,
+                (0..*quarters).fold(waypoint, |(x, y), _| (-y, x)),
+            )),
+        },
+    )?;
+    Ok((horizontal, depth))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_problem_with_input;
+
+    #[test]
+    fn part1_sample() {
+        assert_eq!(
+            run_problem_with_input(&DIVE, SAMPLE, &["part1"]).unwrap(),
+            150usize.into()
+        );
+    }
+
+    #[test]
+    fn part2_sample() {
+        assert_eq!(
+            run_problem_with_input(&DIVE, SAMPLE, &["part2"]).unwrap(),
+            900usize.into()
+        );
+    }
+    #[test]
+    fn strict_reports_invalid_line() {
+        let input = SAMPLE.replacen("up 3", "sideways 3", 1);
+        let error = run_problem_with_input(&DIVE, &input, &["part1"]).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Line 4 is not a valid command: sideways 3"
+        );
+    }
+
+    #[test]
+    fn skip_invalid_sample() {
+        let input = SAMPLE.replacen("up 3", "sideways 3", 1);
+
+        assert_eq!(
+            run_problem_with_input(&DIVE, &input, &["--part", "1", "--skip-invalid"]).unwrap(),
+            195usize.into()
+        );
+    }
+
+    #[test]
+    fn comments_and_blank_lines_are_ignored() {
+        let input = format!(
+            "# practice course\n\n{}\n\n",
+            SAMPLE.replacen("up 3", "up 3 # back up before the trench", 1)
+        );
+
+        assert_eq!(
+            run_problem_with_input(&DIVE, &input, &["part2"]).unwrap(),
+            900usize.into()
+        );
+    }
+
+    #[test]
+    fn waypoint_sample() {
+        assert_eq!(
+            run_problem_with_input(&DIVE, SAMPLE, &["-f", "sample.txt", "-m", "waypoint"]).unwrap(),
+            900isize.into()
+        );
+
+        // Down 4 then right 90 points the waypoint 4 back and 1 down. Up 2 and left 180 turn it
+        // to 4 forward and 1 down. The submarine ends 3 * -4 + 2 * 4 = -4 forward and 5 down.
+        let input = "down 4\nright 90\nforward 3\nup 2\nleft 180\nforward 2\nright 270";
+        assert_eq!(
+            run_problem_with_input(&DIVE, input, &["-f", "sample.txt", "--mode", "waypoint"])
+                .unwrap(),
+            (-20isize).into()
+        );
+    }
+
+    #[test]
+    fn waypoint_above_the_surface_is_an_error() {
+        let input = "forward 2\nup 3\n\nforward 1\nforward 1";
+        let error = run_problem_with_input(&DIVE, input, &["-f", "sample.txt", "-m", "waypoint"])
+            .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Line 4 takes the submarine above the surface"
+        );
+    }
+
+    #[test]
+    fn surfacing_is_an_error() {
+        let input = SAMPLE.replacen("up 3", "up 30", 1);
+
+        assert_eq!(
+            run_problem_with_input(&DIVE, &input, &["part1"])
+                .unwrap_err()
+                .to_string(),
+            "Line 4 takes the submarine above the surface"
+        );
+        assert_eq!(
+            run_problem_with_input(&DIVE, &input, &["part2"])
+                .unwrap_err()
+                .to_string(),
+            "Line 4 takes the submarine above the surface"
+        );
+    }
+
+    #[test]
+    fn turns_need_waypoint_mode() {
+        let input = format!("{}\nleft 90", SAMPLE.trim_end());
+        let error = run_problem_with_input(&DIVE, &input, &["part2"]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Left and right only turn the waypoint, pass --mode waypoint"
+        );
+
+        let input = format!("{}\nleft 45", SAMPLE.trim_end());
+        let error = run_problem_with_input(&DIVE, &input, &["-f", "sample.txt", "-m", "waypoint"])
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Line 7 is not a valid command: left 45");
+    }
+
+    #[test]
+    fn course_summary_sample() {
+        assert_eq!(
+            run_problem_with_input(&DIVE, SAMPLE, &["--part", "1", "-c"]).unwrap(),
+            vec![
+                ("Forward".to_string(), 15usize.into()),
+                ("Down".to_string(), 13usize.into()),
+                ("Up".to_string(), 3usize.into()),
+            ]
+            .into()
+        );
+    }
+}
@@ -0,0 +1,351 @@
+use crate::{
+    check_grid, count_result, default_sub_command, describe_grid, parse_digit_grid, selected_part,
+    AocError, BitGrid, CommandResult, Example, Grid, Problem,
+};
+use clap::{value_t_or_exit, App, Arg, ArgMatches};
+use nom::IResult;
+use num_bigint::BigUint;
+use std::collections::HashMap;
+use strum::VariantNames;
+use strum_macros::{EnumString, EnumVariantNames};
+
+pub const DUMBO_OCTOPUS: Problem<DumboOctopusArgs, Grid<usize>> = Problem::new(
+    sub_command,
+    "dumbo-octopus",
+    "day11_dumbo_octopus",
+    parse_arguments,
+    parse_data,
+    run,
+)
+.with_long_about(LONG_ABOUT)
+.with_examples(EXAMPLES)
+.with_describe(describe)
+.with_input_check(input_check);
+
+const LONG_ABOUT: &str = "The input is a 10 by 10 grid of octopus energy levels.\n\n\
+    Each step every energy level increases by 1. An octopus above 9 flashes and raises its \
+    eight neighbours by 1, which can cause more flashes. Each octopus flashes at most once per \
+    step and resets to 0 afterwards. one-hundred-steps counts the flashes over 100 steps. \
+    synchronized-flashes finds the first step where every octopus flashes at once.\n\n\
+    flashes-after-steps counts the flashes over the number of steps given with -n. The grid \
+    only has so many states, so the simulation stops as soon as one repeats and counts the rest \
+    of the steps from the cycle between the two, which handles step counts far too large to \
+    simulate.\n\n\
+    Example: the sample has 1656 flashes in 100 steps and synchronizes on step 195.";
+
+const SAMPLE: &str = include_str!("../../day11_dumbo_octopus/sample.txt");
+
+const EXAMPLES: &[Example] = &[
+    Example::new(SAMPLE, 1, "1656"),
+    Example::new(SAMPLE, 2, "195"),
+];
+
+#[derive(Debug)]
+pub struct DumboOctopusArgs {
+    simulation_parameters: SimulationParameters,
+    steps: Option<BigUint>,
+}
+
+#[derive(Debug, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "kebab_case")]
+enum SimulationParameters {
+    OneHundredSteps,
+    SynchronizedFlashes,
+    FlashesAfterSteps,
+}
+
+fn sub_command() -> App<'static, 'static> {
+    default_sub_command(
+        &DUMBO_OCTOPUS,
+        "Simulates dumbo octopi flashing behavoir.",
+        "Path to the input file. Input should be newline delimited sets of 10 integers from 0-9.",
+        "Counts the number of flashes after 100 steps.",
+        "Counts the number of iterartions before all octopi flash.",
+    ).arg(
+        Arg::with_name("simulation-parameters")
+            .short("s")
+            .help(
+                "How long the octopi should be simulated for. The functions available are as follows:\n\n\
+            one-hundred-steps: Counts the number of flashes that happen after 100 steps.\n\n\
+            synchronized-flashes: Counts the number of steps needed before all octopi flash at once.\n\n\
+            flashes-after-steps: Counts the number of flashes that happen after the number of steps given with -n.\n\n",
+            )
+            .takes_value(true)
+            .possible_values(&SimulationParameters::VARIANTS)
+            .required_unless_one(&["part", "explain"]),
+    )
+    .arg(
+        Arg::with_name("steps")
+            .short("n")
+            .long("steps")
+            .help("The number of steps for flashes-after-steps. Any size is accepted, the count repeats once the grid does.")
+            .takes_value(true),
+    )
+}
+
+fn parse_arguments(arguments: &ArgMatches) -> DumboOctopusArgs {
+    match selected_part(arguments) {
+        Some("part1") => DumboOctopusArgs {
+            simulation_parameters: SimulationParameters::OneHundredSteps,
+            steps: steps_argument(arguments),
+        },
+        Some("part2") => DumboOctopusArgs {
+            simulation_parameters: SimulationParameters::SynchronizedFlashes,
+            steps: steps_argument(arguments),
+        },
+        _ => DumboOctopusArgs {
+            simulation_parameters: value_t_or_exit!(
+                arguments.value_of("simulation-parameters"),
+                SimulationParameters
+            ),
+            steps: steps_argument(arguments),
+        },
+    }
+}
+
+fn steps_argument(arguments: &ArgMatches) -> Option<BigUint> {
+    if arguments.is_present("steps") {
+        Some(value_t_or_exit!(arguments.value_of("steps"), BigUint))
+    } else {
+        None
+    }
+}
+
+fn input_check(arguments: &DumboOctopusArgs, octopi: &Grid<usize>) -> Result<(), AocError> {
+    match (&arguments.simulation_parameters, &arguments.steps) {
+        (SimulationParameters::FlashesAfterSteps, None) => Err(AocError::validation(
+            "flashes-after-steps needs the number of steps, pass it with --steps",
+        ))?,
+        (SimulationParameters::FlashesAfterSteps, Some(_)) | (_, None) => (),
+        (_, Some(_)) => Err(AocError::validation(
+            "--steps only applies to flashes-after-steps",
+        ))?,
+    }
+
+    check_grid(octopi)
+}
+
+fn run(arguments: DumboOctopusArgs, octopi: Vec<Vec<usize>>) -> CommandResult {
+    match arguments.simulation_parameters {
+        SimulationParameters::OneHundredSteps => count_flashes_after_100_steps(octopi).into(),
+        SimulationParameters::SynchronizedFlashes => first_iteration_where_all_flash(octopi).into(),
+        SimulationParameters::FlashesAfterSteps => {
+            count_result(count_flashes_after_steps(octopi, &arguments.steps.unwrap()))
+        }
+    }
+}
+
+fn first_iteration_where_all_flash(mut octopi: Vec<Vec<usize>>) -> usize {
+    let mut i = 0usize;
+    loop {
+        i += 1;
+        let (new_octopi, _) = run_step(&octopi);
+        octopi = new_octopi;
+        if octopi
+            .iter()
+            .all(|row| row.iter().all(|octopus| *octopus == 0usize))
+        {
+            break;
+        }
+    }
+    i.into()
+}
+
+fn count_flashes_after_100_steps(mut octopi: Vec<Vec<usize>>) -> usize {
+    let mut flashes = 0usize;
+    for _ in 0..100 {
+        let (new_octopi, new_flashes) = run_step(&octopi);
+        octopi = new_octopi;
+        flashes += new_flashes;
+    }
+    flashes
+}
+
+// Simulates until a state repeats, keeping the running flash total after each step. Past that
+// point the steps go round the cycle between the two sightings of the repeated state.
+fn count_flashes_after_steps(mut octopi: Vec<Vec<usize>>, steps: &BigUint) -> BigUint {
+    let mut seen = HashMap::new();
+    let mut totals = vec![0usize];
+
+    let cycle_start = loop {
+        let step = totals.len() - 1;
+        if BigUint::from(step) == *steps {
+            return totals[step].into();
+        }
+
+        if let Some(cycle_start) = seen.insert(octopi.clone(), step) {
+            break cycle_start;
+        }
+
+        let (new_octopi, flashes) = run_step(&octopi);
+        octopi = new_octopi;
+        totals.push(totals[step] + flashes);
+    };
+
+    let cycle_end = totals.len() - 1;
+    let cycle_length = cycle_end - cycle_start;
+    let cycle_flashes = totals[cycle_end] - totals[cycle_start];
+    let remaining = steps - cycle_start;
+    let cycles = &remaining / cycle_length;
+    let leftover: usize = (&remaining % cycle_length).try_into().unwrap();
+
+    BigUint::from(totals[cycle_start + leftover]) + cycles * cycle_flashes
+}
+
+fn run_step(octopi: &Vec<Vec<usize>>) -> (Vec<Vec<usize>>, usize) {
+    let mut new_octopi: Vec<Vec<usize>> = octopi
+        .iter()
+        .map(|row| row.iter().map(|value| value + 1).collect())
+        .collect();
+
+    let mut flashed_octopi = BitGrid::new(10usize, 10usize);
+    let mut has_flashes = true;
+
+    while has_flashes {
+        has_flashes = false;
+        for i in 0..10usize {
+            for j in 0..10usize {
+                if flashed_octopi.contains(i, j) {
+                    continue;
+                }
+                let octopus = new_octopi.get(i).unwrap().get(j).unwrap();
+                if *octopus > 9usize {
+                    has_flashes = true;
+                    flashed_octopi.insert(i, j);
+                    get_adjacent_octopi((&i, &j)).iter().for_each(|(x, y)| {
+                        *new_octopi.get_mut(*x).unwrap().get_mut(*y).unwrap() += 1
+                    });
+                }
+            }
+        }
+    }
+
+    (
+        new_octopi
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|octopus| if *octopus > 9usize { 0usize } else { *octopus })
+                    .collect()
+            })
+            .collect(),
+        flashed_octopi.len(),
+    )
+}
+
+fn get_adjacent_octopi(point: (&usize, &usize)) -> Vec<(usize, usize)> {
+    let (x, y) = point;
+    let mut adjacents = Vec::new();
+    if *y > 0usize {
+        adjacents.push((*x, y - 1));
+    }
+
+    if *y < 9usize {
+        adjacents.push((*x, y + 1));
+    }
+
+    if *x > 0usize {
+        adjacents.push((x - 1, *y));
+    }
+
+    if *x < 9usize {
+        adjacents.push((x + 1, *y));
+    }
+
+    if *x < 9usize && *y < 9usize {
+        adjacents.push((x + 1, y + 1));
+    }
+
+    if *x < 9usize && *y > 0usize {
+        adjacents.push((x + 1, y - 1));
+    }
+
+    if *x > 0usize && *y > 0usize {
+        adjacents.push((x - 1, y - 1));
+    }
+
+    if *x > 0usize && *y < 9usize {
+        adjacents.push((x - 1, y + 1));
+    }
+
+    adjacents
+}
+
+fn describe(octopi: &Vec<Vec<usize>>) -> String {
+    let mut description = describe_grid(octopi);
+
+    if octopi.len() != 10 || octopi.iter().any(|row| row.len() != 10) {
+        description.push_str("\nAnomaly: the simulation expects a 10 by 10 grid");
+    }
+
+    description
+}
+
+fn parse_data(input: &String) -> IResult<&str, Grid<usize>> {
+    parse_digit_grid(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_problem_with_input;
+
+    #[test]
+    fn part1_sample() {
+        assert_eq!(
+            run_problem_with_input(&DUMBO_OCTOPUS, SAMPLE, &["part1"]).unwrap(),
+            1656usize.into()
+        );
+    }
+
+    #[test]
+    fn part2_sample() {
+        assert_eq!(
+            run_problem_with_input(&DUMBO_OCTOPUS, SAMPLE, &["part2"]).unwrap(),
+            195usize.into()
+        );
+    }
+
+    #[test]
+    fn flashes_after_steps_sample() {
+        let flashes_after = |steps: &str| {
+            run_problem_with_input(
+                &DUMBO_OCTOPUS,
+                SAMPLE,
+                &["-f", "sample.txt", "-s", "flashes-after-steps", "-n", steps],
+            )
+            .unwrap()
+        };
+        let simulated = |steps: usize| {
+            let mut octopi = parse_data(&SAMPLE.to_string()).unwrap().1;
+            let mut flashes = 0usize;
+            for _ in 0..steps {
+                let (new_octopi, new_flashes) = run_step(&octopi);
+                octopi = new_octopi;
+                flashes += new_flashes;
+            }
+            flashes
+        };
+
+        assert_eq!(flashes_after("100"), 1656usize.into());
+        assert_eq!(flashes_after("1234"), simulated(1234).into());
+
+        // The sample synchronizes on step 195, after which all 100 flash every 10th step.
+        let huge = BigUint::from(10u8).pow(30) + 5u8;
+        let expected = BigUint::from(simulated(195)) + (&huge - 195u8) * 10u8;
+        assert_eq!(flashes_after(&huge.to_string()), expected.into());
+    }
+
+    #[test]
+    fn steps_must_match_the_mode() {
+        assert!(run_problem_with_input(
+            &DUMBO_OCTOPUS,
+            SAMPLE,
+            &["-f", "sample.txt", "-s", "flashes-after-steps"]
+        )
+        .is_err());
+        assert!(
+            run_problem_with_input(&DUMBO_OCTOPUS, SAMPLE, &["--part", "1", "-n", "10"]).is_err()
+        );
+    }
+}
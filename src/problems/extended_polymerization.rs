@@ -0,0 +1,487 @@
+use crate::{default_sub_command, selected_part, AocError, CommandResult, Example, Problem};
+use clap::{value_t_or_exit, App, Arg, ArgMatches};
+use nom::{
+    bytes::complete::tag,
+    character::complete::{newline, satisfy},
+    combinator::map_res,
+    multi::{many1, separated_list1},
+    sequence::{pair, separated_pair},
+    IResult,
+};
+use std::{
+    collections::{BTreeSet, HashMap},
+    iter,
+};
+
+pub const EXTENDED_POLYMERIZATION: Problem<ExtendedPolymerizationArgs, Polymer> = Problem::new(
+    sub_command,
+    "extended-polymerization",
+    "day14_extended_polymerization",
+    parse_arguments,
+    parse_data,
+    run,
+)
+.with_long_about(LONG_ABOUT)
+.with_examples(EXAMPLES)
+.with_describe(describe)
+.with_input_check(input_check);
+
+const LONG_ABOUT: &str =
+    "The input is a polymer template followed by pair insertion rules such as CH -> B.\n\n\
+    Each step inserts the rule's element between every matching pair of adjacent elements, all \
+    at the same time. After -p steps, 10 for part 1 and 40 for part 2, the answer is the count \
+    of the most common element minus the count of the least common element.\n\n\
+    Example: NNCB with the sample rules gives 1588 after 10 steps.\n\n\
+    With -l N the first N steps are also carried out on the polymer itself, listing the whole \
+    string after each one ahead of the answer. The polymer roughly doubles every step, so N is \
+    limited to 20.\n\n\
+    Every pair the template can produce, following the rules from its own pairs, needs a rule \
+    or the input is rejected with the pairs that have none. -i instead leaves those pairs as \
+    they are, inserting nothing between them.";
+
+// Past this many steps the literal polymer is millions of elements long.
+const MAX_LITERAL_STEPS: usize = 20;
+
+const SAMPLE: &str = include_str!("../../day14_extended_polymerization/sample.txt");
+
+const EXAMPLES: &[Example] = &[
+    Example::new(SAMPLE, 1, "1588"),
+    Example::new(SAMPLE, 2, "2188189693529"),
+];
+
+#[derive(Debug)]
+pub struct ExtendedPolymerizationArgs {
+    polymerization_count: usize,
+    expand_literal: Option<usize>,
+    identity_missing: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Polymer {
+    elements: Vec<char>,
+    template: Vec<u8>,
+    insertion_rules: Vec<Option<(usize, usize)>>,
+}
+
+type PolyPair = (u8, u8);
+
+type InsertionRule = ((char, char), char);
+
+fn sub_command() -> App<'static, 'static> {
+    default_sub_command(
+        &EXTENDED_POLYMERIZATION,
+        "Extends the polymer a certain number of steps then counts the most common character - the least common",
+        "Path to the input file. Input should be the start polymer followed by the polymerization rules.",
+        "Polymerize the default input 10 times.",
+        "Polymerized the default input 40 times.",
+    ).arg(
+        Arg::with_name("polymerization-count")
+            .short("p")
+            .help("Number of times to polymerize the template")
+            .takes_value(true)
+            .required_unless_one(&["part", "explain"]),
+    )
+    .arg(
+        Arg::with_name("expand-literal")
+            .short("l")
+            .long("expand-literal")
+            .help("If passed, also builds the full polymer for this many steps and lists it after each one.")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("identity-missing")
+            .short("i")
+            .long("identity-missing")
+            .help("If passed, pairs without an insertion rule are left as they are instead of rejecting the input."),
+    )
+}
+
+fn parse_arguments(arguments: &ArgMatches) -> ExtendedPolymerizationArgs {
+    match selected_part(arguments) {
+        Some("part1") => ExtendedPolymerizationArgs {
+            polymerization_count: 10,
+            expand_literal: expand_literal_argument(arguments),
+            identity_missing: arguments.is_present("identity-missing"),
+        },
+        Some("part2") => ExtendedPolymerizationArgs {
+            polymerization_count: 40,
+            expand_literal: expand_literal_argument(arguments),
+            identity_missing: arguments.is_present("identity-missing"),
+        },
+        _ => ExtendedPolymerizationArgs {
+            polymerization_count: value_t_or_exit!(
+                arguments.value_of("polymerization-count"),
+                usize
+            ),
+            expand_literal: expand_literal_argument(arguments),
+            identity_missing: arguments.is_present("identity-missing"),
+        },
+    }
+}
+
+fn expand_literal_argument(arguments: &ArgMatches) -> Option<usize> {
+    if arguments.is_present("expand-literal") {
+        Some(value_t_or_exit!(
+            arguments.value_of("expand-literal"),
+            usize
+        ))
+    } else {
+        None
+    }
+}
+
+fn input_check(arguments: &ExtendedPolymerizationArgs, polymer: &Polymer) -> Result<(), AocError> {
+    if !arguments.identity_missing {
+        let missing: Vec<String> = producible_pairs(polymer)
+            .into_iter()
+            .filter(|pair| polymer.insertion_rules[*pair].is_none())
+            .map(|pair| pair_name(pair, polymer))
+            .collect();
+        if !missing.is_empty() {
+            return Err(AocError::validation(format!(
+                "No insertion rule for {}, which the template can produce. Pass -i to leave them as they are",
+                missing.join(", ")
+            )));
+        }
+    }
+
+    match arguments.expand_literal {
+        Some(steps) if steps > MAX_LITERAL_STEPS => Err(AocError::validation(format!(
+            "Can expand the polymer literally for at most {} steps, not {}",
+            MAX_LITERAL_STEPS, steps
+        ))),
+        Some(steps) if steps > arguments.polymerization_count => {
+            Err(AocError::validation(format!(
+                "Can't expand the polymer literally for {} steps when only polymerizing {} times",
+                steps, arguments.polymerization_count
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn run(arguments: ExtendedPolymerizationArgs, polymer: Polymer) -> CommandResult {
+    let expansion: Option<Vec<(String, CommandResult)>> = arguments.expand_literal.map(|steps| {
+        iter::once((
+            "Template".to_string(),
+            polymer_string(&polymer.template, &polymer).into(),
+        ))
+        .chain(
+            expand_literal(&polymer)
+                .take(steps)
+                .enumerate()
+                .map(|(step, template)| {
+                    (
+                        format!("Step {}", step + 1),
+                        polymer_string(&template, &polymer).into(),
+                    )
+                }),
+        )
+        .collect()
+    });
+
+    let element_count = polymer.elements.len();
+    let mut template = polymer.template.windows(2).fold(
+        vec![0usize; element_count * element_count],
+        |mut acc, items| {
+            acc[pair_index((items[0], items[1]), element_count)] += 1;
+            acc
+        },
+    );
+
+    for _ in 0..arguments.polymerization_count {
+        template = run_polymer_step(&template, &polymer.insertion_rules);
+    }
+
+    let mut counts =
+        template
+            .iter()
+            .enumerate()
+            .fold(vec![0usize; element_count], |mut acc, (pair, count)| {
+                acc[pair / element_count] += count;
+                acc[pair % element_count] += count;
+                acc
+            });
+    counts[usize::from(*polymer.template.first().unwrap())] += 1;
+    counts[usize::from(*polymer.template.last().unwrap())] += 1;
+
+    let present_counts = counts
+        .iter()
+        .filter(|count| **count > 0)
+        .map(|count| count / 2);
+    let top = present_counts.clone().max().unwrap();
+    let bottom = present_counts.min().unwrap();
+
+    match expansion {
+        Some(mut expansion) => {
+            expansion.push(("Difference".to_string(), (top - bottom).into()));
+            expansion.into()
+        }
+        None => (top - bottom).into(),
+    }
+}
+
+// A pair without a rule can only get this far with -i, and stays as it is.
+fn run_polymer_step(template: &[usize], insertion_rules: &[Option<(usize, usize)>]) -> Vec<usize> {
+    template
+        .iter()
+        .enumerate()
+        .filter(|(_, count)| **count > 0)
+        .fold(vec![0usize; template.len()], |mut acc, (pair, count)| {
+            match insertion_rules[pair] {
+                Some((new1, new2)) => {
+                    acc[new1] += count;
+                    acc[new2] += count;
+                }
+                None => acc[pair] += count,
+            }
+            acc
+        })
+}
+
+// Every pair index the template's pairs lead to through the rules, in order.
+fn producible_pairs(polymer: &Polymer) -> BTreeSet<usize> {
+    let element_count = polymer.elements.len();
+    let mut pairs = BTreeSet::new();
+    let mut pending: Vec<usize> = polymer
+        .template
+        .windows(2)
+        .map(|items| pair_index((items[0], items[1]), element_count))
+        .collect();
+
+    while let Some(pair) = pending.pop() {
+        if pairs.insert(pair) {
+            if let Some((new1, new2)) = polymer.insertion_rules[pair] {
+                pending.push(new1);
+                pending.push(new2);
+            }
+        }
+    }
+
+    pairs
+}
+
+fn pair_name(pair: usize, polymer: &Polymer) -> String {
+    let element_count = polymer.elements.len();
+    [
+        polymer.elements[pair / element_count],
+        polymer.elements[pair % element_count],
+    ]
+    .iter()
+    .collect()
+}
+
+// Carries out each step on the polymer itself rather than on counts of its pairs, yielding the
+// template after every step.
+fn expand_literal(polymer: &Polymer) -> impl Iterator<Item = Vec<u8>> + '_ {
+    let element_count = polymer.elements.len();
+    std::iter::successors(Some(polymer.template.clone()), move |template| {
+        let mut expanded = Vec::with_capacity(template.len() * 2);
+        expanded.push(template[0]);
+        template.windows(2).for_each(|items| {
+            if let Some((first, _)) =
+                polymer.insertion_rules[pair_index((items[0], items[1]), element_count)]
+            {
+                expanded.push((first % element_count) as u8);
+            }
+            expanded.push(items[1]);
+        });
+        Some(expanded)
+    })
+    .skip(1)
+}
+
+fn polymer_string(template: &[u8], polymer: &Polymer) -> String {
+    template
+        .iter()
+        .map(|element| polymer.elements[usize::from(*element)])
+        .collect()
+}
+
+fn pair_index(pair: PolyPair, element_count: usize) -> usize {
+    let (first, second) = pair;
+    usize::from(first) * element_count + usize::from(second)
+}
+
+fn describe(polymer: &Polymer) -> String {
+    format!(
+        "Template of {} elements with {} insertion rules over {} distinct elements",
+        polymer.template.len(),
+        polymer
+            .insertion_rules
+            .iter()
+            .filter(|rule| rule.is_some())
+            .count(),
+        polymer.elements.len()
+    )
+}
+
+fn parse_data(input: &String) -> IResult<&str, Polymer> {
+    map_res(
+        separated_pair(parse_polymer_template, tag("\n\n"), parse_insertion_rules),
+        |(template, insertion_rules)| build_polymer(template, insertion_rules),
+    )(input)
+}
+
+fn build_polymer(
+    template: Vec<char>,
+    insertion_rules: Vec<InsertionRule>,
+) -> Result<Polymer, AocError> {
+    let mut element_ids = HashMap::new();
+    let mut elements = Vec::new();
+
+    let template = template
+        .into_iter()
+        .map(|element| get_element_id(element, &mut element_ids, &mut elements))
+        .collect::<Result<Vec<u8>, AocError>>()?;
+    let insertion_rules = insertion_rules
+        .into_iter()
+        .map(|((first, second), insert)| {
+            Ok((
+                (
+                    get_element_id(first, &mut element_ids, &mut elements)?,
+                    get_element_id(second, &mut element_ids, &mut elements)?,
+                ),
+                get_element_id(insert, &mut element_ids, &mut elements)?,
+            ))
+        })
+        .collect::<Result<Vec<(PolyPair, u8)>, AocError>>()?;
+
+    let element_count = elements.len();
+    let insertion_rules = insertion_rules.into_iter().fold(
+        vec![Option::None; element_count * element_count],
+        |mut acc, ((first, second), insert)| {
+            acc[pair_index((first, second), element_count)] = Option::Some((
+                pair_index((first, insert), element_count),
+                pair_index((insert, second), element_count),
+            ));
+            acc
+        },
+    );
+
+    Ok(Polymer {
+        elements,
+        template,
+        insertion_rules,
+    })
+}
+
+fn get_element_id(
+    element: char,
+    element_ids: &mut HashMap<char, u8>,
+    elements: &mut Vec<char>,
+) -> Result<u8, AocError> {
+    if let Some(id) = element_ids.get(&element) {
+        return Ok(*id);
+    }
+
+    let id = u8::try_from(elements.len())
+        .map_err(|_| AocError::validation("Too many distinct elements in polymer"))?;
+    element_ids.insert(element, id);
+    elements.push(element);
+    Ok(id)
+}
+
+fn parse_polymer_template(input: &str) -> IResult<&str, Vec<char>> {
+    many1(parse_element)(input)
+}
+
+fn parse_insertion_rules(input: &str) -> IResult<&str, Vec<InsertionRule>> {
+    separated_list1(
+        newline,
+        separated_pair(
+            pair(parse_element, parse_element),
+            tag(" -> "),
+            parse_element,
+        ),
+    )(input)
+}
+
+fn parse_element(input: &str) -> IResult<&str, char> {
+    satisfy(|c| c.is_ascii_alphabetic())(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_problem_with_input;
+
+    #[test]
+    fn part1_sample() {
+        assert_eq!(
+            run_problem_with_input(&EXTENDED_POLYMERIZATION, SAMPLE, &["part1"]).unwrap(),
+            1588usize.into()
+        );
+    }
+
+    #[test]
+    fn part2_sample() {
+        assert_eq!(
+            run_problem_with_input(&EXTENDED_POLYMERIZATION, SAMPLE, &["part2"]).unwrap(),
+            2188189693529usize.into()
+        );
+    }
+
+    #[test]
+    fn expand_literal_sample() {
+        let (_, polymer) = parse_data(&SAMPLE.to_string()).unwrap();
+        let steps: Vec<String> = expand_literal(&polymer)
+            .take(4)
+            .map(|template| polymer_string(&template, &polymer))
+            .collect();
+
+        assert_eq!(
+            steps,
+            vec![
+                "NCNBCHB",
+                "NBCCNBBBCBHCB",
+                "NBBBCNCCNBBNBNBBCHBHHBCHB",
+                "NBBNBNBBCCNBCNCCNBBNBBNBBBNBBNBBCBHCBHHNHCBBCBHCB",
+            ]
+        );
+        assert_eq!(
+            run_problem_with_input(
+                &EXTENDED_POLYMERIZATION,
+                SAMPLE,
+                &["--part", "1", "-l", "2"]
+            )
+            .unwrap(),
+            vec![
+                ("Template".to_string(), "NNCB".to_string().into()),
+                ("Step 1".to_string(), "NCNBCHB".to_string().into()),
+                ("Step 2".to_string(), "NBCCNBBBCBHCB".to_string().into()),
+                ("Difference".to_string(), 1588usize.into()),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn missing_rules_are_rejected_or_left_alone() {
+        assert_eq!(
+            run_problem_with_input(&EXTENDED_POLYMERIZATION, "AB\n\nAB -> A\nBA -> C", &["part1"])
+                .unwrap_err()
+                .to_string(),
+            "No insertion rule for AA, which the template can produce. Pass -i to leave them as they are"
+        );
+        assert_eq!(
+            run_problem_with_input(
+                &EXTENDED_POLYMERIZATION,
+                "AB\n\nAB -> A\nBA -> C",
+                &["--part", "1", "-i"]
+            )
+            .unwrap(),
+            10usize.into()
+        );
+    }
+
+    #[test]
+    fn expand_literal_past_the_steps_is_rejected() {
+        assert!(run_problem_with_input(
+            &EXTENDED_POLYMERIZATION,
+            SAMPLE,
+            &["--part", "1", "-l", "11"]
+        )
+        .is_err());
+    }
+}
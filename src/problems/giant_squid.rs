@@ -0,0 +1,494 @@
+use crate::{
+    default_sub_command, parse_usize, report_error, selected_part, AocError, CommandResult,
+    Example, Problem,
+};
+use clap::{value_t_or_exit, App, Arg, ArgMatches};
+use nom::{
+    bytes::complete::{tag, take_until, take_while},
+    character::complete::newline,
+    combinator::{cut, eof, map, map_parser, map_res, not},
+    multi::{many1, separated_list0, separated_list1},
+    sequence::{preceded, terminated, tuple},
+    IResult,
+};
+use std::collections::{HashMap, HashSet};
+use std::io::{self, BufRead, Write};
+use std::ops::{BitAnd, BitOr};
+
+pub const GIANT_SQUID: Problem<GiantSquidArgs, BingoGame> = Problem::new(
+    sub_command,
+    "giant-squid",
+    "day4_giant_squid",
+    parse_arguments,
+    parse_bingo_game,
+    run,
+)
+.with_long_about(LONG_ABOUT)
+.with_examples(EXAMPLES)
+.with_describe(describe)
+.with_input_check(input_check);
+
+const LONG_ABOUT: &str =
+    "The input is a comma separated list of numbers to call followed by bingo boards, usually 5 \
+    by 5. Any rectangular board of up to 64 numbers can be played, and -b stops with an error \
+    unless every board is the given size.\n\n\
+    A board wins when every number in a row or column has been called. Diagonals don't count. \
+    The score is the sum of the board's uncalled numbers multiplied by the last number called. \
+    Part 1 scores the first board to win. -s lets the squid win by scoring the last board to \
+    win instead.\n\n\
+    -l plays live: the boards still come from the input, but its numbers are ignored and can be \
+    left empty. Each line typed is called as a number, and the boards that win with it are \
+    printed with their scores. It stops once every board has won or the input ends, and the \
+    answer is the score of each board in the order they won.\n\n\
+    Example: the sample scores 4512 for the first winner and 1924 for the last.";
+
+const SAMPLE: &str = include_str!("../../day4_giant_squid/sample.txt");
+
+const EXAMPLES: &[Example] = &[
+    Example::new(SAMPLE, 1, "4512"),
+    Example::new(SAMPLE, 2, "1924"),
+];
+
+#[derive(Debug)]
+pub struct GiantSquidArgs {
+    squid_win: bool,
+    board_size: Option<usize>,
+    live: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct BingoGame {
+    numbers_to_call: Vec<usize>,
+    boards: Vec<BingoBoard>,
+}
+
+#[derive(Debug, Clone)]
+struct BingoBoard {
+    numbers: Vec<usize>,
+    positions: HashMap<usize, (usize, usize)>,
+    rows: usize,
+    columns: usize,
+    win_masks: Vec<u64>,
+    marked: u64,
+}
+
+impl BingoBoard {
+    fn new(cells: Vec<Vec<usize>>) -> BingoBoard {
+        let rows = cells.len();
+        let columns = cells.first().map(|row| row.len()).unwrap_or(0usize);
+        let positions = cells
+            .iter()
+            .enumerate()
+            .flat_map(|(row, numbers)| {
+                numbers
+                    .iter()
+                    .enumerate()
+                    .map(move |(column, number)| (*number, (row, column)))
+            })
+            .collect();
+        let row_masks = (0..rows).map(|row| {
+            (0..columns).fold(0u64, |mask, column| {
+                mask.bitor(1u64 << (row * columns + column))
+            })
+        });
+        let column_masks = (0..columns).map(|column| {
+            (0..rows).fold(0u64, |mask, row| {
+                mask.bitor(1u64 << (row * columns + column))
+            })
+        });
+
+        BingoBoard {
+            numbers: cells.into_iter().flatten().collect(),
+            positions,
+            rows,
+            columns,
+            win_masks: row_masks.chain(column_masks).collect(),
+            marked: 0u64,
+        }
+    }
+
+    fn mark(&mut self, number: usize) {
+        if let Some((row, column)) = self.positions.get(&number) {
+            self.marked = self.marked.bitor(1u64 << (row * self.columns + column));
+        }
+    }
+
+    fn is_marked(&self, index: usize) -> bool {
+        self.marked.bitand(1u64 << index) != 0u64
+    }
+}
+
+fn sub_command() -> App<'static, 'static> {
+    default_sub_command(
+        &GIANT_SQUID,
+        "Finds the best bingo board to play",
+        "Path to the input file. The numbers to darw in order, followed by a set of boards.",
+        "Finds the best board, sums the uncalled numbers then multiplies that by the last number that was called.",
+        "Finds the worst borad, sums the uncalled numbers  hen multiplies that by the last number that was called.",
+    )
+    .arg(
+        Arg::with_name("squid-win")
+        .short("s")
+        .help("If passed, try to let the squid win (find the worst board)."))
+    .arg(
+        Arg::with_name("board-size")
+            .short("b")
+            .long("board-size")
+            .help("If passed, every board must be this many numbers wide and tall or the run stops with an error.")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("live")
+            .short("l")
+            .long("live")
+            .help("If passed, reads the numbers to call from stdin one per line and prints the boards that win with each.")
+            .conflicts_with("squid-win"),
+    )
+}
+
+fn parse_arguments(arguments: &ArgMatches) -> GiantSquidArgs {
+    match selected_part(arguments) {
+        Some("part1") => GiantSquidArgs {
+            squid_win: false,
+            board_size: board_size_argument(arguments),
+            live: arguments.is_present("live"),
+        },
+        Some("part2") => GiantSquidArgs {
+            squid_win: true,
+            board_size: board_size_argument(arguments),
+            live: arguments.is_present("live"),
+        },
+        _ => GiantSquidArgs {
+            squid_win: arguments.is_present("squid-win"),
+            board_size: board_size_argument(arguments),
+            live: arguments.is_present("live"),
+        },
+    }
+}
+
+fn board_size_argument(arguments: &ArgMatches) -> Option<usize> {
+    if arguments.is_present("board-size") {
+        Some(value_t_or_exit!(arguments.value_of("board-size"), usize))
+    } else {
+        None
+    }
+}
+
+fn input_check(arguments: &GiantSquidArgs, bingo_game: &BingoGame) -> Result<(), AocError> {
+    match arguments.board_size.and_then(|size| {
+        bingo_game
+            .boards
+            .iter()
+            .enumerate()
+            .find(|(_, board)| board.rows != size || board.columns != size)
+            .map(|(index, board)| (size, index, board))
+    }) {
+        Some((size, index, board)) => Err(AocError::validation(format!(
+            "Board {} is {} by {}, expected {} by {}",
+            index + 1,
+            board.rows,
+            board.columns,
+            size,
+            size
+        ))),
+        None => Ok(()),
+    }
+}
+
+fn run(arguments: GiantSquidArgs, bingo_game: BingoGame) -> CommandResult {
+    if arguments.live {
+        let winners = play_live(
+            bingo_game.boards,
+            &mut io::stdin().lock(),
+            &mut io::stdout(),
+        )
+        .unwrap_or_else(|error| {
+            report_error(error);
+            Vec::new()
+        });
+        return if winners.is_empty() {
+            "No board won".to_string().into()
+        } else {
+            winners.into()
+        };
+    }
+
+    process_bingo_winner(find_bingo_winner(
+        bingo_game.boards,
+        bingo_game.numbers_to_call,
+        select_winner(&arguments.squid_win),
+    ))
+    .into()
+}
+
+// Calls each number as it's read rather than from the input, reporting the boards that win with
+// it. A board that has won is out of the game, so it's only reported once.
+fn play_live(
+    boards: Vec<BingoBoard>,
+    calls: &mut dyn BufRead,
+    report: &mut dyn Write,
+) -> Result<Vec<(String, CommandResult)>, AocError> {
+    let mut playing: Vec<(usize, BingoBoard)> = boards.into_iter().enumerate().collect();
+    let mut winners = Vec::new();
+
+    for line in calls.lines() {
+        let line = line?;
+        let number = match line.trim() {
+            "" => continue,
+            number => match number.parse::<usize>() {
+                Ok(number) => number,
+                Err(_) => {
+                    writeln!(report, "{} isn't a number, call another", number)?;
+                    continue;
+                }
+            },
+        };
+
+        playing.iter_mut().for_each(|(_, board)| board.mark(number));
+        let (won, still_playing): (Vec<_>, Vec<_>) = playing
+            .into_iter()
+            .partition(|(_, board)| is_board_winner(board));
+        playing = still_playing;
+
+        if won.is_empty() {
+            writeln!(report, "Called {}: no winners", number)?;
+        }
+        won.into_iter().try_for_each(|(index, board)| {
+            let score = process_bingo_winner((board, number));
+            winners.push((format!("Board {}", index + 1), score.into()));
+            writeln!(
+                report,
+                "Called {}: board {} wins with a score of {}",
+                number,
+                index + 1,
+                score
+            )
+        })?;
+
+        if playing.is_empty() {
+            writeln!(report, "Every board has won")?;
+            break;
+        }
+    }
+
+    Ok(winners)
+}
+
+fn process_bingo_winner(winner: (BingoBoard, usize)) -> usize {
+    let (board, last_number) = winner;
+
+    board
+        .numbers
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !board.is_marked(*index))
+        .fold(0, |acc, (_, number)| acc + number)
+        * last_number
+}
+
+fn find_bingo_winner(
+    mut boards: Vec<BingoBoard>,
+    numbers_to_call: impl IntoIterator<Item = usize>,
+    determine_winner: impl Fn(&Vec<BingoBoard>) -> bool,
+) -> (BingoBoard, usize) {
+    let mut last_called_number = 0usize;
+
+    for number in numbers_to_call {
+        last_called_number = number;
+        boards.iter_mut().for_each(|board| board.mark(number));
+
+        if determine_winner(&boards) {
+            break;
+        }
+
+        boards = boards
+            .into_iter()
+            .filter(|board| !is_board_winner(&board))
+            .collect();
+    }
+
+    (
+        boards.into_iter().find(is_board_winner).unwrap(),
+        last_called_number,
+    )
+}
+
+fn select_winner(squid_win: &bool) -> impl Fn(&Vec<BingoBoard>) -> bool {
+    if *squid_win {
+        is_last_winner
+    } else {
+        is_first_winner
+    }
+}
+
+fn is_first_winner(boards: &Vec<BingoBoard>) -> bool {
+    boards.into_iter().any(|board| is_board_winner(&board))
+}
+
+fn is_last_winner(boards: &Vec<BingoBoard>) -> bool {
+    boards.into_iter().all(|board| is_board_winner(&board))
+}
+
+fn is_board_winner(bingo_board: &BingoBoard) -> bool {
+    bingo_board
+        .win_masks
+        .iter()
+        .any(|mask| bingo_board.marked.bitand(*mask) == *mask)
+}
+
+fn describe(bingo_game: &BingoGame) -> String {
+    let mut description = format!(
+        "{} numbers to call and {} boards",
+        bingo_game.numbers_to_call.len(),
+        bingo_game.boards.len()
+    );
+
+    let unique_numbers: HashSet<&usize> = bingo_game.numbers_to_call.iter().collect();
+    if unique_numbers.len() != bingo_game.numbers_to_call.len() {
+        description.push_str(&format!(
+            "\nAnomaly: {} numbers are called more than once",
+            bingo_game.numbers_to_call.len() - unique_numbers.len()
+        ));
+    }
+
+    bingo_game
+        .boards
+        .iter()
+        .enumerate()
+        .filter(|(_, board)| board.rows != 5 || board.columns != 5)
+        .for_each(|(index, board)| {
+            description.push_str(&format!(
+                "\nAnomaly: board {} is {} by {} rather than 5 by 5",
+                index + 1,
+                board.rows,
+                board.columns
+            ))
+        });
+
+    description
+}
+
+fn parse_bingo_game(input: &String) -> IResult<&str, BingoGame> {
+    map(
+        tuple((parse_numbers_to_call, parse_bingo_boards)),
+        |(numbers_to_call, bingo_boards)| BingoGame {
+            numbers_to_call: numbers_to_call,
+            boards: bingo_boards,
+        },
+    )(input)
+}
+
+fn parse_bingo_boards(input: &str) -> IResult<&str, Vec<BingoBoard>> {
+    // A board that fails to parse is an error rather than the end of the boards, so a malformed
+    // board can't be silently dropped.
+    separated_list0(terminated(tag("\n\n"), not(eof)), cut(parse_bingo_board))(input)
+}
+
+fn parse_bingo_board(input: &str) -> IResult<&str, BingoBoard> {
+    map_res(separated_list1(newline, parse_bingo_cell_row), |cells| {
+        let columns = cells.first().map(|row| row.len()).unwrap_or(0usize);
+        if cells.iter().any(|row| row.len() != columns) {
+            Err(AocError::validation(
+                "Bingo board rows must all be the same length",
+            ))
+        } else if cells.len() * columns > 64 {
+            Err(AocError::validation(
+                "Bingo boards can have at most 64 cells",
+            ))
+        } else {
+            Ok(BingoBoard::new(cells))
+        }
+    })(input)
+}
+
+fn parse_bingo_cell_row(input: &str) -> IResult<&str, Vec<usize>> {
+    many1(preceded(take_while(|c| c == ' '), parse_usize))(input)
+}
+
+fn parse_numbers_to_call(input: &str) -> IResult<&str, Vec<usize>> {
+    map_parser(
+        terminated(take_until("\n\n"), tag("\n\n")),
+        separated_list0(tag(","), parse_usize),
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_problem_with_input;
+
+    #[test]
+    fn part1_sample() {
+        assert_eq!(
+            run_problem_with_input(&GIANT_SQUID, SAMPLE, &["part1"]).unwrap(),
+            4512usize.into()
+        );
+    }
+
+    #[test]
+    fn part2_sample() {
+        assert_eq!(
+            run_problem_with_input(&GIANT_SQUID, SAMPLE, &["part2"]).unwrap(),
+            1924usize.into()
+        );
+    }
+    const SMALL_BOARDS: &str = "1,2,3,4,5,6,7,8,9\n\n1 2 3\n4 5 6\n7 8 9\n\n5 8 7\n6 1 4\n3 9 2";
+
+    #[test]
+    fn small_boards() {
+        assert_eq!(
+            run_problem_with_input(&GIANT_SQUID, SMALL_BOARDS, &["--part", "1", "-b", "3"])
+                .unwrap(),
+            117usize.into()
+        );
+        assert_eq!(
+            run_problem_with_input(&GIANT_SQUID, SMALL_BOARDS, &["part2"]).unwrap(),
+            144usize.into()
+        );
+    }
+
+    #[test]
+    fn board_size_mismatch() {
+        assert_eq!(
+            run_problem_with_input(&GIANT_SQUID, SAMPLE, &["--part", "1", "-b", "3"])
+                .unwrap_err()
+                .to_string(),
+            "Board 1 is 5 by 5, expected 3 by 3"
+        );
+    }
+
+    #[test]
+    fn live_sample() {
+        let (_, bingo_game) = parse_bingo_game(&SAMPLE.to_string()).unwrap();
+        let calls = "7\n4\n9\n5\n11\n17\n23\n2\n0\n14\nfourteen\n\n21\n24\n10\n16\n13\n";
+        let mut report = Vec::new();
+
+        let winners = play_live(bingo_game.boards, &mut calls.as_bytes(), &mut report).unwrap();
+
+        assert_eq!(
+            winners,
+            vec![
+                ("Board 3".to_string(), 4512usize.into()),
+                ("Board 1".to_string(), 2192usize.into()),
+                ("Board 2".to_string(), 1924usize.into()),
+            ]
+        );
+        let report = String::from_utf8(report).unwrap();
+        assert!(report.starts_with("Called 7: no winners\n"));
+        assert!(report.contains("Called 14: no winners\nfourteen isn't a number, call another\n"));
+        assert!(report.ends_with(
+            "Called 24: board 3 wins with a score of 4512\n\
+            Called 10: no winners\n\
+            Called 16: board 1 wins with a score of 2192\n\
+            Called 13: board 2 wins with a score of 1924\n\
+            Every board has won\n"
+        ));
+    }
+
+    #[test]
+    fn ragged_board() {
+        assert!(
+            run_problem_with_input(&GIANT_SQUID, "1,2,3\n\n1 2 3\n4 5\n6 7 8", &["part1"]).is_err()
+        );
+    }
+}
@@ -0,0 +1,264 @@
+use crate::{default_sub_command, parse_usize, selected_part, CommandResult, Example, Problem};
+use clap::{value_t_or_exit, App, Arg, ArgMatches};
+use nom::{
+    bytes::complete::tag, character::complete::newline, combinator::map, multi::separated_list0,
+    sequence::separated_pair, IResult,
+};
+use std::{
+    cmp,
+    collections::{HashMap, HashSet},
+    convert::identity,
+};
+
+pub const HYDROTHERMAL_VENTURE: Problem<HydrothermalVentureArgs, Vec<Line>> = Problem::new(
+    sub_command,
+    "hydrothermal-venture",
+    "day5_hydrothermal_venture",
+    parse_arguments,
+    parse_all_lines,
+    run,
+)
+.with_long_about(LONG_ABOUT)
+.with_examples(EXAMPLES)
+.with_describe(describe);
+
+const LONG_ABOUT: &str =
+    "The input is a list of vent lines written x1,y1 -> x2,y2. Lines are horizontal, vertical \
+    or at exactly 45 degrees.\n\n\
+    The answer is the number of points where at least two lines overlap. -i ignores diagonal \
+    lines, as in part 1. Part 2 counts them. -t instead lists the given number of most \
+    overlapped points with their overlap counts.\n\n\
+    Example: the sample has 5 overlaps without diagonals and 12 with them.";
+
+const SAMPLE: &str = include_str!("../../day5_hydrothermal_venture/sample.txt");
+
+const EXAMPLES: &[Example] = &[Example::new(SAMPLE, 1, "5"), Example::new(SAMPLE, 2, "12")];
+
+#[derive(Debug)]
+pub struct HydrothermalVentureArgs {
+    ignore_diagnal_lines: bool,
+    top: Option<usize>,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+struct Point {
+    x: usize,
+    y: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Line {
+    start: Point,
+    end: Point,
+}
+
+fn sub_command() -> App<'static, 'static> {
+    default_sub_command(
+        &HYDROTHERMAL_VENTURE,
+        "Counts the number of overlapping lines of hydrothermal vents",
+        "Path to the input file. Input should be pairs of coordinates",
+        "Searches the default input ignoring diagnal lines and finds the number of overlapping vents.",
+        "Searches the default input and finds the number of overlapping vents.",
+    )
+    .arg(
+        Arg::with_name("ignore-diagnal-lines")
+        .short("i")
+        .help("If passed, ignore diagnal lines when mapping vents"))
+    .arg(
+        Arg::with_name("top")
+            .short("t")
+            .long("top")
+            .help("If passed, lists this many of the most overlapped points instead of counting overlaps.")
+            .takes_value(true),
+    )
+}
+
+fn parse_arguments(arguments: &ArgMatches) -> HydrothermalVentureArgs {
+    match selected_part(arguments) {
+        Some("part1") => HydrothermalVentureArgs {
+            ignore_diagnal_lines: true,
+            top: top_argument(arguments),
+        },
+        Some("part2") => HydrothermalVentureArgs {
+            ignore_diagnal_lines: false,
+            top: top_argument(arguments),
+        },
+        _ => HydrothermalVentureArgs {
+            ignore_diagnal_lines: arguments.is_present("ignore-diagnal-lines"),
+            top: top_argument(arguments),
+        },
+    }
+}
+
+fn top_argument(arguments: &ArgMatches) -> Option<usize> {
+    if arguments.is_present("top") {
+        Some(value_t_or_exit!(arguments.value_of("top"), usize))
+    } else {
+        None
+    }
+}
+
+fn run(arguments: HydrothermalVentureArgs, lines: Vec<Line>) -> CommandResult {
+    let filter = if arguments.ignore_diagnal_lines {
+        filter_horizontal_and_vertical_lines
+    } else {
+        identity
+    };
+
+    match arguments.top {
+        Some(top) => most_overlapped_points(&filter(lines), top)
+            .into_iter()
+            .map(|(point, count)| (format!("{},{}", point.x, point.y), count.into()))
+            .collect::<Vec<(String, CommandResult)>>()
+            .into(),
+        None => find_overlapping_points(&filter(lines)).into(),
+    }
+}
+
+// Ties are ordered by position, top to bottom then left to right, so the list is stable.
+fn most_overlapped_points(lines: &[Line], top: usize) -> Vec<(Point, usize)> {
+    let mut points: Vec<(Point, usize)> =
+        overlap_vents(&(lines.iter().map(expand_line_into_points).collect()))
+            .into_iter()
+            .collect();
+    points.sort_by_key(|(point, count)| (cmp::Reverse(*count), point.y, point.x));
+    points.truncate(top);
+    points
+}
+
+fn find_overlapping_points(lines: &Vec<Line>) -> usize {
+    overlap_vents(&(lines.into_iter().map(expand_line_into_points).collect()))
+        .into_iter()
+        .filter(|(_, count)| count > &1)
+        .count()
+}
+
+fn overlap_vents(vents: &Vec<HashSet<Point>>) -> HashMap<Point, usize> {
+    let mut coordinates = HashMap::new();
+
+    vents.into_iter().for_each(|vent| {
+        vent.into_iter()
+            .for_each(|point| *coordinates.entry(*point).or_insert(0usize) += 1)
+    });
+
+    coordinates
+}
+
+fn expand_line_into_points(line: &Line) -> HashSet<Point> {
+    let mut points = HashSet::new();
+    let mut x = line.start.x;
+    let mut y = line.start.y;
+
+    while x != line.end.x || y != line.end.y {
+        points.insert(Point { x: x, y: y });
+
+        if x > line.end.x {
+            x -= 1;
+        } else if x < line.end.x {
+            x += 1;
+        }
+
+        if y > line.end.y {
+            y -= 1;
+        } else if y < line.end.y {
+            y += 1;
+        }
+    }
+
+    points.insert(Point {
+        x: line.end.x,
+        y: line.end.y,
+    });
+
+    points
+}
+
+fn filter_horizontal_and_vertical_lines(lines: Vec<Line>) -> Vec<Line> {
+    lines
+        .into_iter()
+        .filter(|line| line.start.x == line.end.x || line.start.y == line.end.y)
+        .collect()
+}
+
+fn describe(lines: &Vec<Line>) -> String {
+    let (horizontal, vertical, diagonal) = lines.iter().fold(
+        (0usize, 0usize, 0usize),
+        |(horizontal, vertical, diagonal), line| {
+            if line.start.y == line.end.y {
+                (horizontal + 1, vertical, diagonal)
+            } else if line.start.x == line.end.x {
+                (horizontal, vertical + 1, diagonal)
+            } else {
+                (horizontal, vertical, diagonal + 1)
+            }
+        },
+    );
+
+    format!(
+        "{} lines: {} horizontal, {} vertical, {} diagonal",
+        lines.len(),
+        horizontal,
+        vertical,
+        diagonal
+    )
+}
+
+fn parse_all_lines(input: &String) -> IResult<&str, Vec<Line>> {
+    separated_list0(newline, parse_line)(input)
+}
+
+fn parse_point(input: &str) -> IResult<&str, Point> {
+    map(
+        separated_pair(parse_usize, tag(","), parse_usize),
+        |(x, y)| Point { x: x, y: y },
+    )(input)
+}
+
+fn parse_line(input: &str) -> IResult<&str, Line> {
+    map(
+        separated_pair(parse_point, tag(" -> "), parse_point),
+        |(start, end)| Line {
+            start: start,
+            end: end,
+        },
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_problem_with_input;
+
+    #[test]
+    fn part1_sample() {
+        assert_eq!(
+            run_problem_with_input(&HYDROTHERMAL_VENTURE, SAMPLE, &["part1"]).unwrap(),
+            5usize.into()
+        );
+    }
+
+    #[test]
+    fn part2_sample() {
+        assert_eq!(
+            run_problem_with_input(&HYDROTHERMAL_VENTURE, SAMPLE, &["part2"]).unwrap(),
+            12usize.into()
+        );
+    }
+    #[test]
+    fn top_sample() {
+        assert_eq!(
+            run_problem_with_input(
+                &HYDROTHERMAL_VENTURE,
+                SAMPLE,
+                &["--part", "2", "--top", "3"]
+            )
+            .unwrap(),
+            vec![
+                ("4,4".to_string(), 3usize.into()),
+                ("6,4".to_string(), 3usize.into()),
+                ("7,1".to_string(), 2usize.into()),
+            ]
+            .into()
+        );
+    }
+}
@@ -0,0 +1,629 @@
+use crate::{
+    count_result, default_sub_command, parse_usize, report_output, selected_part, AocError,
+    CommandResult, Counter, Example, Problem,
+};
+use clap::{value_t_or_exit, App, Arg, ArgMatches};
+use nom::{
+    bytes::complete::tag,
+    character::complete::newline,
+    combinator::map,
+    multi::{separated_list0, separated_list1},
+    IResult,
+};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use strum::VariantNames;
+use strum_macros::{EnumString, EnumVariantNames};
+
+pub const LANTERNFISH: Problem<LanternfishArgs, Vec<Counter<usize>>> = Problem::new(
+    sub_command,
+    "lanternfish",
+    "day6_lanternfish",
+    parse_arguments,
+    parse_data,
+    run,
+)
+.with_long_about(LONG_ABOUT)
+.with_examples(EXAMPLES)
+.with_describe(describe)
+.with_input_check(input_check);
+
+const LONG_ABOUT: &str = "The input is a comma separated list of lanternfish timers. Each \
+    line is a separate school of fish.\n\n\
+    Each day every timer decreases by one. A fish at 0 resets to 6 and spawns a new fish with \
+    a timer of 8. The answer is the number of fish after -d days, 80 for part 1 and 256 for \
+    part 2.\n\n\
+    -m counts the fish with a 9 by 9 matrix raised to the number of days by repeated squaring, \
+    which takes a handful of steps even for a trillion days. The count outgrows 64 bits after \
+    about 440 days, which only -m goes past. It counts in 128 bits, up to about 900 days, and \
+    past that -o gives the count modulo a number instead.\n\n\
+    -b gives the number of fish at each timer value as well as the total. -c writes a CSV with \
+    a row for each day from the start, counting the fish at each timer value.\n\n\
+    With several schools -s merged, the default, counts them all as one population. -s \
+    separate simulates each school on its own and gives the number of fish in each along with \
+    the total. Schools never mix, so the totals always agree.\n\n\
+    Example: 3,4,3,1,2 grows to 26 fish after 18 days and 5934 after 80.";
+
+const SAMPLE: &str = include_str!("../../day6_lanternfish/sample.txt");
+
+const EXAMPLES: &[Example] = &[
+    Example::new(SAMPLE, 1, "5934"),
+    Example::new(SAMPLE, 2, "26984457539"),
+];
+
+#[derive(Debug)]
+pub struct LanternfishArgs {
+    days: usize,
+    matrix: bool,
+    modulo: Option<usize>,
+    breakdown: bool,
+    csv: Option<String>,
+    schools: Schools,
+}
+
+#[derive(Debug, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "kebab_case")]
+enum Schools {
+    Merged,
+    Separate,
+}
+
+// A fish with timer t counts towards row t of the population vector.
+type Matrix = [[u128; 9]; 9];
+
+fn sub_command() -> App<'static, 'static> {
+    default_sub_command(
+        &LANTERNFISH,
+        "Finds how many lantern fish there will be after a certain number of days",
+        "Path to the input file. Input should be comma delimited integers",
+        "Simulates the default input for 80 days",
+        "Simulates the default input for 256 days",
+    )
+    .arg(
+        Arg::with_name("days")
+            .short("d")
+            .long("days")
+            .help("Number of days to simulate")
+            .takes_value(true)
+            .required_unless_one(&["part", "explain"]),
+    )
+    .arg(
+        Arg::with_name("matrix")
+            .short("m")
+            .long("matrix")
+            .help("If passed, advances the population with matrix exponentiation instead of a day at a time. Handles any number of days."),
+    )
+    .arg(
+        Arg::with_name("modulo")
+            .short("o")
+            .long("modulo")
+            .help("If passed, gives the number of fish modulo this number, for day counts whose answer doesn't fit in 64 bits.")
+            .takes_value(true)
+            .requires("matrix"),
+    )
+    .arg(
+        Arg::with_name("breakdown")
+            .short("b")
+            .long("breakdown")
+            .help("If passed, gives the number of fish at each timer value along with the total."),
+    )
+    .arg(
+        Arg::with_name("csv")
+            .short("c")
+            .long("csv")
+            .help("If passed, writes the number of fish at each timer value on every day to the given path as CSV.")
+            .takes_value(true)
+            .conflicts_with("matrix"),
+    )
+    .arg(
+        Arg::with_name("schools")
+            .short("s")
+            .long("schools")
+            .help(
+                "How to count an input with a school of fish on each line. The options are as follows:\n\n\
+            merged: Counts every school as one population.\n\n\
+            separate: Simulates each school on its own and gives each count along with the total.\n\n",
+            )
+            .takes_value(true)
+            .possible_values(Schools::VARIANTS)
+            .conflicts_with_all(&["breakdown", "csv"]),
+    )
+}
+
+fn parse_arguments(arguments: &ArgMatches) -> LanternfishArgs {
+    match selected_part(arguments) {
+        Some("part1") => LanternfishArgs {
+            days: 80,
+            matrix: arguments.is_present("matrix"),
+            modulo: modulo_argument(arguments),
+            breakdown: arguments.is_present("breakdown"),
+            csv: arguments.value_of("csv").map(String::from),
+            schools: schools_argument(arguments),
+        },
+        Some("part2") => LanternfishArgs {
+            days: 256,
+            matrix: arguments.is_present("matrix"),
+            modulo: modulo_argument(arguments),
+            breakdown: arguments.is_present("breakdown"),
+            csv: arguments.value_of("csv").map(String::from),
+            schools: schools_argument(arguments),
+        },
+        _ => LanternfishArgs {
+            days: value_t_or_exit!(arguments.value_of("days"), usize),
+            matrix: arguments.is_present("matrix"),
+            modulo: modulo_argument(arguments),
+            breakdown: arguments.is_present("breakdown"),
+            csv: arguments.value_of("csv").map(String::from),
+            schools: schools_argument(arguments),
+        },
+    }
+}
+
+fn modulo_argument(arguments: &ArgMatches) -> Option<usize> {
+    if arguments.is_present("modulo") {
+        Some(value_t_or_exit!(arguments.value_of("modulo"), usize))
+    } else {
+        None
+    }
+}
+
+// Every school is no bigger than all of them together, so checking the merged schools covers each.
+// Not given a default since clap counts a default as passed when checking conflicts.
+fn schools_argument(arguments: &ArgMatches) -> Schools {
+    if arguments.is_present("schools") {
+        value_t_or_exit!(arguments.value_of("schools"), Schools)
+    } else {
+        Schools::Merged
+    }
+}
+
+fn input_check(arguments: &LanternfishArgs, schools: &Vec<Counter<usize>>) -> Result<(), AocError> {
+    let fishes = &merge_schools(schools);
+    let total = fish_by_matrix(fishes, arguments.days, arguments.modulo)
+        .and_then(|by_timer| matrix_total(&by_timer, arguments.modulo));
+
+    if arguments.modulo == Some(0usize) {
+        Err(AocError::validation("The modulo must be at least 1"))
+    } else if arguments.matrix && total.is_none() {
+        Err(AocError::validation(format!(
+            "The number of fish after {} days doesn't fit in 128 bits, pass --modulo to count it modulo a number",
+            arguments.days
+        )))
+    } else if !arguments.matrix
+        && total
+            .and_then(|total| usize::try_from(total).ok())
+            .is_none()
+    {
+        Err(AocError::validation(format!(
+            "The number of fish after {} days doesn't fit in 64 bits, pass --matrix to count it in 128 bits",
+            arguments.days
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+fn run(arguments: LanternfishArgs, schools: Vec<Counter<usize>>) -> CommandResult {
+    match arguments.schools {
+        Schools::Merged => {
+            let (by_timer, total) = simulate(&arguments, merge_schools(&schools));
+            if arguments.breakdown {
+                breakdown(by_timer.into_iter(), total)
+            } else {
+                count_result(total)
+            }
+        }
+        Schools::Separate => {
+            let totals: Vec<u128> = schools
+                .into_iter()
+                .map(|school| simulate(&arguments, school).1)
+                .collect();
+            let total = reduce(
+                totals.iter().sum(),
+                arguments.modulo.map(|modulo| modulo as u128),
+            )
+            .expect("Overflow is rejected by the input check");
+
+            totals
+                .into_iter()
+                .enumerate()
+                .map(|(index, total)| (format!("School {}", index + 1), count_result(total)))
+                .chain([("Total".to_string(), count_result(total))])
+                .collect::<Vec<(String, CommandResult)>>()
+                .into()
+        }
+    }
+}
+
+fn merge_schools(schools: &[Counter<usize>]) -> Counter<usize> {
+    schools.iter().fold(Counter::new(), |mut fishes, school| {
+        fishes.merge(school.clone());
+        fishes
+    })
+}
+
+// The number of fish at each timer value after the days, along with the total.
+fn simulate(
+    arguments: &LanternfishArgs,
+    starting_fishes: Counter<usize>,
+) -> (Vec<(usize, u128)>, u128) {
+    if arguments.matrix {
+        let by_timer = fish_by_matrix(&starting_fishes, arguments.days, arguments.modulo)
+            .expect("Overflow is rejected by the input check");
+        let total = matrix_total(&by_timer, arguments.modulo)
+            .expect("Overflow is rejected by the input check");
+        return (by_timer.iter().copied().enumerate().collect(), total);
+    }
+
+    // The CSV is opened once, and after the first failed write the rest of its rows are skipped.
+    let mut csv = arguments
+        .csv
+        .as_deref()
+        .map(|path| (path, create_csv(path)));
+    let mut fishes = starting_fishes;
+
+    for day in 0..=arguments.days {
+        if day > 0 {
+            fishes = process_fish_day(fishes);
+        }
+        if let Some((_, written)) = &mut csv {
+            if let Ok(writer) = written {
+                if let Err(error) = write_csv_row(writer, day, &fishes) {
+                    *written = Err(error);
+                }
+            }
+        }
+    }
+
+    if let Some((path, writer)) = csv {
+        report_output(path, writer.and_then(|mut writer| writer.flush()));
+    }
+
+    (
+        fish_by_timer(&fishes)
+            .map(|(timer, count)| (timer, count as u128))
+            .collect(),
+        fishes.total() as u128,
+    )
+}
+
+// Timers above 8 only come from the input, and are listed when there are any.
+fn fish_by_timer(fishes: &Counter<usize>) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let highest = fishes.keys().copied().max().unwrap_or(0usize).max(8usize);
+    (0..=highest).map(move |timer| (timer, fishes.get(&timer)))
+}
+
+fn breakdown(by_timer: impl Iterator<Item = (usize, u128)>, total: u128) -> CommandResult {
+    by_timer
+        .map(|(timer, count)| (format!("Timer {}", timer), count_result(count)))
+        .chain([("Total".to_string(), count_result(total))])
+        .collect::<Vec<(String, CommandResult)>>()
+        .into()
+}
+
+fn create_csv(path: &str) -> Result<BufWriter<File>, io::Error> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    writeln!(writer, "day,0,1,2,3,4,5,6,7,8,total")?;
+    Ok(writer)
+}
+
+fn write_csv_row(
+    writer: &mut BufWriter<File>,
+    day: usize,
+    fishes: &Counter<usize>,
+) -> Result<(), io::Error> {
+    let counts: Vec<String> = (0..=8usize)
+        .map(|timer| fishes.get(&timer).to_string())
+        .collect();
+    writeln!(writer, "{},{},{}", day, counts.join(","), fishes.total())
+}
+
+fn process_fish_day(fish: Counter<usize>) -> Counter<usize> {
+    let new_fish_to_add = fish.get(&0usize);
+
+    let mut final_fishes = Counter::from_counts(fish.into_iter().map(|(days, count)| {
+        if days == 0usize {
+            (6usize, count)
+        } else {
+            (days - 1, count)
+        }
+    }));
+
+    final_fishes.add(8, new_fish_to_add);
+    final_fishes
+}
+
+// The number of fish at each timer value. None when a count, or anything on the way to it, doesn't
+// fit. Every product is reduced as soon as it's made when there's a modulo, which keeps it in a
+// u128 for any usize modulo.
+fn fish_by_matrix(
+    fishes: &Counter<usize>,
+    days: usize,
+    modulo: Option<usize>,
+) -> Option<[u128; 9]> {
+    let modulo = modulo.map(|modulo| modulo as u128);
+    let mut transition = [[0u128; 9]; 9];
+    (0..8).for_each(|timer| transition[timer][timer + 1] = 1);
+    transition[6][0] = 1;
+    transition[8][0] = 1;
+
+    let mut result = identity_matrix();
+    let mut power = transition;
+    let mut remaining_days = days;
+    while remaining_days > 0 {
+        if remaining_days % 2 == 1 {
+            result = multiply_matrices(&result, &power, modulo)?;
+        }
+        remaining_days /= 2;
+        if remaining_days > 0 {
+            power = multiply_matrices(&power, &power, modulo)?;
+        }
+    }
+
+    let mut by_timer = [0u128; 9];
+    for row in 0..9 {
+        by_timer[row] = (0..9).try_fold(0u128, |total, timer| {
+            let fish = result[row][timer].checked_mul(fishes.get(&timer) as u128)?;
+            reduce(total.checked_add(fish)?, modulo)
+        })?;
+    }
+    Some(by_timer)
+}
+
+// None when the total doesn't fit in a u128. Every count is at most the total without a modulo.
+fn matrix_total(by_timer: &[u128; 9], modulo: Option<usize>) -> Option<u128> {
+    let modulo = modulo.map(|modulo| modulo as u128);
+    by_timer.iter().try_fold(0u128, |total, count| {
+        reduce(total.checked_add(*count)?, modulo)
+    })
+}
+
+fn identity_matrix() -> Matrix {
+    let mut identity = [[0u128; 9]; 9];
+    (0..9).for_each(|index| identity[index][index] = 1);
+    identity
+}
+
+fn multiply_matrices(a: &Matrix, b: &Matrix, modulo: Option<u128>) -> Option<Matrix> {
+    let mut product = [[0u128; 9]; 9];
+    for row in 0..9 {
+        for column in 0..9 {
+            product[row][column] = (0..9).try_fold(0u128, |total, k| {
+                let term = reduce(a[row][k].checked_mul(b[k][column])?, modulo)?;
+                reduce(total.checked_add(term)?, modulo)
+            })?;
+        }
+    }
+    Some(product)
+}
+
+fn reduce(value: u128, modulo: Option<u128>) -> Option<u128> {
+    Some(modulo.map_or(value, |modulo| value % modulo))
+}
+
+fn describe(schools: &Vec<Counter<usize>>) -> String {
+    let fishes = merge_schools(schools);
+    let mut description = format!("{} fish", fishes.total());
+    if schools.len() > 1 {
+        description.push_str(&format!(" in {} schools", schools.len()));
+    }
+
+    if fishes.keys().any(|days| *days > 8usize) {
+        description.push_str("\nAnomaly: some fish have a timer above 8");
+    }
+
+    description
+}
+
+// One school per line. A school needs at least one fish, so a trailing newline doesn't add an
+// empty one.
+fn parse_data(input: &String) -> IResult<&str, Vec<Counter<usize>>> {
+    separated_list0(newline, parse_school)(input)
+}
+
+fn parse_school(input: &str) -> IResult<&str, Counter<usize>> {
+    map(separated_list1(tag(","), parse_usize), |fishes| {
+        fishes.into_iter().collect()
+    })(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{run_problem_with_input, scratch_path};
+
+    #[test]
+    fn part1_sample() {
+        assert_eq!(
+            run_problem_with_input(&LANTERNFISH, SAMPLE, &["part1"]).unwrap(),
+            5934usize.into()
+        );
+    }
+
+    #[test]
+    fn part2_sample() {
+        assert_eq!(
+            run_problem_with_input(&LANTERNFISH, SAMPLE, &["part2"]).unwrap(),
+            26984457539usize.into()
+        );
+    }
+    #[test]
+    fn matrix_sample() {
+        assert_eq!(
+            run_problem_with_input(&LANTERNFISH, SAMPLE, &["--part", "2", "-m"]).unwrap(),
+            26984457539usize.into()
+        );
+        assert_eq!(
+            run_problem_with_input(
+                &LANTERNFISH,
+                SAMPLE,
+                &["-f", "sample.txt", "-d", "256", "-m", "-o", "1000"]
+            )
+            .unwrap(),
+            539usize.into()
+        );
+    }
+
+    #[test]
+    fn breakdown_sample() {
+        let expected: CommandResult = vec![
+            ("Timer 0".to_string(), 3usize.into()),
+            ("Timer 1".to_string(), 5usize.into()),
+            ("Timer 2".to_string(), 3usize.into()),
+            ("Timer 3".to_string(), 2usize.into()),
+            ("Timer 4".to_string(), 2usize.into()),
+            ("Timer 5".to_string(), 1usize.into()),
+            ("Timer 6".to_string(), 5usize.into()),
+            ("Timer 7".to_string(), 1usize.into()),
+            ("Timer 8".to_string(), 4usize.into()),
+            ("Total".to_string(), 26usize.into()),
+        ]
+        .into();
+
+        assert_eq!(
+            run_problem_with_input(
+                &LANTERNFISH,
+                SAMPLE,
+                &["-f", "sample.txt", "-d", "18", "-b"]
+            )
+            .unwrap(),
+            expected
+        );
+        assert_eq!(
+            run_problem_with_input(
+                &LANTERNFISH,
+                SAMPLE,
+                &["-f", "sample.txt", "-d", "18", "-b", "-m"]
+            )
+            .unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn csv_sample() {
+        let path = scratch_path("lanternfish_csv_sample.csv");
+        let path = path.to_str().unwrap();
+
+        run_problem_with_input(
+            &LANTERNFISH,
+            SAMPLE,
+            &["-f", "sample.txt", "-d", "2", "-c", path],
+        )
+        .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(path).unwrap(),
+            "day,0,1,2,3,4,5,6,7,8,total\n\
+            0,0,1,1,2,1,0,0,0,0,5\n\
+            1,1,1,2,1,0,0,0,0,0,5\n\
+            2,1,2,1,0,0,0,1,0,1,6\n"
+        );
+    }
+
+    #[test]
+    fn unwritable_csv_is_an_error() {
+        let path = scratch_path("lanternfish_missing").join("fish.csv");
+        let path = path.to_str().unwrap();
+        let error = run_problem_with_input(
+            &LANTERNFISH,
+            SAMPLE,
+            &["-f", "sample.txt", "-d", "2", "-c", path],
+        )
+        .unwrap_err();
+
+        assert!(matches!(error, AocError::Io(_)));
+        assert!(error
+            .to_string()
+            .contains(&format!("{} can't be written", path)));
+    }
+
+    #[test]
+    fn separate_schools() {
+        let input = "3,4,3\n1,2\n";
+
+        assert_eq!(
+            run_problem_with_input(
+                &LANTERNFISH,
+                input,
+                &["-f", "sample.txt", "-d", "18", "-s", "separate"]
+            )
+            .unwrap(),
+            vec![
+                ("School 1".to_string(), 14usize.into()),
+                ("School 2".to_string(), 12usize.into()),
+                ("Total".to_string(), 26usize.into()),
+            ]
+            .into()
+        );
+        assert_eq!(
+            run_problem_with_input(&LANTERNFISH, input, &["--part", "1"]).unwrap(),
+            5934usize.into()
+        );
+        assert_eq!(
+            run_problem_with_input(
+                &LANTERNFISH,
+                input,
+                &[
+                    "-f",
+                    "sample.txt",
+                    "-d",
+                    "256",
+                    "-m",
+                    "-o",
+                    "1000",
+                    "-s",
+                    "separate"
+                ]
+            )
+            .unwrap(),
+            vec![
+                ("School 1".to_string(), 358usize.into()),
+                ("School 2".to_string(), 181usize.into()),
+                ("Total".to_string(), 539usize.into()),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn matrix_huge_days() {
+        assert!(run_problem_with_input(
+            &LANTERNFISH,
+            SAMPLE,
+            &["-f", "sample.txt", "-d", "1000", "-m"]
+        )
+        .unwrap_err()
+        .to_string()
+        .contains("doesn't fit in 128 bits"));
+        assert_eq!(
+            run_problem_with_input(
+                &LANTERNFISH,
+                SAMPLE,
+                &["-f", "sample.txt", "-d", "998", "-m"]
+            )
+            .unwrap(),
+            318892759258767436907541348343071883668u128.into()
+        );
+        assert!(
+            run_problem_with_input(&LANTERNFISH, SAMPLE, &["-f", "sample.txt", "-d", "500"])
+                .unwrap_err()
+                .to_string()
+                .contains("pass --matrix")
+        );
+        assert!(run_problem_with_input(
+            &LANTERNFISH,
+            SAMPLE,
+            &[
+                "-f",
+                "sample.txt",
+                "-d",
+                "1000000000000",
+                "-m",
+                "-o",
+                "1000000007"
+            ]
+        )
+        .is_ok());
+    }
+}
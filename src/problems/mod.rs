@@ -0,0 +1,160 @@
+// Each day is re-exported under its puzzle number so solvers can be used without the CLI, for
+// example problems::day01::SONAR_SWEEP.
+
+use crate::Command;
+
+mod alu;
+mod amphipod;
+mod beacon_scanner;
+mod binary_diagnostic;
+mod chiton;
+mod dirac_dice;
+mod dive;
+mod dumbo_octopus;
+mod extended_polymerization;
+mod giant_squid;
+mod hydrothermal_venture;
+mod lanternfish;
+mod packet_decoder;
+mod passage_pathing;
+mod reactor_reboot;
+mod sea_cucumber;
+mod seven_segment;
+mod smoke_basin;
+mod snailfish;
+mod sonar_sweep;
+mod syntax_scoring;
+mod transparent_origami;
+mod trench_map;
+mod trick_shot;
+mod whale_treachery;
+
+pub mod day01 {
+    pub use super::sonar_sweep::*;
+}
+
+pub mod day02 {
+    pub use super::dive::*;
+}
+
+pub mod day03 {
+    pub use super::binary_diagnostic::*;
+}
+
+pub mod day04 {
+    pub use super::giant_squid::*;
+}
+
+pub mod day05 {
+    pub use super::hydrothermal_venture::*;
+}
+
+pub mod day06 {
+    pub use super::lanternfish::*;
+}
+
+pub mod day07 {
+    pub use super::whale_treachery::*;
+}
+
+pub mod day08 {
+    pub use super::seven_segment::*;
+}
+
+pub mod day09 {
+    pub use super::smoke_basin::*;
+}
+
+pub mod day10 {
+    pub use super::syntax_scoring::*;
+}
+
+pub mod day11 {
+    pub use super::dumbo_octopus::*;
+}
+
+pub mod day12 {
+    pub use super::passage_pathing::*;
+}
+
+pub mod day13 {
+    pub use super::transparent_origami::*;
+}
+
+pub mod day14 {
+    pub use super::extended_polymerization::*;
+}
+
+pub mod day15 {
+    pub use super::chiton::*;
+}
+
+pub mod day16 {
+    pub use super::packet_decoder::*;
+}
+
+pub mod day17 {
+    pub use super::trick_shot::*;
+}
+
+pub mod day18 {
+    pub use super::snailfish::*;
+}
+
+pub mod day19 {
+    pub use super::beacon_scanner::*;
+}
+
+pub mod day20 {
+    pub use super::trench_map::*;
+}
+
+pub mod day21 {
+    pub use super::dirac_dice::*;
+}
+
+pub mod day22 {
+    pub use super::reactor_reboot::*;
+}
+
+pub mod day23 {
+    pub use super::amphipod::*;
+}
+
+pub mod day24 {
+    pub use super::alu::*;
+}
+
+pub mod day25 {
+    pub use super::sea_cucumber::*;
+}
+
+pub fn commands() -> Vec<Box<dyn Command>> {
+    vec![
+        Box::new(day01::SONAR_SWEEP),
+        Box::new(day02::DIVE),
+        Box::new(day03::BINARY_DIAGNOSTIC),
+        Box::new(day04::GIANT_SQUID),
+        Box::new(day05::HYDROTHERMAL_VENTURE),
+        Box::new(day06::LANTERNFISH),
+        Box::new(day07::WHALE_TREACHERY),
+        Box::new(day08::SEVEN_SEGMENT),
+        Box::new(day09::SMOKE_BASIN),
+        Box::new(day10::SYNTAX_SCORING),
+        Box::new(day11::DUMBO_OCTOPUS),
+        Box::new(day12::PASSAGE_PATHING),
+        Box::new(day13::TRANSPARENT_ORIGAMI),
+        Box::new(day14::EXTENDED_POLYMERIZATION),
+        Box::new(day15::CHITON),
+        Box::new(day16::PACKET_DECODER),
+        Box::new(day17::TRICK_SHOT),
+        Box::new(day18::SNAILFISH),
+        Box::new(day19::BEACON_SCANNER),
+        Box::new(day20::TRENCH_MAP),
+        Box::new(day21::DIRAC_DICE),
+        Box::new(day22::REACTOR_REBOOT),
+        Box::new(day23::AMPHIPOD),
+        Box::new(day24::ALU),
+        Box::new(day25::SEA_CUCUMBER),
+    ]
+}
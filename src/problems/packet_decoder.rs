@@ -0,0 +1,1144 @@
+use crate::{
+    count_result, default_sub_command, selected_part, AocError, CommandResult, Example, Problem,
+};
+use clap::{value_t_or_exit, App, Arg, ArgMatches};
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take},
+    combinator::{cut, flat_map, map, map_res, rest},
+    error::{ErrorKind, FromExternalError, ParseError},
+    multi::{count, many0, many_till},
+    sequence::{preceded, tuple},
+    IResult,
+};
+use num_bigint::BigUint;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::BufRead;
+use std::str;
+use strum::VariantNames;
+use strum_macros::{EnumString, EnumVariantNames};
+
+pub const PACKET_DECODER: Problem<PacketDecoderArgs, Vec<u8>> = Problem::new(
+    sub_command,
+    "packet-decoder",
+    "day16_packet_decoder",
+    parse_arguments,
+    parse_data,
+    run,
+)
+.with_long_about(LONG_ABOUT)
+.with_examples(EXAMPLES)
+.with_describe(describe)
+.with_input_check(input_check)
+.with_stream(stream)
+.with_stream_if(is_file_bytes);
+
+const LONG_ABOUT: &str =
+    "The input is a hexadecimal transmission holding a single BITS packet.\n\n\
+    Each packet starts with a 3 bit version and a 3 bit type id. Type 4 is a literal value. \
+    Every other type is an operator over its sub packets: 0 sum, 1 product, 2 minimum, 3 \
+    maximum, 5 greater than, 6 less than and 7 equal to. sum-versions adds up the version of \
+    every packet. process-packet evaluates the expression. max-depth finds how deeply packets \
+    are nested, counting the outermost packet as 1. packet-count counts the packets of each type \
+    along with the total.\n\n\
+    -t reads the transmission as hex (the default), a string of bits, base64, or the raw bytes \
+    of the input file.\n\n\
+    A transmission that can't be decoded is reported with where it went wrong: an invalid \
+    character, a packet or literal value cut off, sub packets that don't fill the length their \
+    operator declares, an operator without any sub packets or a comparison without exactly \
+    two. With -l the packets are read one after another until one can't be, and the answer \
+    covers every whole packet read, reporting how many bits were discarded. \
+    process-packet then gives the value of each packet when there is more than one.\n\n\
+    Example: 8A004A801A8002F478 has a version sum of 16 and C200B40A82 evaluates to 3.";
+
+const SAMPLE: &str = include_str!("../../day16_packet_decoder/sample.txt");
+
+const SAMPLE2: &str = include_str!("../../day16_packet_decoder/sample2.txt");
+
+const SAMPLE3: &str = include_str!("../../day16_packet_decoder/sample3.txt");
+
+const SAMPLE4: &str = include_str!("../../day16_packet_decoder/sample4.txt");
+
+const SAMPLE5: &str = include_str!("../../day16_packet_decoder/sample5.txt");
+
+const SAMPLE6: &str = include_str!("../../day16_packet_decoder/sample6.txt");
+
+const SAMPLE7: &str = include_str!("../../day16_packet_decoder/sample7.txt");
+
+const EXAMPLES: &[Example] = &[
+    Example::new(SAMPLE, 1, "6"),
+    Example::new(SAMPLE, 2, "2021"),
+    Example::new(SAMPLE2, 1, "9"),
+    Example::new(SAMPLE2, 2, "1"),
+    Example::new(SAMPLE3, 1, "14"),
+    Example::new(SAMPLE3, 2, "3"),
+    Example::new(SAMPLE4, 1, "16"),
+    Example::new(SAMPLE5, 1, "12"),
+    Example::new(SAMPLE6, 1, "23"),
+    Example::new(SAMPLE7, 1, "31"),
+];
+
+#[derive(Debug)]
+pub struct PacketDecoderArgs {
+    operation: Operation,
+    format: Format,
+    lenient: bool,
+}
+
+#[derive(Debug, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "kebab_case")]
+enum Operation {
+    SumVersions,
+    ProcessPacket,
+    MaxDepth,
+    PacketCount,
+}
+
+#[derive(Debug, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "kebab_case")]
+enum Format {
+    Hex,
+    Binary,
+    Base64,
+    FileBytes,
+}
+
+#[derive(Debug, Clone)]
+pub struct Packet {
+    version: usize,
+    type_id: usize,
+    packet_contents: PacketContents,
+}
+
+#[derive(Debug, Clone)]
+enum PacketContents {
+    Literal { value: usize },
+    Operator { sub_packets: Vec<Packet> },
+}
+
+fn sub_command() -> App<'static, 'static> {
+    default_sub_command(
+        &PACKET_DECODER,
+        "Parses a packet then performs some operation on the result",
+        "Hex encode string of the packet.",
+        "Parses the packet, then sums all the versions inside.",
+        "Parses the packet, then performs all operations inside and returns the result.",
+    ).arg(
+        Arg::with_name("operation")
+            .short("o")
+            .help(
+                "The type of operation to perform on the packet. The operations available are as follows:\n\n\
+            sum-versions: Sums all version values in the packet.\n\n\
+            process-packet: Processes the instructions of the packet.\n\n\
+            max-depth: Finds the deepest nesting of packets.\n\n\
+            packet-count: Counts the packets of each type.\n\n",
+            )
+            .takes_value(true)
+            .possible_values(&Operation::VARIANTS)
+            .required_unless_one(&["part", "explain"]),
+    )
+    .arg(
+        Arg::with_name("format")
+            .short("t")
+            .long("format")
+            .help(
+                "The format of the transmission. The formats available are as follows:\n\n\
+            hex: Hexadecimal characters, as in the puzzle.\n\n\
+            binary: A string of 0 and 1 characters.\n\n\
+            base64: The transmission bytes encoded as base64.\n\n\
+            file-bytes: The raw bytes of the input file.\n\n",
+            )
+            .takes_value(true)
+            .possible_values(Format::VARIANTS)
+            .default_value("hex"),
+    )
+    .arg(
+        Arg::with_name("lenient")
+            .short("l")
+            .long("lenient")
+            .help("If passed, decodes every whole packet it can and reports the bits it discarded instead of failing."),
+    )
+}
+
+fn parse_arguments(arguments: &ArgMatches) -> PacketDecoderArgs {
+    match selected_part(arguments) {
+        Some("part1") => PacketDecoderArgs {
+            operation: Operation::SumVersions,
+            format: value_t_or_exit!(arguments.value_of("format"), Format),
+            lenient: arguments.is_present("lenient"),
+        },
+        Some("part2") => PacketDecoderArgs {
+            operation: Operation::ProcessPacket,
+            format: value_t_or_exit!(arguments.value_of("format"), Format),
+            lenient: arguments.is_present("lenient"),
+        },
+        _ => PacketDecoderArgs {
+            operation: value_t_or_exit!(arguments.value_of("operation"), Operation),
+            format: value_t_or_exit!(arguments.value_of("format"), Format),
+            lenient: arguments.is_present("lenient"),
+        },
+    }
+}
+
+fn is_file_bytes(arguments: &ArgMatches) -> bool {
+    arguments.value_of("format") == Some("file-bytes")
+}
+
+fn input_check(arguments: &PacketDecoderArgs, transmission: &Vec<u8>) -> Result<(), AocError> {
+    if arguments.lenient {
+        decode_lenient(&arguments.format, transmission)?;
+    } else {
+        decode_packet(&arguments.format, transmission)?;
+    }
+    Ok(())
+}
+
+fn stream(
+    arguments: PacketDecoderArgs,
+    reader: &mut dyn BufRead,
+) -> Result<CommandResult, AocError> {
+    let mut transmission = Vec::new();
+    reader.read_to_end(&mut transmission)?;
+
+    if arguments.lenient {
+        let (packets, discarded) = decode_lenient(&arguments.format, &transmission)?;
+        return Ok(evaluate_lenient(&arguments, &packets, discarded));
+    }
+
+    let packet = decode_packet(&arguments.format, &transmission)?;
+    Ok(evaluate_packet(&arguments, &packet))
+}
+
+fn run(arguments: PacketDecoderArgs, transmission: Vec<u8>) -> CommandResult {
+    if arguments.lenient {
+        let (packets, discarded) =
+            decode_lenient(&arguments.format, &transmission).expect("Transmission was checked");
+        return evaluate_lenient(&arguments, &packets, discarded);
+    }
+
+    let packet = decode_packet(&arguments.format, &transmission).expect("Transmission was checked");
+    evaluate_packet(&arguments, &packet)
+}
+
+// How many packets were decoded and how many bits were discarded come ahead of the answer, which
+// is spliced in when it already has labels of its own.
+fn evaluate_lenient(
+    arguments: &PacketDecoderArgs,
+    packets: &[Packet],
+    discarded: usize,
+) -> CommandResult {
+    let mut result = vec![
+        ("Whole packets".to_string(), packets.len().into()),
+        ("Discarded bits".to_string(), discarded.into()),
+    ];
+    match lenient_answer(arguments, packets) {
+        CommandResult::Labeled(answers) => result.extend(answers),
+        answer => result.push(("Answer".to_string(), answer)),
+    }
+
+    result.into()
+}
+
+fn lenient_answer(arguments: &PacketDecoderArgs, packets: &[Packet]) -> CommandResult {
+    match (&arguments.operation, packets) {
+        (_, [packet]) => evaluate_packet(arguments, packet),
+        (Operation::SumVersions, _) => packets
+            .iter()
+            .map(sum_packet_versions)
+            .sum::<usize>()
+            .into(),
+        (Operation::ProcessPacket, _) => packets
+            .iter()
+            .enumerate()
+            .map(|(index, packet)| {
+                (
+                    format!("Packet {}", index + 1),
+                    count_result(process_packet(packet)),
+                )
+            })
+            .collect::<Vec<(String, CommandResult)>>()
+            .into(),
+        (Operation::MaxDepth, _) => packets.iter().map(max_depth).max().unwrap_or(0).into(),
+        (Operation::PacketCount, _) => packet_counts(packets),
+    }
+}
+
+fn evaluate_packet(arguments: &PacketDecoderArgs, packet: &Packet) -> CommandResult {
+    match arguments.operation {
+        Operation::SumVersions => sum_packet_versions(packet).into(),
+        Operation::ProcessPacket => count_result(process_packet(packet)),
+        Operation::MaxDepth => max_depth(packet).into(),
+        Operation::PacketCount => packet_counts(std::slice::from_ref(packet)),
+    }
+}
+
+fn max_depth(packet: &Packet) -> usize {
+    match &packet.packet_contents {
+        PacketContents::Literal { value: _ } => 1usize,
+        PacketContents::Operator { sub_packets } => {
+            sub_packets.iter().map(max_depth).max().unwrap_or(0usize) + 1
+        }
+    }
+}
+
+// Only the types that appear are listed, in type id order, followed by the total.
+fn packet_counts(packets: &[Packet]) -> CommandResult {
+    let mut counts = BTreeMap::new();
+    packets
+        .iter()
+        .for_each(|packet| count_packets_by_type(packet, &mut counts));
+    let total: usize = counts.values().sum();
+
+    counts
+        .into_iter()
+        .map(|(type_id, count)| (type_name(type_id).to_string(), count.into()))
+        .chain([("Total".to_string(), total.into())])
+        .collect::<Vec<(String, CommandResult)>>()
+        .into()
+}
+
+fn count_packets_by_type(packet: &Packet, counts: &mut BTreeMap<usize, usize>) {
+    *counts.entry(packet.type_id).or_insert(0usize) += 1;
+    if let PacketContents::Operator { sub_packets } = &packet.packet_contents {
+        sub_packets
+            .iter()
+            .for_each(|sub_packet| count_packets_by_type(sub_packet, counts));
+    }
+}
+
+fn type_name(type_id: usize) -> &'static str {
+    match type_id {
+        0 => "Sum",
+        1 => "Product",
+        2 => "Minimum",
+        3 => "Maximum",
+        4 => "Literal",
+        5 => "Greater than",
+        6 => "Less than",
+        _ => "Equal to",
+    }
+}
+
+// Sums and products of 64 bit literals soon outgrow a usize, so values are unbounded. Decoding has
+// already made sure every operator has the sub packets it needs.
+fn process_packet(packet: &Packet) -> BigUint {
+    match &packet.packet_contents {
+        PacketContents::Literal { value } => BigUint::from(*value),
+        PacketContents::Operator { sub_packets } => {
+            if packet.type_id == 0 {
+                sub_packets
+                    .iter()
+                    .map(process_packet)
+                    .fold(BigUint::from(0usize), |acc, result| acc + result)
+            } else if packet.type_id == 1 {
+                sub_packets
+                    .iter()
+                    .map(process_packet)
+                    .fold(BigUint::from(1usize), |acc, result| acc * result)
+            } else if packet.type_id == 2 {
+                sub_packets.iter().map(process_packet).min().unwrap()
+            } else if packet.type_id == 3 {
+                sub_packets.iter().map(process_packet).max().unwrap()
+            } else if packet.type_id == 5 {
+                let first = sub_packets.first().map(process_packet).unwrap();
+                let second = sub_packets.last().map(process_packet).unwrap();
+                if first > second {
+                    BigUint::from(1usize)
+                } else {
+                    BigUint::from(0usize)
+                }
+            } else if packet.type_id == 6 {
+                let first = sub_packets.first().map(process_packet).unwrap();
+                let second = sub_packets.last().map(process_packet).unwrap();
+                if first < second {
+                    BigUint::from(1usize)
+                } else {
+                    BigUint::from(0usize)
+                }
+            } else {
+                let first = sub_packets.first().map(process_packet).unwrap();
+                let second = sub_packets.last().map(process_packet).unwrap();
+                if first == second {
+                    BigUint::from(1usize)
+                } else {
+                    BigUint::from(0usize)
+                }
+            }
+        }
+    }
+}
+
+fn sum_packet_versions(packet: &Packet) -> usize {
+    match &packet.packet_contents {
+        PacketContents::Literal { value: _ } => packet.version,
+        PacketContents::Operator { sub_packets } => {
+            sub_packets
+                .iter()
+                .map(sum_packet_versions)
+                .fold(0usize, |acc, result| acc + result)
+                + packet.version
+        }
+    }
+}
+
+// The format isn't known when validating, so only hex transmissions are decoded.
+fn describe(transmission: &Vec<u8>) -> String {
+    match decode_packet(&Format::Hex, transmission) {
+        Ok(packet) => format!("Transmission of {} packets", count_packets(&packet)),
+        Err(error) => format!(
+            "Transmission of {} bytes that isn't a hex packet\nAnomaly: {}",
+            transmission.len(),
+            error
+        ),
+    }
+}
+
+fn count_packets(packet: &Packet) -> usize {
+    match &packet.packet_contents {
+        PacketContents::Literal { value: _ } => 1usize,
+        PacketContents::Operator { sub_packets } => sub_packets
+            .iter()
+            .map(count_packets)
+            .fold(1usize, |acc, result| acc + result),
+    }
+}
+
+fn parse_data(input: &String) -> IResult<&str, Vec<u8>> {
+    map(rest, |transmission: &str| transmission.as_bytes().to_vec())(input)
+}
+
+// Why a transmission couldn't be decoded. Character offsets count from the start of the trimmed
+// text, bit offsets from the start of the transmission's bits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransmissionError {
+    InvalidCharacter {
+        offset: usize,
+        character: char,
+    },
+    InvalidFormat {
+        format: String,
+    },
+    TruncatedPacket {
+        offset: usize,
+    },
+    TruncatedLiteral {
+        offset: usize,
+    },
+    LiteralTooLarge {
+        offset: usize,
+    },
+    LengthMismatch {
+        offset: usize,
+        declared: usize,
+        used: usize,
+    },
+    SubPacketCount {
+        offset: usize,
+        type_id: usize,
+        count: usize,
+    },
+}
+
+impl fmt::Display for TransmissionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransmissionError::InvalidCharacter { offset, character } => write!(
+                f,
+                "The transmission has an invalid character {:?} at offset {}",
+                character, offset
+            ),
+            TransmissionError::InvalidFormat { format } => {
+                write!(f, "The transmission is not valid {}", format)
+            }
+            TransmissionError::TruncatedPacket { offset } => {
+                write!(f, "The transmission ends mid packet at bit {}", offset)
+            }
+            TransmissionError::TruncatedLiteral { offset } => write!(
+                f,
+                "The literal value at bit {} ends before its last group",
+                offset
+            ),
+            TransmissionError::LiteralTooLarge { offset } => write!(
+                f,
+                "The literal value at bit {} doesn't fit in 64 bits",
+                offset
+            ),
+            TransmissionError::LengthMismatch {
+                offset,
+                declared,
+                used,
+            } => write!(
+                f,
+                "The sub packets at bit {} use {} bits but their operator declares {}",
+                offset, used, declared
+            ),
+            TransmissionError::SubPacketCount {
+                offset,
+                type_id,
+                count,
+            } => write!(
+                f,
+                "The {} packet at bit {} has {} sub packets but needs {}",
+                type_name(*type_id),
+                offset,
+                count,
+                if *type_id < 4 {
+                    "at least 1"
+                } else {
+                    "exactly 2"
+                }
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TransmissionError {}
+
+impl From<TransmissionError> for AocError {
+    fn from(error: TransmissionError) -> Self {
+        AocError::validation(error.to_string())
+    }
+}
+
+// The packet parser's error. Parsers only see what is left of the bits, so positions are kept as
+// the number of bits remaining and turned into offsets once the whole length is known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum BitsError {
+    TruncatedPacket {
+        remaining: usize,
+    },
+    TruncatedLiteral {
+        remaining: usize,
+    },
+    LiteralTooLarge {
+        remaining: usize,
+    },
+    LengthMismatch {
+        remaining: usize,
+        declared: usize,
+        used: usize,
+    },
+    SubPacketCount {
+        remaining: usize,
+        type_id: usize,
+        count: usize,
+    },
+}
+
+impl BitsError {
+    // For errors from a slice of the bits, with this many bits following the slice.
+    fn shift(self, bits: usize) -> BitsError {
+        match self {
+            BitsError::TruncatedPacket { remaining } => BitsError::TruncatedPacket {
+                remaining: remaining + bits,
+            },
+            BitsError::TruncatedLiteral { remaining } => BitsError::TruncatedLiteral {
+                remaining: remaining + bits,
+            },
+            BitsError::LiteralTooLarge { remaining } => BitsError::LiteralTooLarge {
+                remaining: remaining + bits,
+            },
+            BitsError::LengthMismatch {
+                remaining,
+                declared,
+                used,
+            } => BitsError::LengthMismatch {
+                remaining: remaining + bits,
+                declared,
+                used,
+            },
+            BitsError::SubPacketCount {
+                remaining,
+                type_id,
+                count,
+            } => BitsError::SubPacketCount {
+                remaining: remaining + bits,
+                type_id,
+                count,
+            },
+        }
+    }
+
+    fn at(self, total: usize) -> TransmissionError {
+        match self {
+            BitsError::TruncatedPacket { remaining } => TransmissionError::TruncatedPacket {
+                offset: total - remaining,
+            },
+            BitsError::TruncatedLiteral { remaining } => TransmissionError::TruncatedLiteral {
+                offset: total - remaining,
+            },
+            BitsError::LiteralTooLarge { remaining } => TransmissionError::LiteralTooLarge {
+                offset: total - remaining,
+            },
+            BitsError::LengthMismatch {
+                remaining,
+                declared,
+                used,
+            } => TransmissionError::LengthMismatch {
+                offset: total - remaining,
+                declared,
+                used,
+            },
+            BitsError::SubPacketCount {
+                remaining,
+                type_id,
+                count,
+            } => TransmissionError::SubPacketCount {
+                offset: total - remaining,
+                type_id,
+                count,
+            },
+        }
+    }
+}
+
+// Any error nom raises itself means the bits ran out.
+impl<'a> ParseError<&'a str> for BitsError {
+    fn from_error_kind(input: &'a str, _kind: ErrorKind) -> Self {
+        BitsError::TruncatedPacket {
+            remaining: input.len(),
+        }
+    }
+
+    fn append(_input: &'a str, _kind: ErrorKind, other: Self) -> Self {
+        other
+    }
+}
+
+impl<'a, E> FromExternalError<&'a str, E> for BitsError {
+    fn from_external_error(input: &'a str, kind: ErrorKind, _error: E) -> Self {
+        BitsError::from_error_kind(input, kind)
+    }
+}
+
+type BitsResult<'a, T> = IResult<&'a str, T, BitsError>;
+
+// A transmission turned into a string of bits. A bad character in a text format ends the bits
+// there, and the characters from it on are dropped, so a lenient decode can still use the bits
+// before it.
+struct TransmissionBits {
+    bits: String,
+    invalid: Option<TransmissionError>,
+    dropped: usize,
+}
+
+// Every format is turned into a string of bits for the packet parser. Surrounding whitespace is
+// ignored for the text formats.
+fn transmission_bits(
+    format: &Format,
+    transmission: &[u8],
+) -> Result<TransmissionBits, TransmissionError> {
+    let invalid_format = || TransmissionError::InvalidFormat {
+        format: format!("{:?}", format),
+    };
+    let bits = match format {
+        Format::Hex => character_bits(transmission_text(transmission), 4, |c| {
+            c.to_digit(16).map(|digit| format!("{:04b}", digit))
+        }),
+        Format::Binary => character_bits(transmission_text(transmission), 1, |c| {
+            matches!(c, '0' | '1').then(|| c.to_string())
+        }),
+        Format::Base64 => transmission_text(transmission)
+            .and_then(decode_base64)
+            .map(|bytes| TransmissionBits {
+                bits: bytes_to_bits(&bytes),
+                invalid: None,
+                dropped: 0usize,
+            }),
+        Format::FileBytes => Some(TransmissionBits {
+            bits: bytes_to_bits(transmission),
+            invalid: None,
+            dropped: 0usize,
+        }),
+    };
+
+    bits.ok_or_else(invalid_format)
+}
+
+fn character_bits(
+    text: Option<&str>,
+    bits_per_character: usize,
+    to_bits: impl Fn(char) -> Option<String>,
+) -> Option<TransmissionBits> {
+    let text = text?;
+    let mut bits = String::new();
+    for (offset, character) in text.chars().enumerate() {
+        match to_bits(character) {
+            Some(character_bits) => bits.push_str(&character_bits),
+            None => {
+                return Some(TransmissionBits {
+                    bits,
+                    invalid: Some(TransmissionError::InvalidCharacter { offset, character }),
+                    dropped: (text.chars().count() - offset) * bits_per_character,
+                })
+            }
+        }
+    }
+
+    Some(TransmissionBits {
+        bits,
+        invalid: None,
+        dropped: 0usize,
+    })
+}
+
+// The zero bits padding out the end of the transmission are ignored.
+fn decode_packet(format: &Format, transmission: &[u8]) -> Result<Packet, TransmissionError> {
+    let transmission = transmission_bits(format, transmission)?;
+    if let Some(invalid) = transmission.invalid {
+        return Err(invalid);
+    }
+
+    parse_packet(&transmission.bits)
+        .map(|(_, packet)| packet)
+        .map_err(|error| bits_error(error, transmission.bits.len()))
+}
+
+// Reads whole packets one after another until one can't be read or only zero padding is left,
+// returning them with the number of bits that weren't part of any. Fails with why the first packet
+// couldn't be read if there isn't a single whole one.
+fn decode_lenient(
+    format: &Format,
+    transmission: &[u8],
+) -> Result<(Vec<Packet>, usize), TransmissionError> {
+    let transmission = transmission_bits(format, transmission)?;
+    let mut remaining = transmission.bits.as_str();
+    let mut packets = Vec::new();
+
+    while !remaining.chars().all(|bit| bit == '0') {
+        match parse_packet(remaining) {
+            Ok((rest, packet)) => {
+                packets.push(packet);
+                remaining = rest;
+            }
+            Err(error) if packets.is_empty() => {
+                return Err(transmission
+                    .invalid
+                    .unwrap_or_else(|| bits_error(error, transmission.bits.len())))
+            }
+            Err(_) => break,
+        }
+    }
+
+    match transmission.invalid {
+        Some(invalid) if packets.is_empty() => Err(invalid),
+        _ if packets.is_empty() => Err(TransmissionError::TruncatedPacket { offset: 0usize }),
+        _ => Ok((packets, remaining.len() + transmission.dropped)),
+    }
+}
+
+fn bits_error(error: nom::Err<BitsError>, total: usize) -> TransmissionError {
+    match error {
+        nom::Err::Error(error) | nom::Err::Failure(error) => error.at(total),
+        nom::Err::Incomplete(_) => TransmissionError::TruncatedPacket { offset: total },
+    }
+}
+
+fn transmission_text(transmission: &[u8]) -> Option<&str> {
+    str::from_utf8(transmission).ok().map(str::trim)
+}
+
+fn bytes_to_bits(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:08b}", byte)).collect()
+}
+
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let sextets = text
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .take_while(|c| *c != '=')
+        .map(|c| match c {
+            'A'..='Z' => Some(c as u32 - 'A' as u32),
+            'a'..='z' => Some(c as u32 - 'a' as u32 + 26),
+            '0'..='9' => Some(c as u32 - '0' as u32 + 52),
+            '+' => Some(62u32),
+            '/' => Some(63u32),
+            _ => None,
+        })
+        .collect::<Option<Vec<u32>>>()?;
+
+    if sextets.len() % 4 == 1 {
+        return None;
+    }
+
+    // Each group of 4 characters holds 3 bytes, a shorter final group holds one byte fewer than
+    // it has characters.
+    Some(
+        sextets
+            .chunks(4)
+            .flat_map(|group| {
+                let bits = group.iter().fold(0u32, |acc, sextet| acc << 6 | sextet)
+                    << (6 * (4 - group.len()));
+                (0..group.len() - 1).map(move |index| (bits >> (16 - 8 * index)) as u8)
+            })
+            .collect(),
+    )
+}
+
+// Once the header is read the packet is committed to, so errors in its body aren't backtracked out
+// of and keep their position.
+fn parse_packet(input: &str) -> BitsResult<'_, Packet> {
+    map(
+        tuple((
+            parse_packet_version,
+            flat_map(parse_type_id, |type_id| cut(parse_packet_info(type_id))),
+        )),
+        |(version, (type_id, packet_contents))| Packet {
+            version: version,
+            type_id: type_id,
+            packet_contents: packet_contents,
+        },
+    )(input)
+}
+
+fn parse_packet_version(input: &str) -> BitsResult<'_, usize> {
+    parse_bits_number(3usize)(input)
+}
+
+fn parse_type_id(input: &str) -> BitsResult<'_, usize> {
+    parse_bits_number(3usize)(input)
+}
+
+fn parse_bits_number(length: usize) -> impl Fn(&str) -> BitsResult<'_, usize> {
+    move |input| map_res(take(length), |bits| usize::from_str_radix(bits, 2))(input)
+}
+
+fn parse_packet_info(type_id: usize) -> impl Fn(&str) -> BitsResult<'_, (usize, PacketContents)> {
+    move |input| {
+        if type_id == 4 {
+            map(parse_literal, |contents| (type_id, contents))(input)
+        } else {
+            map(parse_sub_packets(type_id), |contents| (type_id, contents))(input)
+        }
+    }
+}
+
+fn parse_literal(input: &str) -> BitsResult<'_, PacketContents> {
+    let (rest, (list, last)) = many_till(
+        preceded(tag("1"), take(4usize)),
+        preceded(tag("0"), take(4usize)),
+    )(input)
+    .map_err(|error: nom::Err<BitsError>| {
+        error.map(|_| BitsError::TruncatedLiteral {
+            remaining: input.len(),
+        })
+    })?;
+
+    let mut result = list.join("");
+    result.push_str(last);
+    usize::from_str_radix(&result, 2)
+        .map(|value| (rest, PacketContents::Literal { value }))
+        .map_err(|_| {
+            nom::Err::Failure(BitsError::LiteralTooLarge {
+                remaining: input.len(),
+            })
+        })
+}
+
+// Sum, product, minimum and maximum need at least one sub packet and the comparisons exactly two.
+// The error points at the operator's header.
+fn parse_sub_packets(type_id: usize) -> impl Fn(&str) -> BitsResult<'_, PacketContents> {
+    move |input| {
+        let (rest, sub_packets) = alt((
+            parse_sub_packets_by_length,
+            flat_map(
+                preceded(tag("1"), parse_bits_number(11usize)),
+                parse_n_packets,
+            ),
+        ))(input)?;
+
+        let count = sub_packets.len();
+        if (type_id < 4 && count == 0) || (type_id > 4 && count != 2) {
+            return Err(nom::Err::Failure(BitsError::SubPacketCount {
+                remaining: input.len() + 6,
+                type_id,
+                count,
+            }));
+        }
+
+        Ok((rest, PacketContents::Operator { sub_packets }))
+    }
+}
+
+// The sub packets have to fill exactly the number of bits their operator declares.
+fn parse_sub_packets_by_length(input: &str) -> BitsResult<'_, Vec<Packet>> {
+    let (sub_packets_start, length) = preceded(tag("0"), parse_bits_number(15usize))(input)?;
+    let (rest, sub_packet_bits) = take(length)(sub_packets_start)?;
+    let (unused, sub_packets) = many0(parse_packet)(sub_packet_bits)
+        .map_err(|error| error.map(|error| error.shift(rest.len())))?;
+
+    if !unused.is_empty() {
+        return Err(nom::Err::Failure(BitsError::LengthMismatch {
+            remaining: sub_packets_start.len(),
+            declared: length,
+            used: length - unused.len(),
+        }));
+    }
+
+    Ok((rest, sub_packets))
+}
+
+fn parse_n_packets(n: usize) -> impl Fn(&str) -> BitsResult<'_, Vec<Packet>> {
+    move |input| count(parse_packet, n)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_problem_with_input;
+
+    #[test]
+    fn part1_sample() {
+        assert_eq!(
+            run_problem_with_input(&PACKET_DECODER, SAMPLE, &["part1"]).unwrap(),
+            6usize.into()
+        );
+    }
+
+    #[test]
+    fn part2_sample() {
+        assert_eq!(
+            run_problem_with_input(&PACKET_DECODER, SAMPLE, &["part2"]).unwrap(),
+            2021usize.into()
+        );
+    }
+
+    #[test]
+    fn product_beyond_64_bits() {
+        let product: BigUint = BigUint::from(1usize) << 126usize;
+
+        assert_eq!(
+            run_problem_with_input(
+                &PACKET_DECODER,
+                "060084C42108421084210842001310842108421084210800",
+                &["part2"]
+            )
+            .unwrap(),
+            product.into()
+        );
+    }
+
+    #[test]
+    fn part1_sample2() {
+        assert_eq!(
+            run_problem_with_input(&PACKET_DECODER, SAMPLE2, &["part1"]).unwrap(),
+            9usize.into()
+        );
+    }
+
+    #[test]
+    fn part2_sample2() {
+        assert_eq!(
+            run_problem_with_input(&PACKET_DECODER, SAMPLE2, &["part2"]).unwrap(),
+            1usize.into()
+        );
+    }
+
+    #[test]
+    fn part1_sample3() {
+        assert_eq!(
+            run_problem_with_input(&PACKET_DECODER, SAMPLE3, &["part1"]).unwrap(),
+            14usize.into()
+        );
+    }
+
+    #[test]
+    fn part2_sample3() {
+        assert_eq!(
+            run_problem_with_input(&PACKET_DECODER, SAMPLE3, &["part2"]).unwrap(),
+            3usize.into()
+        );
+    }
+
+    #[test]
+    fn part1_sample4() {
+        assert_eq!(
+            run_problem_with_input(&PACKET_DECODER, SAMPLE4, &["part1"]).unwrap(),
+            16usize.into()
+        );
+    }
+
+    #[test]
+    fn part1_sample5() {
+        assert_eq!(
+            run_problem_with_input(&PACKET_DECODER, SAMPLE5, &["part1"]).unwrap(),
+            12usize.into()
+        );
+    }
+
+    #[test]
+    fn part1_sample6() {
+        assert_eq!(
+            run_problem_with_input(&PACKET_DECODER, SAMPLE6, &["part1"]).unwrap(),
+            23usize.into()
+        );
+    }
+
+    #[test]
+    fn part1_sample7() {
+        assert_eq!(
+            run_problem_with_input(&PACKET_DECODER, SAMPLE7, &["part1"]).unwrap(),
+            31usize.into()
+        );
+    }
+    #[test]
+    fn binary_format_sample4() {
+        assert_eq!(
+            run_problem_with_input(
+                &PACKET_DECODER,
+                "100010100000000001001010100000000001101010000000000000101111010001111000",
+                &["--part", "1", "--format", "binary"]
+            )
+            .unwrap(),
+            16usize.into()
+        );
+    }
+
+    #[test]
+    fn base64_format_sample4() {
+        assert_eq!(
+            run_problem_with_input(
+                &PACKET_DECODER,
+                "igBKgBqAAvR4",
+                &["--part", "1", "--format", "base64"]
+            )
+            .unwrap(),
+            16usize.into()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "cli")]
+    fn file_bytes_format_sample4() {
+        use crate::{scratch_path, Command};
+
+        let path = scratch_path("packet_decoder_file_bytes_sample4.bin");
+        std::fs::write(
+            &path,
+            [0x8A, 0x00, 0x4A, 0x80, 0x1A, 0x80, 0x02, 0xF4, 0x78],
+        )
+        .unwrap();
+        let arguments = PACKET_DECODER
+            .sub_command()
+            .get_matches_from_safe(["packet-decoder", "--part", "1", "--format", "file-bytes"])
+            .unwrap();
+
+        assert_eq!(
+            PACKET_DECODER
+                .run(&arguments, &path.to_str().unwrap().to_string())
+                .unwrap(),
+            16usize.into()
+        );
+    }
+
+    #[test]
+    fn invalid_transmission_is_an_error() {
+        assert!(run_problem_with_input(&PACKET_DECODER, "XYZ", &["part1"]).is_err());
+    }
+
+    #[test]
+    fn malformed_transmissions_are_explained() {
+        assert_eq!(
+            decode_packet(&Format::Hex, b"8A00Z4A").unwrap_err(),
+            TransmissionError::InvalidCharacter {
+                offset: 4,
+                character: 'Z'
+            }
+        );
+        assert_eq!(
+            decode_packet(&Format::Hex, b"D2FE").unwrap_err(),
+            TransmissionError::TruncatedLiteral { offset: 6 }
+        );
+        assert_eq!(
+            decode_packet(&Format::Hex, b"38007345291200").unwrap_err(),
+            TransmissionError::LengthMismatch {
+                offset: 22,
+                declared: 28,
+                used: 27
+            }
+        );
+        assert_eq!(
+            run_problem_with_input(&PACKET_DECODER, "EE00D40C82", &["part1"])
+                .unwrap_err()
+                .to_string(),
+            "The transmission ends mid packet at bit 40"
+        );
+        assert_eq!(
+            decode_packet(&Format::Hex, b"16004408").unwrap_err(),
+            TransmissionError::SubPacketCount {
+                offset: 0,
+                type_id: 5,
+                count: 1
+            }
+        );
+        assert_eq!(
+            run_problem_with_input(&PACKET_DECODER, "080000", &["--part", "2"])
+                .unwrap_err()
+                .to_string(),
+            "The Minimum packet at bit 0 has 0 sub packets but needs at least 1"
+        );
+    }
+
+    #[test]
+    fn depth_and_count_sample4() {
+        assert_eq!(
+            run_problem_with_input(
+                &PACKET_DECODER,
+                SAMPLE4,
+                &["-f", "sample4.txt", "-o", "max-depth"]
+            )
+            .unwrap(),
+            4usize.into()
+        );
+        assert_eq!(
+            run_problem_with_input(
+                &PACKET_DECODER,
+                SAMPLE3,
+                &["-f", "sample3.txt", "-o", "packet-count"]
+            )
+            .unwrap(),
+            vec![
+                ("Maximum".to_string(), 1usize.into()),
+                ("Literal".to_string(), 3usize.into()),
+                ("Total".to_string(), 4usize.into()),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn lenient_sample() {
+        assert_eq!(
+            decode_lenient(&Format::Hex, b"D2FE28D2")
+                .map(|(packets, discarded)| (packets.len(), discarded))
+                .unwrap(),
+            (1, 11)
+        );
+        assert_eq!(
+            run_problem_with_input(&PACKET_DECODER, "D2FE28XYZ", &["--part", "2", "-l"]).unwrap(),
+            vec![
+                ("Whole packets".to_string(), 1usize.into()),
+                ("Discarded bits".to_string(), 15usize.into()),
+                ("Answer".to_string(), 2021usize.into()),
+            ]
+            .into()
+        );
+        assert_eq!(
+            run_problem_with_input(
+                &PACKET_DECODER,
+                "110100101111111000101110100101111111000101",
+                &["--part", "2", "-l", "-t", "binary"]
+            )
+            .unwrap(),
+            vec![
+                ("Whole packets".to_string(), 2usize.into()),
+                ("Discarded bits".to_string(), 0usize.into()),
+                ("Packet 1".to_string(), 2021usize.into()),
+                ("Packet 2".to_string(), 2021usize.into()),
+            ]
+            .into()
+        );
+        assert!(run_problem_with_input(&PACKET_DECODER, "XYZ", &["--part", "1", "-l"]).is_err());
+    }
+}
@@ -0,0 +1,734 @@
+use crate::random::SplitMix64;
+use crate::{
+    default_sub_command, parse_usize, report_output, selected_part, CommandResult, Example, Problem,
+};
+use clap::{value_t_or_exit, App, Arg, ArgMatches};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{newline, one_of},
+    combinator::{map, opt, value},
+    multi::{many1, separated_list0},
+    sequence::{pair, preceded, separated_pair},
+    IResult,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    fs::File,
+    io::{self, BufWriter, Write},
+    iter::{self, FromIterator},
+};
+
+pub const PASSAGE_PATHING: Problem<PassagePathingArgs, Vec<Passage>> = Problem::new(
+    sub_command,
+    "passage-pathing",
+    "day12_passage_pathing",
+    parse_arguments,
+    parse_data,
+    run,
+)
+.with_long_about(LONG_ABOUT)
+.with_examples(EXAMPLES)
+.with_describe(describe)
+.with_generate(generate);
+
+const LONG_ABOUT: &str =
+    "Each line connects two caves. Uppercase caves are big and lowercase caves are small.\n\n\
+    The answer is the number of paths from start to end. Big caves can be visited any number \
+    of times. -r sets how many times a path may revisit a small cave: part 1 allows none and \
+    part 2 allows one. start and end are never revisited. \
+    -d writes the caves and their passages as a Graphviz DOT file.\n\n\
+    A passage can be given a weight with A-b=5. Passages without one weigh 1. -b counts only \
+    the paths whose passages weigh at most the budget in total. -l instead finds the heaviest \
+    simple path, one that never visits any cave twice, big caves included.\n\n\
+    -c reports how many of the paths pass through each cave instead, along with the total. A \
+    path that revisits a cave only counts once for it. Paths are counted from the caves they \
+    have left to visit rather than listed one by one, so this copes with far more paths.\n\n\
+    Example: the smallest sample has 10 paths, or 36 with one revisit.";
+
+const SAMPLE: &str = include_str!("../../day12_passage_pathing/sample.txt");
+
+const SAMPLE2: &str = include_str!("../../day12_passage_pathing/sample2.txt");
+
+const SAMPLE3: &str = include_str!("../../day12_passage_pathing/sample3.txt");
+
+const EXAMPLES: &[Example] = &[
+    Example::new(SAMPLE, 1, "10"),
+    Example::new(SAMPLE, 2, "36"),
+    Example::new(SAMPLE2, 1, "19"),
+    Example::new(SAMPLE2, 2, "103"),
+    Example::new(SAMPLE3, 1, "226"),
+    Example::new(SAMPLE3, 2, "3509"),
+];
+
+#[derive(Debug)]
+pub struct PassagePathingArgs {
+    revisits: usize,
+    export_dot: Option<String>,
+    budget: Option<usize>,
+    longest: bool,
+    cave_stats: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Passage {
+    a: Cave<'static>,
+    b: Cave<'static>,
+    weight: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Cave<'a> {
+    Start,
+    End,
+    Big { name: &'a str },
+    Small { name: &'a str },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Journey<'a> {
+    visited_caves: HashSet<Cave<'a>>,
+    caves: Vec<Cave<'a>>,
+    remaining_revisits: usize,
+    weight: usize,
+}
+
+// The passages out of each cave and what they weigh.
+type CavePaths = HashMap<Cave<'static>, HashMap<Cave<'static>, usize>>;
+
+// Where a path is, the small caves it has been through and how many revisits it has left. Paths
+// in the same state finish the same ways, however they got there.
+type PathState = (Cave<'static>, BTreeSet<&'static str>, usize);
+
+// The number of ways to finish a path from some state, and how many of them pass through each
+// cave on the way.
+#[derive(Debug, Clone, Default)]
+struct PathCounts {
+    paths: usize,
+    through: HashMap<Cave<'static>, usize>,
+}
+
+fn sub_command() -> App<'static, 'static> {
+    default_sub_command(
+        &PASSAGE_PATHING,
+        "Goes through all possible paths and counts them",
+        "Path to the input file. Input should valid transitions from cave to cave.",
+        "Searches the default input for the maximum number of valid paths.",
+        "Searches the default input for the maximum number but one small cave may be reused.",
+    )
+    .arg(
+        Arg::with_name("revisits")
+            .short("r")
+            .long("revisits")
+            .help("Number of times small caves may be revisited along a single path. 0 never revisits a small cave, 1 allows a single revisit etc.")
+            .takes_value(true)
+            .required_unless_one(&["part", "explain"]),
+    )
+    .arg(
+        Arg::with_name("export-dot")
+            .short("d")
+            .long("export-dot")
+            .help("If passed, writes the cave graph to the given path as a Graphviz DOT file. Big caves are boxes, small caves are ellipses and start and end are filled in.")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("budget")
+            .short("b")
+            .long("budget")
+            .help("If passed, only counts paths whose passages weigh at most this much in total.")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("longest")
+            .short("l")
+            .long("longest")
+            .help("If passed, gives the weight of the heaviest path that never visits a cave twice instead of counting paths.")
+            .conflicts_with("budget"),
+    )
+    .arg(
+        Arg::with_name("cave-stats")
+            .short("c")
+            .long("cave-stats")
+            .help("If passed, reports how many paths pass through each cave.")
+            .conflicts_with_all(&["budget", "longest"]),
+    )
+}
+
+fn parse_arguments(arguments: &ArgMatches) -> PassagePathingArgs {
+    match selected_part(arguments) {
+        Some("part1") => PassagePathingArgs {
+            revisits: 0usize,
+            export_dot: arguments.value_of("export-dot").map(String::from),
+            budget: budget_argument(arguments),
+            longest: arguments.is_present("longest"),
+            cave_stats: arguments.is_present("cave-stats"),
+        },
+        Some("part2") => PassagePathingArgs {
+            revisits: 1usize,
+            export_dot: arguments.value_of("export-dot").map(String::from),
+            budget: budget_argument(arguments),
+            longest: arguments.is_present("longest"),
+            cave_stats: arguments.is_present("cave-stats"),
+        },
+        _ => PassagePathingArgs {
+            revisits: value_t_or_exit!(arguments.value_of("revisits"), usize),
+            export_dot: arguments.value_of("export-dot").map(String::from),
+            budget: budget_argument(arguments),
+            longest: arguments.is_present("longest"),
+            cave_stats: arguments.is_present("cave-stats"),
+        },
+    }
+}
+
+fn budget_argument(arguments: &ArgMatches) -> Option<usize> {
+    arguments
+        .value_of("budget")
+        .map(|_| value_t_or_exit!(arguments.value_of("budget"), usize))
+}
+
+fn run(arguments: PassagePathingArgs, paths: Vec<Passage>) -> CommandResult {
+    let cave_paths = paths.into_iter().fold(
+        HashMap::new(),
+        |mut cave_paths: CavePaths, Passage { a, b, weight }| {
+            match a {
+                Cave::Start => (),
+                _ => match b {
+                    Cave::End => (),
+                    _ => {
+                        cave_paths.entry(b).or_default().insert(a, weight);
+                        ()
+                    }
+                },
+            };
+            match b {
+                Cave::Start => (),
+                _ => match a {
+                    Cave::End => (),
+                    _ => {
+                        cave_paths.entry(a).or_default().insert(b, weight);
+                        ()
+                    }
+                },
+            };
+
+            cave_paths
+        },
+    );
+
+    if let Some(path) = &arguments.export_dot {
+        report_output(path, export_caves_as_dot(&cave_paths, path));
+    }
+
+    if arguments.cave_stats {
+        let counts = count_paths(
+            &cave_paths,
+            (Cave::Start, BTreeSet::new(), arguments.revisits),
+            &mut HashMap::new(),
+        );
+        let caves: BTreeMap<&str, usize> = cave_paths
+            .iter()
+            .flat_map(|(cave, neighbours)| iter::once(cave).chain(neighbours.keys()))
+            .filter(|cave| !matches!(cave, Cave::Start | Cave::End))
+            .map(|cave| {
+                (
+                    cave_name(cave),
+                    counts.through.get(cave).copied().unwrap_or(0usize),
+                )
+            })
+            .collect();
+
+        return caves
+            .into_iter()
+            .map(|(name, paths)| (name.to_string(), paths.into()))
+            .chain([("Paths".to_string(), counts.paths.into())])
+            .collect::<Vec<(String, CommandResult)>>()
+            .into();
+    }
+
+    let mut start = Journey {
+        visited_caves: HashSet::new(),
+        caves: vec![Cave::Start],
+        remaining_revisits: if arguments.longest {
+            0usize
+        } else {
+            arguments.revisits
+        },
+        weight: 0usize,
+    };
+
+    start.visited_caves.insert(Cave::Start);
+
+    let journies = find_all_journies(
+        &cave_paths,
+        start,
+        arguments.budget.unwrap_or(usize::MAX),
+        arguments.longest,
+    );
+
+    if arguments.longest {
+        journies
+            .iter()
+            .map(|journey| journey.weight)
+            .max()
+            .unwrap_or(0usize)
+            .into()
+    } else {
+        journies.len().into()
+    }
+}
+
+// Weights are never negative, so a journey over the budget can be dropped without following it
+// any further. A simple journey treats big caves like small ones and never revisits them.
+fn find_all_journies(
+    cave_paths: &CavePaths,
+    journey: Journey<'static>,
+    budget: usize,
+    simple: bool,
+) -> Vec<Journey<'static>> {
+    let mut journies = cave_paths
+        .get(journey.caves.last().unwrap())
+        .unwrap_or(&HashMap::new())
+        .iter()
+        .map(|(cave, weight)| {
+            let weight = journey.weight.saturating_add(*weight);
+            let revisit = match cave {
+                Cave::Big { name: _ } if !simple => false,
+                _ => journey.visited_caves.contains(cave),
+            };
+            if weight > budget || (revisit && journey.remaining_revisits == 0) {
+                return Vec::new();
+            }
+
+            let mut new_journey = journey.clone();
+            if revisit {
+                new_journey.remaining_revisits -= 1;
+            } else {
+                new_journey.visited_caves.insert(*cave);
+            }
+            new_journey.caves.push(*cave);
+            new_journey.weight = weight;
+            find_all_journies(cave_paths, new_journey, budget, simple)
+        })
+        .fold(Vec::new(), |mut acc, mut sub_journies| {
+            acc.append(&mut sub_journies);
+            acc
+        });
+
+    if journey.visited_caves.contains(&Cave::End) {
+        journies.push(journey);
+    }
+
+    journies
+}
+
+// Counts the paths that finish from this state, remembering the counts for every state seen. Each
+// path passes through the cave it's in, and through whatever the way it goes on passes through.
+fn count_paths(
+    cave_paths: &CavePaths,
+    state: PathState,
+    known: &mut HashMap<PathState, PathCounts>,
+) -> PathCounts {
+    if let Some(counts) = known.get(&state) {
+        return counts.clone();
+    }
+
+    let (cave, visited, remaining_revisits) = &state;
+    let mut counts = PathCounts::default();
+    if *cave == Cave::End {
+        counts.paths = 1usize;
+    }
+
+    cave_paths
+        .get(cave)
+        .into_iter()
+        .flat_map(|neighbours| neighbours.keys())
+        .for_each(|next| {
+            let revisit = match next {
+                Cave::Small { name } => visited.contains(name),
+                _ => false,
+            };
+            if revisit && *remaining_revisits == 0 {
+                return;
+            }
+
+            let mut next_visited = visited.clone();
+            if let Cave::Small { name } = next {
+                next_visited.insert(name);
+            }
+            let next_counts = count_paths(
+                cave_paths,
+                (
+                    *next,
+                    next_visited,
+                    remaining_revisits - usize::from(revisit),
+                ),
+                known,
+            );
+
+            counts.paths += next_counts.paths;
+            next_counts
+                .through
+                .into_iter()
+                .for_each(|(cave, paths)| *counts.through.entry(cave).or_default() += paths);
+        });
+    counts.through.insert(*cave, counts.paths);
+
+    known.insert(state, counts.clone());
+    counts
+}
+
+// The passages are undirected, so each one is written once however many directions it can be
+// travelled in. Caves and passages are sorted so the same input always gives the same file.
+// Passages that weigh anything but 1 are labelled with their weight.
+fn export_caves_as_dot(cave_paths: &CavePaths, path: &str) -> Result<(), io::Error> {
+    let caves: BTreeSet<(&str, &str)> = cave_paths
+        .iter()
+        .flat_map(|(cave, neighbours)| iter::once(cave).chain(neighbours.keys()))
+        .map(|cave| {
+            let style = match cave {
+                Cave::Start | Cave::End => "shape=doublecircle, style=filled, fillcolor=gold",
+                Cave::Big { name: _ } => "shape=box, style=filled, fillcolor=lightblue",
+                Cave::Small { name: _ } => "shape=ellipse",
+            };
+            (cave_name(cave), style)
+        })
+        .collect();
+    let passages: BTreeSet<(&str, &str, usize)> = cave_paths
+        .iter()
+        .flat_map(|(cave, neighbours)| {
+            neighbours.iter().map(move |(neighbour, weight)| {
+                let (a, b) = (cave_name(cave), cave_name(neighbour));
+                (a.min(b), a.max(b), *weight)
+            })
+        })
+        .collect();
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    writeln!(writer, "graph caves {{")?;
+    caves
+        .iter()
+        .try_for_each(|(name, style)| writeln!(writer, "    \"{}\" [{}];", name, style))?;
+    passages
+        .iter()
+        .try_for_each(|(a, b, weight)| match weight {
+            1 => writeln!(writer, "    \"{}\" -- \"{}\";", a, b),
+            _ => writeln!(writer, "    \"{}\" -- \"{}\" [label={}];", a, b, weight),
+        })?;
+    writeln!(writer, "}}")?;
+    writer.flush()
+}
+
+fn cave_name<'a>(cave: &Cave<'a>) -> &'a str {
+    match cave {
+        Cave::Start => "start",
+        Cave::End => "end",
+        Cave::Big { name } | Cave::Small { name } => name,
+    }
+}
+
+// size is the number of caves besides start and end. Each cave is joined to one that came before
+// it so end can always be reached, then extra passages are added at random. Two big caves are
+// never joined since that would allow infinitely many paths.
+fn generate(random: &mut SplitMix64, size: usize) -> String {
+    let caves: Vec<(String, bool)> = iter::once(("start".to_string(), false))
+        .chain((0..size).map(|index| {
+            let name = generated_cave_name(index);
+            if random.below(3usize) == 0 {
+                (name.to_uppercase(), true)
+            } else {
+                (name, false)
+            }
+        }))
+        .chain(iter::once(("end".to_string(), false)))
+        .collect();
+    let can_join = |a: usize, b: usize| a != b && !(caves[a].1 && caves[b].1);
+
+    let mut passages: Vec<(usize, usize)> = (1..caves.len())
+        .map(|cave| {
+            let candidates: Vec<usize> = (0..cave).filter(|other| can_join(cave, *other)).collect();
+            (candidates[random.below(candidates.len())], cave)
+        })
+        .collect();
+    let mut joined: HashSet<(usize, usize)> = passages.iter().copied().collect();
+
+    (0..size / 2).for_each(|_| {
+        let a = random.below(caves.len());
+        let b = random.below(caves.len());
+        if can_join(a, b) && joined.insert((a.min(b), a.max(b))) {
+            passages.push((a, b));
+        }
+    });
+
+    passages
+        .into_iter()
+        .map(|(a, b)| format!("{}-{}\n", caves[a].0, caves[b].0))
+        .collect()
+}
+
+// Names never start with e or s, so they can't be mistaken for the start and end caves.
+fn generated_cave_name(index: usize) -> String {
+    const FIRST_LETTERS: &[u8] = b"abcdfghijklmnopqrtuvwxyz";
+    let mut name = vec![FIRST_LETTERS[index % FIRST_LETTERS.len()]];
+    let mut rest = index / FIRST_LETTERS.len();
+
+    loop {
+        name.push(b'a' + (rest % 26usize) as u8);
+        rest /= 26usize;
+        if rest == 0 {
+            break;
+        }
+    }
+
+    String::from_utf8(name).expect("Cave names are ascii")
+}
+
+fn describe(paths: &Vec<Passage>) -> String {
+    let caves: HashSet<&Cave> = paths
+        .iter()
+        .flat_map(|passage| [&passage.a, &passage.b])
+        .collect();
+    let big_caves = caves
+        .iter()
+        .filter(|cave| matches!(cave, Cave::Big { name: _ }))
+        .count();
+    let small_caves = caves
+        .iter()
+        .filter(|cave| matches!(cave, Cave::Small { name: _ }))
+        .count();
+    let mut description = format!(
+        "{} passages between {} caves: {} big, {} small",
+        paths.len(),
+        caves.len(),
+        big_caves,
+        small_caves
+    );
+
+    let weights: BTreeSet<usize> = paths.iter().map(|passage| passage.weight).collect();
+    if weights.iter().any(|weight| *weight != 1) {
+        description.push_str(&format!(
+            "\nPassages weigh from {} to {}",
+            weights.iter().next().unwrap_or(&0usize),
+            weights.iter().next_back().unwrap_or(&0usize)
+        ));
+    }
+
+    if !caves.contains(&Cave::Start) || !caves.contains(&Cave::End) {
+        description.push_str("\nAnomaly: the start or end cave is missing");
+    }
+
+    description
+}
+
+fn parse_data(input: &String) -> IResult<&str, Vec<Passage>> {
+    separated_list0(newline, parse_path)(input)
+}
+
+fn parse_path(input: &str) -> IResult<&str, Passage> {
+    map(
+        pair(
+            separated_pair(parse_cave, tag("-"), parse_cave),
+            opt(preceded(tag("="), parse_usize)),
+        ),
+        |((a, b), weight)| Passage {
+            a,
+            b,
+            weight: weight.unwrap_or(1usize),
+        },
+    )(input)
+}
+
+fn parse_cave(input: &str) -> IResult<&str, Cave<'static>> {
+    alt((
+        parse_start_cave,
+        parse_end_cave,
+        parse_big_cave,
+        parse_small_cave,
+    ))(input)
+}
+
+fn parse_start_cave(input: &str) -> IResult<&str, Cave<'static>> {
+    value(Cave::Start, tag("start"))(input)
+}
+
+fn parse_end_cave(input: &str) -> IResult<&str, Cave<'static>> {
+    value(Cave::End, tag("end"))(input)
+}
+
+fn parse_big_cave(input: &str) -> IResult<&str, Cave<'static>> {
+    map(many1(one_of("ABCDEFGHIJKLMNOPQRSTUVWXYZ")), |name| {
+        Cave::Big {
+            name: Box::leak(String::from_iter(name.into_iter()).into_boxed_str()),
+        }
+    })(input)
+}
+
+fn parse_small_cave(input: &str) -> IResult<&str, Cave<'static>> {
+    map(many1(one_of("abcdefghijklmnopqrstuvwxyz")), |name| {
+        Cave::Small {
+            name: Box::leak(String::from_iter(name.into_iter()).into_boxed_str()),
+        }
+    })(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{run_problem_with_input, scratch_path, AocError, Command};
+
+    #[test]
+    fn part1_sample() {
+        assert_eq!(
+            run_problem_with_input(&PASSAGE_PATHING, SAMPLE, &["part1"]).unwrap(),
+            10usize.into()
+        );
+    }
+
+    #[test]
+    fn part2_sample() {
+        assert_eq!(
+            run_problem_with_input(&PASSAGE_PATHING, SAMPLE, &["part2"]).unwrap(),
+            36usize.into()
+        );
+    }
+
+    #[test]
+    fn revisits_sample() {
+        assert_eq!(
+            run_problem_with_input(
+                &PASSAGE_PATHING,
+                SAMPLE,
+                &["-f", "sample.txt", "--revisits", "1"]
+            )
+            .unwrap(),
+            36usize.into()
+        );
+    }
+
+    #[test]
+    fn part1_sample2() {
+        assert_eq!(
+            run_problem_with_input(&PASSAGE_PATHING, SAMPLE2, &["part1"]).unwrap(),
+            19usize.into()
+        );
+    }
+
+    #[test]
+    fn part2_sample2() {
+        assert_eq!(
+            run_problem_with_input(&PASSAGE_PATHING, SAMPLE2, &["part2"]).unwrap(),
+            103usize.into()
+        );
+    }
+
+    #[test]
+    fn part1_sample3() {
+        assert_eq!(
+            run_problem_with_input(&PASSAGE_PATHING, SAMPLE3, &["part1"]).unwrap(),
+            226usize.into()
+        );
+    }
+
+    #[test]
+    fn part2_sample3() {
+        assert_eq!(
+            run_problem_with_input(&PASSAGE_PATHING, SAMPLE3, &["part2"]).unwrap(),
+            3509usize.into()
+        );
+    }
+    #[test]
+    fn generate_is_valid() {
+        let input = PASSAGE_PATHING.generate(11u64, 8usize).unwrap();
+
+        assert_eq!(input, PASSAGE_PATHING.generate(11u64, 8usize).unwrap());
+        assert!(!PASSAGE_PATHING
+            .validate_input(&input)
+            .unwrap()
+            .contains("Anomaly"));
+        assert_ne!(
+            run_problem_with_input(&PASSAGE_PATHING, &input, &["part1"]).unwrap(),
+            0usize.into()
+        );
+    }
+
+    #[test]
+    fn export_dot_sample() {
+        let path = scratch_path("passage_pathing_export_dot_sample.dot");
+        let path = path.to_str().unwrap();
+
+        assert_eq!(
+            run_problem_with_input(&PASSAGE_PATHING, SAMPLE, &["--part", "1", "-d", path]).unwrap(),
+            10usize.into()
+        );
+
+        let dot = std::fs::read_to_string(path).unwrap();
+        assert!(dot.starts_with("graph caves {"));
+        assert!(dot.contains("\"A\" [shape=box"));
+        assert!(dot.contains("\"start\" [shape=doublecircle"));
+        assert_eq!(dot.matches(" -- ").count(), 7);
+    }
+
+    #[test]
+    fn unwritable_dot_file_is_an_error() {
+        let path = scratch_path("passage_pathing_missing").join("caves.dot");
+        let path = path.to_str().unwrap();
+        let error = run_problem_with_input(&PASSAGE_PATHING, SAMPLE, &["--part", "1", "-d", path])
+            .unwrap_err();
+
+        assert!(matches!(error, AocError::Io(_)));
+        assert!(error
+            .to_string()
+            .contains(&format!("{} can't be written", path)));
+    }
+
+    #[test]
+    fn cave_stats_sample() {
+        assert_eq!(
+            run_problem_with_input(&PASSAGE_PATHING, SAMPLE, &["--part", "1", "-c"]).unwrap(),
+            vec![
+                ("A".to_string(), 9usize.into()),
+                ("b".to_string(), 8usize.into()),
+                ("c".to_string(), 5usize.into()),
+                ("d".to_string(), 0usize.into()),
+                ("Paths".to_string(), 10usize.into()),
+            ]
+            .into()
+        );
+        assert_eq!(
+            run_problem_with_input(&PASSAGE_PATHING, SAMPLE, &["--part", "2", "-c"]).unwrap(),
+            vec![
+                ("A".to_string(), 34usize.into()),
+                ("b".to_string(), 33usize.into()),
+                ("c".to_string(), 23usize.into()),
+                ("d".to_string(), 8usize.into()),
+                ("Paths".to_string(), 36usize.into()),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn weighted_sample() {
+        let input: String = ["=2", "=1", "=4", "=3", "=1", "=5", "=2"]
+            .iter()
+            .zip(SAMPLE.lines())
+            .map(|(weight, passage)| format!("{}{}\n", passage, weight))
+            .collect();
+
+        assert_eq!(
+            run_problem_with_input(&PASSAGE_PATHING, &input, &["part1"]).unwrap(),
+            10usize.into()
+        );
+        assert_eq!(
+            run_problem_with_input(&PASSAGE_PATHING, &input, &["--part", "1", "-b", "10"]).unwrap(),
+            4usize.into()
+        );
+        assert_eq!(
+            run_problem_with_input(&PASSAGE_PATHING, &input, &["--part", "1", "-l"]).unwrap(),
+            9usize.into()
+        );
+        assert!(PASSAGE_PATHING
+            .validate_input(&input)
+            .unwrap()
+            .contains("Passages weigh from 1 to 5"));
+    }
+}
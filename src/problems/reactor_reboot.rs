@@ -0,0 +1,1079 @@
+use crate::random::SplitMix64;
+use crate::{
+    default_sub_command, deterministic, parse_isize, report_output, selected_part, AocError,
+    CommandResult, Deadline, Example, Problem,
+};
+use clap::{App, Arg, ArgMatches, ErrorKind};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::newline,
+    combinator::{map, opt, value},
+    multi::separated_list0,
+    sequence::{preceded, separated_pair, tuple},
+    IResult,
+};
+use std::{
+    cmp::{max, min},
+    collections::HashSet,
+    fs::File,
+    io::{self, BufWriter, Write},
+};
+
+pub const REACTOR_REBOOT: Problem<ReactorRebootArgs, Vec<RebootStep>> = Problem::new(
+    sub_command,
+    "reactor-reboot",
+    "day22_reactor_reboot",
+    parse_arguments,
+    parse_data,
+    run,
+)
+.with_long_about(LONG_ABOUT)
+.with_examples(EXAMPLES)
+.with_describe(describe)
+.with_input_check(input_check)
+.with_generate(generate);
+
+const LONG_ABOUT: &str =
+    "Each line turns a cuboid of cubes on or off, given as x, y and z ranges.\n\n\
+    The steps apply in order to a grid that starts with every cube off. The answer is the \
+    number of cubes that are on at the end. -l only considers cubes within -50..50 on every \
+    axis, as in part 1. -e writes the final on cuboids to an OBJ file.\n\n\
+    A range whose low end is above its high end, such as x=10..-10, is rejected with its line \
+    unless -n is passed to swap the ends. So are steps covering more cubes than fit in 64 bits.\n\n\
+    -q asks how many cubes are on within a region instead, such as -q 10..12,10..12,10..12, and \
+    can be passed more than once. The answer is the count for each region, found by \
+    intersecting it with the final on cuboids.\n\n\
+    Example: the first small sample leaves 39 cubes on.";
+
+const SAMPLE: &str = include_str!("../../day22_reactor_reboot/sample.txt");
+
+const SAMPLE2: &str = include_str!("../../day22_reactor_reboot/sample2.txt");
+
+const SAMPLE3: &str = include_str!("../../day22_reactor_reboot/sample3.txt");
+
+const EXAMPLES: &[Example] = &[
+    Example::new(SAMPLE, 1, "39"),
+    Example::new(SAMPLE2, 1, "590784"),
+    Example::new(SAMPLE3, 1, "474140"),
+    Example::new(SAMPLE3, 2, "2758514936282235"),
+];
+
+#[derive(Debug)]
+pub struct ReactorRebootArgs {
+    limit_cubes: bool,
+    export: Option<String>,
+    timeline: bool,
+    normalize: bool,
+    queries: Vec<Cuboid>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RebootStep {
+    turn_on: bool,
+    cuboid: Cuboid,
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+struct Cuboid {
+    x_range: Range,
+    y_range: Range,
+    z_range: Range,
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+struct Range {
+    low: isize,
+    high: isize,
+}
+
+fn sub_command() -> App<'static, 'static> {
+    default_sub_command(
+        &REACTOR_REBOOT,
+        "Follows the reboot steps then reports the number of turned on cubes.",
+        "Path to the input file. Input should be a newline delimited set of reboot instructions.",
+        "Finds all on cubes within a limited region for the default input.",
+        "Finds all on cubes for the default input.",
+    )
+    .arg(
+        Arg::with_name("limit-cubes")
+            .short("l")
+            .help("If passed, limits the area considered to -50, 50 for all dimensions."),
+    )
+    .arg(
+        Arg::with_name("export")
+            .short("e")
+            .long("export")
+            .help("If passed, writes the final on cuboids to the given path as an OBJ mesh.")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("timeline")
+            .short("t")
+            .long("timeline")
+            .help("If passed, reports the number of on cubes after each reboot step."),
+    )
+    .arg(
+        Arg::with_name("normalize")
+            .short("n")
+            .long("normalize")
+            .help(
+            "If passed, swaps the ends of ranges written high to low instead of rejecting them.",
+        ),
+    )
+    .arg(
+        Arg::with_name("query")
+            .short("q")
+            .long("query")
+            .help("A region as x0..x1,y0..y1,z0..z1 to count the on cubes within. Can be passed more than once.")
+            .multiple(true)
+            .takes_value(true)
+            .allow_hyphen_values(true)
+            .number_of_values(1)
+            .conflicts_with("timeline"),
+    )
+}
+
+fn parse_arguments(arguments: &ArgMatches) -> ReactorRebootArgs {
+    match selected_part(arguments) {
+        Some("part1") => ReactorRebootArgs {
+            limit_cubes: true,
+            export: arguments.value_of("export").map(String::from),
+            timeline: arguments.is_present("timeline"),
+            normalize: arguments.is_present("normalize"),
+            queries: queries_argument(arguments),
+        },
+        Some("part2") => ReactorRebootArgs {
+            limit_cubes: false,
+            export: arguments.value_of("export").map(String::from),
+            timeline: arguments.is_present("timeline"),
+            normalize: arguments.is_present("normalize"),
+            queries: queries_argument(arguments),
+        },
+        _ => ReactorRebootArgs {
+            limit_cubes: arguments.is_present("limit-cubes"),
+            export: arguments.value_of("export").map(String::from),
+            timeline: arguments.is_present("timeline"),
+            normalize: arguments.is_present("normalize"),
+            queries: queries_argument(arguments),
+        },
+    }
+}
+
+// A region that can't be read exits the same way an invalid argument value does. Ends written
+// high to low are swapped since a query has no line to report.
+fn queries_argument(arguments: &ArgMatches) -> Vec<Cuboid> {
+    arguments
+        .values_of("query")
+        .into_iter()
+        .flatten()
+        .map(|query| match parse_query(query) {
+            Ok(("", cuboid)) => normalize_cuboid(&cuboid),
+            _ => clap::Error::with_description(
+                &format!("Invalid query {}, it should be x0..x1,y0..y1,z0..z1", query),
+                ErrorKind::InvalidValue,
+            )
+            .exit(),
+        })
+        .collect()
+}
+
+fn parse_query(input: &str) -> IResult<&str, Cuboid> {
+    map(
+        tuple((
+            preceded(opt(tag("x=")), parse_range),
+            preceded(tag(","), preceded(opt(tag("y=")), parse_range)),
+            preceded(tag(","), preceded(opt(tag("z=")), parse_range)),
+        )),
+        |(x_range, y_range, z_range)| Cuboid {
+            x_range,
+            y_range,
+            z_range,
+        },
+    )(input)
+}
+
+fn parse_range(input: &str) -> IResult<&str, Range> {
+    map(
+        separated_pair(parse_isize, tag(".."), parse_isize),
+        |(low, high)| Range { low, high },
+    )(input)
+}
+
+// Every step that will be run has to be the right way round and small enough to count. Since the
+// on cubes never overlap, the total can't be more than the box around all of the steps, so that
+// fitting means the total does too.
+fn input_check(
+    arguments: &ReactorRebootArgs,
+    reboot_steps: &Vec<RebootStep>,
+) -> Result<(), AocError> {
+    if !arguments.normalize {
+        if let Some((index, (axis, range))) = reboot_steps
+            .iter()
+            .enumerate()
+            .find_map(|(index, step)| reversed_ranges(&step.cuboid).first().map(|r| (index, *r)))
+        {
+            return Err(AocError::validation(format!(
+                "Line {} has a reversed {} range {}..{}, pass --normalize to swap it",
+                index + 1,
+                axis,
+                range.low,
+                range.high
+            )));
+        }
+    }
+
+    let steps: Vec<(usize, RebootStep)> = reboot_steps
+        .iter()
+        .enumerate()
+        .filter_map(|(index, step)| prepare_step(arguments, step).map(|step| (index + 1, step)))
+        .collect();
+    if let Some((line, _)) = steps
+        .iter()
+        .find(|(_, step)| checked_cuboid_size(&step.cuboid).is_none())
+    {
+        return Err(AocError::validation(format!(
+            "Line {} covers more cubes than fit in 64 bits",
+            line
+        )));
+    }
+
+    let cuboids: Vec<Cuboid> = steps.into_iter().map(|(_, step)| step.cuboid).collect();
+    match bounding_cuboid(&cuboids) {
+        Some(bounds) if checked_cuboid_size(&bounds).is_none() => Err(AocError::validation(
+            "Together the steps span more cubes than fit in 64 bits",
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn run(arguments: ReactorRebootArgs, reboot_steps: Vec<RebootStep>) -> CommandResult {
+    let filtered_steps: Vec<RebootStep> = reboot_steps
+        .iter()
+        .filter_map(|step| prepare_step(&arguments, step))
+        .collect();
+
+    let (on_cubes, timeline) = run_steps(filtered_steps);
+
+    if let Some(path) = &arguments.export {
+        report_output(path, export_cuboids_as_obj(&on_cubes, path));
+    }
+
+    if !arguments.queries.is_empty() {
+        return arguments
+            .queries
+            .iter()
+            .map(|query| {
+                (
+                    format!(
+                        "{}..{},{}..{},{}..{}",
+                        query.x_range.low,
+                        query.x_range.high,
+                        query.y_range.low,
+                        query.y_range.high,
+                        query.z_range.low,
+                        query.z_range.high
+                    ),
+                    on_cubes_within(&on_cubes, query).into(),
+                )
+            })
+            .collect::<Vec<(String, CommandResult)>>()
+            .into();
+    }
+
+    if arguments.timeline {
+        timeline
+            .into_iter()
+            .enumerate()
+            .map(|(index, total)| (format!("Step {}", index + 1), total.into()))
+            .collect::<Vec<(String, CommandResult)>>()
+            .into()
+    } else {
+        on_cubes.iter().map(get_cuboid_size).sum::<isize>().into()
+    }
+}
+
+// The on cuboids never overlap, so their intersections with the region add up to the count.
+fn on_cubes_within(on_cubes: &HashSet<Cuboid>, region: &Cuboid) -> isize {
+    on_cubes
+        .iter()
+        .filter_map(|cuboid| get_cuboid_intersection(cuboid, region))
+        .map(|intersection| get_cuboid_size(&intersection))
+        .sum()
+}
+
+// writes each cuboid as a closed box with 8 vertices and 6 quad faces. Cube coordinates are
+// inclusive so the far corner of each box sits at high + 1.
+fn export_cuboids_as_obj(cuboids: &HashSet<Cuboid>, path: &str) -> Result<(), io::Error> {
+    let mut cuboids: Vec<&Cuboid> = cuboids.iter().collect();
+    if deterministic() {
+        cuboids.sort();
+    }
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    cuboids.iter().enumerate().try_for_each(|(index, cuboid)| {
+        let (x0, x1) = (cuboid.x_range.low, cuboid.x_range.high + 1);
+        let (y0, y1) = (cuboid.y_range.low, cuboid.y_range.high + 1);
+        let (z0, z1) = (cuboid.z_range.low, cuboid.z_range.high + 1);
+        let base = index * 8;
+
+        writeln!(writer, "o cuboid{}", index)?;
+        [
+            (x0, y0, z0),
+            (x1, y0, z0),
+            (x1, y1, z0),
+            (x0, y1, z0),
+            (x0, y0, z1),
+            (x1, y0, z1),
+            (x1, y1, z1),
+            (x0, y1, z1),
+        ]
+        .iter()
+        .try_for_each(|(x, y, z)| writeln!(writer, "v {} {} {}", x, y, z))?;
+        [
+            [1, 4, 3, 2],
+            [5, 6, 7, 8],
+            [1, 2, 6, 5],
+            [4, 8, 7, 3],
+            [1, 5, 8, 4],
+            [2, 3, 7, 6],
+        ]
+        .iter()
+        .try_for_each(|[a, b, c, d]| {
+            writeln!(
+                writer,
+                "f {} {} {} {}",
+                base + a,
+                base + b,
+                base + c,
+                base + d
+            )
+        })
+    })?;
+
+    writer.flush()
+}
+
+// returns the final on cuboids along with the total on volume after each step. The on cuboids never
+// overlap, so the total only changes by the intersections removed and the cuboid added. The steps
+// stop once the deadline passes.
+fn run_steps(reboot_steps: Vec<RebootStep>) -> (HashSet<Cuboid>, Vec<isize>) {
+    let mut on_cubes = HashSet::new();
+    let mut total = 0isize;
+    let mut timeline = Vec::new();
+    let deadline = Deadline::current();
+
+    reboot_steps
+        .into_iter()
+        .take_while(|_| !deadline.expired())
+        .for_each(|step| {
+            on_cubes = on_cubes
+                .iter()
+                .map(|cube| match get_cuboid_intersection(cube, &step.cuboid) {
+                    Option::Some(intersection) => {
+                        total -= get_cuboid_size(&intersection);
+                        fracture_cuboid(cube, &intersection)
+                    }
+                    Option::None => vec![*cube],
+                })
+                .flatten()
+                .collect();
+            if step.turn_on {
+                total += get_cuboid_size(&step.cuboid);
+                on_cubes.insert(step.cuboid);
+            }
+            timeline.push(total);
+        });
+
+    (on_cubes, timeline)
+}
+
+// Normalizes the step if asked to, and leaves it out if it's outside the limited region.
+fn prepare_step(arguments: &ReactorRebootArgs, step: &RebootStep) -> Option<RebootStep> {
+    let step = if arguments.normalize {
+        RebootStep {
+            turn_on: step.turn_on,
+            cuboid: normalize_cuboid(&step.cuboid),
+        }
+    } else {
+        step.clone()
+    };
+
+    if arguments.limit_cubes && !is_step_within_target(&step, -50isize, 50isize) {
+        None
+    } else {
+        Some(step)
+    }
+}
+
+fn reversed_ranges(cuboid: &Cuboid) -> Vec<(&'static str, Range)> {
+    [
+        ("x", cuboid.x_range),
+        ("y", cuboid.y_range),
+        ("z", cuboid.z_range),
+    ]
+    .into_iter()
+    .filter(|(_, range)| range.low > range.high)
+    .collect()
+}
+
+fn normalize_cuboid(cuboid: &Cuboid) -> Cuboid {
+    Cuboid {
+        x_range: normalize_range(&cuboid.x_range),
+        y_range: normalize_range(&cuboid.y_range),
+        z_range: normalize_range(&cuboid.z_range),
+    }
+}
+
+fn normalize_range(range: &Range) -> Range {
+    Range {
+        low: min(range.low, range.high),
+        high: max(range.low, range.high),
+    }
+}
+
+fn bounding_cuboid(cuboids: &[Cuboid]) -> Option<Cuboid> {
+    cuboids.iter().copied().reduce(|bounds, cuboid| Cuboid {
+        x_range: bounding_range(&bounds.x_range, &cuboid.x_range),
+        y_range: bounding_range(&bounds.y_range, &cuboid.y_range),
+        z_range: bounding_range(&bounds.z_range, &cuboid.z_range),
+    })
+}
+
+fn bounding_range(a: &Range, b: &Range) -> Range {
+    Range {
+        low: min(a.low, b.low),
+        high: max(a.high, b.high),
+    }
+}
+
+// Sizes are checked to fit by input_check before anything is run.
+fn get_cuboid_size(cuboid: &Cuboid) -> isize {
+    checked_cuboid_size(cuboid).expect("Cuboid size fits in an isize")
+}
+
+fn checked_cuboid_size(cuboid: &Cuboid) -> Option<isize> {
+    checked_range_size(&cuboid.x_range)?
+        .checked_mul(checked_range_size(&cuboid.y_range)?)?
+        .checked_mul(checked_range_size(&cuboid.z_range)?)
+}
+
+fn checked_range_size(range: &Range) -> Option<isize> {
+    range.high.checked_sub(range.low)?.checked_add(1isize)
+}
+
+// breaks this base cuboid into up to 26 individual cubes with the region specified by the sub_cube not represented.
+fn fracture_cuboid(base: &Cuboid, sub_cube: &Cuboid) -> Vec<Cuboid> {
+    let x_high_range = get_high_range(&base.x_range, &sub_cube.x_range);
+    let x_low_range = get_low_range(&base.x_range, &sub_cube.x_range);
+
+    let y_high_range = get_high_range(&base.y_range, &sub_cube.y_range);
+    let y_low_range = get_low_range(&base.y_range, &sub_cube.y_range);
+
+    let z_high_range = get_high_range(&base.z_range, &sub_cube.z_range);
+    let z_low_range = get_low_range(&base.z_range, &sub_cube.z_range);
+
+    let mut ranges = Vec::new();
+
+    // ==================== top ====================
+    // top middle middle
+    ranges.push(match z_high_range {
+        Option::Some(z_range) => Option::Some(Cuboid {
+            x_range: sub_cube.x_range,
+            y_range: sub_cube.y_range,
+            z_range: z_range,
+        }),
+        _ => Option::None,
+    });
+
+    // top middle right
+    ranges.push(match (z_high_range, x_high_range) {
+        (Option::Some(z_range), Option::Some(x_range)) => Option::Some(Cuboid {
+            x_range: x_range,
+            y_range: sub_cube.y_range,
+            z_range: z_range,
+        }),
+        _ => Option::None,
+    });
+
+    // top middle left
+    ranges.push(match (z_high_range, x_low_range) {
+        (Option::Some(z_range), Option::Some(x_range)) => Option::Some(Cuboid {
+            x_range: x_range,
+            y_range: sub_cube.y_range,
+            z_range: z_range,
+        }),
+        _ => Option::None,
+    });
+
+    // top top middle
+    ranges.push(match (z_high_range, y_high_range) {
+        (Option::Some(z_range), Option::Some(y_range)) => Option::Some(Cuboid {
+            x_range: sub_cube.x_range,
+            y_range: y_range,
+            z_range: z_range,
+        }),
+        _ => Option::None,
+    });
+
+    // top low middle
+    ranges.push(match (z_high_range, y_low_range) {
+        (Option::Some(z_range), Option::Some(y_range)) => Option::Some(Cuboid {
+            x_range: sub_cube.x_range,
+            y_range: y_range,
+            z_range: z_range,
+        }),
+        _ => Option::None,
+    });
+
+    // top top right
+    ranges.push(match (z_high_range, y_high_range, x_high_range) {
+        (Option::Some(z_range), Option::Some(y_range), Option::Some(x_range)) => {
+            Option::Some(Cuboid {
+                x_range: x_range,
+                y_range: y_range,
+                z_range: z_range,
+            })
+        }
+        _ => Option::None,
+    });
+
+    // top top left
+    ranges.push(match (z_high_range, y_high_range, x_low_range) {
+        (Option::Some(z_range), Option::Some(y_range), Option::Some(x_range)) => {
+            Option::Some(Cuboid {
+                x_range: x_range,
+                y_range: y_range,
+                z_range: z_range,
+            })
+        }
+        _ => Option::None,
+    });
+
+    // top low right
+    ranges.push(match (z_high_range, y_low_range, x_high_range) {
+        (Option::Some(z_range), Option::Some(y_range), Option::Some(x_range)) => {
+            Option::Some(Cuboid {
+                x_range: x_range,
+                y_range: y_range,
+                z_range: z_range,
+            })
+        }
+        _ => Option::None,
+    });
+
+    // top low left
+    ranges.push(match (z_high_range, y_low_range, x_low_range) {
+        (Option::Some(z_range), Option::Some(y_range), Option::Some(x_range)) => {
+            Option::Some(Cuboid {
+                x_range: x_range,
+                y_range: y_range,
+                z_range: z_range,
+            })
+        }
+        _ => Option::None,
+    });
+
+    // ==================== middle ====================
+    // middle middle right
+    ranges.push(match x_high_range {
+        Option::Some(x_range) => Option::Some(Cuboid {
+            x_range: x_range,
+            y_range: sub_cube.y_range,
+            z_range: sub_cube.z_range,
+        }),
+        _ => Option::None,
+    });
+
+    // middle middle left
+    ranges.push(match x_low_range {
+        Option::Some(x_range) => Option::Some(Cuboid {
+            x_range: x_range,
+            y_range: sub_cube.y_range,
+            z_range: sub_cube.z_range,
+        }),
+        _ => Option::None,
+    });
+
+    // middle top middle
+    ranges.push(match y_high_range {
+        Option::Some(y_range) => Option::Some(Cuboid {
+            x_range: sub_cube.x_range,
+            y_range: y_range,
+            z_range: sub_cube.z_range,
+        }),
+        _ => Option::None,
+    });
+
+    // middle bottom middle
+    ranges.push(match y_low_range {
+        Option::Some(y_range) => Option::Some(Cuboid {
+            x_range: sub_cube.x_range,
+            y_range: y_range,
+            z_range: sub_cube.z_range,
+        }),
+        _ => Option::None,
+    });
+
+    // middle top right
+    ranges.push(match (x_high_range, y_high_range) {
+        (Option::Some(x_range), Option::Some(y_range)) => Option::Some(Cuboid {
+            x_range: x_range,
+            y_range: y_range,
+            z_range: sub_cube.z_range,
+        }),
+        _ => Option::None,
+    });
+
+    // middle top left
+    ranges.push(match (x_low_range, y_high_range) {
+        (Option::Some(x_range), Option::Some(y_range)) => Option::Some(Cuboid {
+            x_range: x_range,
+            y_range: y_range,
+            z_range: sub_cube.z_range,
+        }),
+        _ => Option::None,
+    });
+
+    // middle bottom right
+    ranges.push(match (x_high_range, y_low_range) {
+        (Option::Some(x_range), Option::Some(y_range)) => Option::Some(Cuboid {
+            x_range: x_range,
+            y_range: y_range,
+            z_range: sub_cube.z_range,
+        }),
+        _ => Option::None,
+    });
+
+    // middle bottom left
+    ranges.push(match (x_low_range, y_low_range) {
+        (Option::Some(x_range), Option::Some(y_range)) => Option::Some(Cuboid {
+            x_range: x_range,
+            y_range: y_range,
+            z_range: sub_cube.z_range,
+        }),
+        _ => Option::None,
+    });
+
+    // ==================== bottom ====================
+    // low middle middle
+    ranges.push(match z_low_range {
+        Option::Some(z_range) => Option::Some(Cuboid {
+            x_range: sub_cube.x_range,
+            y_range: sub_cube.y_range,
+            z_range: z_range,
+        }),
+        _ => Option::None,
+    });
+
+    // low middle right
+    ranges.push(match (z_low_range, x_high_range) {
+        (Option::Some(z_range), Option::Some(x_range)) => Option::Some(Cuboid {
+            x_range: x_range,
+            y_range: sub_cube.y_range,
+            z_range: z_range,
+        }),
+        _ => Option::None,
+    });
+
+    // low middle left
+    ranges.push(match (z_low_range, x_low_range) {
+        (Option::Some(z_range), Option::Some(x_range)) => Option::Some(Cuboid {
+            x_range: x_range,
+            y_range: sub_cube.y_range,
+            z_range: z_range,
+        }),
+        _ => Option::None,
+    });
+
+    // low top middle
+    ranges.push(match (z_low_range, y_high_range) {
+        (Option::Some(z_range), Option::Some(y_range)) => Option::Some(Cuboid {
+            x_range: sub_cube.x_range,
+            y_range: y_range,
+            z_range: z_range,
+        }),
+        _ => Option::None,
+    });
+
+    // low low middle
+    ranges.push(match (z_low_range, y_low_range) {
+        (Option::Some(z_range), Option::Some(y_range)) => Option::Some(Cuboid {
+            x_range: sub_cube.x_range,
+            y_range: y_range,
+            z_range: z_range,
+        }),
+        _ => Option::None,
+    });
+
+    // low top right
+    ranges.push(match (z_low_range, y_high_range, x_high_range) {
+        (Option::Some(z_range), Option::Some(y_range), Option::Some(x_range)) => {
+            Option::Some(Cuboid {
+                x_range: x_range,
+                y_range: y_range,
+                z_range: z_range,
+            })
+        }
+        _ => Option::None,
+    });
+
+    // low top left
+    ranges.push(match (z_low_range, y_high_range, x_low_range) {
+        (Option::Some(z_range), Option::Some(y_range), Option::Some(x_range)) => {
+            Option::Some(Cuboid {
+                x_range: x_range,
+                y_range: y_range,
+                z_range: z_range,
+            })
+        }
+        _ => Option::None,
+    });
+
+    // low low right
+    ranges.push(match (z_low_range, y_low_range, x_high_range) {
+        (Option::Some(z_range), Option::Some(y_range), Option::Some(x_range)) => {
+            Option::Some(Cuboid {
+                x_range: x_range,
+                y_range: y_range,
+                z_range: z_range,
+            })
+        }
+        _ => Option::None,
+    });
+
+    // low low left
+    ranges.push(match (z_low_range, y_low_range, x_low_range) {
+        (Option::Some(z_range), Option::Some(y_range), Option::Some(x_range)) => {
+            Option::Some(Cuboid {
+                x_range: x_range,
+                y_range: y_range,
+                z_range: z_range,
+            })
+        }
+        _ => Option::None,
+    });
+
+    ranges
+        .into_iter()
+        .map(Option::into_iter)
+        .flatten()
+        .collect()
+}
+
+fn get_high_range(base: &Range, sub_range: &Range) -> Option<Range> {
+    let low = sub_range.high + 1;
+    let high = base.high;
+    get_range_from_low_high(low, high)
+}
+
+fn get_low_range(base: &Range, sub_range: &Range) -> Option<Range> {
+    let high = sub_range.low - 1;
+    let low = base.low;
+    get_range_from_low_high(low, high)
+}
+
+fn is_step_within_target(reboot_step: &RebootStep, low_target: isize, high_target: isize) -> bool {
+    let target_cuboid = Cuboid {
+        x_range: Range {
+            low: low_target,
+            high: high_target,
+        },
+        y_range: Range {
+            low: low_target,
+            high: high_target,
+        },
+        z_range: Range {
+            low: low_target,
+            high: high_target,
+        },
+    };
+
+    match get_cuboid_intersection(&reboot_step.cuboid, &target_cuboid) {
+        Some(_) => true,
+        _ => false,
+    }
+}
+
+fn get_cuboid_intersection(first: &Cuboid, second: &Cuboid) -> Option<Cuboid> {
+    let x_intersection = get_range_intersection(&first.x_range, &second.x_range);
+    let y_intersection = get_range_intersection(&first.y_range, &second.y_range);
+    let z_intersection = get_range_intersection(&first.z_range, &second.z_range);
+
+    match (x_intersection, y_intersection, z_intersection) {
+        (Some(x), Some(y), Some(z)) => Option::Some(Cuboid {
+            x_range: x,
+            y_range: y,
+            z_range: z,
+        }),
+        _ => Option::None,
+    }
+}
+
+fn get_range_intersection(first: &Range, second: &Range) -> Option<Range> {
+    let low = max(first.low, second.low);
+    let high = min(first.high, second.high);
+    get_range_from_low_high(low, high)
+}
+
+fn get_range_from_low_high(low: isize, high: isize) -> Option<Range> {
+    if high < low {
+        Option::None
+    } else {
+        Option::Some(Range {
+            low: low,
+            high: high,
+        })
+    }
+}
+
+// size is the number of steps. Like the real inputs, about half of the steps fall within the
+// initialization region and the first step always turns cubes on.
+fn generate(random: &mut SplitMix64, size: usize) -> String {
+    (0..size)
+        .map(|index| {
+            let (bound, max_extent) = if random.below(2usize) == 0 {
+                (50isize, 30isize)
+            } else {
+                (100000isize, 30000isize)
+            };
+            let ranges: Vec<String> = ["x", "y", "z"]
+                .iter()
+                .map(|axis| {
+                    let low = random.between(-bound, bound - max_extent);
+                    let high = low + random.between(0isize, max_extent);
+                    format!("{}={}..{}", axis, low, high)
+                })
+                .collect();
+            let state = if index == 0 || random.below(2usize) == 0 {
+                "on"
+            } else {
+                "off"
+            };
+
+            format!("{} {}\n", state, ranges.join(","))
+        })
+        .collect()
+}
+
+fn describe(reboot_steps: &Vec<RebootStep>) -> String {
+    let on_steps = reboot_steps.iter().filter(|step| step.turn_on).count();
+
+    let mut description = format!(
+        "{} reboot steps: {} on, {} off",
+        reboot_steps.len(),
+        on_steps,
+        reboot_steps.len() - on_steps
+    );
+
+    reboot_steps.iter().enumerate().for_each(|(index, step)| {
+        reversed_ranges(&step.cuboid)
+            .iter()
+            .for_each(|(axis, range)| {
+                description.push_str(&format!(
+                    "\nAnomaly: line {} has a reversed {} range {}..{}",
+                    index + 1,
+                    axis,
+                    range.low,
+                    range.high
+                ))
+            })
+    });
+
+    description
+}
+
+fn parse_data(input: &String) -> IResult<&str, Vec<RebootStep>> {
+    separated_list0(newline, parse_reboot_step)(input)
+}
+
+fn parse_reboot_step(input: &str) -> IResult<&str, RebootStep> {
+    map(
+        tuple((
+            alt((value(true, tag("on")), value(false, tag("off")))),
+            preceded(tag(" x="), parse_isize),
+            preceded(tag(".."), parse_isize),
+            preceded(tag(",y="), parse_isize),
+            preceded(tag(".."), parse_isize),
+            preceded(tag(",z="), parse_isize),
+            preceded(tag(".."), parse_isize),
+        )),
+        |(turn_on, x_low, x_high, y_low, y_high, z_low, z_high)| RebootStep {
+            turn_on: turn_on,
+            cuboid: Cuboid {
+                x_range: Range {
+                    low: x_low,
+                    high: x_high,
+                },
+                y_range: Range {
+                    low: y_low,
+                    high: y_high,
+                },
+                z_range: Range {
+                    low: z_low,
+                    high: z_high,
+                },
+            },
+        },
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{complete_parsing, run_problem_with_input, scratch_path, Command};
+
+    #[test]
+    fn part1_sample() {
+        assert_eq!(
+            run_problem_with_input(&REACTOR_REBOOT, SAMPLE, &["part1"]).unwrap(),
+            39isize.into()
+        );
+    }
+
+    #[test]
+    fn part1_sample2() {
+        assert_eq!(
+            run_problem_with_input(&REACTOR_REBOOT, SAMPLE2, &["part1"]).unwrap(),
+            590784isize.into()
+        );
+    }
+
+    #[test]
+    fn part1_sample3() {
+        assert_eq!(
+            run_problem_with_input(&REACTOR_REBOOT, SAMPLE3, &["part1"]).unwrap(),
+            474140isize.into()
+        );
+    }
+
+    #[test]
+    fn part2_sample3() {
+        assert_eq!(
+            run_problem_with_input(&REACTOR_REBOOT, SAMPLE3, &["part2"]).unwrap(),
+            2758514936282235isize.into()
+        );
+    }
+
+    #[test]
+    fn timeline_sample() {
+        assert_eq!(
+            run_problem_with_input(&REACTOR_REBOOT, SAMPLE, &["--part", "1", "--timeline"])
+                .unwrap(),
+            vec![
+                ("Step 1".to_string(), 27isize.into()),
+                ("Step 2".to_string(), 46isize.into()),
+                ("Step 3".to_string(), 38isize.into()),
+                ("Step 4".to_string(), 39isize.into()),
+            ]
+            .into()
+        );
+    }
+    #[test]
+    fn query_sample() {
+        assert_eq!(
+            run_problem_with_input(
+                &REACTOR_REBOOT,
+                SAMPLE,
+                &[
+                    "--part",
+                    "1",
+                    "-q",
+                    "10..10,10..10,10..10",
+                    "-q",
+                    "x=13..11,y=11..13,z=11..13",
+                    "-q",
+                    "-5..5,0..5,0..5"
+                ]
+            )
+            .unwrap(),
+            vec![
+                ("10..10,10..10,10..10".to_string(), 1isize.into()),
+                ("11..13,11..13,11..13".to_string(), 26isize.into()),
+                ("-5..5,0..5,0..5".to_string(), 0isize.into()),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn reversed_range_is_rejected() {
+        let input = SAMPLE.replacen("x=11..13", "x=13..11", 1);
+        let error = run_problem_with_input(&REACTOR_REBOOT, &input, &["part1"]).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Line 2 has a reversed x range 13..11, pass --normalize to swap it"
+        );
+        assert!(REACTOR_REBOOT
+            .validate_input(&input)
+            .unwrap()
+            .contains("Anomaly: line 2 has a reversed x range 13..11"));
+    }
+
+    #[test]
+    fn normalize_swaps_reversed_ranges() {
+        let input = SAMPLE.replacen("x=11..13", "x=13..11", 1);
+
+        assert_eq!(
+            run_problem_with_input(&REACTOR_REBOOT, &input, &["--part", "1", "-n"]).unwrap(),
+            39isize.into()
+        );
+    }
+
+    #[test]
+    fn oversized_steps_are_rejected() {
+        let huge = format!("on x={}..{},y=0..0,z=0..0", isize::MIN / 2, isize::MAX / 2);
+        let error = run_problem_with_input(&REACTOR_REBOOT, &huge, &["part2"]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Line 1 covers more cubes than fit in 64 bits"
+        );
+
+        let apart = format!(
+            "on x=0..0,y={}..{},z=0..0\non x=0..0,y={}..{},z=0..0",
+            isize::MIN / 2,
+            isize::MIN / 2,
+            isize::MAX / 2,
+            isize::MAX / 2
+        );
+        let error = run_problem_with_input(&REACTOR_REBOOT, &apart, &["part2"]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Together the steps span more cubes than fit in 64 bits"
+        );
+        assert_eq!(
+            run_problem_with_input(&REACTOR_REBOOT, &apart, &["part1"]).unwrap(),
+            0isize.into()
+        );
+    }
+
+    #[test]
+    fn unwritable_export_is_an_error() {
+        let path = scratch_path("reactor_reboot_missing").join("cubes.obj");
+        let path = path.to_str().unwrap();
+        let error =
+            run_problem_with_input(&REACTOR_REBOOT, SAMPLE, &["--part", "1", "--export", path])
+                .unwrap_err();
+
+        assert!(matches!(error, AocError::Io(_)));
+        assert!(error
+            .to_string()
+            .contains(&format!("{} can't be written", path)));
+    }
+
+    #[test]
+    fn generate_is_valid() {
+        let input = REACTOR_REBOOT.generate(3u64, 20usize).unwrap();
+
+        assert_eq!(input, REACTOR_REBOOT.generate(3u64, 20usize).unwrap());
+        assert!(!REACTOR_REBOOT
+            .validate_input(&input)
+            .unwrap()
+            .contains("Anomaly"));
+
+        let reboot_steps = complete_parsing(parse_data)(&input).unwrap();
+        assert_eq!(reboot_steps.len(), 20);
+        assert!(reboot_steps[0].turn_on);
+    }
+}
@@ -0,0 +1,501 @@
+use crate::{
+    default_sub_command, describe_grid, report_output, selected_part, CommandResult, Direction,
+    Example, Problem,
+};
+use clap::{App, Arg, ArgMatches};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::newline,
+    combinator::value,
+    multi::{many1, separated_list0},
+    IResult,
+};
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+};
+
+pub const SEA_CUCUMBER: Problem<SeaCucumberArgs, Vec<Vec<SeaCucumber>>> = Problem::new(
+    sub_command,
+    "sea-cucumber",
+    "day25_sea_cucumber",
+    parse_arguments,
+    parse_data,
+    run,
+)
+.with_long_about(LONG_ABOUT)
+.with_examples(EXAMPLES)
+.with_describe(describe)
+.with_cross_check(cross_check_arguments);
+
+const LONG_ABOUT: &str =
+    "The input is a grid of east facing (>) and south facing (v) sea cucumbers.\n\n\
+    Each step the east facing herd moves first, then the south facing herd. A sea cucumber \
+    moves forward one space if that space is empty, wrapping around the edges. The answer is \
+    the first step where no sea cucumber moves. -n runs the cell by cell simulation instead of \
+    the bitset one and --cross-check runs both and compares the answers.\n\n\
+    -r reports how many of each herd moved on every step, ending with the steady step. -g \
+    writes the grid once nothing moves to a file, in the same format as the input.\n\n\
+    Example: the sample stops moving on step 58.";
+
+const SAMPLE: &str = include_str!("../../day25_sea_cucumber/sample.txt");
+
+const EXAMPLES: &[Example] = &[Example::new(SAMPLE, 1, "58")];
+
+#[derive(Debug)]
+pub struct SeaCucumberArgs {
+    naive: bool,
+    report: bool,
+    grid_file: Option<String>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SeaCucumber {
+    Right,
+    Down,
+    None,
+}
+
+// Each row of the grid is stored as a set of 64 bit words, one bitset per herd.
+#[derive(Debug)]
+struct SeaCucumberBits {
+    width: usize,
+    right: Vec<Vec<u64>>,
+    down: Vec<Vec<u64>>,
+}
+
+fn sub_command() -> App<'static, 'static> {
+    default_sub_command(
+        &SEA_CUCUMBER,
+        "Simulates sea cucumber movements and finds the steady state.",
+        "Path to the input file. Input should be the initial state of the sea cucumbers.",
+        "Returns the number of steps to reach steady state for the default input.",
+        "The same as part 1!",
+    )
+    .arg(
+        Arg::with_name("naive")
+            .short("n")
+            .long("naive")
+            .help("If passed, simulates the grid cell by cell instead of using bitsets."),
+    )
+    .arg(Arg::with_name("report").short("r").long("report").help(
+        "If passed, reports how many east and south facing sea cucumbers moved on each step.",
+    ))
+    .arg(
+        Arg::with_name("grid-file")
+            .short("g")
+            .long("grid-file")
+            .help("If passed, writes the grid once no sea cucumber moves to the given path.")
+            .takes_value(true),
+    )
+}
+
+fn parse_arguments(arguments: &ArgMatches) -> SeaCucumberArgs {
+    match selected_part(arguments) {
+        Some("part1") => SeaCucumberArgs {
+            naive: arguments.is_present("naive"),
+            report: arguments.is_present("report"),
+            grid_file: arguments.value_of("grid-file").map(String::from),
+        },
+        Some("part2") => SeaCucumberArgs {
+            naive: arguments.is_present("naive"),
+            report: arguments.is_present("report"),
+            grid_file: arguments.value_of("grid-file").map(String::from),
+        },
+        _ => SeaCucumberArgs {
+            naive: arguments.is_present("naive"),
+            report: arguments.is_present("report"),
+            grid_file: arguments.value_of("grid-file").map(String::from),
+        },
+    }
+}
+
+// The alternate run only checks the answer, so it leaves the grid file to the main run.
+fn cross_check_arguments(arguments: &ArgMatches) -> SeaCucumberArgs {
+    let arguments = parse_arguments(arguments);
+    SeaCucumberArgs {
+        naive: !arguments.naive,
+        grid_file: None,
+        ..arguments
+    }
+}
+
+fn run(arguments: SeaCucumberArgs, mut sea_cucumbers: Vec<Vec<SeaCucumber>>) -> CommandResult {
+    let moves = simulate(&arguments, &mut sea_cucumbers);
+
+    if let Some(path) = &arguments.grid_file {
+        report_output(path, write_grid(&sea_cucumbers, path));
+    }
+
+    if arguments.report {
+        moves
+            .iter()
+            .enumerate()
+            .map(|(index, (east, south))| {
+                (
+                    format!("Step {}", index + 1),
+                    format!("{} east and {} south moved", east, south).into(),
+                )
+            })
+            .chain(std::iter::once((
+                "Steady state".to_string(),
+                moves.len().into(),
+            )))
+            .collect::<Vec<(String, CommandResult)>>()
+            .into()
+    } else {
+        moves.len().into()
+    }
+}
+
+// How many of the east and south facing herds moved on each step, up to the first step where
+// neither did. The grid is only brought up to date from the bitsets when it's written out.
+fn simulate(
+    arguments: &SeaCucumberArgs,
+    sea_cucumbers: &mut Vec<Vec<SeaCucumber>>,
+) -> Vec<(usize, usize)> {
+    let mut moves = Vec::new();
+
+    if arguments.naive {
+        loop {
+            let count = run_step(sea_cucumbers);
+            moves.push(count);
+            if count == (0, 0) {
+                break;
+            }
+        }
+    } else {
+        let mut sea_cucumber_bits = to_bits(sea_cucumbers);
+        loop {
+            let count = run_bit_step(&mut sea_cucumber_bits);
+            moves.push(count);
+            if count == (0, 0) {
+                break;
+            }
+        }
+        if arguments.grid_file.is_some() {
+            *sea_cucumbers = from_bits(&sea_cucumber_bits);
+        }
+    }
+
+    moves
+}
+
+fn write_grid(sea_cucumbers: &[Vec<SeaCucumber>], path: &str) -> Result<(), std::io::Error> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    sea_cucumbers.iter().try_for_each(|row| {
+        writeln!(
+            writer,
+            "{}",
+            row.iter()
+                .map(|cucumber| match cucumber {
+                    SeaCucumber::Right => '>',
+                    SeaCucumber::Down => 'v',
+                    SeaCucumber::None => '.',
+                })
+                .collect::<String>()
+        )
+    })?;
+    writer.flush()
+}
+
+fn to_bits(sea_cucumbers: &[Vec<SeaCucumber>]) -> SeaCucumberBits {
+    let width = sea_cucumbers.first().map(|row| row.len()).unwrap_or(0usize);
+    let words = width.div_ceil(64);
+    let herd_bits = |herd: fn(&SeaCucumber) -> bool| -> Vec<Vec<u64>> {
+        sea_cucumbers
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, cucumber)| herd(cucumber))
+                    .fold(vec![0u64; words], |mut acc, (j, _)| {
+                        acc[j / 64] |= 1u64 << (j % 64);
+                        acc
+                    })
+            })
+            .collect()
+    };
+
+    SeaCucumberBits {
+        width,
+        right: herd_bits(|cucumber| matches!(cucumber, SeaCucumber::Right)),
+        down: herd_bits(|cucumber| matches!(cucumber, SeaCucumber::Down)),
+    }
+}
+
+fn from_bits(sea_cucumbers: &SeaCucumberBits) -> Vec<Vec<SeaCucumber>> {
+    let bit = |row: &[u64], j: usize| row[j / 64] & (1u64 << (j % 64)) != 0;
+
+    sea_cucumbers
+        .right
+        .iter()
+        .zip(sea_cucumbers.down.iter())
+        .map(|(right, down)| {
+            (0..sea_cucumbers.width)
+                .map(|j| {
+                    if bit(right, j) {
+                        SeaCucumber::Right
+                    } else if bit(down, j) {
+                        SeaCucumber::Down
+                    } else {
+                        SeaCucumber::None
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn run_bit_step(sea_cucumbers: &mut SeaCucumberBits) -> (usize, usize) {
+    let width = sea_cucumbers.width;
+    let mut east = 0usize;
+
+    for i in 0..sea_cucumbers.right.len() {
+        let occupied = or_bits(&sea_cucumbers.right[i], &sea_cucumbers.down[i]);
+        let moving: Vec<u64> = sea_cucumbers.right[i]
+            .iter()
+            .zip(next_bits(&occupied, width).iter())
+            .map(|(right, next_occupied)| right & !next_occupied)
+            .collect();
+        east += count_bits(&moving);
+
+        let moved = previous_bits(&moving, width);
+        sea_cucumbers.right[i]
+            .iter_mut()
+            .zip(moving.iter().zip(moved.iter()))
+            .for_each(|(right, (moving, moved))| *right = (*right & !moving) | moved);
+    }
+
+    let height = sea_cucumbers.down.len();
+    let mut south = 0usize;
+    let moving: Vec<Vec<u64>> = (0..height)
+        .map(|i| {
+            let below = (i + 1) % height;
+            let occupied = or_bits(&sea_cucumbers.right[below], &sea_cucumbers.down[below]);
+            sea_cucumbers.down[i]
+                .iter()
+                .zip(occupied.iter())
+                .map(|(down, occupied)| down & !occupied)
+                .collect()
+        })
+        .collect();
+
+    moving.iter().enumerate().for_each(|(i, moving)| {
+        south += count_bits(moving);
+        let below = (i + 1) % height;
+        sea_cucumbers.down[i]
+            .iter_mut()
+            .zip(moving.iter())
+            .for_each(|(down, moving)| *down &= !moving);
+        sea_cucumbers.down[below]
+            .iter_mut()
+            .zip(moving.iter())
+            .for_each(|(down, moving)| *down |= moving);
+    });
+
+    (east, south)
+}
+
+fn or_bits(first: &[u64], second: &[u64]) -> Vec<u64> {
+    first
+        .iter()
+        .zip(second.iter())
+        .map(|(a, b)| a | b)
+        .collect()
+}
+
+fn count_bits(bits: &[u64]) -> usize {
+    bits.iter().map(|word| word.count_ones() as usize).sum()
+}
+
+// Bit j of the result is bit (j + 1) % width of the row.
+fn next_bits(row: &[u64], width: usize) -> Vec<u64> {
+    let mut result: Vec<u64> = (0..row.len())
+        .map(|w| {
+            let carry = row.get(w + 1).map(|next| next << 63).unwrap_or(0u64);
+            (row[w] >> 1) | carry
+        })
+        .collect();
+
+    let last = width - 1;
+    result[last / 64] =
+        (result[last / 64] & !(1u64 << (last % 64))) | ((row[0] & 1u64) << (last % 64));
+    result
+}
+
+// Bit j of the result is bit (j + width - 1) % width of the row.
+fn previous_bits(row: &[u64], width: usize) -> Vec<u64> {
+    let last = width - 1;
+    let wrapped = (row[last / 64] >> (last % 64)) & 1u64;
+    let mut result: Vec<u64> = (0..row.len())
+        .map(|w| {
+            let carry = if w > 0 { row[w - 1] >> 63 } else { wrapped };
+            (row[w] << 1) | carry
+        })
+        .collect();
+
+    if !width.is_multiple_of(64) {
+        result[last / 64] &= (1u64 << (width % 64)) - 1;
+    }
+    result
+}
+
+fn run_step(sea_cucumbers: &mut [Vec<SeaCucumber>]) -> (usize, usize) {
+    let east = move_herd(sea_cucumbers, SeaCucumber::Right, Direction::East);
+    let south = move_herd(sea_cucumbers, SeaCucumber::Down, Direction::South);
+    (east, south)
+}
+
+// Every sea cucumber in the herd with an empty space ahead of it moves at once, so the spaces are
+// all checked before any of them move.
+fn move_herd(
+    sea_cucumbers: &mut [Vec<SeaCucumber>],
+    herd: SeaCucumber,
+    heading: Direction,
+) -> usize {
+    let height = sea_cucumbers.len();
+    let width = sea_cucumbers.first().map(|row| row.len()).unwrap_or(0usize);
+    let movements: Vec<((usize, usize), (usize, usize))> = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .filter(|(x, y)| sea_cucumbers[*y][*x] == herd)
+        .map(|position| (position, heading.wrapping_offset(position, (width, height))))
+        .filter(|(_, (x, y))| sea_cucumbers[*y][*x] == SeaCucumber::None)
+        .collect();
+
+    movements.iter().for_each(|((x, y), (new_x, new_y))| {
+        sea_cucumbers[*y][*x] = SeaCucumber::None;
+        sea_cucumbers[*new_y][*new_x] = herd;
+    });
+
+    movements.len()
+}
+
+fn describe(sea_cucumbers: &Vec<Vec<SeaCucumber>>) -> String {
+    let (right, down) =
+        sea_cucumbers
+            .iter()
+            .flatten()
+            .fold((0usize, 0usize), |(right, down), cucumber| match cucumber {
+                SeaCucumber::Right => (right + 1, down),
+                SeaCucumber::Down => (right, down + 1),
+                SeaCucumber::None => (right, down),
+            });
+
+    format!(
+        "{}\n{} east facing and {} south facing sea cucumbers",
+        describe_grid(sea_cucumbers),
+        right,
+        down
+    )
+}
+
+fn parse_data(input: &String) -> IResult<&str, Vec<Vec<SeaCucumber>>> {
+    separated_list0(newline, parse_cucumber_row)(input)
+}
+
+fn parse_cucumber_row(input: &str) -> IResult<&str, Vec<SeaCucumber>> {
+    many1(parse_cucumber)(input)
+}
+
+fn parse_cucumber(input: &str) -> IResult<&str, SeaCucumber> {
+    alt((
+        value(SeaCucumber::Right, tag(">")),
+        value(SeaCucumber::Down, tag("v")),
+        value(SeaCucumber::None, tag(".")),
+    ))(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{run_problem_with_input, scratch_path, AocError};
+
+    #[test]
+    fn part1_sample() {
+        assert_eq!(
+            run_problem_with_input(&SEA_CUCUMBER, SAMPLE, &["part1"]).unwrap(),
+            58usize.into()
+        );
+    }
+
+    #[test]
+    fn naive_sample() {
+        assert_eq!(
+            run_problem_with_input(&SEA_CUCUMBER, SAMPLE, &["-f", "sample.txt", "--naive"])
+                .unwrap(),
+            58usize.into()
+        );
+    }
+    #[test]
+    fn report_sample() {
+        let report = run_problem_with_input(
+            &SEA_CUCUMBER,
+            SAMPLE,
+            &["--part", "1", "-r", "--cross-check"],
+        )
+        .unwrap();
+        let CommandResult::Labeled(steps) = report else {
+            panic!("the report is labeled by step");
+        };
+
+        assert_eq!(steps.len(), 59);
+        assert_eq!(
+            steps[0],
+            (
+                "Step 1".to_string(),
+                "10 east and 14 south moved".to_string().into()
+            )
+        );
+        assert_eq!(
+            steps[57],
+            (
+                "Step 58".to_string(),
+                "0 east and 0 south moved".to_string().into()
+            )
+        );
+        assert_eq!(steps[58], ("Steady state".to_string(), 58usize.into()));
+    }
+
+    #[test]
+    fn grid_file_sample() {
+        let steady = "..>>v>vv..\n..v.>>vv..\n..>>v>>vv.\n..>>>>>vv.\nv......>vv\n\
+            v>v....>>v\nvvv.....>>\n>vv......>\n.>v.vv.v..\n";
+
+        ["bits", "naive"].iter().for_each(|engine| {
+            let grid_file = scratch_path(&format!("sea_cucumber_grid_file_{}.txt", engine));
+            let grid_file = grid_file.to_str().unwrap();
+            let mut arguments = vec!["--part", "1", "-g", grid_file];
+            if *engine == "naive" {
+                arguments.push("-n");
+            }
+
+            run_problem_with_input(&SEA_CUCUMBER, SAMPLE, &arguments).unwrap();
+            assert_eq!(std::fs::read_to_string(grid_file).unwrap(), steady);
+        });
+    }
+
+    #[test]
+    fn unwritable_grid_file_is_an_error() {
+        let grid_file = scratch_path("sea_cucumber_missing").join("grid.txt");
+        let grid_file = grid_file.to_str().unwrap();
+        let error =
+            run_problem_with_input(&SEA_CUCUMBER, SAMPLE, &["--part", "1", "-g", grid_file])
+                .unwrap_err();
+
+        assert!(matches!(error, AocError::Io(_)));
+        assert!(error
+            .to_string()
+            .contains(&format!("{} can't be written", grid_file)));
+    }
+
+    #[test]
+    fn cross_check_sample() {
+        assert_eq!(
+            run_problem_with_input(&SEA_CUCUMBER, SAMPLE, &["--part", "1", "--cross-check"])
+                .unwrap(),
+            58usize.into()
+        );
+    }
+}
@@ -0,0 +1,334 @@
+use crate::seven_segment::{
+    decode_line, decode_line_by_frequency, DecodeError, DigitMap, Pattern, Wire,
+};
+use crate::{
+    default_sub_command, log, parse_usize, selected_part, AocError, CommandResult, Example,
+    LogLevel, Problem,
+};
+use clap::{value_t_or_exit, App, Arg, ArgMatches};
+use nom::{
+    bytes::complete::{tag, take},
+    character::complete::newline,
+    combinator::{map, map_res},
+    multi::{many0, separated_list0},
+    sequence::separated_pair,
+    IResult,
+};
+use std::str::FromStr;
+use strum::VariantNames;
+use strum_macros::{EnumString, EnumVariantNames};
+
+pub const SEVEN_SEGMENT: Problem<SevenSegmentArgs, Vec<SignalLine>> = Problem::new(
+    sub_command,
+    "seven-segment",
+    "day8_seven_segment",
+    parse_arguments,
+    parse_data,
+    run,
+)
+.with_long_about(LONG_ABOUT)
+.with_examples(EXAMPLES)
+.with_describe(describe)
+.with_input_check(input_check)
+.with_cross_check(cross_check_arguments);
+
+const LONG_ABOUT: &str =
+    "Each line lists the ten unique signal patterns seen on a scrambled four digit display, \
+    then a |, then the four output digits. The wires a to g connect to the segments in a \
+    random order that is fixed for each line.\n\n\
+    count-uniques counts the output digits that use a unique number of segments: 1, 4, 7 and \
+    8. full-decode works out the wiring for each line, decodes its four digit output and sums \
+    all of the outputs.\n\n\
+    A line whose wiring can't be worked out stops the run with its line number and the reason. \
+    -s skips those lines instead and uses the rest. -v lists the lines it skipped.\n\n\
+    The deductive strategy finds 1, 4, 7 and 8 by length and works out the rest from how they \
+    overlap. The frequency strategy counts how often each wire appears across the ten \
+    patterns. Adding up those counts for a pattern's wires gives a different total for each \
+    digit whatever the wiring, so no digit depends on another being found first. \
+    --cross-check runs both and compares the answers.\n\n\
+    Example: the larger sample has 26 unique digits and its outputs sum to 61229.";
+
+const SAMPLE: &str = include_str!("../../day8_seven_segment/sample.txt");
+
+const EXAMPLES: &[Example] = &[
+    Example::new(SAMPLE, 1, "26"),
+    Example::new(SAMPLE, 2, "61229"),
+];
+
+#[derive(Debug)]
+pub struct SevenSegmentArgs {
+    decode_function: DecodeFunction,
+    skip_invalid: bool,
+    strategy: Strategy,
+}
+
+#[derive(Debug, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "kebab_case")]
+enum DecodeFunction {
+    CountUniques,
+    FullDecode,
+}
+
+#[derive(Debug, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "kebab_case")]
+enum Strategy {
+    Deductive,
+    Frequency,
+}
+
+#[derive(Debug, Clone)]
+pub struct SignalLine {
+    input: Vec<Pattern>,
+    output: Vec<Pattern>,
+}
+
+fn sub_command() -> App<'static, 'static> {
+    default_sub_command(
+        &SEVEN_SEGMENT,
+        "Decodes segment lines based on the signal input.",
+        "Path to the input file. Input should 10 signals, followed by signal output",
+        "Finds the total number of output signals that are 1, 4, 7, or 8.",
+        "Parse the full signal, then sum all the outputs.",
+    )
+    .arg(
+        Arg::with_name("decode")
+            .short("d")
+            .help(
+                "The type of decoding requests. The functions available are as follows:\n\n\
+            count-unique: Counts the total number of 1, 4, 7, and 8 signals.\n\n\
+            full-decode: Fully decodes the signal then sums all the signals.\n\n",
+            )
+            .takes_value(true)
+            .possible_values(&DecodeFunction::VARIANTS)
+            .required_unless_one(&["part", "explain"]),
+    )
+    .arg(
+        Arg::with_name("skip-invalid")
+            .short("s")
+            .long("skip-invalid")
+            .help("If passed, skips lines that can't be decoded instead of failing. -v lists them."),
+    )
+    .arg(
+        Arg::with_name("strategy")
+            .short("t")
+            .long("strategy")
+            .help(
+                "How each line's wiring is worked out. The strategies available are as follows:\n\n\
+            deductive: Finds 1, 4, 7 and 8 by length, then the rest from how they overlap.\n\n\
+            frequency: Matches each pattern by how often its wires appear across all ten.\n\n",
+            )
+            .takes_value(true)
+            .possible_values(Strategy::VARIANTS)
+            .default_value("deductive"),
+    )
+}
+
+fn parse_arguments(arguments: &ArgMatches) -> SevenSegmentArgs {
+    match selected_part(arguments) {
+        Some("part1") => SevenSegmentArgs {
+            decode_function: DecodeFunction::CountUniques,
+            skip_invalid: arguments.is_present("skip-invalid"),
+            strategy: value_t_or_exit!(arguments.value_of("strategy"), Strategy),
+        },
+        Some("part2") => SevenSegmentArgs {
+            decode_function: DecodeFunction::FullDecode,
+            skip_invalid: arguments.is_present("skip-invalid"),
+            strategy: value_t_or_exit!(arguments.value_of("strategy"), Strategy),
+        },
+        _ => SevenSegmentArgs {
+            decode_function: value_t_or_exit!(arguments.value_of("decode"), DecodeFunction),
+            skip_invalid: arguments.is_present("skip-invalid"),
+            strategy: value_t_or_exit!(arguments.value_of("strategy"), Strategy),
+        },
+    }
+}
+
+fn cross_check_arguments(arguments: &ArgMatches) -> SevenSegmentArgs {
+    let arguments = parse_arguments(arguments);
+    SevenSegmentArgs {
+        strategy: match arguments.strategy {
+            Strategy::Deductive => Strategy::Frequency,
+            Strategy::Frequency => Strategy::Deductive,
+        },
+        ..arguments
+    }
+}
+
+fn input_check(
+    arguments: &SevenSegmentArgs,
+    signal_lines: &Vec<SignalLine>,
+) -> Result<(), AocError> {
+    if arguments.skip_invalid {
+        return Ok(());
+    }
+
+    signal_lines
+        .iter()
+        .enumerate()
+        .try_for_each(|(index, signal_line)| {
+            decode_output(&arguments.strategy, signal_line)
+                .map(|_| ())
+                .map_err(|e| {
+                    AocError::validation(format!("Line {} can't be decoded: {}", index + 1, e))
+                })
+        })
+}
+
+fn run(arguments: SevenSegmentArgs, signal_lines: Vec<SignalLine>) -> CommandResult {
+    let decode_function = match arguments.decode_function {
+        DecodeFunction::CountUniques => count_1_4_7_8,
+        DecodeFunction::FullDecode => concat_signal,
+    };
+
+    signal_lines
+        .iter()
+        .enumerate()
+        .filter_map(
+            |(index, signal_line)| match decode_output(&arguments.strategy, signal_line) {
+                Ok(digits) => Some(digits),
+                Err(e) => {
+                    log!(LogLevel::Debug, "Skipped line {}: {}", index + 1, e);
+                    None
+                }
+            },
+        )
+        .map(decode_function)
+        .fold(0usize, |sum, line| sum + line)
+        .into()
+}
+
+fn count_1_4_7_8(signal: Vec<usize>) -> usize {
+    signal
+        .into_iter()
+        .filter(|item| *item == 1usize || *item == 4usize || *item == 7usize || *item == 8usize)
+        .count()
+}
+
+fn concat_signal(signal: Vec<usize>) -> usize {
+    let (_, result) = parse_usize(
+        &signal
+            .into_iter()
+            .map(|item| item.to_string())
+            .collect::<Vec<String>>()
+            .join(""),
+    )
+    .unwrap();
+
+    result
+}
+
+fn decode_output(strategy: &Strategy, signal_line: &SignalLine) -> Result<Vec<usize>, DecodeError> {
+    let decode: fn(&[Pattern]) -> Result<DigitMap, DecodeError> = match strategy {
+        Strategy::Deductive => decode_line,
+        Strategy::Frequency => decode_line_by_frequency,
+    };
+    decode(&signal_line.input)?.decode(&signal_line.output)
+}
+
+fn describe(signal_lines: &Vec<SignalLine>) -> String {
+    let mut description = format!("{} signal lines", signal_lines.len());
+
+    signal_lines
+        .iter()
+        .enumerate()
+        .filter(|(_, signal_line)| signal_line.input.len() != 10 || signal_line.output.len() != 4)
+        .for_each(|(index, signal_line)| {
+            description.push_str(&format!(
+                "\nAnomaly: line {} has {} signals and {} outputs",
+                index + 1,
+                signal_line.input.len(),
+                signal_line.output.len()
+            ))
+        });
+
+    description
+}
+
+fn parse_data(input: &String) -> IResult<&str, Vec<SignalLine>> {
+    separated_list0(newline, parse_singal_line)(input)
+}
+
+fn parse_singal_line(input: &str) -> IResult<&str, SignalLine> {
+    map(
+        separated_pair(parse_singals, tag("| "), parse_singals),
+        |(i, output)| SignalLine {
+            input: i,
+            output: output,
+        },
+    )(input)
+}
+
+fn parse_singals(input: &str) -> IResult<&str, Vec<Pattern>> {
+    separated_list0(tag(" "), parse_segment)(input)
+}
+
+fn parse_segment(input: &str) -> IResult<&str, Pattern> {
+    map(many0(parse_signal_wire), |signals| {
+        signals.into_iter().collect()
+    })(input)
+}
+
+fn parse_signal_wire(input: &str) -> IResult<&str, Wire> {
+    map_res(take(1usize), Wire::from_str)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_problem_with_input;
+
+    #[test]
+    fn part1_sample() {
+        assert_eq!(
+            run_problem_with_input(&SEVEN_SEGMENT, SAMPLE, &["part1"]).unwrap(),
+            26usize.into()
+        );
+    }
+
+    #[test]
+    fn part2_sample() {
+        assert_eq!(
+            run_problem_with_input(&SEVEN_SEGMENT, SAMPLE, &["part2"]).unwrap(),
+            61229usize.into()
+        );
+    }
+
+    const AMBIGUOUS_LINE: &str =
+        "ab cd abc abcd abcdefg bcdef acdfg abcdf abcdef bcdefg | ab ab ab ab";
+
+    #[test]
+    fn invalid_line_is_reported() {
+        let input = format!("{}\n{}", SAMPLE.trim_end(), AMBIGUOUS_LINE);
+
+        assert_eq!(
+            run_problem_with_input(&SEVEN_SEGMENT, &input, &["part2"])
+                .unwrap_err()
+                .to_string(),
+            "Line 11 can't be decoded: 2 patterns could be 1: ab, cd"
+        );
+    }
+
+    #[test]
+    fn frequency_strategy_sample() {
+        assert_eq!(
+            run_problem_with_input(&SEVEN_SEGMENT, SAMPLE, &["--part", "2", "-t", "frequency"])
+                .unwrap(),
+            61229usize.into()
+        );
+        assert_eq!(
+            run_problem_with_input(&SEVEN_SEGMENT, SAMPLE, &["--part", "2", "--cross-check"])
+                .unwrap(),
+            61229usize.into()
+        );
+    }
+
+    #[test]
+    fn skip_invalid_sums_the_rest() {
+        let input = format!("{}\n{}", SAMPLE.trim_end(), AMBIGUOUS_LINE);
+
+        assert_eq!(
+            run_problem_with_input(&SEVEN_SEGMENT, &input, &["--part", "2", "-s"]).unwrap(),
+            61229usize.into()
+        );
+    }
+}
@@ -0,0 +1,394 @@
+use crate::{
+    check_grid, default_sub_command, describe_grid, parse_digit_grid, report_output, selected_part,
+    AocError, BitGrid, CommandResult, Example, Grid, Problem,
+};
+use clap::{value_t_or_exit, App, Arg, ArgMatches};
+use nom::IResult;
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+};
+use strum::VariantNames;
+use strum_macros::{EnumString, EnumVariantNames};
+
+pub const SMOKE_BASIN: Problem<SmokeBasinArgs, Grid<usize>> = Problem::new(
+    sub_command,
+    "smoke-basin",
+    "day9_smoke_basin",
+    parse_arguments,
+    parse_data,
+    run,
+)
+.with_long_about(LONG_ABOUT)
+.with_examples(EXAMPLES)
+.with_describe(describe)
+.with_input_check(input_check);
+
+const LONG_ABOUT: &str = "The input is a heightmap of digits.\n\n\
+    A low point is lower than all of its up, down, left and right neighbours. risk-level sums \
+    one plus the height of every low point. big-basins finds the basins, the areas bounded by \
+    height 9 that flow down to each low point, and multiplies the sizes of the three largest. \
+    -b lists every basin's size instead, largest first. -l writes a map labelling each cell with \
+    its basin number, or . for the height 9 ridges.\n\n\
+    Example: the sample has a risk level of 15 and its largest basins multiply to 1134.";
+
+const SAMPLE: &str = include_str!("../../day9_smoke_basin/sample.txt");
+
+const EXAMPLES: &[Example] = &[
+    Example::new(SAMPLE, 1, "15"),
+    Example::new(SAMPLE, 2, "1134"),
+];
+
+#[derive(Debug)]
+pub struct SmokeBasinArgs {
+    topography_function: TopographyFunction,
+    basin_report: bool,
+    label_map: Option<String>,
+}
+
+#[derive(Debug, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "kebab_case")]
+enum TopographyFunction {
+    RiskLevel,
+    BigBasins,
+}
+
+fn sub_command() -> App<'static, 'static> {
+    default_sub_command(
+        &SMOKE_BASIN,
+        "Finds low points in lava tube smoke stacks then calculates values",
+        "Path to the input file. Input should be integers and newlines with equal sizes.",
+        "Searches the default input for the total risk level.",
+        "Searches the default input for the largest three basins, then multiplies their sizes.",
+    )
+    .arg(
+        Arg::with_name("topography-function")
+            .short("t")
+            .help(
+                "The type topography requests. The functions available are as follows:\n\n\
+            risk-level: Finds the low points then calculates the total risk level.\n\n\
+            big-basin: Finds the largest three basins then multiplies thier sizes.\n\n",
+            )
+            .takes_value(true)
+            .possible_values(&TopographyFunction::VARIANTS)
+            .required_unless_one(&["part", "explain"]),
+    )
+    .arg(
+        Arg::with_name("basin-report")
+            .short("b")
+            .long("basin-report")
+            .help("If passed, lists the size of every basin, largest first, instead of the answer."),
+    )
+    .arg(
+        Arg::with_name("label-map")
+            .short("l")
+            .long("label-map")
+            .help("If passed, writes the heightmap to the given path with each cell labelled by its basin number, or . for ridges.")
+            .takes_value(true),
+    )
+}
+
+fn parse_arguments(arguments: &ArgMatches) -> SmokeBasinArgs {
+    match selected_part(arguments) {
+        Some("part1") => SmokeBasinArgs {
+            topography_function: TopographyFunction::RiskLevel,
+            basin_report: arguments.is_present("basin-report"),
+            label_map: arguments.value_of("label-map").map(String::from),
+        },
+        Some("part2") => SmokeBasinArgs {
+            topography_function: TopographyFunction::BigBasins,
+            basin_report: arguments.is_present("basin-report"),
+            label_map: arguments.value_of("label-map").map(String::from),
+        },
+        _ => SmokeBasinArgs {
+            topography_function: value_t_or_exit!(
+                arguments.value_of("topography-function"),
+                TopographyFunction
+            ),
+            basin_report: arguments.is_present("basin-report"),
+            label_map: arguments.value_of("label-map").map(String::from),
+        },
+    }
+}
+
+fn input_check(_arguments: &SmokeBasinArgs, smoke_points: &Grid<usize>) -> Result<(), AocError> {
+    check_grid(smoke_points)
+}
+
+fn run(arguments: SmokeBasinArgs, smoke_points: Vec<Vec<usize>>) -> CommandResult {
+    let topography = match arguments.topography_function {
+        TopographyFunction::RiskLevel => calculate_risk_level,
+        TopographyFunction::BigBasins => calculate_top_3_basin_sizes,
+    };
+
+    let low_points = find_low_points(&smoke_points);
+
+    if let Some(path) = &arguments.label_map {
+        let basins = find_basins(&low_points, &smoke_points);
+        report_output(path, write_label_map(&basins, &smoke_points, path));
+    }
+
+    if arguments.basin_report {
+        let mut basin_sizes: Vec<(usize, usize)> = find_basins(&low_points, &smoke_points)
+            .iter()
+            .enumerate()
+            .map(|(index, basin)| (index + 1, basin.len()))
+            .collect();
+        basin_sizes.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+        basin_sizes
+            .into_iter()
+            .map(|(id, size)| (format!("Basin {}", id), size.into()))
+            .collect::<Vec<(String, CommandResult)>>()
+            .into()
+    } else {
+        topography(&low_points, &smoke_points).into()
+    }
+}
+
+fn calculate_risk_level(low_points: &Vec<(usize, usize)>, smoke_points: &Vec<Vec<usize>>) -> usize {
+    low_points
+        .iter()
+        .map(|(i, j)| smoke_points.get(*i).unwrap().get(*j).unwrap() + 1)
+        .fold(0usize, |sum, risk_level| sum + risk_level)
+}
+
+fn calculate_top_3_basin_sizes(
+    low_points: &Vec<(usize, usize)>,
+    smoke_points: &Vec<Vec<usize>>,
+) -> usize {
+    let mut basin_sizes: Vec<usize> = find_basins(low_points, smoke_points)
+        .iter()
+        .map(|basin| basin.len())
+        .collect();
+
+    basin_sizes.sort();
+
+    basin_sizes
+        .into_iter()
+        .rev()
+        .take(3)
+        .fold(1usize, |product, basin_size| product * basin_size)
+        .into()
+}
+
+// Basins are returned in the same order as the low points they flow to.
+fn find_basins(low_points: &[(usize, usize)], smoke_points: &Vec<Vec<usize>>) -> Vec<BitGrid> {
+    let column_length = smoke_points.len();
+    let row_length = smoke_points.first().unwrap().len();
+
+    low_points
+        .iter()
+        .map(|low_point| {
+            let mut basin = BitGrid::new(column_length, row_length);
+            find_basin_from_low_point(
+                *low_point,
+                smoke_points,
+                &column_length,
+                &row_length,
+                &mut basin,
+            );
+            basin
+        })
+        .collect()
+}
+
+fn write_label_map(
+    basins: &[BitGrid],
+    smoke_points: &[Vec<usize>],
+    path: &str,
+) -> Result<(), io::Error> {
+    let mut writer = BufWriter::new(File::create(path)?);
+
+    smoke_points.iter().enumerate().try_for_each(|(i, row)| {
+        let labels: Vec<String> = (0..row.len())
+            .map(|j| {
+                basins
+                    .iter()
+                    .position(|basin| basin.contains(i, j))
+                    .map(|index| (index + 1).to_string())
+                    .unwrap_or_else(|| ".".to_string())
+            })
+            .collect();
+        writeln!(writer, "{}", labels.join(" "))
+    })?;
+
+    writer.flush()
+}
+
+fn find_basin_from_low_point(
+    low_point: (usize, usize),
+    smoke_points: &Vec<Vec<usize>>,
+    column_length: &usize,
+    row_length: &usize,
+    result: &mut BitGrid,
+) -> () {
+    let (mut x, mut y) = low_point;
+    result.insert(x, y);
+
+    if x > 0usize {
+        x -= 1usize;
+        if *smoke_points.get(x).unwrap().get(y).unwrap() < 9usize && !result.contains(x, y) {
+            find_basin_from_low_point((x, y), &smoke_points, column_length, row_length, result);
+        }
+    }
+
+    x = low_point.0;
+
+    if x < (*column_length - 1usize) {
+        x += 1usize;
+        if *smoke_points.get(x).unwrap().get(y).unwrap() < 9usize && !result.contains(x, y) {
+            find_basin_from_low_point((x, y), &smoke_points, column_length, row_length, result);
+        }
+    }
+
+    x = low_point.0;
+
+    if y > 0usize {
+        y -= 1usize;
+        if *smoke_points.get(x).unwrap().get(y).unwrap() < 9usize && !result.contains(x, y) {
+            find_basin_from_low_point((x, y), &smoke_points, column_length, row_length, result);
+        }
+    }
+
+    y = low_point.1;
+
+    if y < (*row_length - 1usize) {
+        y += 1usize;
+        if *smoke_points.get(x).unwrap().get(y).unwrap() < 9usize && !result.contains(x, y) {
+            find_basin_from_low_point((x, y), &smoke_points, column_length, row_length, result);
+        }
+    }
+}
+
+fn find_low_points(smoke_points: &Vec<Vec<usize>>) -> Vec<(usize, usize)> {
+    let column_length = smoke_points.len();
+    let mut low_points = Vec::new();
+    for i in 0..column_length {
+        let row = smoke_points.get(i).unwrap();
+        let row_length = row.len();
+        for j in 0..row_length {
+            let current = row.get(j).unwrap();
+            let adjacents = get_adjacent_indicies((&i, &j), &column_length, &row_length);
+            let low_point = adjacents
+                .iter()
+                .map(|(x, y)| smoke_points.get(*x).unwrap().get(*y).unwrap())
+                .all(|value| current < value);
+
+            if low_point {
+                low_points.push((i, j));
+            }
+        }
+    }
+
+    low_points
+}
+
+fn get_adjacent_indicies(
+    point: (&usize, &usize),
+    column_length: &usize,
+    row_length: &usize,
+) -> Vec<(usize, usize)> {
+    let (x, y) = point;
+    let mut adjacents = Vec::new();
+    if *y > 0usize {
+        adjacents.push((*x, y - 1));
+    }
+
+    if *y < (*row_length - 1usize) {
+        adjacents.push((*x, y + 1));
+    }
+
+    if *x > 0usize {
+        adjacents.push((x - 1, *y));
+    }
+
+    if *x < (*column_length - 1usize) {
+        adjacents.push((x + 1, *y));
+    }
+
+    adjacents
+}
+
+fn describe(smoke_points: &Vec<Vec<usize>>) -> String {
+    describe_grid(smoke_points)
+}
+
+fn parse_data(input: &String) -> IResult<&str, Grid<usize>> {
+    parse_digit_grid(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{run_problem_with_input, scratch_path};
+
+    #[test]
+    fn part1_sample() {
+        assert_eq!(
+            run_problem_with_input(&SMOKE_BASIN, SAMPLE, &["part1"]).unwrap(),
+            15usize.into()
+        );
+    }
+
+    #[test]
+    fn part2_sample() {
+        assert_eq!(
+            run_problem_with_input(&SMOKE_BASIN, SAMPLE, &["part2"]).unwrap(),
+            1134usize.into()
+        );
+    }
+
+    #[test]
+    fn ragged_rows_are_rejected() {
+        assert_eq!(
+            run_problem_with_input(&SMOKE_BASIN, "2199\n398\n9856\n", &["part1"])
+                .unwrap_err()
+                .to_string(),
+            "Row 2 has 3 columns but the first row has 4"
+        );
+    }
+    #[test]
+    fn basin_report_sample() {
+        assert_eq!(
+            run_problem_with_input(&SMOKE_BASIN, SAMPLE, &["--part", "2", "--basin-report"])
+                .unwrap(),
+            vec![
+                ("Basin 3".to_string(), 14usize.into()),
+                ("Basin 2".to_string(), 9usize.into()),
+                ("Basin 4".to_string(), 9usize.into()),
+                ("Basin 1".to_string(), 3usize.into()),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn label_map_sample() {
+        let label_map = scratch_path("smoke_basin_label_map_sample.txt");
+        let label_map = label_map.to_str().unwrap();
+
+        assert_eq!(
+            run_problem_with_input(&SMOKE_BASIN, SAMPLE, &["--part", "2", "-l", label_map])
+                .unwrap(),
+            1134usize.into()
+        );
+
+        let labels = std::fs::read_to_string(label_map).unwrap();
+        assert_eq!(labels.lines().count(), 5);
+        assert_eq!(labels.lines().next(), Some("1 1 . . . 2 2 2 2 2"));
+    }
+
+    #[test]
+    fn unwritable_label_map_is_an_error() {
+        let label_map = scratch_path("smoke_basin_missing").join("labels.txt");
+        let label_map = label_map.to_str().unwrap();
+        let error = run_problem_with_input(&SMOKE_BASIN, SAMPLE, &["--part", "2", "-l", label_map])
+            .unwrap_err();
+
+        assert!(matches!(error, AocError::Io(_)));
+        assert!(error
+            .to_string()
+            .contains(&format!("{} can't be written", label_map)));
+    }
+}
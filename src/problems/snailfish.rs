@@ -0,0 +1,417 @@
+use crate::random::SplitMix64;
+use crate::snailfish::{parse_snail_number, running_sums, Element, SnailNumber};
+use crate::{
+    default_sub_command, report_error, selected_part, AocError, CommandResult, Deadline, Example,
+    IteratorExt, Problem,
+};
+use clap::{value_t_or_exit, App, Arg, ArgMatches};
+use nom::{
+    character::complete::newline, combinator::all_consuming, multi::separated_list0, IResult,
+};
+use std::io::BufRead;
+use strum::VariantNames;
+use strum_macros::{EnumString, EnumVariantNames};
+
+pub const SNAILFISH: Problem<SnailfishArgs, Vec<SnailNumber>> = Problem::new(
+    sub_command,
+    "snailfish",
+    "day18_snailfish",
+    parse_arguments,
+    parse_data,
+    run,
+)
+.with_long_about(LONG_ABOUT)
+.with_examples(EXAMPLES)
+.with_describe(describe)
+.with_generate(generate)
+.with_stream(stream)
+.with_stream_if(is_streamed);
+
+const LONG_ABOUT: &str = "Each line is a snailfish number: a pair whose elements are regular numbers or other pairs.\n\n\
+    Adding two numbers makes a new pair and then reduces it. A pair nested inside four pairs \
+    explodes and a regular number of 10 or more splits, with every explosion handled before \
+    any split. The magnitude is 3 times the left element plus 2 times the right. sum-all adds \
+    every number in order and returns the magnitude. max-sum finds the largest magnitude from \
+    adding any two different numbers. running-sum adds them in order like sum-all but gives \
+    the magnitude of the sum so far after each number, which shows exactly which addition goes \
+    wrong when comparing against another solver.\n\n\
+    With -s the file is read a line at a time, so sum-all and running-sum only ever hold the \
+    running sum. Files over 256MB are always read that way.\n\n\
+    With -c every sum is checked once it is reduced: no pair may be nested inside four others and \
+    no regular number may be over 9. The first sum that breaks either rule stops the run and the \
+    offending pair is printed, which catches a reduction order bug where it happens rather than \
+    as a wrong magnitude at the end. The first number is checked as read, so an input that isn't \
+    already reduced is reported too.\n\n\
+    Example: the homework sample gives 4140 and 3993.";
+
+const SAMPLE: &str = include_str!("../../day18_snailfish/sample.txt");
+
+const EXAMPLES: &[Example] = &[
+    Example::new(SAMPLE, 1, "4140"),
+    Example::new(SAMPLE, 2, "3993"),
+];
+
+#[derive(Debug)]
+pub struct SnailfishArgs {
+    question: Question,
+    check_invariants: bool,
+}
+
+#[derive(Debug, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "kebab_case")]
+enum Question {
+    SumAll,
+    MaxSum,
+    RunningSum,
+}
+
+fn sub_command() -> App<'static, 'static> {
+    default_sub_command(
+        &SNAILFISH,
+        "Takes a list of pair numbers then performs calculations on them.",
+        "Path to the input file. Input should be newline delimited pairs.",
+        "Sums all the pairs, then finds the magnitude for the default input.",
+        "Sums each combination of pair then finds the maximum magnitude for the default input.",
+    )
+    .arg(
+        Arg::with_name("question")
+            .short("q")
+            .help(
+                "The question to answer requests. The questions available are as follows:\n\n\
+            sum-all: Sums all pairs, then finds the magnitude.\n\n\
+            max-sum: Checks each combination of pairs and returns the magnitude of the max sum.\n\n\
+            running-sum: Sums all pairs, giving the magnitude after each one is added.\n\n",
+            )
+            .takes_value(true)
+            .possible_values(&Question::VARIANTS)
+            .required_unless_one(&["part", "explain"]),
+    )
+    .arg(Arg::with_name("stream").short("s").long("stream").help(
+        "If passed, reads the numbers a line at a time instead of reading the whole file first.",
+    ))
+    .arg(
+        Arg::with_name("check-invariants")
+            .short("c")
+            .long("check-invariants")
+            .help(
+                "If passed, checks every sum is fully reduced and stops at the first that isn't.",
+            ),
+    )
+}
+
+fn parse_arguments(arguments: &ArgMatches) -> SnailfishArgs {
+    match selected_part(arguments) {
+        Some("part1") => SnailfishArgs {
+            question: Question::SumAll,
+            check_invariants: arguments.is_present("check-invariants"),
+        },
+        Some("part2") => SnailfishArgs {
+            question: Question::MaxSum,
+            check_invariants: arguments.is_present("check-invariants"),
+        },
+        _ => SnailfishArgs {
+            question: value_t_or_exit!(arguments.value_of("question"), Question),
+            check_invariants: arguments.is_present("check-invariants"),
+        },
+    }
+}
+
+fn is_streamed(arguments: &ArgMatches) -> bool {
+    arguments.is_present("stream")
+}
+
+fn run(arguments: SnailfishArgs, pairs: Vec<SnailNumber>) -> CommandResult {
+    match arguments.question {
+        Question::MaxSum => max_sum(&arguments, &pairs).map(CommandResult::from),
+        _ => sum_in_order(&arguments, pairs.into_iter().map(Ok)),
+    }
+    .unwrap_or_else(|error| {
+        report_error(error);
+        0usize.into()
+    })
+}
+
+fn stream(arguments: SnailfishArgs, reader: &mut dyn BufRead) -> Result<CommandResult, AocError> {
+    let numbers = stream_snail_numbers(reader);
+    match arguments.question {
+        Question::MaxSum => numbers
+            .collect::<Result<Vec<SnailNumber>, AocError>>()
+            .and_then(|pairs| max_sum(&arguments, &pairs))
+            .map(CommandResult::from),
+        _ => sum_in_order(&arguments, numbers),
+    }
+}
+
+// Adds the numbers in order as they come, so only the running sum is held. The first number that
+// can't be read stops the sum and is given instead, as does the first sum breaking an invariant.
+fn sum_in_order(
+    arguments: &SnailfishArgs,
+    numbers: impl Iterator<Item = Result<SnailNumber, AocError>>,
+) -> Result<CommandResult, AocError> {
+    let mut error = None;
+    let mut violation = None;
+    let magnitudes = running_sums(numbers.map_while(|number| match number {
+        Ok(number) => Some(number),
+        Err(e) => {
+            error = Some(e);
+            None
+        }
+    }))
+    .enumerate()
+    .map_while(|(index, sum)| {
+        match check_invariants(arguments, &sum, || format!("after number {}", index + 1)) {
+            Ok(()) => Some(sum.magnitude()),
+            Err(e) => {
+                violation = Some(e);
+                None
+            }
+        }
+    });
+
+    let answer = match arguments.question {
+        Question::RunningSum => magnitudes
+            .enumerate()
+            .map(|(index, magnitude)| (format!("Number {}", index + 1), magnitude.into()))
+            .collect::<Vec<(String, CommandResult)>>()
+            .into(),
+        _ => magnitudes.last().unwrap_or(0usize).into(),
+    };
+
+    match error.or(violation) {
+        Some(e) => Err(e),
+        None => Ok(answer),
+    }
+}
+
+// Stops trying pairs once the deadline passes.
+fn max_sum(arguments: &SnailfishArgs, pairs: &[SnailNumber]) -> Result<usize, AocError> {
+    let deadline = Deadline::current();
+    pairs
+        .iter()
+        .enumerate()
+        .combinations2()
+        .take_while(|_| !deadline.expired())
+        .flat_map(|((i, first), (j, second))| {
+            [
+                (i, j, first.clone() + second.clone()),
+                (j, i, second.clone() + first.clone()),
+            ]
+        })
+        .map(|(i, j, sum)| {
+            check_invariants(arguments, &sum, || {
+                format!("of number {} and number {}", i + 1, j + 1)
+            })
+            .map(|_| sum.magnitude())
+        })
+        .try_fold(0usize, |max, magnitude| {
+            magnitude.map(|magnitude| max.max(magnitude))
+        })
+}
+
+// Only checks when asked to. which_sum is only built for the error, so checking costs nothing
+// more than the walk over the number.
+fn check_invariants(
+    arguments: &SnailfishArgs,
+    sum: &SnailNumber,
+    which_sum: impl FnOnce() -> String,
+) -> Result<(), AocError> {
+    if !arguments.check_invariants {
+        return Ok(());
+    }
+
+    match sum.invariant_violation() {
+        Some(violation) => Err(AocError::validation(format!(
+            "The sum {} isn't reduced: {}",
+            which_sum(),
+            violation
+        ))),
+        None => Ok(()),
+    }
+}
+
+// One number per line, parsed as each line is read. Blank lines are skipped.
+fn stream_snail_numbers(
+    reader: &mut dyn BufRead,
+) -> impl Iterator<Item = Result<SnailNumber, AocError>> + '_ {
+    reader
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| line.as_ref().map(|line| !line.is_empty()).unwrap_or(true))
+        .map(|(index, line)| {
+            let line = line?;
+            let number = all_consuming(parse_snail_number)(line.as_str())
+                .map(|(_, number)| number)
+                .map_err(|error| AocError::from_nom(&line, error).on_line(index + 1));
+            number
+        })
+}
+
+// size is the number of snailfish numbers. Every number is already reduced, so pairs nest at most
+// four deep and regular numbers stay below 10.
+fn generate(random: &mut SplitMix64, size: usize) -> String {
+    (0..size)
+        .map(|_| format!("{}\n", generate_snail_number(random, 1usize)))
+        .collect()
+}
+
+fn generate_snail_number(random: &mut SplitMix64, depth: usize) -> SnailNumber {
+    SnailNumber::new(
+        generate_element(random, depth),
+        generate_element(random, depth),
+    )
+}
+
+fn generate_element(random: &mut SplitMix64, depth: usize) -> Element {
+    if depth < 4usize && random.below(3usize) > 0 {
+        Element::Number(Box::new(generate_snail_number(random, depth + 1)))
+    } else {
+        Element::Literal(random.below(10usize))
+    }
+}
+
+fn describe(pairs: &Vec<SnailNumber>) -> String {
+    format!("{} snailfish numbers", pairs.len())
+}
+
+fn parse_data(input: &String) -> IResult<&str, Vec<SnailNumber>> {
+    separated_list0(newline, parse_snail_number)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{complete_parsing, run_problem_with_input, Command};
+
+    #[test]
+    fn part1_sample() {
+        assert_eq!(
+            run_problem_with_input(&SNAILFISH, SAMPLE, &["part1"]).unwrap(),
+            4140usize.into()
+        );
+    }
+
+    #[test]
+    fn part2_sample() {
+        assert_eq!(
+            run_problem_with_input(&SNAILFISH, SAMPLE, &["part2"]).unwrap(),
+            3993usize.into()
+        );
+    }
+
+    #[test]
+    fn running_sum_sample() {
+        let magnitudes: CommandResult = [
+            1636usize, 3381, 4143, 3962, 4326, 4248, 4173, 4405, 4353, 4140,
+        ]
+        .iter()
+        .enumerate()
+        .map(|(index, magnitude)| (format!("Number {}", index + 1), (*magnitude).into()))
+        .collect::<Vec<(String, CommandResult)>>()
+        .into();
+
+        assert_eq!(
+            run_problem_with_input(
+                &SNAILFISH,
+                SAMPLE,
+                &["-f", "sample.txt", "-q", "running-sum"]
+            )
+            .unwrap(),
+            magnitudes
+        );
+        assert_eq!(
+            stream(
+                SnailfishArgs {
+                    question: Question::RunningSum,
+                    check_invariants: false,
+                },
+                &mut SAMPLE.as_bytes(),
+            )
+            .unwrap(),
+            magnitudes
+        );
+    }
+
+    #[test]
+    fn stream_sample() {
+        assert_eq!(
+            run_problem_with_input(&SNAILFISH, SAMPLE, &["--part", "1", "-s"]).unwrap(),
+            4140usize.into()
+        );
+        assert_eq!(
+            stream(
+                SnailfishArgs {
+                    question: Question::MaxSum,
+                    check_invariants: false,
+                },
+                &mut SAMPLE.as_bytes(),
+            )
+            .unwrap(),
+            3993usize.into()
+        );
+    }
+
+    #[test]
+    fn stream_reports_invalid_line() {
+        let input = SAMPLE.replacen("[[[5,[2,8]],4],[5,[[9,9],0]]]", "[[5,[2,8]],4", 1);
+        let error = stream(
+            SnailfishArgs {
+                question: Question::SumAll,
+                check_invariants: false,
+            },
+            &mut input.as_bytes(),
+        )
+        .unwrap_err();
+
+        assert!(matches!(error, AocError::Parse { line: 2, .. }));
+        assert_eq!(
+            error.to_string(),
+            "Parse error at line 2, column 13: unexpected end of line"
+        );
+    }
+
+    #[test]
+    fn check_invariants_reports_the_first_unreduced_sum() {
+        assert_eq!(
+            run_problem_with_input(&SNAILFISH, SAMPLE, &["--part", "1", "-c"]).unwrap(),
+            4140usize.into()
+        );
+        assert_eq!(
+            run_problem_with_input(&SNAILFISH, SAMPLE, &["--part", "2", "-c"]).unwrap(),
+            3993usize.into()
+        );
+
+        let input = format!("[[[[[1,1],2],3],4],5]\n{}", SAMPLE);
+        let error = stream(
+            SnailfishArgs {
+                question: Question::SumAll,
+                check_invariants: true,
+            },
+            &mut input.as_bytes(),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "The sum after number 1 isn't reduced: pair [1,1] is nested inside 4 other pairs"
+        );
+        assert_eq!(
+            run_problem_with_input(
+                &SNAILFISH,
+                &input,
+                &["-f", "sample.txt", "-q", "sum-all", "-c"]
+            )
+            .unwrap_err()
+            .to_string(),
+            "The sum after number 1 isn't reduced: pair [1,1] is nested inside 4 other pairs"
+        );
+    }
+
+    #[test]
+    fn generate_is_valid() {
+        let input = SNAILFISH.generate(5u64, 10usize).unwrap();
+
+        assert_eq!(input, SNAILFISH.generate(5u64, 10usize).unwrap());
+        assert_eq!(complete_parsing(parse_data)(&input).unwrap().len(), 10);
+        assert!(run_problem_with_input(&SNAILFISH, &input, &["part1"]).is_ok());
+    }
+}
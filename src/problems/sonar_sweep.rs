@@ -0,0 +1,212 @@
+use crate::{
+    default_sub_command, parse_usize, selected_part, CommandResult, Example, IteratorExt, Problem,
+};
+use clap::{value_t_or_exit, App, Arg, ArgMatches};
+use nom::{character::complete::newline, multi::separated_list0, IResult};
+
+pub const SONAR_SWEEP: Problem<SonarSweepArgs, Vec<usize>> = Problem::new(
+    sub_command,
+    "sonar-sweep",
+    "day1_sonar_sweep",
+    parse_arguments,
+    parse_data,
+    run,
+)
+.with_long_about(LONG_ABOUT)
+.with_examples(EXAMPLES)
+.with_describe(describe);
+
+const LONG_ABOUT: &str =
+    "The input is a list of depth measurements taken as the submarine descends.\n\n\
+    Part 1 counts how many measurements are larger than the previous one. Part 2 sums each \
+    window of three consecutive measurements and counts how many windows are larger than the \
+    previous window. -s sets the window size, so -s 1 is part 1 and -s 3 is part 2.\n\n\
+    -t reports statistics on the windows instead: the increases, decreases and unchanged samples, \
+    the longest run of increasing samples, and the minimum, maximum and mean depth.\n\n\
+    Example: 199, 200, 208, 210, 200, 207, 240, 269, 260, 263 has 7 increases and 5 window \
+    increases.";
+
+const SAMPLE: &str = include_str!("../../day1_sonar_sweep/sample.txt");
+
+const EXAMPLES: &[Example] = &[Example::new(SAMPLE, 1, "7"), Example::new(SAMPLE, 2, "5")];
+
+#[derive(Debug)]
+pub struct SonarSweepArgs {
+    sample_size: usize,
+    stats: bool,
+}
+
+fn sub_command() -> App<'static, 'static> {
+    default_sub_command(
+        &SONAR_SWEEP,
+        "Counts every time the number in the input increases between each sample",
+        "Path to the input file. Input should be newline delimited integers.",
+        "Searches the default input with a sample size of 1.",
+        "Searches the default input with a sample size of 3.",
+    )
+    .arg(
+        Arg::with_name("sample")
+            .short("s")
+            .help("Number of consecttive items that must be sampled")
+            .takes_value(true)
+            .required_unless_one(&["part", "explain"]),
+    )
+    .arg(
+        Arg::with_name("stats")
+            .short("t")
+            .long("stats")
+            .help("If passed, reports statistics on the samples instead of only the increases."),
+    )
+}
+
+fn parse_arguments(arguments: &ArgMatches) -> SonarSweepArgs {
+    match selected_part(arguments) {
+        Some("part1") => SonarSweepArgs {
+            sample_size: 1,
+            stats: arguments.is_present("stats"),
+        },
+        Some("part2") => SonarSweepArgs {
+            sample_size: 3,
+            stats: arguments.is_present("stats"),
+        },
+        _ => SonarSweepArgs {
+            sample_size: value_t_or_exit!(arguments.value_of("sample"), usize),
+            stats: arguments.is_present("stats"),
+        },
+    }
+}
+
+fn run(arguments: SonarSweepArgs, samples: Vec<usize>) -> CommandResult {
+    let samples = aggregate_samples(&samples, &arguments.sample_size);
+    if arguments.stats {
+        return depth_stats(&samples);
+    }
+
+    count_increases(samples).into()
+}
+
+fn describe(samples: &Vec<usize>) -> String {
+    format!(
+        "{} depth samples ranging from {} to {}",
+        samples.len(),
+        samples.iter().min().unwrap_or(&0usize),
+        samples.iter().max().unwrap_or(&0usize)
+    )
+}
+
+fn parse_data(input: &String) -> IResult<&str, Vec<usize>> {
+    separated_list0(newline, parse_usize)(input)
+}
+
+fn aggregate_samples(input: &Vec<usize>, sample_size: &usize) -> Vec<usize> {
+    input.iter().copied().window_sums(*sample_size).collect()
+}
+
+fn count_increases(input: Vec<usize>) -> usize {
+    input
+        .into_iter()
+        .pairwise()
+        .filter(|(previous, next)| next > previous)
+        .count()
+}
+
+fn depth_stats(samples: &[usize]) -> CommandResult {
+    let (increases, decreases, unchanged, longest_run, _) = samples.iter().pairwise().fold(
+        (0usize, 0usize, 0usize, samples.len().min(1), 1usize),
+        |(increases, decreases, unchanged, longest_run, run), (previous, next)| {
+            if next > previous {
+                (
+                    increases + 1,
+                    decreases,
+                    unchanged,
+                    longest_run.max(run + 1),
+                    run + 1,
+                )
+            } else if next < previous {
+                (increases, decreases + 1, unchanged, longest_run, 1)
+            } else {
+                (increases, decreases, unchanged + 1, longest_run, 1)
+            }
+        },
+    );
+    let mean = match samples.len() {
+        0 => 0f64,
+        len => samples.iter().sum::<usize>() as f64 / len as f64,
+    };
+
+    vec![
+        ("Increases".to_string(), increases.into()),
+        ("Decreases".to_string(), decreases.into()),
+        ("Unchanged".to_string(), unchanged.into()),
+        ("Longest increasing run".to_string(), longest_run.into()),
+        (
+            "Min depth".to_string(),
+            samples.iter().copied().min().unwrap_or(0usize).into(),
+        ),
+        (
+            "Max depth".to_string(),
+            samples.iter().copied().max().unwrap_or(0usize).into(),
+        ),
+        ("Mean depth".to_string(), format!("{:.2}", mean).into()),
+    ]
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{run_problem_with_input, Command};
+
+    #[test]
+    fn part1_sample() {
+        assert_eq!(
+            run_problem_with_input(&SONAR_SWEEP, SAMPLE, &["part1"]).unwrap(),
+            7usize.into()
+        );
+    }
+
+    #[test]
+    fn part2_sample() {
+        assert_eq!(
+            run_problem_with_input(&SONAR_SWEEP, SAMPLE, &["part2"]).unwrap(),
+            5usize.into()
+        );
+    }
+
+    #[test]
+    fn part_flag_sample() {
+        assert_eq!(
+            run_problem_with_input(&SONAR_SWEEP, SAMPLE, &["--part", "2"]).unwrap(),
+            5usize.into()
+        );
+    }
+
+    #[test]
+    fn stats_sample() {
+        assert_eq!(
+            run_problem_with_input(&SONAR_SWEEP, SAMPLE, &["--part", "1", "-t"]).unwrap(),
+            vec![
+                ("Increases".to_string(), 7usize.into()),
+                ("Decreases".to_string(), 2usize.into()),
+                ("Unchanged".to_string(), 0usize.into()),
+                ("Longest increasing run".to_string(), 4usize.into()),
+                ("Min depth".to_string(), 199usize.into()),
+                ("Max depth".to_string(), 269usize.into()),
+                ("Mean depth".to_string(), "225.60".to_string().into()),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn both_sample() {
+        let results: Vec<CommandResult> = SONAR_SWEEP
+            .run_both_with_input(&SAMPLE.to_string())
+            .unwrap()
+            .into_iter()
+            .map(|(result, _)| result)
+            .collect();
+
+        assert_eq!(results, vec![7usize.into(), 5usize.into()]);
+    }
+}
@@ -0,0 +1,656 @@
+use crate::{default_sub_command, selected_part, AocError, CommandResult, Example, Problem};
+use clap::{value_t_or_exit, App, Arg, ArgMatches};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{newline, one_of},
+    combinator::{flat_map, map, opt, recognize, value},
+    multi::{many0, many1, separated_list0},
+    IResult,
+};
+use strum::VariantNames;
+use strum_macros::{EnumString, EnumVariantNames};
+
+pub const SYNTAX_SCORING: Problem<SyntaxScoringArgs, Vec<String>> = Problem::new(
+    sub_command,
+    "syntax-scoring",
+    "day10_syntax_scoring",
+    parse_arguments,
+    parse_data,
+    run,
+)
+.with_long_about(LONG_ABOUT)
+.with_examples(EXAMPLES)
+.with_describe(describe)
+.with_input_check(input_check)
+.with_cross_check(cross_check_arguments);
+
+const LONG_ABOUT: &str = "Each line is a sequence of (), [], {} and <> chunks.\n\n\
+    A corrupted line closes a chunk with the wrong character. corrupted scores the first \
+    illegal character of each such line as 3, 57, 1197 or 25137 and sums them. An incomplete \
+    line ends before closing all of its chunks. incomplete scores the missing closing \
+    characters, multiplying by 5 and adding 1 to 4 for each one, and returns the middle score. \
+    -d lists whether each line is valid, incomplete or corrupted, with the column of the first \
+    illegal character for corrupted lines, followed by the score.\n\n\
+    The tree engine parses each line into its nested chunks before scoring them. The stack \
+    engine reads each line once, keeping a stack of the brackets still open, which allocates \
+    far less for deeply nested lines. --cross-check runs both and compares the answers. A line \
+    that closes a chunk when none are open is rejected, and so is incomplete scoring when every \
+    line is corrupted.\n\n\
+    Example: the sample scores 26397 for corrupted lines and 288957 for incomplete ones.";
+
+const SAMPLE: &str = include_str!("../../day10_syntax_scoring/sample.txt");
+
+const EXAMPLES: &[Example] = &[
+    Example::new(SAMPLE, 1, "26397"),
+    Example::new(SAMPLE, 2, "288957"),
+];
+
+#[derive(Debug)]
+pub struct SyntaxScoringArgs {
+    scoring_function: ScoringFunction,
+    diagnose: bool,
+    engine: Engine,
+}
+
+#[derive(Debug, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "kebab_case")]
+enum ScoringFunction {
+    Corrupted,
+    Incomplete,
+}
+
+#[derive(Debug, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "kebab_case")]
+enum Engine {
+    Tree,
+    Stack,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bracket {
+    Paren,
+    Square,
+    Curly,
+    Angle,
+}
+
+#[derive(Debug, Clone)]
+pub enum Chunk {
+    CorruptedChunk {
+        first: Bracket,
+        chunks: Vec<Chunk>,
+        invalid: Bracket,
+        column: usize,
+    },
+    IncompleteChunk {
+        first: Bracket,
+        chunks: Vec<Chunk>,
+    },
+    CompleteChunk {
+        bracket: Bracket,
+        chunks: Vec<Chunk>,
+    },
+}
+
+fn sub_command() -> App<'static, 'static> {
+    default_sub_command(
+        &SYNTAX_SCORING,
+        "Parses chunks from lines of chunks then calculates stats based on the result.",
+        "Path to the input file. Input should be newline delimited chunks.",
+        "Searches the default and scores all the lines with corrupted chunks.",
+        "Searches the default, scores the incomplete lines and returns the middle valued one.",
+    )
+    .arg(
+        Arg::with_name("scoring-function")
+            .short("s")
+            .help(
+                "The scoring type to use. The functions available are as follows:\n\n\
+            corrupted: Scores the first Corrupted Chunk of each line then sums the scores.\n\n\
+            incomplete: Scores the non Corrupted Incomplete Lines returns the middle score.\n\n",
+            )
+            .takes_value(true)
+            .possible_values(&ScoringFunction::VARIANTS)
+            .required_unless_one(&["part", "explain"]),
+    )
+    .arg(Arg::with_name("diagnose").short("d").long("diagnose").help(
+        "If passed, lists whether each line is valid, incomplete or corrupted before the score.",
+    ))
+    .arg(
+        Arg::with_name("engine")
+            .short("e")
+            .long("engine")
+            .help(
+                "How the lines are read. The engines available are as follows:\n\n\
+            tree: Parses each line into nested chunks, then scores the chunks.\n\n\
+            stack: Reads each line once with a stack of the open brackets.\n\n",
+            )
+            .takes_value(true)
+            .possible_values(Engine::VARIANTS)
+            .default_value("tree"),
+    )
+}
+
+fn parse_arguments(arguments: &ArgMatches) -> SyntaxScoringArgs {
+    match selected_part(arguments) {
+        Some("part1") => SyntaxScoringArgs {
+            scoring_function: ScoringFunction::Corrupted,
+            diagnose: arguments.is_present("diagnose"),
+            engine: value_t_or_exit!(arguments.value_of("engine"), Engine),
+        },
+        Some("part2") => SyntaxScoringArgs {
+            scoring_function: ScoringFunction::Incomplete,
+            diagnose: arguments.is_present("diagnose"),
+            engine: value_t_or_exit!(arguments.value_of("engine"), Engine),
+        },
+        _ => SyntaxScoringArgs {
+            scoring_function: value_t_or_exit!(
+                arguments.value_of("scoring-function"),
+                ScoringFunction
+            ),
+            diagnose: arguments.is_present("diagnose"),
+            engine: value_t_or_exit!(arguments.value_of("engine"), Engine),
+        },
+    }
+}
+
+// The alternate run diagnoses the lines too, so the engines have to agree on every line.
+fn cross_check_arguments(arguments: &ArgMatches) -> SyntaxScoringArgs {
+    let arguments = parse_arguments(arguments);
+    SyntaxScoringArgs {
+        engine: match arguments.engine {
+            Engine::Tree => Engine::Stack,
+            Engine::Stack => Engine::Tree,
+        },
+        ..arguments
+    }
+}
+
+// Neither engine has a chunk for a closing bracket to end when none are open, and the middle
+// score needs at least one line that isn't corrupted.
+fn input_check(arguments: &SyntaxScoringArgs, lines: &Vec<String>) -> Result<(), AocError> {
+    lines
+        .iter()
+        .enumerate()
+        .find_map(|(index, line)| {
+            line.chars()
+                .scan(0usize, |open, c| {
+                    Some(match read_bracket(c) {
+                        Some((_, true)) => {
+                            *open += 1;
+                            None
+                        }
+                        _ if *open == 0 => Some(()),
+                        _ => {
+                            *open -= 1;
+                            None
+                        }
+                    })
+                })
+                .position(|unopened| unopened.is_some())
+                .map(|column| (index, column))
+        })
+        .map_or(Ok(()), |(index, column)| {
+            Err(AocError::validation(format!(
+                "Line {} closes a chunk at column {} when none are open",
+                index + 1,
+                column + 1
+            )))
+        })?;
+
+    if let ScoringFunction::Incomplete = arguments.scoring_function {
+        if lines.iter().all(|line| read_line(line).is_err()) {
+            Err(AocError::validation(
+                "Every line is corrupted, so there's no middle score to take",
+            ))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn run(arguments: SyntaxScoringArgs, lines: Vec<String>) -> CommandResult {
+    match arguments.engine {
+        Engine::Tree => run_tree(&arguments, lines),
+        Engine::Stack => run_stack(&arguments, lines),
+    }
+}
+
+fn run_tree(arguments: &SyntaxScoringArgs, lines: Vec<String>) -> CommandResult {
+    let chunk_lines: Vec<Vec<Chunk>> = lines
+        .iter()
+        .map(|line| {
+            parse_line(line)
+                .map(|(_, chunks)| chunks)
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let diagnoses: Option<Vec<String>> = arguments.diagnose.then(|| {
+        chunk_lines
+            .iter()
+            .map(|chunks| diagnose_line(chunks))
+            .collect()
+    });
+
+    let score = match arguments.scoring_function {
+        ScoringFunction::Corrupted => sum_corrupted_chunks(chunk_lines),
+        ScoringFunction::Incomplete => middle_incomplete_chunk_score(chunk_lines),
+    };
+
+    with_diagnoses(diagnoses, score)
+}
+
+// Lists the diagnosis of each line ahead of the score when -d was passed.
+fn with_diagnoses(diagnoses: Option<Vec<String>>, score: usize) -> CommandResult {
+    match diagnoses {
+        Some(diagnoses) => diagnoses
+            .into_iter()
+            .enumerate()
+            .map(|(index, diagnosis)| (format!("Line {}", index + 1), diagnosis.into()))
+            .chain(std::iter::once(("Score".to_string(), score.into())))
+            .collect::<Vec<(String, CommandResult)>>()
+            .into(),
+        None => score.into(),
+    }
+}
+
+fn run_stack(arguments: &SyntaxScoringArgs, lines: Vec<String>) -> CommandResult {
+    let read_lines: Vec<Result<Vec<Bracket>, Corruption>> =
+        lines.iter().map(|line| read_line(line)).collect();
+
+    let diagnoses: Option<Vec<String>> = arguments
+        .diagnose
+        .then(|| read_lines.iter().map(diagnose_read_line).collect());
+
+    let score = match arguments.scoring_function {
+        ScoringFunction::Corrupted => read_lines
+            .iter()
+            .filter_map(|line| line.as_ref().err())
+            .map(|(_, found, _)| corruption_score(*found))
+            .sum(),
+        ScoringFunction::Incomplete => middle_score(
+            read_lines
+                .iter()
+                .filter_map(|line| line.as_ref().ok())
+                .map(|open| completion_score(open.iter().rev()))
+                .collect(),
+        ),
+    };
+
+    with_diagnoses(diagnoses, score)
+}
+
+// The bracket that was expected, the one found and its 1-based column.
+type Corruption = (Bracket, Bracket, usize);
+
+// Reads the line left to right with a stack of the brackets still open, giving the first illegal
+// closing bracket or else the brackets left open, innermost last.
+fn read_line(line: &str) -> Result<Vec<Bracket>, Corruption> {
+    let mut open = Vec::new();
+
+    line.chars()
+        .enumerate()
+        .filter_map(|(index, c)| read_bracket(c).map(|bracket| (index, bracket)))
+        .try_for_each(|(index, (bracket, opens))| {
+            if opens {
+                open.push(bracket);
+                return Ok(());
+            }
+            match open.pop() {
+                Some(expected) if expected != bracket => Err((expected, bracket, index + 1)),
+                _ => Ok(()),
+            }
+        })?;
+
+    Ok(open)
+}
+
+// The bracket a character is and whether it opens a chunk.
+fn read_bracket(c: char) -> Option<(Bracket, bool)> {
+    match c {
+        '(' => Some((Bracket::Paren, true)),
+        '[' => Some((Bracket::Square, true)),
+        '{' => Some((Bracket::Curly, true)),
+        '<' => Some((Bracket::Angle, true)),
+        ')' => Some((Bracket::Paren, false)),
+        ']' => Some((Bracket::Square, false)),
+        '}' => Some((Bracket::Curly, false)),
+        '>' => Some((Bracket::Angle, false)),
+        _ => None,
+    }
+}
+
+fn diagnose_read_line(line: &Result<Vec<Bracket>, Corruption>) -> String {
+    match line {
+        Err(corruption) => describe_corruption(corruption),
+        Ok(open) if open.is_empty() => "valid".to_string(),
+        Ok(_) => "incomplete".to_string(),
+    }
+}
+
+fn middle_incomplete_chunk_score(chunk_lines: Vec<Vec<Chunk>>) -> usize {
+    middle_score(
+        chunk_lines
+            .iter()
+            .filter(|chunks| {
+                chunks
+                    .iter()
+                    .map(evaluate_corrupt_chunks)
+                    .all(|value| value == 0)
+            })
+            .map(|chunks| chunks.iter().map(evaluate_incomplete_chunks).sum::<usize>())
+            .collect(),
+    )
+}
+
+// Every line without a corruption is scored, so complete lines count as 0.
+fn middle_score(mut scores: Vec<usize>) -> usize {
+    scores.sort();
+    *scores.get(scores.len() / 2).unwrap()
+}
+
+fn evaluate_incomplete_chunks(chunk: &Chunk) -> usize {
+    completion_score(list_missing_brackets(chunk).iter())
+}
+
+fn completion_score<'a>(missing: impl Iterator<Item = &'a Bracket>) -> usize {
+    missing.fold(0usize, |mut acc, bracket| {
+        acc *= 5;
+        acc + match bracket {
+            Bracket::Paren => 1usize,
+            Bracket::Square => 2usize,
+            &Bracket::Curly => 3usize,
+            &Bracket::Angle => 4usize,
+        }
+    })
+}
+
+fn list_missing_brackets(chunk: &Chunk) -> Vec<Bracket> {
+    match chunk {
+        Chunk::CompleteChunk { bracket: _, chunks } => chunks
+            .iter()
+            .map(list_missing_brackets)
+            .fold(Vec::new(), |mut acc, mut brackets| {
+                acc.append(&mut brackets);
+                acc
+            }),
+        Chunk::IncompleteChunk { first, chunks } => {
+            let mut result = chunks.iter().map(list_missing_brackets).fold(
+                Vec::new(),
+                |mut acc, mut brackets| {
+                    acc.append(&mut brackets);
+                    acc
+                },
+            );
+            result.push(*first);
+            result
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn sum_corrupted_chunks(chunk_lines: Vec<Vec<Chunk>>) -> usize {
+    chunk_lines
+        .iter()
+        .map(|chunks| {
+            chunks
+                .iter()
+                .map(evaluate_corrupt_chunks)
+                .filter(|value| value > &0usize)
+                .next()
+                .unwrap_or(0usize)
+        })
+        .fold(0usize, |sum, invalid_score| sum + invalid_score)
+}
+
+fn evaluate_corrupt_chunks(chunk: &Chunk) -> usize {
+    first_corruption(chunk)
+        .map(|(_, invalid, _)| corruption_score(invalid))
+        .unwrap_or(0usize)
+}
+
+fn corruption_score(invalid: Bracket) -> usize {
+    match invalid {
+        Bracket::Paren => 3usize,
+        Bracket::Square => 57usize,
+        Bracket::Curly => 1197usize,
+        Bracket::Angle => 25137usize,
+    }
+}
+
+// Finds the first illegal closing bracket.
+fn first_corruption(chunk: &Chunk) -> Option<Corruption> {
+    match chunk {
+        Chunk::CompleteChunk { bracket: _, chunks } => chunks.iter().find_map(first_corruption),
+        Chunk::IncompleteChunk { first: _, chunks } => chunks.iter().find_map(first_corruption),
+        Chunk::CorruptedChunk {
+            first,
+            chunks,
+            invalid,
+            column,
+        } => chunks
+            .iter()
+            .find_map(first_corruption)
+            .or(Some((*first, *invalid, *column))),
+    }
+}
+
+fn diagnose_line(chunks: &[Chunk]) -> String {
+    match chunks.iter().find_map(first_corruption) {
+        Some(corruption) => describe_corruption(&corruption),
+        None if chunks
+            .iter()
+            .all(|chunk| matches!(chunk, Chunk::CompleteChunk { .. })) =>
+        {
+            "valid".to_string()
+        }
+        None => "incomplete".to_string(),
+    }
+}
+
+fn describe_corruption((expected, found, column): &Corruption) -> String {
+    format!(
+        "corrupted at column {}, expected {} but found {}",
+        column,
+        closing_character(*expected),
+        closing_character(*found)
+    )
+}
+
+fn closing_character(bracket: Bracket) -> char {
+    match bracket {
+        Bracket::Paren => ')',
+        Bracket::Square => ']',
+        Bracket::Curly => '}',
+        Bracket::Angle => '>',
+    }
+}
+
+fn describe(lines: &Vec<String>) -> String {
+    format!("{} lines of chunks", lines.len())
+}
+
+// Lines are kept as they are so each engine can read them its own way.
+fn parse_data(input: &String) -> IResult<&str, Vec<String>> {
+    separated_list0(
+        newline,
+        map(recognize(many1(one_of("()[]{}<>"))), String::from),
+    )(input)
+}
+
+// Columns are worked out from how much of the line is left, so the length of the whole line is
+// passed down to every chunk.
+fn parse_line(input: &str) -> IResult<&str, Vec<Chunk>> {
+    let line_length = input.len();
+    many1(move |input| parse_chunk(line_length, input))(input)
+}
+
+fn parse_chunk(line_length: usize, input: &str) -> IResult<&str, Chunk> {
+    flat_map(
+        alt((
+            value(Bracket::Paren, tag("(")),
+            value(Bracket::Square, tag("[")),
+            value(Bracket::Curly, tag("{")),
+            value(Bracket::Angle, tag("<")),
+        )),
+        move |first| parse_rest_of_chunk(line_length, first),
+    )(input)
+}
+
+fn parse_rest_of_chunk(
+    line_length: usize,
+    first: Bracket,
+) -> impl FnMut(&str) -> IResult<&str, Chunk> {
+    move |input: &str| {
+        let (input, chunks) = many0(move |input| parse_chunk(line_length, input))(input)?;
+        let column = line_length - input.len() + 1;
+
+        let (input, bracket) = opt(alt((
+            value(Bracket::Paren, tag(")")),
+            value(Bracket::Square, tag("]")),
+            value(Bracket::Curly, tag("}")),
+            value(Bracket::Angle, tag(">")),
+        )))(input)?;
+
+        let chunk = match bracket {
+            Some(b) => match b {
+                _ if b == first => Chunk::CompleteChunk { bracket: b, chunks },
+                _ => Chunk::CorruptedChunk {
+                    first: first,
+                    chunks: chunks,
+                    invalid: b,
+                    column,
+                },
+            },
+            None => Chunk::IncompleteChunk { first, chunks },
+        };
+
+        Ok((input, chunk))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{complete_parsing, run_problem_with_input};
+
+    const CORRUPTED_LINE: &str = "{([(<{}[<>[]}>{[]{[(<()>";
+
+    #[test]
+    fn part1_sample() {
+        assert_eq!(
+            run_problem_with_input(&SYNTAX_SCORING, SAMPLE, &["part1"]).unwrap(),
+            26397usize.into()
+        );
+    }
+
+    #[test]
+    fn part2_sample() {
+        assert_eq!(
+            run_problem_with_input(&SYNTAX_SCORING, SAMPLE, &["part2"]).unwrap(),
+            288957usize.into()
+        );
+    }
+    #[test]
+    fn stack_sample() {
+        assert_eq!(
+            run_problem_with_input(
+                &SYNTAX_SCORING,
+                SAMPLE,
+                &["--part", "1", "-e", "stack", "--cross-check"]
+            )
+            .unwrap(),
+            26397usize.into()
+        );
+        assert_eq!(
+            run_problem_with_input(
+                &SYNTAX_SCORING,
+                SAMPLE,
+                &["--part", "2", "-e", "stack", "--cross-check"]
+            )
+            .unwrap(),
+            288957usize.into()
+        );
+        assert_eq!(
+            diagnose_read_line(&read_line(CORRUPTED_LINE)),
+            "corrupted at column 13, expected ] but found }"
+        );
+        assert_eq!(
+            diagnose_read_line(&read_line("[<>({}){}[([])<>]]")),
+            "valid"
+        );
+    }
+
+    #[test]
+    fn unopened_close_is_rejected() {
+        assert_eq!(
+            run_problem_with_input(&SYNTAX_SCORING, "()\n(])>\n", &["--part", "1"])
+                .unwrap_err()
+                .to_string(),
+            "Line 2 closes a chunk at column 3 when none are open"
+        );
+    }
+
+    #[test]
+    fn all_corrupted_has_no_middle_score() {
+        for engine in ["tree", "stack"] {
+            assert_eq!(
+                run_problem_with_input(
+                    &SYNTAX_SCORING,
+                    "(]\n",
+                    &["--part", "2", "-e", engine, "--cross-check"]
+                )
+                .unwrap_err()
+                .to_string(),
+                "Every line is corrupted, so there's no middle score to take"
+            );
+        }
+    }
+
+    #[test]
+    fn diagnose_result() {
+        let diagnosis: CommandResult = vec![
+            ("Line 1".to_string(), "valid".to_string().into()),
+            (
+                "Line 2".to_string(),
+                "corrupted at column 2, expected ) but found ]"
+                    .to_string()
+                    .into(),
+            ),
+            ("Line 3".to_string(), "incomplete".to_string().into()),
+            ("Score".to_string(), 57usize.into()),
+        ]
+        .into();
+
+        for engine in ["tree", "stack"] {
+            assert_eq!(
+                run_problem_with_input(
+                    &SYNTAX_SCORING,
+                    "()\n(]\n[<\n",
+                    &["--part", "1", "-d", "-e", engine, "--cross-check"]
+                )
+                .unwrap(),
+                diagnosis
+            );
+        }
+    }
+
+    #[test]
+    fn diagnose_sample() {
+        let lines = complete_parsing(parse_data)(&SAMPLE.to_string()).unwrap();
+
+        assert_eq!(
+            diagnose_line(&parse_line(&lines[0]).unwrap().1),
+            "incomplete"
+        );
+        assert_eq!(
+            diagnose_line(&parse_line(CORRUPTED_LINE).unwrap().1),
+            "corrupted at column 13, expected ] but found }"
+        );
+        assert_eq!(
+            diagnose_line(&parse_line("[<>({}){}[([])<>]]").unwrap().1),
+            "valid"
+        );
+    }
+}
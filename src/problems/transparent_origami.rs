@@ -0,0 +1,722 @@
+use crate::{
+    default_sub_command, log, parse_usize, random::SplitMix64, render_points, report_output,
+    seed_argument, seeded_random, selected_part, AocError, CommandResult, Example, LogLevel,
+    Problem, RenderStyle,
+};
+use clap::{value_t_or_exit, App, Arg, ArgMatches, ErrorKind};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::newline,
+    combinator::map,
+    multi::separated_list1,
+    sequence::{preceded, separated_pair},
+    IResult,
+};
+use std::{
+    collections::{BTreeSet, HashSet},
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    iter,
+};
+use strum::VariantNames;
+
+pub const TRANSPARENT_ORIGAMI: Problem<TransparentOrigamiArgs, Paper> = Problem::new(
+    sub_command,
+    "transparent-origami",
+    "day13_transparent_origami",
+    parse_arguments,
+    parse_data,
+    run,
+)
+.with_long_about(LONG_ABOUT)
+.with_examples(EXAMPLES)
+.with_describe(describe)
+.with_input_check(input_check);
+
+const LONG_ABOUT: &str =
+    "The input is a list of dot coordinates followed by fold instructions such as fold along \
+    y=7.\n\n\
+    Folding mirrors the dots across the line, up or to the left, and overlapping dots merge. \
+    The answer is the number of visible dots, and -v prints the folded paper to read the letters \
+    it spells. -d chooses how it's drawn: ascii uses # and ., block fits two rows into each \
+    line and braille fits a 2 by 4 square of dots into each character for papers too large for \
+    the terminal. -l performs only the first fold, as in part 1. \
+    -e writes the folded paper to an SVG image, with -s setting the size of each dot.\n\n\
+    -u works backwards from a pattern of # and . to the dots and folds of a sheet of -z size, \
+    such as 1311x895, that fold down to it. Each dot comes from randomly chosen places on the \
+    sheet, set by -r or else the global --seed. The generated input is printed, or written to -o, and the answer is the \
+    dots it folds down to. The day's own input isn't used.\n\n\
+    -t reports each fold instead: the width and height the dots cover afterwards and how many \
+    dots landed on one already there and merged with it, followed by the dots left.\n\n\
+    Example: the sample has 17 dots after the first fold.";
+
+const SAMPLE: &str = include_str!("../../day13_transparent_origami/sample.txt");
+
+const EXAMPLES: &[Example] = &[Example::new(SAMPLE, 1, "17"), Example::new(SAMPLE, 2, "16")];
+
+#[derive(Debug)]
+pub struct TransparentOrigamiArgs {
+    limit_folds: bool,
+    export: Option<String>,
+    scale: usize,
+    unfold: Option<Unfold>,
+    draw: RenderStyle,
+    stats: bool,
+}
+
+// A pattern to generate an input for, and the sheet the input's dots are spread over.
+#[derive(Debug)]
+struct Unfold {
+    pattern: Vec<Point>,
+    pattern_size: (usize, usize),
+    sheet_size: (usize, usize),
+    seed: u64,
+    output: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Paper {
+    points: Vec<Point>,
+    folds: Vec<Fold>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Point {
+    x: usize,
+    y: usize,
+}
+
+#[derive(Debug, Clone)]
+enum Fold {
+    Veritical { y: usize },
+    Horizontal { x: usize },
+}
+
+fn sub_command() -> App<'static, 'static> {
+    default_sub_command(
+        &TRANSPARENT_ORIGAMI,
+        "Takes a list of dots and fold instructions, then folds the dots on themselves and displays the paper.",
+        "Path to the input file. Input should be newline delimited points followed by fold instructions.",
+        "Performs the first fold on the default input then counts the dots.",
+        "Performs all folds on the default input then counts the dots.",
+    ).arg(
+        Arg::with_name("limit-folds")
+            .short("l")
+            .help("If passed, only the first fold is preformed."),
+    )
+    .arg(
+        Arg::with_name("export")
+            .short("e")
+            .long("export")
+            .help("If passed, writes the folded paper to the given path as an SVG image.")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("scale")
+            .short("s")
+            .long("scale")
+            .help("The width in pixels of each dot in the exported image.")
+            .takes_value(true)
+            .default_value("10"),
+    )
+    .arg(
+        Arg::with_name("draw")
+            .short("d")
+            .long("draw")
+            .help("How the folded paper is drawn when printed with -v. ascii uses # and ., block and braille pack several dots into each character.")
+            .takes_value(true)
+            .possible_values(RenderStyle::VARIANTS)
+            .default_value("ascii"),
+    )
+    .arg(
+        Arg::with_name("stats")
+            .short("t")
+            .long("stats")
+            .help("If passed, reports the size of the dots and how many merged after each fold.")
+            .conflicts_with("unfold"),
+    )
+    .arg(
+        Arg::with_name("unfold")
+            .short("u")
+            .long("unfold")
+            .help("If passed, generates an input that folds down to the pattern in this file, # for dots and . for blanks.")
+            .takes_value(true)
+            .requires("sheet-size"),
+    )
+    .arg(
+        Arg::with_name("sheet-size")
+            .short("z")
+            .long("sheet-size")
+            .help("The size of the unfolded sheet as widthxheight, such as 1311x895.")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("seed")
+            .short("r")
+            .long("seed")
+            .help("The seed for placing dots on the unfolded sheet. The same seed always gives the same input. Defaults to the global --seed.")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("output")
+            .short("o")
+            .long("output")
+            .help("If passed, writes the unfolded input to the given path instead of stdout.")
+            .takes_value(true),
+    )
+}
+
+fn parse_arguments(arguments: &ArgMatches) -> TransparentOrigamiArgs {
+    match selected_part(arguments) {
+        Some("part1") => TransparentOrigamiArgs {
+            limit_folds: true,
+            export: arguments.value_of("export").map(String::from),
+            scale: value_t_or_exit!(arguments.value_of("scale"), usize),
+            unfold: unfold_argument(arguments),
+            draw: value_t_or_exit!(arguments.value_of("draw"), RenderStyle),
+            stats: arguments.is_present("stats"),
+        },
+        Some("part2") => TransparentOrigamiArgs {
+            limit_folds: false,
+            export: arguments.value_of("export").map(String::from),
+            scale: value_t_or_exit!(arguments.value_of("scale"), usize),
+            unfold: unfold_argument(arguments),
+            draw: value_t_or_exit!(arguments.value_of("draw"), RenderStyle),
+            stats: arguments.is_present("stats"),
+        },
+        _ => TransparentOrigamiArgs {
+            limit_folds: arguments.is_present("limit-folds"),
+            export: arguments.value_of("export").map(String::from),
+            scale: value_t_or_exit!(arguments.value_of("scale"), usize),
+            unfold: unfold_argument(arguments),
+            draw: value_t_or_exit!(arguments.value_of("draw"), RenderStyle),
+            stats: arguments.is_present("stats"),
+        },
+    }
+}
+
+// A pattern or sheet size that can't be read exits the same way an invalid argument value does.
+fn unfold_argument(arguments: &ArgMatches) -> Option<Unfold> {
+    arguments.value_of("unfold").map(|path| {
+        let (pattern, pattern_size) = load_pattern(path).unwrap_or_else(|e| {
+            clap::Error::with_description(
+                &format!("Invalid pattern {}: {}", path, e),
+                ErrorKind::InvalidValue,
+            )
+            .exit()
+        });
+        let sheet_size = arguments
+            .value_of("sheet-size")
+            .and_then(parse_size)
+            .unwrap_or_else(|| {
+                clap::Error::with_description(
+                    "The sheet size should be widthxheight, such as 1311x895",
+                    ErrorKind::InvalidValue,
+                )
+                .exit()
+            });
+
+        Unfold {
+            pattern,
+            pattern_size,
+            sheet_size,
+            seed: seed_argument(arguments, "seed"),
+            output: arguments.value_of("output").map(String::from),
+        }
+    })
+}
+
+// The dots of the pattern in reading order along with its width and height.
+fn load_pattern(path: &str) -> Result<(Vec<Point>, (usize, usize)), AocError> {
+    let pattern = fs::read_to_string(path)?;
+    let lines: Vec<&str> = pattern.lines().collect();
+    let width = lines.iter().map(|line| line.len()).max().unwrap_or(0usize);
+
+    let points = lines
+        .iter()
+        .enumerate()
+        .flat_map(|(y, line)| {
+            line.chars().enumerate().filter_map(move |(x, c)| match c {
+                '#' => Some(Ok(Point { x, y })),
+                '.' => None,
+                _ => Some(Err(AocError::validation(format!(
+                    "Unexpected {} on line {}",
+                    c,
+                    y + 1
+                )))),
+            })
+        })
+        .collect::<Result<Vec<Point>, AocError>>()?;
+
+    Ok((points, (width, lines.len())))
+}
+
+fn parse_size(size: &str) -> Option<(usize, usize)> {
+    let (width, height) = size.split_once('x')?;
+    Some((width.parse().ok()?, height.parse().ok()?))
+}
+
+fn input_check(arguments: &TransparentOrigamiArgs, _paper: &Paper) -> Result<(), AocError> {
+    match &arguments.unfold {
+        Some(unfold)
+            if unfold.pattern_size.0 > unfold.sheet_size.0
+                || unfold.pattern_size.1 > unfold.sheet_size.1 =>
+        {
+            Err(AocError::validation(format!(
+                "The pattern is {}x{}, too large for a {}x{} sheet",
+                unfold.pattern_size.0,
+                unfold.pattern_size.1,
+                unfold.sheet_size.0,
+                unfold.sheet_size.1
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn run(arguments: TransparentOrigamiArgs, paper: Paper) -> CommandResult {
+    if let Some(unfold) = &arguments.unfold {
+        let unfolded = unfold_paper(unfold);
+        let input = paper_to_string(&unfolded);
+        match &unfold.output {
+            Some(path) => report_output(path, fs::write(path, input)),
+            None => print!("{}", input),
+        }
+        return fold_all(&unfolded).len().into();
+    }
+
+    let folds = if arguments.limit_folds {
+        &paper.folds[..1]
+    } else {
+        &paper.folds[..]
+    };
+
+    if arguments.stats {
+        return fold_stats(&paper, folds);
+    }
+
+    let points = folds
+        .iter()
+        .fold(paper_points(&paper), |acc, fold| fold_paper(&acc, fold).0);
+
+    log!(
+        LogLevel::Debug,
+        "{}",
+        display_points(&points, arguments.draw)
+    );
+
+    if let Some(path) = &arguments.export {
+        report_output(path, export_points_as_svg(&points, arguments.scale, path));
+    }
+
+    points.len().into()
+}
+
+fn paper_points(paper: &Paper) -> HashSet<Point> {
+    paper.points.iter().copied().collect()
+}
+
+fn fold_all(paper: &Paper) -> HashSet<Point> {
+    paper
+        .folds
+        .iter()
+        .fold(paper_points(paper), |acc, fold| fold_paper(&acc, fold).0)
+}
+
+fn fold_stats(paper: &Paper, folds: &[Fold]) -> CommandResult {
+    let mut points = paper_points(paper);
+    let mut stats: Vec<(String, CommandResult)> = folds
+        .iter()
+        .enumerate()
+        .map(|(index, fold)| {
+            let (folded, merged) = fold_paper(&points, fold);
+            points = folded;
+            let (width, height) = bounds(&points);
+            (
+                format!("Fold {} along {}", index + 1, fold_name(fold)),
+                format!("{}x{}, {} merged", width, height, merged).into(),
+            )
+        })
+        .collect();
+    stats.push(("Dots".to_string(), points.len().into()));
+
+    stats.into()
+}
+
+// The width and height from the top left corner to the furthest dots.
+fn bounds(points: &HashSet<Point>) -> (usize, usize) {
+    points
+        .iter()
+        .fold((0usize, 0usize), |(width, height), point| {
+            (width.max(point.x + 1), height.max(point.y + 1))
+        })
+}
+
+// Folds along columns and rows take turns while there are both. Each dot of the pattern is placed
+// once or twice on the sheet, each time at a random one of the places that fold onto it.
+fn unfold_paper(unfold: &Unfold) -> Paper {
+    let mut random = seeded_random(unfold.seed);
+    let columns = fold_lines(unfold.sheet_size.0, unfold.pattern_size.0);
+    let rows = fold_lines(unfold.sheet_size.1, unfold.pattern_size.1);
+
+    let copies: Vec<Point> = unfold
+        .pattern
+        .iter()
+        .flat_map(|point| iter::repeat_n(*point, 1 + random.below(2)))
+        .collect();
+    let points: BTreeSet<Point> = copies
+        .into_iter()
+        .map(|point| Point {
+            x: unfold_position(point.x, unfold.sheet_size.0, &columns, &mut random),
+            y: unfold_position(point.y, unfold.sheet_size.1, &rows, &mut random),
+        })
+        .collect();
+    let mut points: Vec<Point> = points.into_iter().collect();
+    (1..points.len())
+        .rev()
+        .for_each(|index| points.swap(index, random.below(index + 1)));
+
+    let folds = (0..columns.len().max(rows.len()))
+        .flat_map(|index| {
+            columns
+                .get(index)
+                .map(|x| Fold::Horizontal { x: *x })
+                .into_iter()
+                .chain(rows.get(index).map(|y| Fold::Veritical { y: *y }))
+        })
+        .collect();
+
+    Paper { points, folds }
+}
+
+// Folding a side of length n along f leaves f, and nothing may fold past the edge so f has to be
+// at least n / 2. Each fold halves the side until what's left is the pattern's.
+fn fold_lines(sheet: usize, pattern: usize) -> Vec<usize> {
+    iter::successors(Some(sheet), |side| {
+        if *side > pattern {
+            Some(pattern.max(side / 2))
+        } else {
+            None
+        }
+    })
+    .skip(1)
+    .collect()
+}
+
+// Undoes the folds from the last, each time keeping the position or taking its mirror image.
+fn unfold_position(
+    position: usize,
+    sheet: usize,
+    lines: &[usize],
+    random: &mut SplitMix64,
+) -> usize {
+    lines
+        .iter()
+        .enumerate()
+        .rev()
+        .fold(position, |position, (index, line)| {
+            let side = if index == 0 { sheet } else { lines[index - 1] };
+            let mirrored = 2 * line - position;
+            if mirrored < side && random.below(2) == 1 {
+                mirrored
+            } else {
+                position
+            }
+        })
+}
+
+fn paper_to_string(paper: &Paper) -> String {
+    let points = paper
+        .points
+        .iter()
+        .map(|point| format!("{},{}", point.x, point.y))
+        .collect::<Vec<String>>()
+        .join("\n");
+    let folds = paper
+        .folds
+        .iter()
+        .map(|fold| format!("fold along {}", fold_name(fold)))
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    format!("{}\n\n{}\n", points, folds)
+}
+
+fn fold_name(fold: &Fold) -> String {
+    match fold {
+        Fold::Veritical { y } => format!("y={}", y),
+        Fold::Horizontal { x } => format!("x={}", x),
+    }
+}
+
+fn display_points(points: &HashSet<Point>, style: RenderStyle) -> String {
+    render_points(points.iter().map(|point| (point.x, point.y)), style)
+}
+
+fn export_points_as_svg(
+    points: &HashSet<Point>,
+    scale: usize,
+    path: &str,
+) -> Result<(), io::Error> {
+    let max_x = points.iter().map(|point| point.x).max().unwrap_or(0usize);
+    let max_y = points.iter().map(|point| point.y).max().unwrap_or(0usize);
+    let (width, height) = ((max_x + 1) * scale, (max_y + 1) * scale);
+    let mut sorted_points: Vec<&Point> = points.iter().collect();
+    sorted_points.sort_by_key(|point| (point.y, point.x));
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    writeln!(
+        writer,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">",
+        width, height, width, height
+    )?;
+    writeln!(
+        writer,
+        "<rect width=\"{}\" height=\"{}\" fill=\"white\"/>",
+        width, height
+    )?;
+    sorted_points.iter().try_for_each(|point| {
+        writeln!(
+            writer,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"black\"/>",
+            point.x * scale,
+            point.y * scale,
+            scale,
+            scale
+        )
+    })?;
+    writeln!(writer, "</svg>")?;
+
+    writer.flush()
+}
+
+// The folded dots, along with how many landed on a dot already there.
+fn fold_paper(points: &HashSet<Point>, fold: &Fold) -> (HashSet<Point>, usize) {
+    let folded: HashSet<Point> = match fold {
+        Fold::Veritical { y } => points
+            .iter()
+            .map(|point| {
+                if point.y > *y {
+                    Point {
+                        x: point.x,
+                        y: y - (point.y - y),
+                    }
+                } else {
+                    *point
+                }
+            })
+            .collect(),
+        Fold::Horizontal { x } => points
+            .iter()
+            .map(|point| {
+                if point.x > *x {
+                    Point {
+                        x: x - (point.x - x),
+                        y: point.y,
+                    }
+                } else {
+                    *point
+                }
+            })
+            .collect(),
+    };
+    let merged = points.len() - folded.len();
+
+    (folded, merged)
+}
+
+fn describe(paper: &Paper) -> String {
+    let mut description = format!(
+        "{} points and {} folds",
+        paper.points.len(),
+        paper.folds.len()
+    );
+
+    let unique_points: HashSet<&Point> = paper.points.iter().collect();
+    if unique_points.len() != paper.points.len() {
+        description.push_str(&format!(
+            "\nAnomaly: {} points are duplicated",
+            paper.points.len() - unique_points.len()
+        ));
+    }
+
+    description
+}
+
+fn parse_data(input: &String) -> IResult<&str, Paper> {
+    map(
+        separated_pair(
+            separated_list1(newline, parse_point),
+            tag("\n\n"),
+            separated_list1(newline, parse_fold),
+        ),
+        |(points, folds)| Paper {
+            points: points,
+            folds: folds,
+        },
+    )(input)
+}
+
+fn parse_point(input: &str) -> IResult<&str, Point> {
+    map(
+        separated_pair(parse_usize, tag(","), parse_usize),
+        |(x, y)| Point { x: x, y: y },
+    )(input)
+}
+
+fn parse_fold(input: &str) -> IResult<&str, Fold> {
+    preceded(
+        tag("fold along "),
+        alt((
+            map(
+                separated_pair(tag("y"), tag("="), parse_usize),
+                |(_, value)| Fold::Veritical { y: value },
+            ),
+            map(
+                separated_pair(tag("x"), tag("="), parse_usize),
+                |(_, value)| Fold::Horizontal { x: value },
+            ),
+        )),
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{run_problem_with_input, scratch_path};
+
+    #[test]
+    fn part1_sample() {
+        assert_eq!(
+            run_problem_with_input(&TRANSPARENT_ORIGAMI, SAMPLE, &["part1"]).unwrap(),
+            17usize.into()
+        );
+    }
+
+    #[test]
+    fn part2_sample() {
+        assert_eq!(
+            run_problem_with_input(&TRANSPARENT_ORIGAMI, SAMPLE, &["part2"]).unwrap(),
+            16usize.into()
+        );
+    }
+    #[test]
+    fn stats_sample() {
+        assert_eq!(
+            run_problem_with_input(&TRANSPARENT_ORIGAMI, SAMPLE, &["--part", "2", "-t"]).unwrap(),
+            vec![
+                (
+                    "Fold 1 along y=7".to_string(),
+                    "11x5, 1 merged".to_string().into()
+                ),
+                (
+                    "Fold 2 along x=5".to_string(),
+                    "5x5, 1 merged".to_string().into()
+                ),
+                ("Dots".to_string(), 16usize.into()),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn export_sample() {
+        let path = scratch_path("transparent_origami_export_sample.svg");
+        let path = path.to_str().unwrap();
+
+        assert_eq!(
+            run_problem_with_input(&TRANSPARENT_ORIGAMI, SAMPLE, &["--part", "2", "-e", path])
+                .unwrap(),
+            16usize.into()
+        );
+
+        let svg = std::fs::read_to_string(path).unwrap();
+        assert!(
+            svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"50\" height=\"50\"")
+        );
+        assert_eq!(svg.matches("fill=\"black\"").count(), 16);
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+
+    #[test]
+    fn unwritable_outputs_are_errors() {
+        let missing = scratch_path("transparent_origami_missing");
+        let export_path = missing.join("export.svg");
+        let export_path = export_path.to_str().unwrap();
+        let error = run_problem_with_input(
+            &TRANSPARENT_ORIGAMI,
+            SAMPLE,
+            &["--part", "2", "-e", export_path],
+        )
+        .unwrap_err();
+        assert!(matches!(error, AocError::Io(_)));
+        assert!(error
+            .to_string()
+            .contains(&format!("{} can't be written", export_path)));
+
+        let pattern_path = scratch_path("transparent_origami_missing_pattern.txt");
+        std::fs::write(&pattern_path, "#\n").unwrap();
+        let output_path = missing.join("unfolded.txt");
+        let output_path = output_path.to_str().unwrap();
+        let error = run_problem_with_input(
+            &TRANSPARENT_ORIGAMI,
+            SAMPLE,
+            &[
+                "--part",
+                "2",
+                "-u",
+                pattern_path.to_str().unwrap(),
+                "-z",
+                "10x10",
+                "-o",
+                output_path,
+            ],
+        )
+        .unwrap_err();
+        assert!(matches!(error, AocError::Io(_)));
+        assert!(error
+            .to_string()
+            .contains(&format!("{} can't be written", output_path)));
+    }
+
+    #[test]
+    fn unfold_folds_back_to_the_pattern() {
+        let pattern = "#####\n#...#\n#...#\n#...#\n#####\n";
+        let pattern_path = scratch_path("transparent_origami_unfold_pattern.txt");
+        let output_path = scratch_path("transparent_origami_unfold_output.txt");
+        std::fs::write(&pattern_path, pattern).unwrap();
+
+        assert_eq!(
+            run_problem_with_input(
+                &TRANSPARENT_ORIGAMI,
+                SAMPLE,
+                &[
+                    "--part",
+                    "2",
+                    "-u",
+                    pattern_path.to_str().unwrap(),
+                    "-z",
+                    "40x30",
+                    "-r",
+                    "5",
+                    "-o",
+                    output_path.to_str().unwrap()
+                ]
+            )
+            .unwrap(),
+            16usize.into()
+        );
+
+        let unfolded = std::fs::read_to_string(&output_path).unwrap();
+        let (_, paper) = parse_data(&unfolded).unwrap();
+        assert!(paper
+            .points
+            .iter()
+            .all(|point| point.x < 40 && point.y < 30));
+        assert_eq!(
+            display_points(&fold_all(&paper), RenderStyle::Ascii),
+            pattern.trim_end()
+        );
+    }
+}
@@ -0,0 +1,506 @@
+use crate::{
+    default_sub_command, describe_grid, selected_part, AocError, CommandResult, Example, Problem,
+};
+use clap::{value_t_or_exit, App, Arg, ArgMatches};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::newline,
+    combinator::{map, value},
+    multi::{many1, separated_list1},
+    sequence::separated_pair,
+    IResult,
+};
+use std::iter;
+
+pub const TRENCH_MAP: Problem<TrenchMapArgs, TrenchMap> = Problem::new(
+    sub_command,
+    "trench-map",
+    "day20_trench_map",
+    parse_arguments,
+    parse_data,
+    run,
+)
+.with_long_about(LONG_ABOUT)
+.with_examples(EXAMPLES)
+.with_describe(describe)
+.with_input_check(input_check);
+
+const LONG_ABOUT: &str =
+    "The input is a 512 pixel image enhancement algorithm followed by one or more images of light \
+    (#) and dark (.) pixels, each separated by a blank line.\n\n\
+    Each enhancement reads the 3 by 3 square around every pixel as a 9 bit number and replaces \
+    the pixel with that index of the algorithm. The image is infinite, so the pixels \
+    surrounding it can flip too. -n sets the number of enhancements, 2 for part 1 and 50 for \
+    part 2. The answer is the number of lit pixels. With several images the lit pixels are \
+    reported for each image along with the total.\n\n\
+    An enhancement algorithm that isn't exactly 512 pixels is rejected before enhancing.\n\n\
+    -t reports each enhancement instead: how many pixels are lit and the box around them, with \
+    the top left of the original image at 0,0. When the algorithm lights every pixel around \
+    the image the lit pixels go on forever and are reported as infinite.\n\n\
+    Example: the sample has 35 lit pixels after 2 enhancements and 3351 after 50.";
+
+const SAMPLE: &str = include_str!("../../day20_trench_map/sample.txt");
+
+const EXAMPLES: &[Example] = &[
+    Example::new(SAMPLE, 1, "35"),
+    Example::new(SAMPLE, 2, "3351"),
+];
+
+#[derive(Debug)]
+pub struct TrenchMapArgs {
+    n: usize,
+    stats: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct TrenchMap {
+    image_enhancement_algorithm: Vec<Pixel>,
+    images: Vec<Vec<Vec<Pixel>>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pixel {
+    Light,
+    Dark,
+}
+
+fn sub_command() -> App<'static, 'static> {
+    default_sub_command(
+        &TRENCH_MAP,
+        "Ecnhances an image then counts the number of Light pixels in the images.",
+        "Path to the input file. Input should be the image enhancement algorithm followed by one or more images separated by blank lines.",
+        "Enchances the image in the default input twice.",
+        "Enchances the image in the default input 50 times.",
+    ).arg(
+        Arg::with_name("number")
+            .short("n")
+            .help("Number of times to enchance the image.")
+            .takes_value(true)
+            .required_unless_one(&["part", "explain"]),
+    )
+    .arg(
+        Arg::with_name("stats")
+            .short("t")
+            .long("stats")
+            .help("If passed, reports the lit pixels and their bounding box after each enhancement."),
+    )
+}
+
+fn parse_arguments(arguments: &ArgMatches) -> TrenchMapArgs {
+    match selected_part(arguments) {
+        Some("part1") => TrenchMapArgs {
+            n: 2usize,
+            stats: arguments.is_present("stats"),
+        },
+        Some("part2") => TrenchMapArgs {
+            n: 50usize,
+            stats: arguments.is_present("stats"),
+        },
+        _ => TrenchMapArgs {
+            n: value_t_or_exit!(arguments.value_of("number"), usize),
+            stats: arguments.is_present("stats"),
+        },
+    }
+}
+
+fn input_check(_arguments: &TrenchMapArgs, trench_map: &TrenchMap) -> Result<(), AocError> {
+    match trench_map.image_enhancement_algorithm.len() {
+        512usize => Ok(()),
+        length => Err(AocError::validation(format!(
+            "The enhancement algorithm is {} pixels but should be 512, one for each 3 by 3 square",
+            length
+        ))),
+    }
+}
+
+fn run(arguments: TrenchMapArgs, trench_map: TrenchMap) -> CommandResult {
+    if arguments.stats {
+        return enhancement_stats(&trench_map, arguments.n);
+    }
+
+    let lit_counts: Vec<usize> = trench_map
+        .images
+        .iter()
+        .map(|image| count_lit_pixels(image, &trench_map.image_enhancement_algorithm, arguments.n))
+        .collect();
+
+    if lit_counts.len() == 1 {
+        return lit_counts[0].into();
+    }
+
+    let total = lit_counts.iter().sum::<usize>();
+    lit_counts
+        .into_iter()
+        .enumerate()
+        .map(|(index, lit_count)| (format!("Image {}", index + 1), lit_count.into()))
+        .chain(iter::once(("Total".to_string(), total.into())))
+        .collect::<Vec<(String, CommandResult)>>()
+        .into()
+}
+
+fn count_lit_pixels(
+    image: &[Vec<Pixel>],
+    image_enhancement_algorithm: &Vec<Pixel>,
+    n: usize,
+) -> usize {
+    let (new_image, _) = (0..n).fold(
+        (image.to_vec(), Pixel::Dark),
+        |(new_image, expand_pixels), _| {
+            enhance(&new_image, image_enhancement_algorithm, expand_pixels)
+        },
+    );
+
+    lit_pixels(&new_image).count()
+}
+
+// Enhances the image once, along with the pixels that surround it forever.
+fn enhance(
+    image: &Vec<Vec<Pixel>>,
+    image_enhancement_algorithm: &Vec<Pixel>,
+    expand_pixels: Pixel,
+) -> (Vec<Vec<Pixel>>, Pixel) {
+    let new_image = expand_image(image, &expand_pixels);
+    let enhanced = new_image
+        .iter()
+        .enumerate()
+        .map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(|(x, _)| {
+                    map_pixel_to_real_pixel(
+                        x,
+                        y,
+                        &new_image,
+                        image_enhancement_algorithm,
+                        &expand_pixels,
+                    )
+                })
+                .collect()
+        })
+        .collect();
+
+    (
+        enhanced,
+        map_pixel_set_to_new_pixel(&vec![expand_pixels; 9], image_enhancement_algorithm),
+    )
+}
+
+fn lit_pixels(image: &[Vec<Pixel>]) -> impl Iterator<Item = (usize, usize)> + '_ {
+    image.iter().enumerate().flat_map(|(y, row)| {
+        row.iter()
+            .enumerate()
+            .filter(|(_, pixel)| **pixel == Pixel::Light)
+            .map(move |(x, _)| (x, y))
+    })
+}
+
+fn enhancement_stats(trench_map: &TrenchMap, n: usize) -> CommandResult {
+    trench_map
+        .images
+        .iter()
+        .enumerate()
+        .flat_map(|(index, image)| {
+            let mut new_image = image.clone();
+            let mut expand_pixels = Pixel::Dark;
+
+            (1..=n)
+                .map(|step| {
+                    let (enhanced, expanded) = enhance(
+                        &new_image,
+                        &trench_map.image_enhancement_algorithm,
+                        expand_pixels,
+                    );
+                    new_image = enhanced;
+                    expand_pixels = expanded;
+
+                    let label = if trench_map.images.len() == 1 {
+                        format!("Step {}", step)
+                    } else {
+                        format!("Image {} step {}", index + 1, step)
+                    };
+                    (label, step_stats(&new_image, expand_pixels, step).into())
+                })
+                .collect::<Vec<(String, CommandResult)>>()
+        })
+        .collect::<Vec<(String, CommandResult)>>()
+        .into()
+}
+
+// The image grows by a pixel on every side with each step, so the original top left is at step,
+// step in the enhanced image.
+fn step_stats(image: &[Vec<Pixel>], expand_pixels: Pixel, step: usize) -> String {
+    if expand_pixels == Pixel::Light {
+        return "infinite lit pixels, everything around the image is lit".to_string();
+    }
+
+    let (count, bounds) = lit_pixels(image).fold(
+        (0usize, None),
+        |(count, bounds): (usize, Option<(usize, usize, usize, usize)>), (x, y)| {
+            let bounds = match bounds {
+                Some((min_x, min_y, max_x, max_y)) => {
+                    (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y))
+                }
+                None => (x, y, x, y),
+            };
+            (count + 1, Some(bounds))
+        },
+    );
+
+    match bounds {
+        Some((min_x, min_y, max_x, max_y)) => format!(
+            "{} lit pixels from {},{} to {},{}",
+            count,
+            min_x as isize - step as isize,
+            min_y as isize - step as isize,
+            max_x as isize - step as isize,
+            max_y as isize - step as isize
+        ),
+        None => "0 lit pixels".to_string(),
+    }
+}
+
+fn expand_image(image: &Vec<Vec<Pixel>>, expand_pixels: &Pixel) -> Vec<Vec<Pixel>> {
+    let desired_x = image.first().expect("At least one row").len() + 2;
+    let top_bottom_rows = vec![*expand_pixels; desired_x];
+    let mut new_image = vec![top_bottom_rows.clone()];
+
+    new_image.extend(image.iter().map(|row| {
+        let mut new_row = vec![*expand_pixels];
+        new_row.extend(row.iter());
+        new_row.push(*expand_pixels);
+        new_row
+    }));
+    new_image.push(top_bottom_rows);
+    new_image
+}
+
+fn map_pixel_to_real_pixel(
+    x: usize,
+    y: usize,
+    image: &Vec<Vec<Pixel>>,
+    image_enhancement_algorithm: &Vec<Pixel>,
+    default: &Pixel,
+) -> Pixel {
+    map_pixel_set_to_new_pixel(
+        &get_adjacent_pixels(&image, x, y, default),
+        &image_enhancement_algorithm,
+    )
+}
+
+fn map_pixel_set_to_new_pixel(
+    pixel_set: &Vec<Pixel>,
+    image_enhancement_algorithm: &Vec<Pixel>,
+) -> Pixel {
+    let bits = pixel_set
+        .iter()
+        .map(|pixel| match pixel {
+            Pixel::Light => "1",
+            Pixel::Dark => "0",
+        })
+        .collect::<Vec<&str>>()
+        .join("");
+
+    image_enhancement_algorithm
+        .get(usize::from_str_radix(&bits, 2).unwrap())
+        .expect("The algorithm is checked to be 512 pixels")
+        .to_owned()
+}
+
+fn get_adjacent_pixels(pixel: &Vec<Vec<Pixel>>, x: usize, y: usize, default: &Pixel) -> Vec<Pixel> {
+    let mut pixels = Vec::new();
+
+    pixels.push(if y > 0usize && x > 0usize {
+        *pixel
+            .get(y - 1)
+            .and_then(|result| result.get(x - 1))
+            .unwrap_or(default)
+    } else {
+        *default
+    });
+
+    pixels.push(if y > 0usize {
+        *pixel
+            .get(y - 1)
+            .and_then(|result| result.get(x))
+            .unwrap_or(default)
+    } else {
+        *default
+    });
+
+    pixels.push(if y > 0usize {
+        *pixel
+            .get(y - 1)
+            .and_then(|result| result.get(x + 1))
+            .unwrap_or(default)
+    } else {
+        *default
+    });
+
+    pixels.push(if x > 0usize {
+        *pixel
+            .get(y)
+            .and_then(|result| result.get(x - 1))
+            .unwrap_or(default)
+    } else {
+        *default
+    });
+
+    pixels.push(
+        *pixel
+            .get(y)
+            .and_then(|result| result.get(x))
+            .unwrap_or(default),
+    );
+
+    pixels.push(
+        *pixel
+            .get(y)
+            .and_then(|result| result.get(x + 1))
+            .unwrap_or(default),
+    );
+
+    pixels.push(if x > 0usize {
+        *pixel
+            .get(y + 1)
+            .and_then(|result| result.get(x - 1))
+            .unwrap_or(default)
+    } else {
+        *default
+    });
+
+    pixels.push(
+        *pixel
+            .get(y + 1)
+            .and_then(|result| result.get(x))
+            .unwrap_or(default),
+    );
+
+    pixels.push(
+        *pixel
+            .get(y + 1)
+            .and_then(|result| result.get(x + 1))
+            .unwrap_or(default),
+    );
+
+    pixels
+}
+
+fn describe(trench_map: &TrenchMap) -> String {
+    let mut description = format!(
+        "Enhancement algorithm of {} pixels",
+        trench_map.image_enhancement_algorithm.len()
+    );
+
+    if trench_map.images.len() == 1 {
+        description.push_str(&format!("\n{}", describe_grid(&trench_map.images[0])));
+    } else {
+        description.push_str(&format!("\n{} images", trench_map.images.len()));
+        trench_map
+            .images
+            .iter()
+            .enumerate()
+            .for_each(|(index, image)| {
+                description.push_str(&format!("\nImage {}: {}", index + 1, describe_grid(image)))
+            });
+    }
+
+    if trench_map.image_enhancement_algorithm.len() != 512 {
+        description.push_str("\nAnomaly: the enhancement algorithm should be 512 pixels");
+    }
+
+    description
+}
+
+fn parse_data(input: &String) -> IResult<&str, TrenchMap> {
+    map(
+        separated_pair(
+            parse_pixel_line,
+            tag("\n\n"),
+            separated_list1(tag("\n\n"), parse_image),
+        ),
+        |(image_enhancement_algorithm, images)| TrenchMap {
+            image_enhancement_algorithm: image_enhancement_algorithm,
+            images,
+        },
+    )(input)
+}
+
+fn parse_image(input: &str) -> IResult<&str, Vec<Vec<Pixel>>> {
+    separated_list1(newline, parse_pixel_line)(input)
+}
+
+fn parse_pixel_line(input: &str) -> IResult<&str, Vec<Pixel>> {
+    many1(parse_pixel)(input)
+}
+
+fn parse_pixel(input: &str) -> IResult<&str, Pixel> {
+    alt((value(Pixel::Dark, tag(".")), value(Pixel::Light, tag("#"))))(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_problem_with_input;
+
+    #[test]
+    fn part1_sample() {
+        assert_eq!(
+            run_problem_with_input(&TRENCH_MAP, SAMPLE, &["part1"]).unwrap(),
+            35usize.into()
+        );
+    }
+
+    #[test]
+    fn part2_sample() {
+        assert_eq!(
+            run_problem_with_input(&TRENCH_MAP, SAMPLE, &["part2"]).unwrap(),
+            3351usize.into()
+        );
+    }
+    #[test]
+    fn batch_sample() {
+        let (algorithm, image) = SAMPLE.trim_end().split_once("\n\n").unwrap();
+        let input = format!("{}\n\n{}\n\n{}\n", algorithm, image, image);
+
+        assert_eq!(
+            run_problem_with_input(&TRENCH_MAP, &input, &["part1"]).unwrap(),
+            vec![
+                ("Image 1".to_string(), 35usize.into()),
+                ("Image 2".to_string(), 35usize.into()),
+                ("Total".to_string(), 70usize.into()),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn stats_sample() {
+        assert_eq!(
+            run_problem_with_input(&TRENCH_MAP, SAMPLE, &["--part", "1", "-t"]).unwrap(),
+            vec![
+                (
+                    "Step 1".to_string(),
+                    "24 lit pixels from -1,-1 to 5,5".to_string().into()
+                ),
+                (
+                    "Step 2".to_string(),
+                    "35 lit pixels from -2,-2 to 6,6".to_string().into()
+                ),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn truncated_algorithm_is_rejected() {
+        let input = SAMPLE.replacen("..#.#", "", 1);
+
+        assert_eq!(
+            run_problem_with_input(&TRENCH_MAP, &input, &["part1"])
+                .unwrap_err()
+                .to_string(),
+            "The enhancement algorithm is 507 pixels but should be 512, one for each 3 by 3 square"
+        );
+    }
+}
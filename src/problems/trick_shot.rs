@@ -1,4 +1,7 @@
-use adventofcode2021::{default_sub_command, parse_isize, CommandResult, Problem};
+use crate::{
+    default_sub_command, inverse_triangular, parse_isize, selected_part, sum_range, triangular,
+    CommandResult, Example, Problem,
+};
 use clap::{value_t_or_exit, App, Arg, ArgMatches};
 use nom::{
     bytes::complete::tag,
@@ -6,7 +9,6 @@ use nom::{
     sequence::{preceded, tuple},
     IResult,
 };
-use num_integer::Roots;
 use strum::VariantNames;
 use strum_macros::{EnumString, EnumVariantNames};
 
@@ -17,7 +19,26 @@ pub const TRICK_SHOT: Problem<TrickShotArgs, Target> = Problem::new(
     parse_arguments,
     parse_data,
     run,
-);
+)
+.with_long_about(LONG_ABOUT)
+.with_examples(EXAMPLES)
+.with_describe(describe);
+
+const LONG_ABOUT: &str = "The input gives a target area as x and y ranges.\n\n\
+    A probe launched with an integer velocity moves by its velocity each step. Drag pulls the \
+    x velocity towards 0 and gravity lowers the y velocity by 1. A launch hits when the probe \
+    is inside the target at the end of any step. max-height finds the highest y reached by a \
+    launch that hits. trajectory-count counts every launch velocity that hits. max-steps finds \
+    the launch that ends the most steps inside the target, giving the count of steps and the \
+    velocity as x,y. Ties go to the lowest x velocity, then the lowest y.\n\n\
+    Example: target area: x=20..30, y=-10..-5 has a max height of 45 and 112 velocities.";
+
+const SAMPLE: &str = include_str!("../../day17_trick_shot/sample.txt");
+
+const EXAMPLES: &[Example] = &[
+    Example::new(SAMPLE, 1, "45"),
+    Example::new(SAMPLE, 2, "112"),
+];
 
 #[derive(Debug)]
 pub struct TrickShotArgs {
@@ -29,9 +50,10 @@ pub struct TrickShotArgs {
 enum Metric {
     MaxHeight,
     TrajectoryCount,
+    MaxSteps,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Target {
     lower_x: isize,
     upper_x: isize,
@@ -52,16 +74,17 @@ fn sub_command() -> App<'static, 'static> {
             .help(
                 "The type of metric to calculate. The functions available are as follows:\n\n\
             max-height: Counts height for any trajectory to hit a target.\n\n\
-            trajectory-count: Counts the total number of valid trajectories for the target.\n\n",
+            trajectory-count: Counts the total number of valid trajectories for the target.\n\n\
+            max-steps: Finds the trajectory that spends the most steps in the target.\n\n",
             )
             .takes_value(true)
             .possible_values(&Metric::VARIANTS)
-            .required(true),
+            .required_unless_one(&["part", "explain"]),
     )
 }
 
 fn parse_arguments(arguments: &ArgMatches) -> TrickShotArgs {
-    match arguments.subcommand_name() {
+    match selected_part(arguments) {
         Some("part1") => TrickShotArgs {
             metric: Metric::MaxHeight,
         },
@@ -78,9 +101,25 @@ fn run(arguments: TrickShotArgs, target: Target) -> CommandResult {
     match arguments.metric {
         Metric::MaxHeight => find_max_possible_height(&target).into(),
         Metric::TrajectoryCount => find_all_valid_trajectories(&target).len().into(),
+        Metric::MaxSteps => {
+            let ((x, y), steps) = find_most_steps_in_target(&target);
+            vec![
+                ("Steps in target".to_string(), steps.into()),
+                ("Velocity".to_string(), format!("{},{}", x, y).into()),
+            ]
+            .into()
+        }
     }
 }
 
+fn find_most_steps_in_target(target: &Target) -> ((isize, isize), usize) {
+    find_all_valid_trajectories(target)
+        .into_iter()
+        .map(|(x, y)| ((x, y), steps_in_target(&x, &y, target)))
+        .min_by_key(|((x, y), steps)| (std::cmp::Reverse(*steps), *x, *y))
+        .unwrap_or(((0, 0), 0))
+}
+
 fn find_all_valid_trajectories(target: &Target) -> Vec<(isize, isize)> {
     let mut valid_trajectories = Vec::new();
 
@@ -100,8 +139,14 @@ fn find_max_possible_height(target: &Target) -> isize {
 }
 
 fn is_valid_trajectory(x: &isize, y: &isize, target: &Target) -> bool {
+    steps_in_target(x, y, target) > 0
+}
+
+// Counts every step that ends inside the target until the probe passes it, rather than stopping
+// at the first.
+fn steps_in_target(x: &isize, y: &isize, target: &Target) -> usize {
     let mut n = 0isize;
-    let mut valid = false;
+    let mut steps = 0usize;
 
     loop {
         let x_n = x_at_n(x, &n);
@@ -110,14 +155,13 @@ fn is_valid_trajectory(x: &isize, y: &isize, target: &Target) -> bool {
         if x_n > target.upper_x || y_n < target.lower_y {
             break;
         } else if x_n >= target.lower_x && y_n <= target.upper_y {
-            valid = true;
-            break;
+            steps += 1;
         }
 
         n += 1
     }
 
-    valid
+    steps
 }
 
 fn find_max_possible_y(target: &Target) -> isize {
@@ -134,9 +178,8 @@ fn find_max_possible_x(target: &Target) -> isize {
 }
 
 fn find_min_possible_x(target: &Target) -> isize {
-    // should be ((target.lower_x * 8 + 1).sqrt()) - 1 / 2 (hard coding to the positive root)
-    // but since we are getting the integer sqrt we can omit it
-    ((target.lower_x * 8 + 1).sqrt()) / 2
+    // x stops moving once drag brings it to 0, by then it has gone triangular(x) across
+    inverse_triangular(target.lower_x)
 }
 
 fn x_at_n(x: &isize, n: &isize) -> isize {
@@ -148,11 +191,26 @@ fn x_at_n(x: &isize, n: &isize) -> isize {
 }
 
 fn max_y(y: &isize) -> isize {
-    y_at_n(&y, &*y)
+    triangular(*y)
 }
 
 fn y_at_n(y: &isize, n: &isize) -> isize {
-    ((2isize * y + 1isize) * n - (n * n)) / 2
+    // y + (y - 1) + ... + (y - n + 1)
+    sum_range(y - n + 1, *y)
+}
+
+fn describe(target: &Target) -> String {
+    let mut description = format!(
+        "Target area of {} by {}",
+        target.upper_x - target.lower_x + 1,
+        target.upper_y - target.lower_y + 1
+    );
+
+    if target.lower_x > target.upper_x || target.lower_y > target.upper_y {
+        description.push_str("\nAnomaly: target ranges are reversed");
+    }
+
+    description
 }
 
 fn parse_data(input: &String) -> IResult<&str, Target> {
@@ -171,3 +229,42 @@ fn parse_data(input: &String) -> IResult<&str, Target> {
         },
     )(input)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_problem_with_input;
+
+    #[test]
+    fn part1_sample() {
+        assert_eq!(
+            run_problem_with_input(&TRICK_SHOT, SAMPLE, &["part1"]).unwrap(),
+            45isize.into()
+        );
+    }
+
+    #[test]
+    fn part2_sample() {
+        assert_eq!(
+            run_problem_with_input(&TRICK_SHOT, SAMPLE, &["part2"]).unwrap(),
+            112usize.into()
+        );
+    }
+
+    #[test]
+    fn max_steps_sample() {
+        assert_eq!(
+            run_problem_with_input(
+                &TRICK_SHOT,
+                SAMPLE,
+                &["-f", "sample.txt", "-m", "max-steps"]
+            )
+            .unwrap(),
+            vec![
+                ("Steps in target".to_string(), 2usize.into()),
+                ("Velocity".to_string(), "6,1".to_string().into()),
+            ]
+            .into()
+        );
+    }
+}
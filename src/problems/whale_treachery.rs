@@ -0,0 +1,407 @@
+use crate::{
+    absolute_difference, checked_triangular, default_sub_command, parse_usize, selected_part,
+    triangular, AocError, CommandResult, Counter, Example, Problem,
+};
+use clap::{value_t_or_exit, App, Arg, ArgMatches, ErrorKind};
+use nom::{bytes::complete::tag, combinator::map, multi::separated_list0, IResult};
+use std::{fs, ops::RangeInclusive};
+use strum::VariantNames;
+use strum_macros::{EnumString, EnumVariantNames};
+
+pub const WHALE_TREACHERY: Problem<WhaleTreacheryArgs, Counter<usize>> = Problem::new(
+    sub_command,
+    "whale-treachery",
+    "day7_whale_treachery",
+    parse_arguments,
+    parse_data,
+    run,
+)
+.with_long_about(LONG_ABOUT)
+.with_examples(EXAMPLES)
+.with_describe(describe)
+.with_input_check(input_check)
+.with_cross_check(cross_check_arguments);
+
+const LONG_ABOUT: &str = "The input is a comma separated list of crab positions.\n\n\
+    Every crab has to move to the same position using the least total fuel. With the constant \
+    fuel function each step costs 1 fuel. With the linear fuel function each step costs one \
+    more than the last, so moving n steps costs n(n+1)/2. With the quadratic fuel function \
+    moving n steps costs n squared.\n\n\
+    -c reads the costs from a file instead: comma or newline separated numbers where the first \
+    is the cost of moving 1 step, the second of moving 2 steps and so on. It needs a cost for \
+    every distance between the outermost crabs.\n\n\
+    Every position between the outermost crabs is tried unless -a is passed, which only tries \
+    the positions the fuel function allows to be best. With constant fuel that's the median, \
+    since moving away from it passes more crabs than it gets closer to. With quadratic fuel \
+    it's the whole numbers either side of the mean. With linear fuel the best position is \
+    within half a step of the mean, so usually the whole numbers either side of it and at most \
+    three positions. A cost table can't be solved this way. --cross-check runs both and \
+    compares the answers.\n\n\
+    Example: 16,1,2,0,4,2,7,1,2,14 costs 37 at position 2 with constant fuel and 168 at \
+    position 5 with linear fuel.";
+
+const SAMPLE: &str = include_str!("../../day7_whale_treachery/sample.txt");
+
+const EXAMPLES: &[Example] = &[
+    Example::new(SAMPLE, 1, "37"),
+    Example::new(SAMPLE, 2, "168"),
+];
+
+#[derive(Debug)]
+pub struct WhaleTreacheryArgs {
+    cost_model: CostModel,
+    analytic: bool,
+}
+
+#[derive(Debug, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "kebab_case")]
+enum FuelFunction {
+    Constant,
+    Linear,
+    Quadratic,
+}
+
+#[derive(Debug)]
+enum CostModel {
+    Constant,
+    Linear,
+    Quadratic,
+    // The cost of moving n steps is at index n - 1.
+    Table(Vec<usize>),
+}
+
+impl CostModel {
+    fn cost(&self, distance: usize) -> usize {
+        match self {
+            CostModel::Constant => distance,
+            CostModel::Linear => triangular(distance),
+            CostModel::Quadratic => distance * distance,
+            CostModel::Table(costs) => match distance {
+                0 => 0usize,
+                _ => costs[distance - 1],
+            },
+        }
+    }
+}
+
+fn sub_command() -> App<'static, 'static> {
+    default_sub_command(
+        &WHALE_TREACHERY,
+        "Finds the minimum fuel cost to align",
+        "Path to the input file. Input should be comma delimited integers.",
+        "Finds the minium fuel cost with constant fuel and the default input.",
+        "Finds the minium fuel cost with linear fuel and the default input.",
+    ).arg(
+        Arg::with_name("fuel-function")
+            .short("n")
+            .help("The type of fuel consumption for the crabs. The functions available are as follows:\n\n\
+            constant: Each distance from the target costs 1 fuel.\n\n\
+            linear: Each distance from the target costs 1 more fuel than the previous distance.\n\n\
+            quadratic: Each distance from the target costs the distance squared.\n\n")
+            .takes_value(true)
+            .possible_values(&FuelFunction::VARIANTS)
+            .required_unless_one(&["part", "explain", "cost-table"]),
+    )
+    .arg(
+        Arg::with_name("cost-table")
+            .short("c")
+            .long("cost-table")
+            .help("If passed, reads the fuel cost of each distance from this file instead of using a fuel function. The first number is the cost of moving 1 step.")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("analytic")
+            .short("a")
+            .long("analytic")
+            .help("If passed, only tries the positions around the median or mean that can be best for the fuel function instead of every position.")
+            .conflicts_with("cost-table"),
+    )
+}
+
+fn parse_arguments(arguments: &ArgMatches) -> WhaleTreacheryArgs {
+    match selected_part(arguments) {
+        Some("part1") => WhaleTreacheryArgs {
+            cost_model: cost_model(arguments, || FuelFunction::Constant),
+            analytic: arguments.is_present("analytic"),
+        },
+        Some("part2") => WhaleTreacheryArgs {
+            cost_model: cost_model(arguments, || FuelFunction::Linear),
+            analytic: arguments.is_present("analytic"),
+        },
+        _ => WhaleTreacheryArgs {
+            cost_model: cost_model(arguments, || {
+                value_t_or_exit!(arguments.value_of("fuel-function"), FuelFunction)
+            }),
+            analytic: arguments.is_present("analytic"),
+        },
+    }
+}
+
+// A cost table has no analytic answer, so both runs scan every position.
+fn cross_check_arguments(arguments: &ArgMatches) -> WhaleTreacheryArgs {
+    let arguments = parse_arguments(arguments);
+    WhaleTreacheryArgs {
+        analytic: !arguments.analytic && !matches!(arguments.cost_model, CostModel::Table(_)),
+        ..arguments
+    }
+}
+
+// A cost table replaces the fuel function. One that can't be read exits the same way an invalid
+// argument value does.
+fn cost_model(arguments: &ArgMatches, fuel_function: impl Fn() -> FuelFunction) -> CostModel {
+    match arguments.value_of("cost-table") {
+        Some(path) => CostModel::Table(load_cost_table(path).unwrap_or_else(|e| {
+            clap::Error::with_description(
+                &format!("Invalid cost table {}: {}", path, e),
+                ErrorKind::InvalidValue,
+            )
+            .exit()
+        })),
+        None => match fuel_function() {
+            FuelFunction::Constant => CostModel::Constant,
+            FuelFunction::Linear => CostModel::Linear,
+            FuelFunction::Quadratic => CostModel::Quadratic,
+        },
+    }
+}
+
+fn load_cost_table(path: &str) -> Result<Vec<usize>, AocError> {
+    fs::read_to_string(path)?
+        .split([',', '\n'])
+        .map(str::trim)
+        .filter(|cost| !cost.is_empty())
+        .map(|cost| {
+            cost.parse::<usize>().map_err(|_| {
+                AocError::validation(format!("{} in the cost table is not a cost", cost))
+            })
+        })
+        .collect()
+}
+
+fn input_check(arguments: &WhaleTreacheryArgs, crabs: &Counter<usize>) -> Result<(), AocError> {
+    let spread = crabs.keys().max().unwrap_or(&0usize) - crabs.keys().min().unwrap_or(&0usize);
+
+    match &arguments.cost_model {
+        CostModel::Table(costs) if costs.len() < spread => Err(AocError::validation(format!(
+            "The cost table has costs for up to {} steps but the crabs are {} apart",
+            costs.len(),
+            spread
+        ))),
+        CostModel::Linear if checked_triangular(spread).is_none() => {
+            Err(AocError::validation(format!(
+                "The crabs are {} apart, too far for linear fuel costs to fit in 64 bits",
+                spread
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn run(arguments: WhaleTreacheryArgs, crabs: Counter<usize>) -> CommandResult {
+    candidate_positions(&arguments, &crabs)
+        .map(|position| {
+            fuel_cost_at_position(&crabs, &position, |distance| {
+                arguments.cost_model.cost(distance)
+            })
+        })
+        .reduce(|min, item| if item < min { item } else { min })
+        .unwrap_or(0usize)
+        .into()
+}
+
+// Every position from the first crab to the last, or with -a only the ones that can be best for
+// the fuel function.
+fn candidate_positions(
+    arguments: &WhaleTreacheryArgs,
+    crabs: &Counter<usize>,
+) -> RangeInclusive<usize> {
+    match (&arguments.cost_model, arguments.analytic) {
+        (CostModel::Constant, true) => {
+            let median = median(crabs);
+            median..=median
+        }
+        (CostModel::Linear, true) => around_mean(crabs, true),
+        (CostModel::Quadratic, true) => around_mean(crabs, false),
+        _ => {
+            let min = *crabs
+                .keys()
+                .reduce(|min, item| if item < min { item } else { min })
+                .unwrap_or(&0usize);
+            let max = *crabs
+                .keys()
+                .fold(&0usize, |max, item| if item > max { item } else { max });
+            min..=max
+        }
+    }
+}
+
+// The lower middle crab's position. Any position between the two middle crabs costs the same.
+fn median(crabs: &Counter<usize>) -> usize {
+    let mut positions: Vec<(&usize, &usize)> = crabs.iter().collect();
+    positions.sort();
+    let middle = crabs.total().div_ceil(2);
+
+    positions
+        .into_iter()
+        .scan(0usize, |passed, (position, count)| {
+            *passed += count;
+            Some((*position, *passed))
+        })
+        .find(|(_, passed)| *passed >= middle)
+        .map(|(position, _)| position)
+        .unwrap_or(0usize)
+}
+
+// The whole positions either side of the mean, widened by half a step each way when half_step is
+// set. Counting in halves keeps it to whole numbers.
+fn around_mean(crabs: &Counter<usize>, half_step: bool) -> RangeInclusive<usize> {
+    let count = crabs.total();
+    if count == 0 {
+        return 0usize..=0usize;
+    }
+    let total: usize = crabs.iter().map(|(position, crabs)| position * crabs).sum();
+    let slack = if half_step { count } else { 0usize };
+
+    (2 * total).saturating_sub(slack) / (2 * count)..=(2 * total + slack).div_ceil(2 * count)
+}
+
+fn fuel_cost_at_position(
+    crabs: &Counter<usize>,
+    position: &usize,
+    fuel_function: impl Fn(usize) -> usize,
+) -> usize {
+    crabs
+        .into_iter()
+        .map(|(crab, count)| {
+            let n = absolute_difference(*crab, *position);
+
+            fuel_function(n) * count
+        })
+        .fold(0usize, |sum, fuel_cost| sum + fuel_cost)
+}
+
+fn describe(crabs: &Counter<usize>) -> String {
+    format!(
+        "{} crabs at {} distinct positions from {} to {}, most crowded at {}",
+        crabs.total(),
+        crabs.len(),
+        crabs.keys().min().unwrap_or(&0usize),
+        crabs.keys().max().unwrap_or(&0usize),
+        crabs
+            .most_common_ordered()
+            .first()
+            .map(|(position, _)| **position)
+            .unwrap_or(0usize)
+    )
+}
+
+fn parse_data(input: &String) -> IResult<&str, Counter<usize>> {
+    map(separated_list0(tag(","), parse_usize), |crabs| {
+        crabs.into_iter().collect()
+    })(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{run_problem_with_input, scratch_path};
+
+    #[test]
+    fn part1_sample() {
+        assert_eq!(
+            run_problem_with_input(&WHALE_TREACHERY, SAMPLE, &["part1"]).unwrap(),
+            37usize.into()
+        );
+    }
+
+    #[test]
+    fn part2_sample() {
+        assert_eq!(
+            run_problem_with_input(&WHALE_TREACHERY, SAMPLE, &["part2"]).unwrap(),
+            168usize.into()
+        );
+    }
+    #[test]
+    fn quadratic_sample() {
+        assert_eq!(
+            run_problem_with_input(
+                &WHALE_TREACHERY,
+                SAMPLE,
+                &["-f", "sample.txt", "-n", "quadratic"]
+            )
+            .unwrap(),
+            291usize.into()
+        );
+    }
+
+    #[test]
+    fn cost_table_sample() {
+        let table = scratch_path("whale_treachery_cost_table_sample.txt");
+        std::fs::write(&table, "1,3,6,10,15,21,28,36\n45,55,66,78,91,105,120,136\n").unwrap();
+        let table = table.to_str().unwrap();
+
+        assert_eq!(
+            run_problem_with_input(&WHALE_TREACHERY, SAMPLE, &["--part", "1", "-c", table])
+                .unwrap(),
+            168usize.into()
+        );
+    }
+
+    #[test]
+    fn analytic_sample() {
+        assert_eq!(
+            run_problem_with_input(&WHALE_TREACHERY, SAMPLE, &["--part", "1", "-a"]).unwrap(),
+            37usize.into()
+        );
+        assert_eq!(
+            run_problem_with_input(
+                &WHALE_TREACHERY,
+                SAMPLE,
+                &["--part", "2", "-a", "--cross-check"]
+            )
+            .unwrap(),
+            168usize.into()
+        );
+        assert_eq!(
+            run_problem_with_input(
+                &WHALE_TREACHERY,
+                SAMPLE,
+                &["-f", "sample.txt", "-n", "quadratic", "-a", "--cross-check"]
+            )
+            .unwrap(),
+            291usize.into()
+        );
+    }
+
+    #[test]
+    fn analytic_matches_scan_at_the_edges() {
+        ["0,5,5", "3,3,3", "0,0,0,9", "1,2,2,2,2,2,2,2,2,2,30"]
+            .iter()
+            .for_each(|crabs| {
+                ["constant", "linear", "quadratic"]
+                    .iter()
+                    .for_each(|fuel_function| {
+                        assert!(run_problem_with_input(
+                            &WHALE_TREACHERY,
+                            crabs,
+                            &["-f", "sample.txt", "-n", fuel_function, "--cross-check"]
+                        )
+                        .is_ok());
+                    })
+            });
+    }
+
+    #[test]
+    fn cost_table_too_short() {
+        let table = scratch_path("whale_treachery_cost_table_too_short.txt");
+        std::fs::write(&table, "1,2,3\n").unwrap();
+        let table = table.to_str().unwrap();
+
+        assert_eq!(
+            run_problem_with_input(&WHALE_TREACHERY, SAMPLE, &["-f", "sample.txt", "-c", table])
+                .unwrap_err()
+                .to_string(),
+            "The cost table has costs for up to 3 steps but the crabs are 16 apart"
+        );
+    }
+}
@@ -0,0 +1,32 @@
+use adventofcode2021::{problems, Command};
+
+pub struct Year {
+    pub year: usize,
+    pub commands: Vec<Box<dyn Command>>,
+}
+
+lazy_static! {
+    pub static ref YEARS: Vec<Year> = vec![Year {
+        year: 2021,
+        commands: problems::commands(),
+    }];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn commands_are_unique_within_their_year() {
+        YEARS.iter().for_each(|year| {
+            let names: HashSet<&str> = year.commands.iter().map(|command| command.name()).collect();
+
+            assert_eq!(names.len(), year.commands.len());
+            assert!(year
+                .commands
+                .iter()
+                .all(|command| command.year() == year.year));
+        });
+    }
+}